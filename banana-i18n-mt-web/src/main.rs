@@ -11,8 +11,10 @@ use tower_http::cors::CorsLayer;
 use tower_http::services::ServeDir;
 use tracing::info;
 
+mod ws;
+
 use banana_i18n::parser::Parser;
-use banana_i18n_mt::{GoogleTranslateProvider, Reassembler, prepare_for_translation};
+use banana_i18n_mt::{MachineTranslator, Reassembler, from_config, prepare_for_translation};
 
 #[derive(Serialize, Deserialize)]
 pub struct TranslateRequest {
@@ -32,9 +34,54 @@ pub struct ErrorResponse {
     pub error: String,
 }
 
+/// Default limit on the expanded source text of one `/api/translate` request, in bytes
+pub const DEFAULT_MAX_SOURCE_BYTES: usize = 50_000;
+
 #[derive(Clone)]
 pub struct AppState {
-    pub translator: Arc<GoogleTranslateProvider>,
+    pub translator: Arc<dyn MachineTranslator>,
+    /// Shared state of the collaborative `/ws` endpoint
+    pub hub: Arc<ws::Hub>,
+    /// Largest total length of expanded source texts sent to the provider per request
+    pub max_source_bytes: usize,
+}
+
+impl AppState {
+    pub fn new(translator: Arc<dyn MachineTranslator>) -> Self {
+        Self {
+            translator,
+            hub: Arc::new(ws::Hub::new()),
+            max_source_bytes: DEFAULT_MAX_SOURCE_BYTES,
+        }
+    }
+
+    /// Set the limit on expanded source text per request, in bytes
+    pub fn with_max_source_bytes(mut self, max_source_bytes: usize) -> Self {
+        self.max_source_bytes = max_source_bytes;
+        self
+    }
+
+    /// Check texts about to be sent to the provider against `max_source_bytes`
+    ///
+    /// Used by every endpoint that calls the provider, before the call. The
+    /// limit applies to the expanded variants, which is what the provider
+    /// bills for: a short message with several magic words can expand to
+    /// dozens of variants.
+    ///
+    /// # Returns
+    /// An error message if the texts are over the limit
+    pub fn check_source_size(&self, source_texts: &[String]) -> Result<(), String> {
+        let source_bytes: usize = source_texts.iter().map(String::len).sum();
+        if source_bytes > self.max_source_bytes {
+            return Err(format!(
+                "Message expands to {} variants totalling {} bytes, over the limit of {} bytes",
+                source_texts.len(),
+                source_bytes,
+                self.max_source_bytes
+            ));
+        }
+        Ok(())
+    }
 }
 
 #[tokio::main]
@@ -47,22 +94,30 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         )
         .init();
 
-    // Initialize Google Translate provider
-    let translator = GoogleTranslateProvider::from_env()
-        .map_err(|e| format!("Failed to initialize translator: {}", e))?;
-    let state = AppState {
-        translator: Arc::new(translator),
+    // Initialize the provider named by MT_PROVIDER (Google Translate by default)
+    let provider = std::env::var("MT_PROVIDER").unwrap_or_else(|_| "google".to_string());
+    let translator =
+        from_config(&provider).map_err(|e| format!("Failed to initialize translator: {}", e))?;
+
+    // Fail fast on bad credentials or connectivity instead of on the first request
+    translator
+        .health_check()
+        .await
+        .map_err(|e| format!("Translator health check failed: {}", e))?;
+
+    // Cap the text sent to the (billed) provider per request; MT_MAX_SOURCE_BYTES overrides
+    let max_source_bytes = match std::env::var("MT_MAX_SOURCE_BYTES") {
+        Ok(value) => value
+            .parse()
+            .map_err(|e| format!("Invalid MT_MAX_SOURCE_BYTES '{}': {}", value, e))?,
+        Err(_) => DEFAULT_MAX_SOURCE_BYTES,
     };
 
+    let state = AppState::new(Arc::from(translator)).with_max_source_bytes(max_source_bytes);
+
     info!("🍌 Starting banana-i18n MT Web Server");
 
-    // Build router
-    let app = Router::new()
-        .route("/", get(serve_index))
-        .route("/api/translate", post(translate_message))
-        .nest_service("/static", ServeDir::new("banana-i18n-mt-web/src/static"))
-        .layer(CorsLayer::permissive())
-        .with_state(state);
+    let app = app(state);
 
     let listener = tokio::net::TcpListener::bind("127.0.0.1:3000").await?;
     info!("🚀 Server running at http://127.0.0.1:3000");
@@ -72,6 +127,18 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+/// Build the router with all routes
+fn app(state: AppState) -> Router {
+    Router::new()
+        .route("/", get(serve_index))
+        .route("/healthz", get(health_check))
+        .route("/api/translate", post(translate_message))
+        .route("/ws", get(ws::ws_handler))
+        .nest_service("/static", ServeDir::new("banana-i18n-mt-web/src/static"))
+        .layer(CorsLayer::permissive())
+        .with_state(state)
+}
+
 async fn serve_index() -> impl IntoResponse {
     (
         StatusCode::OK,
@@ -80,6 +147,20 @@ async fn serve_index() -> impl IntoResponse {
     )
 }
 
+async fn health_check(
+    State(state): State<AppState>,
+) -> Result<&'static str, (StatusCode, Json<ErrorResponse>)> {
+    state.translator.health_check().await.map_err(|e| {
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(ErrorResponse {
+                error: format!("Translation provider unavailable: {}", e),
+            }),
+        )
+    })?;
+    Ok("ok")
+}
+
 async fn translate_message(
     State(state): State<AppState>,
     Json(request): Json<TranslateRequest>,
@@ -106,6 +187,11 @@ async fn translate_message(
     // Get source texts for translation
     let source_texts = context.source_texts();
 
+    // Reject oversized requests before any provider call
+    state
+        .check_source_size(&source_texts)
+        .map_err(|error| (StatusCode::PAYLOAD_TOO_LARGE, Json(ErrorResponse { error })))?;
+
     // Translate using the provider
     let translated_texts = state
         .translator
@@ -124,7 +210,7 @@ async fn translate_message(
     context.update_translations(translated_texts);
 
     // Reassemble back to wikitext
-    let reassembler = Reassembler::new(context.variable_types.clone());
+    let reassembler = Reassembler::from_context(&context);
     let translated_message = reassembler.reassemble(context.variants).map_err(|e| {
         (
             StatusCode::INTERNAL_SERVER_ERROR,
@@ -144,3 +230,269 @@ async fn translate_message(
         source: request.message,
     }))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use banana_i18n_mt::{MockMode, MockTranslator};
+    use futures_util::{SinkExt, StreamExt};
+    use tokio_tungstenite::tungstenite::Message;
+    use ws::ServerEvent;
+
+    type Client = tokio_tungstenite::WebSocketStream<
+        tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>,
+    >;
+
+    /// Serve the router with a mock translator on a local port
+    async fn spawn_server() -> String {
+        serve(AppState::new(Arc::new(MockTranslator::new(
+            MockMode::Suffix,
+        ))))
+        .await
+    }
+
+    /// Serve the router with the given state on a local port
+    async fn serve(state: AppState) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app(state)).await.unwrap() });
+        format!("ws://{}/ws", addr)
+    }
+
+    async fn connect(url: &str) -> Client {
+        tokio_tungstenite::connect_async(url).await.unwrap().0
+    }
+
+    async fn send(client: &mut Client, command: serde_json::Value) {
+        client
+            .send(Message::Text(command.to_string().into()))
+            .await
+            .unwrap();
+    }
+
+    /// Read events up to and including the next reassembly or error
+    async fn events_until_done(client: &mut Client) -> Vec<ServerEvent> {
+        let mut events = Vec::new();
+        loop {
+            let message = tokio::time::timeout(std::time::Duration::from_secs(5), client.next())
+                .await
+                .expect("timed out waiting for an event")
+                .unwrap()
+                .unwrap();
+            let Message::Text(text) = message else {
+                continue;
+            };
+            let event: ServerEvent = serde_json::from_str(&text).unwrap();
+            let done = !matches!(event, ServerEvent::Variant { .. });
+            events.push(event);
+            if done {
+                return events;
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_translate_rejects_oversized_expansion() {
+        let mock = MockTranslator::new(MockMode::Suffix);
+        let state = AppState::new(Arc::new(mock.clone())).with_max_source_bytes(1_000);
+
+        // 3 × 3 × 2 × 3 = 54 variants of ~60 bytes each, from a ~170 byte message
+        let request = TranslateRequest {
+            message: "{{GENDER:$1|He|She}} sent {{GENDER:$2|him|her}} {{PLURAL:$3|a message|$3 messages}} about {{GENDER:$4|his|her}} account"
+                .to_string(),
+            target_language: "fr".to_string(),
+            key: "sent".to_string(),
+        };
+        assert!(request.message.len() < 1_000);
+
+        let Err((status, Json(error))) =
+            translate_message(State(state.clone()), Json(request)).await
+        else {
+            panic!("oversized request was accepted");
+        };
+        assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+        assert!(error.error.contains("over the limit of 1000 bytes"));
+        assert_eq!(mock.call_count(), 0);
+
+        // Small messages still go through
+        let request = TranslateRequest {
+            message: "Hello".to_string(),
+            target_language: "fr".to_string(),
+            key: "greeting".to_string(),
+        };
+        let Ok(Json(response)) = translate_message(State(state), Json(request)).await else {
+            panic!("small request was rejected");
+        };
+        assert_eq!(response.translated, "Hello_fr");
+        assert_eq!(mock.call_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_ws_translate_and_retranslate() {
+        let url = spawn_server().await;
+        let mut client = connect(&url).await;
+
+        send(
+            &mut client,
+            serde_json::json!({
+                "type": "translate",
+                "key": "sent",
+                "message": "{{GENDER:$1|He|She}} sent it",
+                "target": "fr"
+            }),
+        )
+        .await;
+        let events = events_until_done(&mut client).await;
+
+        // 3 GENDER variants, then the reassembly
+        assert_eq!(events.len(), 4);
+        assert!(matches!(
+            &events[0],
+            ServerEvent::Variant { key, index: 0, source, .. }
+                if key == "sent" && source == "He sent it"
+        ));
+        assert!(matches!(
+            &events[3],
+            ServerEvent::Reassembled { key, translated }
+                if key == "sent" && translated.contains("{{GENDER:$1|")
+        ));
+
+        send(
+            &mut client,
+            serde_json::json!({ "type": "retranslate", "variant_index": 1 }),
+        )
+        .await;
+        let events = events_until_done(&mut client).await;
+        assert_eq!(events.len(), 2);
+        assert!(matches!(&events[0], ServerEvent::Variant { index: 1, .. }));
+        assert!(matches!(&events[1], ServerEvent::Reassembled { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_ws_rejects_oversized_expansion() {
+        let mock = MockTranslator::new(MockMode::Suffix);
+        let url = serve(AppState::new(Arc::new(mock.clone())).with_max_source_bytes(1_000)).await;
+        let mut client = connect(&url).await;
+
+        send(
+            &mut client,
+            serde_json::json!({
+                "type": "translate",
+                "key": "sent",
+                "message": "{{GENDER:$1|He|She}} sent {{GENDER:$2|him|her}} {{PLURAL:$3|a message|$3 messages}} about {{GENDER:$4|his|her}} account",
+                "target": "fr"
+            }),
+        )
+        .await;
+        let events = events_until_done(&mut client).await;
+        assert_eq!(events.len(), 1);
+        assert!(
+            matches!(&events[0], ServerEvent::Error { error } if error.contains("over the limit of 1000 bytes"))
+        );
+        assert_eq!(mock.call_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_ws_broadcasts_to_other_reviewers() {
+        let url = spawn_server().await;
+        let mut translator = connect(&url).await;
+        let mut reviewer = connect(&url).await;
+
+        send(
+            &mut translator,
+            serde_json::json!({
+                "type": "translate",
+                "key": "greeting",
+                "message": "Hello",
+                "target": "de"
+            }),
+        )
+        .await;
+
+        let seen = events_until_done(&mut reviewer).await;
+        assert_eq!(
+            seen.last(),
+            Some(&ServerEvent::Reassembled {
+                key: "greeting".to_string(),
+                translated: "Hello_de".to_string(),
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_ws_forgets_least_recently_used_messages() {
+        let mut state = AppState::new(Arc::new(MockTranslator::new(MockMode::Suffix)));
+        state.hub = Arc::new(ws::Hub::with_max_sessions(2));
+        let url = serve(state).await;
+        let mut client = connect(&url).await;
+
+        for key in ["a", "b"] {
+            send(
+                &mut client,
+                serde_json::json!({ "type": "translate", "key": key, "message": "Hi", "target": "fr" }),
+            )
+            .await;
+            events_until_done(&mut client).await;
+        }
+        // Retranslating "a" makes "b" the least recently used message
+        send(
+            &mut client,
+            serde_json::json!({ "type": "retranslate", "key": "a", "variant_index": 0 }),
+        )
+        .await;
+        events_until_done(&mut client).await;
+        send(
+            &mut client,
+            serde_json::json!({ "type": "translate", "key": "c", "message": "Hi", "target": "fr" }),
+        )
+        .await;
+        events_until_done(&mut client).await;
+
+        let retranslate = |key: &str| serde_json::json!({ "type": "retranslate", "key": key, "variant_index": 0 });
+        send(&mut client, retranslate("b")).await;
+        let events = events_until_done(&mut client).await;
+        assert!(
+            matches!(&events[0], ServerEvent::Error { error } if error.contains("has not been translated"))
+        );
+        for key in ["a", "c"] {
+            send(&mut client, retranslate(key)).await;
+            let events = events_until_done(&mut client).await;
+            assert!(matches!(
+                events.last(),
+                Some(ServerEvent::Reassembled { .. })
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_ws_errors_go_to_sender_only() {
+        let url = spawn_server().await;
+        let mut client = connect(&url).await;
+
+        send(
+            &mut client,
+            serde_json::json!({ "type": "retranslate", "variant_index": 0 }),
+        )
+        .await;
+        let events = events_until_done(&mut client).await;
+        assert!(
+            matches!(&events[0], ServerEvent::Error { error } if error.contains("Nothing to retranslate"))
+        );
+
+        client.send(Message::Text("not json".into())).await.unwrap();
+        let events = events_until_done(&mut client).await;
+        assert!(
+            matches!(&events[0], ServerEvent::Error { error } if error.contains("Invalid command"))
+        );
+
+        // The connection survives bad commands, and a disconnect does not affect the server
+        client.close(None).await.unwrap();
+        let mut other = connect(&url).await;
+        send(
+            &mut other,
+            serde_json::json!({ "type": "translate", "key": "k", "message": "Hi", "target": "fr" }),
+        )
+        .await;
+        assert_eq!(events_until_done(&mut other).await.len(), 2);
+    }
+}
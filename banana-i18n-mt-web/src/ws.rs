@@ -0,0 +1,379 @@
+//! WebSocket endpoint for collaborative translation
+//!
+//! Every connected reviewer sees translations as they are produced: results
+//! of a command are broadcast to all connections, errors only go back to the
+//! connection that sent the command.
+//!
+//! # Protocol
+//!
+//! Client commands:
+//! - `{"type": "translate", "key": ..., "message": ..., "target": ...}`
+//! - `{"type": "retranslate", "variant_index": N}`, optionally with `"key"`
+//!   (defaults to the last key this connection translated)
+//!
+//! Server events:
+//! - `{"type": "variant", "key": ..., "index": N, "source": ..., "translated": ...}`
+//! - `{"type": "reassembled", "key": ..., "translated": ...}`
+//! - `{"type": "error", "error": ...}`
+
+use axum::{
+    extract::{
+        State,
+        ws::{Message, WebSocket, WebSocketUpgrade},
+    },
+    response::IntoResponse,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tokio::sync::{broadcast, mpsc};
+use tokio::task::JoinSet;
+use tracing::{info, warn};
+
+use banana_i18n::parser::Parser;
+use banana_i18n_mt::{MessageContext, prepare_for_translation, reassemble_from_context};
+
+use crate::AppState;
+
+/// Number of events buffered for a slow connection before it skips ahead
+const EVENT_BUFFER: usize = 256;
+
+/// Default number of translated messages kept for retranslation
+pub const DEFAULT_MAX_SESSIONS: usize = 256;
+
+/// A command sent by a client
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClientCommand {
+    /// Expand, translate and reassemble a message
+    Translate {
+        key: String,
+        message: String,
+        target: String,
+    },
+    /// Translate one variant of an already translated message again
+    Retranslate {
+        #[serde(default)]
+        key: Option<String>,
+        variant_index: usize,
+    },
+}
+
+/// An event sent to clients
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ServerEvent {
+    /// The translation of one variant
+    Variant {
+        key: String,
+        index: usize,
+        source: String,
+        translated: String,
+    },
+    /// The reassembled wikitext after all variants are translated
+    Reassembled { key: String, translated: String },
+    /// A command failed
+    Error { error: String },
+}
+
+/// A message being translated, shared by all connections
+struct Session {
+    context: MessageContext,
+    target: String,
+    /// Value of `Sessions::clock` when the message was last (re)translated
+    last_used: u64,
+}
+
+/// Translated messages by key, bounded by evicting the least recently used
+struct Sessions {
+    by_key: HashMap<String, Session>,
+    clock: u64,
+    capacity: usize,
+}
+
+impl Sessions {
+    fn tick(&mut self) -> u64 {
+        self.clock += 1;
+        self.clock
+    }
+
+    /// Store a translated message, evicting the least recently used one when full
+    fn insert(&mut self, key: String, context: MessageContext, target: String) {
+        let last_used = self.tick();
+        if !self.by_key.contains_key(&key)
+            && self.by_key.len() >= self.capacity
+            && let Some(oldest) = self
+                .by_key
+                .iter()
+                .min_by_key(|(_, session)| session.last_used)
+                .map(|(key, _)| key.clone())
+        {
+            self.by_key.remove(&oldest);
+        }
+        self.by_key.insert(
+            key,
+            Session {
+                context,
+                target,
+                last_used,
+            },
+        );
+    }
+
+    /// Look up a message, marking it as recently used
+    fn get_mut(&mut self, key: &str) -> Option<&mut Session> {
+        let now = self.tick();
+        let session = self.by_key.get_mut(key)?;
+        session.last_used = now;
+        Some(session)
+    }
+}
+
+/// Shared state of the collaborative translation endpoint
+pub struct Hub {
+    /// Results, broadcast to every connection
+    events: broadcast::Sender<ServerEvent>,
+    /// Messages translated so far, for retranslation
+    sessions: Mutex<Sessions>,
+}
+
+impl Hub {
+    pub fn new() -> Self {
+        Self::with_max_sessions(DEFAULT_MAX_SESSIONS)
+    }
+
+    /// Create a hub keeping at most `max_sessions` (at least 1) messages for retranslation
+    ///
+    /// Translating more messages than that forgets the least recently used
+    /// one, so a long-running server does not grow without bound.
+    pub fn with_max_sessions(max_sessions: usize) -> Self {
+        Self {
+            events: broadcast::channel(EVENT_BUFFER).0,
+            sessions: Mutex::new(Sessions {
+                by_key: HashMap::new(),
+                clock: 0,
+                capacity: max_sessions.max(1),
+            }),
+        }
+    }
+
+    fn publish(&self, event: ServerEvent) {
+        // No receivers just means nobody is connected any more
+        let _ = self.events.send(event);
+    }
+}
+
+impl Default for Hub {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub async fn ws_handler(ws: WebSocketUpgrade, State(state): State<AppState>) -> impl IntoResponse {
+    ws.on_upgrade(move |socket| handle_socket(socket, state))
+}
+
+/// Serve one connection until the client disconnects
+///
+/// Commands run as concurrent tasks, so a slow translation does not hold up
+/// a retranslation or the events of other reviewers. Tasks still running
+/// when the client disconnects are aborted with the connection.
+async fn handle_socket(mut socket: WebSocket, state: AppState) {
+    let mut events = state.hub.events.subscribe();
+    let (errors_tx, mut errors) = mpsc::unbounded_channel();
+    let mut tasks = JoinSet::new();
+    let mut last_key: Option<String> = None;
+
+    loop {
+        let event = tokio::select! {
+            incoming = socket.recv() => {
+                let text = match incoming {
+                    Some(Ok(Message::Text(text))) => text,
+                    Some(Ok(Message::Close(_))) | None => break,
+                    Some(Ok(_)) => continue,
+                    Some(Err(e)) => {
+                        warn!("WebSocket receive failed: {}", e);
+                        break;
+                    }
+                };
+                match serde_json::from_str::<ClientCommand>(&text) {
+                    Ok(command) => {
+                        let command = with_default_key(command, &mut last_key);
+                        tasks.spawn(run_command(state.clone(), command, errors_tx.clone()));
+                        continue;
+                    }
+                    Err(e) => ServerEvent::Error {
+                        error: format!("Invalid command: {}", e),
+                    },
+                }
+            }
+            received = events.recv() => match received {
+                Ok(event) => event,
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    warn!("WebSocket client lagged, skipped {} events", skipped);
+                    continue;
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            },
+            Some(error) = errors.recv() => error,
+            Some(_) = tasks.join_next() => continue,
+        };
+
+        let payload = serde_json::to_string(&event).expect("events serialize to JSON");
+        if socket.send(Message::Text(payload.into())).await.is_err() {
+            break;
+        }
+    }
+
+    tasks.abort_all();
+}
+
+/// Remember the key of a translate command, and fill it into a retranslate without one
+fn with_default_key(command: ClientCommand, last_key: &mut Option<String>) -> ClientCommand {
+    match command {
+        ClientCommand::Translate { ref key, .. } => {
+            *last_key = Some(key.clone());
+            command
+        }
+        ClientCommand::Retranslate {
+            key: None,
+            variant_index,
+        } => ClientCommand::Retranslate {
+            key: last_key.clone(),
+            variant_index,
+        },
+        other => other,
+    }
+}
+
+async fn run_command(
+    state: AppState,
+    command: ClientCommand,
+    errors: mpsc::UnboundedSender<ServerEvent>,
+) {
+    let result = match command {
+        ClientCommand::Translate {
+            key,
+            message,
+            target,
+        } => translate(&state, key, &message, target).await,
+        ClientCommand::Retranslate { key, variant_index } => match key {
+            Some(key) => retranslate(&state, key, variant_index).await,
+            None => Err("Nothing to retranslate: no message was translated yet".to_string()),
+        },
+    };
+
+    if let Err(error) = result {
+        let _ = errors.send(ServerEvent::Error { error });
+    }
+}
+
+/// Translate all variants of a message, publishing each one and the reassembly
+async fn translate(
+    state: &AppState,
+    key: String,
+    message: &str,
+    target: String,
+) -> Result<(), String> {
+    info!("WebSocket: translating '{}' to {}", key, target);
+
+    let ast = Parser::new(message).parse();
+    let mut context = prepare_for_translation(&ast, "en", &key)
+        .map_err(|e| format!("Failed to prepare message for translation: {}", e))?;
+
+    let source_texts = context.source_texts();
+    state.check_source_size(&source_texts)?;
+    let translated = state
+        .translator
+        .translate_as_block(&source_texts, "en", &target)
+        .await
+        .map_err(|e| format!("Translation service error: {}", e))?;
+    context.update_translations(translated);
+
+    for (index, variant) in context.variants.iter().enumerate() {
+        state.hub.publish(ServerEvent::Variant {
+            key: key.clone(),
+            index,
+            source: variant.source_text.clone(),
+            translated: variant.translated_text.clone(),
+        });
+    }
+
+    let reassembled = reassemble_from_context(&context)
+        .map_err(|e| format!("Failed to reassemble message: {}", e))?;
+    state
+        .hub
+        .sessions
+        .lock()
+        .unwrap()
+        .insert(key.clone(), context, target);
+    state.hub.publish(ServerEvent::Reassembled {
+        key,
+        translated: reassembled,
+    });
+
+    Ok(())
+}
+
+/// Translate one variant again, publishing it and the new reassembly
+async fn retranslate(state: &AppState, key: String, variant_index: usize) -> Result<(), String> {
+    let (source, target) = {
+        let mut sessions = state.hub.sessions.lock().unwrap();
+        let session = sessions
+            .get_mut(&key)
+            .ok_or_else(|| format!("Message '{}' has not been translated", key))?;
+        let variant = session.context.variants.get(variant_index).ok_or_else(|| {
+            format!(
+                "Message '{}' has no variant {} ({} variants)",
+                key,
+                variant_index,
+                session.context.variants.len()
+            )
+        })?;
+        (variant.source_text.clone(), session.target.clone())
+    };
+
+    state.check_source_size(std::slice::from_ref(&source))?;
+    info!(
+        "WebSocket: retranslating variant {} of '{}'",
+        variant_index, key
+    );
+    let translated = state
+        .translator
+        .translate(&source, "en", &target)
+        .await
+        .map_err(|e| format!("Translation service error: {}", e))?;
+
+    // The message may have been translated again meanwhile; update what is there now
+    let context = {
+        let mut sessions = state.hub.sessions.lock().unwrap();
+        let session = sessions
+            .get_mut(&key)
+            .filter(|session| {
+                session
+                    .context
+                    .variants
+                    .get(variant_index)
+                    .is_some_and(|variant| variant.source_text == source)
+            })
+            .ok_or_else(|| format!("Message '{}' changed during retranslation", key))?;
+        session.context.variants[variant_index].translated_text = translated.clone();
+        session.context.clone()
+    };
+
+    state.hub.publish(ServerEvent::Variant {
+        key: key.clone(),
+        index: variant_index,
+        source,
+        translated,
+    });
+
+    let reassembled = reassemble_from_context(&context)
+        .map_err(|e| format!("Failed to reassemble message: {}", e))?;
+    state.hub.publish(ServerEvent::Reassembled {
+        key,
+        translated: reassembled,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,858 @@
+//! Standalone anchor protection for bring-your-own-MT integrations
+//!
+//! The expansion pipeline protects placeholders (`$1`, `$2`) from machine
+//! translation by replacing them with numeric anchor tokens (`777001`,
+//! `777002`), which MT systems leave intact. This module exposes that step on
+//! its own, for callers that run their own MT and do not need PLURAL/GENDER
+//! expansion or reassembly.
+//!
+//! This is the minimal integration point: `protect()` the source string, send
+//! the protected string to any MT system, then `unprotect()` the result.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use banana_i18n_mt::anchors::{protect, unprotect};
+//!
+//! let (protected, anchors) = protect("$1 sent $2 messages")?;
+//! assert_eq!(protected, "777001 sent 777002 messages");
+//!
+//! let translated = my_mt_system(&protected); // "777001 a envoyé 777002 messages"
+//! let result = unprotect(&translated, &anchors)?;
+//! assert_eq!(result, "$1 a envoyé $2 messages");
+//! ```
+//!
+//! # Anchor Styles
+//!
+//! Numeric anchors are the pipeline's internal format, but MT engines differ
+//! in what they leave alone. A provider declares its `AnchorStyle` through
+//! `MachineTranslator::preferred_anchor_style()`; the orchestration layer in
+//! `pipeline` renders numeric anchors in that style with `AnchorStyle::apply()`
+//! before translation and converts them back with `AnchorStyle::recover()`.
+//!
+//! # Right-to-Left Output
+//!
+//! Engines translating into Arabic or Hebrew often wrap the left-to-right
+//! anchors in directional marks (U+200E, U+200F, U+061C) or isolates
+//! (U+2066..U+2069), inside styled anchors too (`⟦\u{200F}1⟧`). `recover()`
+//! ignores marks inside an anchor, and `strip_bidi_around_anchors()` removes
+//! the ones MT added around it, so they do not leak into the restored message.
+
+use crate::error::{MtError, MtResult};
+use crate::expansion::{LINK_ANCHOR_BASE, PLACEHOLDER_ANCHOR_BASE, placeholder_anchor};
+use regex::Regex;
+
+/// A placeholder replaced by an anchor token
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnchorToken {
+    /// The anchor token sent to MT (e.g., "777001")
+    pub anchor: String,
+    /// The original text the anchor stands for (e.g., "$1")
+    pub original: String,
+}
+
+/// Invisible separator between an anchor and a digit written next to it
+///
+/// Without it, `77$1` would be sent as the single number `77777001`, which
+/// no longer reads as an anchor. Restoring the anchor removes a joiner before
+/// it; a joiner after it stays, as `$1` directly followed by a digit would
+/// read as a different placeholder.
+pub const ANCHOR_JOINER: char = '\u{2060}';
+
+/// Private-use character written around anchors while a text is built, until
+/// `join_anchors()` knows what ended up next to them
+pub(crate) const ANCHOR_MARK: char = '\u{E000}';
+
+/// A numeric anchor found by `find_anchors()`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AnchorMatch<'a> {
+    /// Byte offset of the match, including a leading `ANCHOR_JOINER`
+    pub start: usize,
+    /// Byte offset after the six digits
+    pub end: usize,
+    /// The six-digit anchor (e.g., "777001")
+    pub anchor: &'a str,
+}
+
+impl AnchorMatch<'_> {
+    /// Whether this is a link anchor (`888NNN`) rather than a placeholder anchor
+    pub fn is_link(&self) -> bool {
+        self.anchor.starts_with("888")
+    }
+
+    /// The anchor's index: 1 for both "777001" and "888001"
+    pub fn index(&self) -> usize {
+        self.anchor[3..].parse().unwrap()
+    }
+}
+
+/// Find the numeric anchors in a text
+///
+/// A run of ASCII digits holds anchors only if it splits exactly into
+/// six-digit `777NNN`/`888NNN` chunks, as `$1$2` ("777001777002") does. Any
+/// other run is a literal number and is left alone, so neither `7770123` nor
+/// `8880001` is read as an anchor.
+///
+/// # Arguments
+/// * `text` - Anchored text, before or after translation
+///
+/// # Returns
+/// The anchors in order of appearance
+pub fn find_anchors(text: &str) -> Vec<AnchorMatch<'_>> {
+    let bytes = text.as_bytes();
+    let joiner = ANCHOR_JOINER.len_utf8();
+    let mut anchors: Vec<AnchorMatch> = Vec::new();
+    let mut pos = 0;
+    while pos < bytes.len() {
+        if !bytes[pos].is_ascii_digit() {
+            pos += 1;
+            continue;
+        }
+        let start = pos;
+        while pos < bytes.len() && bytes[pos].is_ascii_digit() {
+            pos += 1;
+        }
+        let run = &bytes[start..pos];
+        let is_anchors = run.len().is_multiple_of(6)
+            && run
+                .chunks(6)
+                .all(|chunk| chunk.starts_with(b"777") || chunk.starts_with(b"888"));
+        if !is_anchors {
+            continue;
+        }
+
+        // A joiner only belongs to the anchor when it separates it from a digit
+        let joined = text[..start].ends_with(ANCHOR_JOINER)
+            && text[..start - joiner].ends_with(|c: char| c.is_ascii_digit());
+        for chunk_start in (start..pos).step_by(6) {
+            let mut found = AnchorMatch {
+                start: chunk_start,
+                end: chunk_start + 6,
+                anchor: &text[chunk_start..chunk_start + 6],
+            };
+            if chunk_start == start && joined {
+                found.start -= joiner;
+            }
+            anchors.push(found);
+        }
+    }
+    anchors
+}
+
+/// Replace every anchor found by `find_anchors()`, leading joiners included
+pub(crate) fn replace_anchors(
+    text: &str,
+    mut replace: impl FnMut(&AnchorMatch) -> String,
+) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut last = 0;
+    for found in find_anchors(text) {
+        result.push_str(&text[last..found.start]);
+        result.push_str(&replace(&found));
+        last = found.end;
+    }
+    result.push_str(&text[last..]);
+    result
+}
+
+/// Settle the `ANCHOR_MARK`s written around anchors
+///
+/// A mark between two digits becomes an `ANCHOR_JOINER`; every other mark,
+/// including those between two adjacent anchors, is dropped.
+pub(crate) fn join_anchors(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let is_digit = |i: Option<usize>| {
+        i.and_then(|i| chars.get(i))
+            .is_some_and(char::is_ascii_digit)
+    };
+    chars
+        .iter()
+        .enumerate()
+        .filter_map(|(i, &c)| match c {
+            ANCHOR_MARK if is_digit(i.checked_sub(1)) && is_digit(Some(i + 1)) => {
+                Some(ANCHOR_JOINER)
+            }
+            ANCHOR_MARK => None,
+            c => Some(c),
+        })
+        .collect()
+}
+
+/// How anchors are written in the text sent to a provider
+///
+/// | Style        | Placeholder `$1` | First link     |
+/// |--------------|------------------|----------------|
+/// | `Numeric`    | `777001`         | `888001`       |
+/// | `Underscore` | `_ID1_`          | `_LINK1_`      |
+/// | `XmlTag`     | `<x id="1"/>`    | `<x id="L1"/>` |
+/// | `Bracketed`  | `⟦1⟧`            | `⟦L1⟧`         |
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AnchorStyle {
+    /// Six-digit numbers, the pipeline's internal format (default)
+    #[default]
+    Numeric,
+    /// Underscore-delimited identifiers
+    Underscore,
+    /// Self-closing XML tags, for engines with tag handling
+    XmlTag,
+    /// Numbers in mathematical white square brackets, which tokenizers keep
+    /// whole where they split `_ID1_` at the underscores
+    Bracketed,
+}
+
+impl AnchorStyle {
+    /// Render the numeric anchors in `text` in this style
+    ///
+    /// Only anchors found by `find_anchors()` are rendered; other numbers,
+    /// such as `7770123`, are left as they are. An `ANCHOR_JOINER` in front
+    /// of an anchor is kept, so `recover()` gives back the original text.
+    pub fn apply(&self, text: &str) -> String {
+        if *self == AnchorStyle::Numeric {
+            return text.to_string();
+        }
+
+        replace_anchors(text, |anchor| {
+            let joiner = &text[anchor.start..anchor.end - anchor.anchor.len()];
+            let index = anchor.index();
+            let styled = match (self, anchor.is_link()) {
+                (AnchorStyle::Underscore, false) => format!("_ID{}_", index),
+                (AnchorStyle::Underscore, true) => format!("_LINK{}_", index),
+                (AnchorStyle::XmlTag, false) => format!("<x id=\"{}\"/>", index),
+                (AnchorStyle::XmlTag, true) => format!("<x id=\"L{}\"/>", index),
+                (AnchorStyle::Bracketed, false) => format!("⟦{}⟧", index),
+                (AnchorStyle::Bracketed, true) => format!("⟦L{}⟧", index),
+                (AnchorStyle::Numeric, _) => anchor.anchor.to_string(),
+            };
+            format!("{}{}", joiner, styled)
+        })
+    }
+
+    /// Convert anchors in this style back to numeric anchors
+    ///
+    /// Tolerates the variations MT engines commonly introduce: changed case
+    /// and inner whitespace for `Underscore`, single quotes or a space
+    /// before `/>` for `XmlTag`, and inner whitespace for `Bracketed`.
+    /// Directional marks count as whitespace.
+    pub fn recover(&self, text: &str) -> String {
+        let Some(pattern) = self.recover_pattern() else {
+            return text.to_string();
+        };
+        let re = Regex::new(pattern).unwrap();
+        re.replace_all(text, |caps: &regex::Captures| {
+            let index: usize = caps[2].parse().unwrap();
+            let link = matches!(&caps[1], "L" | "l") || caps[1].eq_ignore_ascii_case("LINK");
+            let base = if link {
+                LINK_ANCHOR_BASE
+            } else {
+                PLACEHOLDER_ANCHOR_BASE
+            };
+            format!("{}", base + index)
+        })
+        .to_string()
+    }
+
+    /// Pattern `recover()` treats as an anchor in this style
+    fn recover_pattern(&self) -> Option<&'static str> {
+        match self {
+            AnchorStyle::Numeric => None,
+            AnchorStyle::Underscore => Some(
+                r"(?i)_[\s\x{200E}\x{200F}\x{061C}]*(ID|LINK)[\s\x{200E}\x{200F}\x{061C}]*(\d+)[\s\x{200E}\x{200F}\x{061C}]*_",
+            ),
+            AnchorStyle::XmlTag => Some(
+                r#"<x[\s\x{200E}\x{200F}\x{061C}]+id\s*=\s*["']?(L?)(\d+)["']?[\s\x{200E}\x{200F}\x{061C}]*/?>"#,
+            ),
+            AnchorStyle::Bracketed => Some(
+                r"⟦[\s\x{200E}\x{200F}\x{061C}]*([Ll]?)[\s\x{200E}\x{200F}\x{061C}]*(\d+)[\s\x{200E}\x{200F}\x{061C}]*⟧",
+            ),
+        }
+    }
+}
+
+/// Whether a text contains directional marks or isolates
+pub fn has_bidi_controls(text: &str) -> bool {
+    text.chars().any(|c| {
+        matches!(
+            c,
+            '\u{200E}' | '\u{200F}' | '\u{061C}' | '\u{2066}'..='\u{2069}'
+        )
+    })
+}
+
+/// Remove directional marks MT put around numeric anchors
+///
+/// Marks directly before or after an anchor are dropped, as is an isolate
+/// pair that wraps nothing but the anchor. Marks elsewhere, such as between
+/// words, are kept. Only call this for texts whose source had no
+/// directional controls (see `has_bidi_controls()`); marks written on
+/// purpose next to a placeholder would be lost.
+///
+/// # Example
+/// ```ignore
+/// let text = "أرسل \u{200F}777001\u{200F} رسالة";
+/// assert_eq!(strip_bidi_around_anchors(text), "أرسل 777001 رسالة");
+/// ```
+pub fn strip_bidi_around_anchors(text: &str) -> String {
+    let re = Regex::new(
+        r"[\x{200E}\x{200F}\x{061C}]*(?:[\x{2066}-\x{2068}]((?:777|888)\d{3})\x{2069}|((?:777|888)\d{3}))[\x{200E}\x{200F}\x{061C}]*",
+    )
+    .unwrap();
+    re.replace_all(text, |caps: &regex::Captures| {
+        caps.get(1)
+            .or_else(|| caps.get(2))
+            .unwrap()
+            .as_str()
+            .to_string()
+    })
+    .to_string()
+}
+
+/// A problem in source text that would be corrupted by the pipeline
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PrecheckWarning {
+    /// Text a provider anchor style would recover as an anchor (e.g. `_ID1_`)
+    AnchorLike { token: String, offset: usize },
+    /// A number in the numeric anchor range (e.g. `777001`), which would be
+    /// restored as a placeholder or link
+    NumericAnchor { token: String, offset: usize },
+    /// `{{` without a matching `}}`, or `}}` without a matching `{{`
+    UnbalancedBraces { offset: usize },
+}
+
+impl std::fmt::Display for PrecheckWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PrecheckWarning::AnchorLike { token, offset } => {
+                write!(f, "Anchor-like token '{}' at byte {}", token, offset)
+            }
+            PrecheckWarning::NumericAnchor { token, offset } => {
+                write!(
+                    f,
+                    "Number '{}' at byte {} collides with anchor tokens",
+                    token, offset
+                )
+            }
+            PrecheckWarning::UnbalancedBraces { offset } => {
+                write!(f, "Unbalanced braces at byte {}", offset)
+            }
+        }
+    }
+}
+
+/// Check source text for content the pipeline would corrupt
+///
+/// Run before expansion. Anchor tokens left in a message by a previous
+/// round or a copy-paste (`_ID1_`, `<x id="1"/>`, `777001`) would be
+/// "recovered" as placeholders after translation, and unbalanced braces
+/// make magic words parse differently than intended. Callers can reject or
+/// sanitize messages with warnings.
+///
+/// # Arguments
+/// * `message` - Source wikitext
+///
+/// # Returns
+/// Warnings in order of their position in the message; empty if the source is safe
+pub fn precheck_source(message: &str) -> Vec<PrecheckWarning> {
+    let mut warnings = Vec::new();
+
+    for style in [
+        AnchorStyle::Underscore,
+        AnchorStyle::XmlTag,
+        AnchorStyle::Bracketed,
+    ] {
+        let re = Regex::new(style.recover_pattern().unwrap()).unwrap();
+        warnings.extend(re.find_iter(message).map(|m| PrecheckWarning::AnchorLike {
+            token: m.as_str().to_string(),
+            offset: m.start(),
+        }));
+    }
+
+    // Only numbers that read as whole anchors are restored as placeholders
+    warnings.extend(
+        find_anchors(message)
+            .into_iter()
+            .map(|m| PrecheckWarning::NumericAnchor {
+                token: m.anchor.to_string(),
+                offset: m.end - m.anchor.len(),
+            }),
+    );
+
+    let mut open = Vec::new();
+    let mut rest = message;
+    let mut offset = 0;
+    while !rest.is_empty() {
+        let step = if rest.starts_with("{{") {
+            open.push(offset);
+            2
+        } else if rest.starts_with("}}") {
+            if open.pop().is_none() {
+                warnings.push(PrecheckWarning::UnbalancedBraces { offset });
+            }
+            2
+        } else {
+            rest.chars().next().map_or(1, char::len_utf8)
+        };
+        rest = &rest[step..];
+        offset += step;
+    }
+    warnings.extend(
+        open.into_iter()
+            .map(|offset| PrecheckWarning::UnbalancedBraces { offset }),
+    );
+
+    warnings.sort_by_key(|warning| match warning {
+        PrecheckWarning::AnchorLike { offset, .. }
+        | PrecheckWarning::NumericAnchor { offset, .. }
+        | PrecheckWarning::UnbalancedBraces { offset } => *offset,
+    });
+    warnings
+}
+
+/// Replace placeholders with anchor tokens for machine translation
+///
+/// A placeholder written right after a digit (`77$1`) is kept apart from it
+/// by an `ANCHOR_JOINER`, so the anchor can still be found after translation.
+///
+/// # Arguments
+/// * `text` - Source text containing `$N` placeholders
+///
+/// # Returns
+/// * `Ok((String, Vec<AnchorToken>))` - The MT-ready string and one
+///   `AnchorToken` per distinct placeholder, in order of first appearance
+/// * `Err(MtError::AnchorTokenError)` - If a placeholder is beyond
+///   `MAX_ANCHOR_INDEX` (`$1000` and up) and so has no anchor
+pub fn protect(text: &str) -> MtResult<(String, Vec<AnchorToken>)> {
+    let re = Regex::new(r"\$(\d+)").unwrap();
+
+    let mut anchors: Vec<AnchorToken> = Vec::new();
+    for cap in re.captures_iter(text) {
+        let original = cap[0].to_string();
+        if anchors.iter().any(|a| a.original == original) {
+            continue;
+        }
+        let index = cap[1].parse::<usize>().unwrap_or(usize::MAX);
+        anchors.push(AnchorToken {
+            anchor: placeholder_anchor(index)?,
+            original,
+        });
+    }
+
+    let protected = re
+        .replace_all(text, |caps: &regex::Captures| {
+            anchors.iter().find(|a| a.original == caps[0]).map_or_else(
+                || caps[0].to_string(),
+                |a| format!("{ANCHOR_MARK}{}{ANCHOR_MARK}", a.anchor),
+            )
+        })
+        .to_string();
+    Ok((join_anchors(&protected), anchors))
+}
+
+/// Restore placeholders in translated text
+///
+/// Every anchor must survive translation; MT output that dropped or mangled an
+/// anchor cannot be restored faithfully and is rejected.
+///
+/// # Arguments
+/// * `translated` - MT output of a string produced by `protect()`
+/// * `anchors` - The anchors returned by `protect()`
+///
+/// # Returns
+/// * `Ok(String)` - The translation with original placeholders restored
+/// * `Err(MtError::AnchorTokenError)` - If an anchor is missing from the translation
+pub fn unprotect(translated: &str, anchors: &[AnchorToken]) -> MtResult<String> {
+    let found = find_anchors(translated);
+    let missing: Vec<&str> = anchors
+        .iter()
+        .filter(|a| !found.iter().any(|m| m.anchor == a.anchor))
+        .map(|a| a.original.as_str())
+        .collect();
+    if !missing.is_empty() {
+        return Err(MtError::AnchorTokenError(
+            format!(
+                "Translation lost placeholders {}: '{}'",
+                missing.join(", "),
+                translated
+            )
+            .into(),
+        ));
+    }
+
+    Ok(replace_anchors(translated, |m| {
+        anchors.iter().find(|a| a.anchor == m.anchor).map_or_else(
+            || translated[m.start..m.end].to_string(),
+            |a| a.original.clone(),
+        )
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // ========== Anchor Style Tests ==========
+
+    #[test]
+    fn test_anchor_styles_round_trip() {
+        let text = "777001 sent 777002 messages via 888001";
+        for style in [
+            AnchorStyle::Numeric,
+            AnchorStyle::Underscore,
+            AnchorStyle::XmlTag,
+            AnchorStyle::Bracketed,
+        ] {
+            assert_eq!(style.recover(&style.apply(text)), text);
+        }
+        assert_eq!(
+            AnchorStyle::Bracketed.apply(text),
+            "⟦1⟧ sent ⟦2⟧ messages via ⟦L1⟧"
+        );
+        assert_eq!(
+            AnchorStyle::XmlTag.apply(text),
+            r#"<x id="1"/> sent <x id="2"/> messages via <x id="L1"/>"#
+        );
+        assert_eq!(
+            AnchorStyle::Underscore.apply(text),
+            "_ID1_ sent _ID2_ messages via _LINK1_"
+        );
+    }
+
+    #[test]
+    fn test_anchor_styles_leave_literal_numbers() {
+        let joined = format!("Room 77{}777001", ANCHOR_JOINER);
+        let text = format!("Call 7770123 or 888001234 about 777002, {}", joined);
+        for style in [
+            AnchorStyle::Underscore,
+            AnchorStyle::XmlTag,
+            AnchorStyle::Bracketed,
+        ] {
+            assert_eq!(style.recover(&style.apply(&text)), text);
+        }
+        assert_eq!(
+            AnchorStyle::Bracketed.apply(&text),
+            format!(
+                "Call 7770123 or 888001234 about ⟦2⟧, Room 77{}⟦1⟧",
+                ANCHOR_JOINER
+            )
+        );
+    }
+
+    #[test]
+    fn test_anchor_style_recovers_mt_variations() {
+        assert_eq!(
+            AnchorStyle::XmlTag.recover("<x id='1' /> a envoyé <x id=\"2\"/> messages"),
+            "777001 a envoyé 777002 messages"
+        );
+        assert_eq!(
+            AnchorStyle::Underscore.recover("_id1_ a envoyé _ ID2 _ messages"),
+            "777001 a envoyé 777002 messages"
+        );
+        assert_eq!(
+            AnchorStyle::Bracketed.recover("⟦ 1 ⟧ a envoyé ⟦2⟧ messages via ⟦l1⟧"),
+            "777001 a envoyé 777002 messages via 888001"
+        );
+    }
+
+    #[test]
+    fn test_recover_and_strip_rtl_marks_around_anchors() {
+        assert_eq!(
+            AnchorStyle::Bracketed.recover("أرسل ⟦\u{200F}1⟧ رسالة إلى ⟦2\u{200E}⟧"),
+            "أرسل 777001 رسالة إلى 777002"
+        );
+        assert_eq!(
+            AnchorStyle::Underscore.recover("שלח _ID\u{200F}1_"),
+            "שלח 777001"
+        );
+
+        let marked = "أرسل \u{200F}777001\u{200F} إلى \u{2068}777002\u{2069}\u{061C}.";
+        assert!(has_bidi_controls(marked));
+        let stripped = strip_bidi_around_anchors(marked);
+        assert_eq!(stripped, "أرسل 777001 إلى 777002.");
+        assert!(!has_bidi_controls(&stripped));
+
+        // Marks away from anchors stay
+        let between_words = "مرحبا\u{200F} بالعالم 777001";
+        assert_eq!(strip_bidi_around_anchors(between_words), between_words);
+    }
+
+    // ========== Source Precheck Tests ==========
+
+    #[test]
+    fn test_precheck_flags_leftover_anchor_tokens() {
+        let warnings = precheck_source("_ID1_ sent {{PLURAL:$2|a message|$2 messages}}");
+        assert_eq!(
+            warnings,
+            vec![PrecheckWarning::AnchorLike {
+                token: "_ID1_".to_string(),
+                offset: 0,
+            }]
+        );
+        assert_eq!(
+            warnings[0].to_string(),
+            "Anchor-like token '_ID1_' at byte 0"
+        );
+    }
+
+    #[test]
+    fn test_precheck_flags_numeric_anchors_and_braces() {
+        let warnings = precheck_source("Ticket 777001 has {{PLURAL:$1|a reply|$1 replies}");
+        assert_eq!(
+            warnings,
+            vec![
+                PrecheckWarning::NumericAnchor {
+                    token: "777001".to_string(),
+                    offset: 7,
+                },
+                PrecheckWarning::UnbalancedBraces { offset: 18 },
+            ]
+        );
+
+        assert_eq!(
+            precheck_source("}} {{GENDER:$1|He|She}}"),
+            vec![PrecheckWarning::UnbalancedBraces { offset: 0 }]
+        );
+    }
+
+    #[test]
+    fn test_precheck_ignores_numbers_that_are_not_anchors() {
+        assert!(precheck_source("Call 7770123 or 888001234, room 1777001").is_empty());
+        assert_eq!(
+            precheck_source("Codes 777001888002"),
+            vec![
+                PrecheckWarning::NumericAnchor {
+                    token: "777001".to_string(),
+                    offset: 6,
+                },
+                PrecheckWarning::NumericAnchor {
+                    token: "888002".to_string(),
+                    offset: 12,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_precheck_passes_clean_source() {
+        assert!(
+            precheck_source("{{GENDER:$1|He|She}} sent {{PLURAL:$2|one|$2}} [[Help:ID|IDs]]")
+                .is_empty()
+        );
+    }
+
+    // ========== Protect/Unprotect Tests ==========
+
+    #[test]
+    fn test_protect_rejects_out_of_range_placeholders() {
+        let (protected, anchors) = protect("$999 and $1").unwrap();
+        assert_eq!(protected, "777999 and 777001");
+        assert_eq!(unprotect(&protected, &anchors).unwrap(), "$999 and $1");
+
+        match protect("$999 and $1000") {
+            Err(MtError::AnchorTokenError(msg)) => assert!(msg.contains("$1000")),
+            other => panic!("Expected AnchorTokenError, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_protect_placeholders() {
+        let (protected, anchors) = protect("$1 sent $2 messages to $1").unwrap();
+        assert_eq!(protected, "777001 sent 777002 messages to 777001");
+        assert_eq!(
+            anchors,
+            vec![
+                AnchorToken {
+                    anchor: "777001".to_string(),
+                    original: "$1".to_string(),
+                },
+                AnchorToken {
+                    anchor: "777002".to_string(),
+                    original: "$2".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_round_trip_through_transformation() {
+        let source = "$1 sent $2 messages";
+        let (protected, anchors) = protect(source).unwrap();
+
+        // Arbitrary "MT": reverse word order and uppercase the words
+        let translated = protected
+            .split(' ')
+            .rev()
+            .map(|w| w.to_uppercase())
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        let restored = unprotect(&translated, &anchors).unwrap();
+        assert_eq!(restored, "MESSAGES $2 SENT $1");
+    }
+
+    #[test]
+    fn test_adjacent_placeholders() {
+        let (protected, anchors) = protect("$1$2$3").unwrap();
+        assert_eq!(protected, "777001777002777003");
+        assert_eq!(unprotect(&protected, &anchors).unwrap(), "$1$2$3");
+    }
+
+    #[test]
+    fn test_literal_numbers_are_not_anchors() {
+        let (protected, anchors) = protect("Call 7770123 or $1").unwrap();
+        assert_eq!(protected, "Call 7770123 or 777001");
+        assert_eq!(
+            unprotect("Appelez 7770123 ou 777001", &anchors).unwrap(),
+            "Appelez 7770123 ou $1"
+        );
+
+        for literal in ["7770123", "888001234", "1777001", "777001888"] {
+            assert!(find_anchors(literal).is_empty(), "{}", literal);
+        }
+        let found = find_anchors("777001888002");
+        assert_eq!(found.len(), 2);
+        assert!(found[1].is_link());
+        assert_eq!(found[1].index(), 2);
+    }
+
+    #[test]
+    fn test_placeholder_after_digit() {
+        let (protected, anchors) = protect("Room 77$1 is free").unwrap();
+        assert_eq!(protected, format!("Room 77{}777001 is free", ANCHOR_JOINER));
+        assert_eq!(
+            unprotect(&protected, &anchors).unwrap(),
+            "Room 77$1 is free"
+        );
+
+        // Without the joiner the digits run together and the anchor is lost
+        assert!(unprotect("Salle 77777001 libre", &anchors).is_err());
+    }
+
+    #[test]
+    fn test_anchor_before_digit() {
+        // The joiner stays, as "$15" would be a different placeholder
+        let (_, anchors) = protect("$1").unwrap();
+        let text = format!("777001{}5 files", ANCHOR_JOINER);
+        assert_eq!(
+            unprotect(&text, &anchors).unwrap(),
+            format!("$1{}5 files", ANCHOR_JOINER)
+        );
+
+        // MT that merged the anchor into a following number lost it
+        let (_, anchors) = protect("$1 5 files").unwrap();
+        assert!(unprotect("7770015 fichiers", &anchors).is_err());
+    }
+
+    #[test]
+    fn test_unprotect_rejects_lost_anchor() {
+        let (_, anchors) = protect("Hello, $1!").unwrap();
+        let result = unprotect("Bonjour !", &anchors);
+        match result {
+            Err(MtError::AnchorTokenError(msg)) => assert!(msg.contains("$1")),
+            other => panic!("Expected AnchorTokenError, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_no_placeholders() {
+        let (protected, anchors) = protect("Hello world").unwrap();
+        assert_eq!(protected, "Hello world");
+        assert!(anchors.is_empty());
+        assert_eq!(
+            unprotect("Bonjour le monde", &anchors).unwrap(),
+            "Bonjour le monde"
+        );
+    }
+
+    // ========== Property Tests ==========
+
+    mod properties {
+        use super::*;
+        use crate::data::TranslationVariant;
+        use crate::expansion::{MAX_ANCHOR_INDEX, replace_placeholders_with_anchors};
+        use crate::reassembly::Reassembler;
+        use proptest::prelude::*;
+        use std::collections::HashMap;
+
+        /// Text without `$`, including numbers that start like anchors
+        ///
+        /// It never starts with a digit, which would extend a placeholder
+        /// before it, and holds no number that is itself a run of anchors.
+        fn plain_text() -> impl Strategy<Value = String> {
+            prop_oneof![
+                3 => "[a-zA-Z ,.!?éñ日本語-]{1,8}",
+                1 => "[a-z ][0-9]{1,8}",
+                1 => "[a-z ](777|888)[0-9]{0,7}",
+            ]
+            .prop_filter("number reads as anchors", |text| {
+                find_anchors(text).is_empty()
+            })
+        }
+
+        /// Placeholder indices over the whole anchor range, biased towards
+        /// indices that are prefixes of one another ($1, $10, $100)
+        fn placeholder() -> impl Strategy<Value = String> {
+            prop_oneof![
+                2 => prop::sample::select(vec![1usize, 10, 11, 100, 101, 110]),
+                1 => 1usize..=MAX_ANCHOR_INDEX,
+            ]
+            .prop_map(|index| format!("${}", index))
+        }
+
+        /// Text mixing placeholders (adjacent and repeated) with plain text
+        fn message() -> impl Strategy<Value = String> {
+            prop::collection::vec(prop_oneof![plain_text(), placeholder()], 0..10)
+                .prop_map(|parts| parts.concat())
+        }
+
+        fn placeholder_count(text: &str) -> usize {
+            Regex::new(r"\$\d+").unwrap().find_iter(text).count()
+        }
+
+        fn anchor_count(text: &str) -> usize {
+            find_anchors(text).len()
+        }
+
+        /// Restore anchors the way reassembly does
+        fn recover(anchored: &str) -> String {
+            let variant = TranslationVariant::with_translation(
+                HashMap::new(),
+                String::new(),
+                anchored.to_string(),
+            );
+            Reassembler::new(HashMap::new())
+                .reassemble(vec![variant])
+                .unwrap()
+        }
+
+        proptest! {
+            #[test]
+            fn pipeline_anchors_round_trip(source in message()) {
+                let anchored = replace_placeholders_with_anchors(&source).unwrap();
+                prop_assert!(!anchored.contains('$'));
+                prop_assert_eq!(anchor_count(&anchored), placeholder_count(&source));
+                prop_assert_eq!(recover(&anchored), source);
+            }
+
+            #[test]
+            fn protect_unprotect_round_trip(source in message()) {
+                let (protected, anchors) = protect(&source).unwrap();
+                prop_assert!(!protected.contains('$'));
+                prop_assert_eq!(unprotect(&protected, &anchors).unwrap(), source);
+            }
+
+            #[test]
+            fn anchor_styles_round_trip(
+                source in message(),
+                style in prop::sample::select(vec![
+                    AnchorStyle::Underscore,
+                    AnchorStyle::XmlTag,
+                    AnchorStyle::Bracketed,
+                ]),
+            ) {
+                let anchored = replace_placeholders_with_anchors(&source).unwrap();
+                let styled = style.apply(&anchored);
+                prop_assert_eq!(anchor_count(&styled), 0);
+                prop_assert_eq!(recover(&style.recover(&styled)), source);
+            }
+        }
+    }
+}
@@ -1,14 +1,23 @@
 use banana_i18n::parser::Parser;
+use banana_i18n_mt::anchors::find_anchors;
 use banana_i18n_mt::{
-    GoogleTranslateProvider, MachineTranslator, MockMode, MockTranslator, Reassembler,
-    prepare_for_translation,
+    MessageContext, Reassembler, SUPPORTED_PROVIDERS, from_config, prepare_for_translation,
 };
-use clap::{Arg, Command};
-use std::env;
+use clap::{Arg, ArgMatches, Command};
+use serde_json::json;
+use std::collections::BTreeSet;
+use std::io::{IsTerminal, Write};
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let matches = Command::new("banana-mt")
+    let matches = cli().get_matches();
+    let mut stdout = std::io::stdout();
+    let color = stdout.is_terminal() && std::env::var_os("NO_COLOR").is_none();
+    run(&matches, &mut stdout, color).await
+}
+
+fn cli() -> Command {
+    Command::new("banana-mt")
         .version("0.1.0")
         .about("Machine Translation CLI for banana-i18n")
         .arg(
@@ -30,11 +39,21 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 .help("Source language code (default: en)")
                 .default_value("en"),
         )
+        .arg(
+            Arg::new("provider")
+                .long("provider")
+                .short('p')
+                .help(format!(
+                    "Translation provider: {} (default: google)",
+                    SUPPORTED_PROVIDERS.join(", ")
+                ))
+                .default_value("google"),
+        )
         .arg(
             Arg::new("mock")
                 .long("mock")
                 .short('m')
-                .help("Use mock translator instead of Google Translate")
+                .help("Use mock translator (same as --provider mock)")
                 .action(clap::ArgAction::SetTrue),
         )
         .arg(
@@ -50,23 +69,45 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 .short('k')
                 .help("Message key for context (default: auto-generated)"),
         )
-        .get_matches();
+        .arg(
+            Arg::new("format")
+                .long("format")
+                .short('f')
+                .help("Output format: text (wikitext only), pretty or json (default: text)")
+                .value_parser(["text", "pretty", "json"])
+                .default_value("text"),
+        )
+}
 
+/// Translate the message given on the command line and write the result to `out`
+///
+/// `color` enables ANSI colors in `pretty` output.
+async fn run(
+    matches: &ArgMatches,
+    out: &mut impl Write,
+    color: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
     let source_message = matches.get_one::<String>("message").unwrap();
     let target_locale = matches.get_one::<String>("target-locale").unwrap();
     let source_locale = matches.get_one::<String>("source-locale").unwrap();
-    let use_mock = matches.get_flag("mock");
-    let verbose = matches.get_flag("verbose");
+    let provider_name = if matches.get_flag("mock") {
+        "mock"
+    } else {
+        matches.get_one::<String>("provider").unwrap().as_str()
+    };
+    let format = matches.get_one::<String>("format").unwrap().as_str();
+    // Progress output would corrupt the other formats
+    let verbose = matches.get_flag("verbose") && format == "text";
     let message_key = matches
         .get_one::<String>("key")
         .map(|s| s.as_str())
         .unwrap_or("cli-message");
 
     if verbose {
-        println!("📝 Source: \"{}\"", source_message);
-        println!("🌍 {} → {}", source_locale, target_locale);
-        println!("🔑 Key: {}", message_key);
-        println!();
+        writeln!(out, "📝 Source: \"{}\"", source_message)?;
+        writeln!(out, "🌍 {} → {}", source_locale, target_locale)?;
+        writeln!(out, "🔑 Key: {}", message_key)?;
+        writeln!(out)?;
     }
 
     // 1. Parse message
@@ -74,7 +115,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let ast = parser.parse();
 
     if verbose {
-        println!("✅ Parsed message ({} nodes)", ast.len());
+        writeln!(out, "✅ Parsed message ({} nodes)", ast.len())?;
     }
 
     // 2. Prepare for translation
@@ -87,39 +128,37 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     };
 
     if verbose {
-        println!("📦 Expanded to {} variants", context.variant_count());
-        println!("   Variables: {:?}", context.variable_types);
+        writeln!(out, "📦 Expanded to {} variants", context.variant_count())?;
+        writeln!(out, "   Variables: {:?}", context.variable_types)?;
 
         if context.variant_count() <= 10 {
             for (i, variant) in context.variants.iter().enumerate() {
-                println!("   [{}] \"{}\"", i, variant.source_text);
+                writeln!(out, "   [{}] \"{}\"", i, variant.source_text)?;
             }
         } else {
             for (i, variant) in context.variants.iter().take(5).enumerate() {
-                println!("   [{}] \"{}\"", i, variant.source_text);
+                writeln!(out, "   [{}] \"{}\"", i, variant.source_text)?;
             }
-            println!("   ... {} more variants", context.variant_count() - 5);
+            writeln!(out, "   ... {} more variants", context.variant_count() - 5)?;
         }
-        println!();
+        writeln!(out)?;
     }
 
     // 3. Translate
     let source_texts = context.source_texts();
-    let translated_texts = if use_mock {
-        let mock_translator = MockTranslator::new(MockMode::Suffix);
-        mock_translator
+    let provider = match from_config(provider_name) {
+        Ok(provider) => provider,
+        Err(e) => {
+            eprintln!("❌ {}", e);
+            eprintln!("   Or use --mock to use mock translator");
+            return Err(e.into());
+        }
+    };
+    let translated_texts = if provider_name == "mock" {
+        provider
             .translate_batch(&source_texts, source_locale, target_locale)
             .await?
     } else {
-        // Check for API key
-        if env::var("GOOGLE_TRANSLATE_API_KEY").is_err() {
-            eprintln!("❌ GOOGLE_TRANSLATE_API_KEY environment variable not set");
-            eprintln!("   Set it with: export GOOGLE_TRANSLATE_API_KEY=your_api_key");
-            eprintln!("   Or use --mock to use mock translator");
-            return Err("Missing API key".into());
-        }
-
-        let provider = GoogleTranslateProvider::from_env()?;
         provider
             .translate_as_block(&source_texts, source_locale, target_locale)
             .await?
@@ -128,23 +167,23 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     context.update_translations(translated_texts);
 
     if verbose {
-        println!("🌍 Translated variants:");
+        writeln!(out, "🌍 Translated variants:")?;
         if context.variant_count() <= 10 {
             for (i, variant) in context.variants.iter().enumerate() {
-                println!("   [{}] \"{}\"", i, variant.translated_text);
+                writeln!(out, "   [{}] \"{}\"", i, variant.translated_text)?;
             }
         } else {
             for (i, variant) in context.variants.iter().take(5).enumerate() {
-                println!("   [{}] \"{}\"", i, variant.translated_text);
+                writeln!(out, "   [{}] \"{}\"", i, variant.translated_text)?;
             }
-            println!("   ... {} more variants", context.variant_count() - 5);
+            writeln!(out, "   ... {} more variants", context.variant_count() - 5)?;
         }
-        println!();
+        writeln!(out)?;
     }
 
     // 4. Reassemble
-    let reassembler = Reassembler::new(context.variable_types.clone());
-    let result = match reassembler.reassemble(context.variants) {
+    let reassembler = Reassembler::from_context(&context);
+    let output = match reassembler.reassemble_detailed(context.variants.clone()) {
         Ok(res) => res,
         Err(e) => {
             eprintln!("❌ Failed to reassemble: {}", e);
@@ -152,10 +191,205 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
     };
 
-    if verbose {
-        println!("🔧 Reassembled wikitext:");
+    let report = Report {
+        source: source_message,
+        source_locale,
+        target_locale,
+        provider: provider.provider_name(),
+        context: &context,
+        wikitext: &output.wikitext,
+        warnings: &output.warnings,
+    };
+    match format {
+        "json" => writeln!(out, "{}", serde_json::to_string_pretty(&report.to_json())?)?,
+        "pretty" => report.write_pretty(out, color)?,
+        _ => {
+            if verbose {
+                writeln!(out, "🔧 Reassembled wikitext:")?;
+            }
+            writeln!(out, "{}", output.wikitext)?;
+        }
     }
-    println!("{}", result);
 
     Ok(())
 }
+
+/// Everything known about one translated message, for `pretty` and `json` output
+struct Report<'a> {
+    source: &'a str,
+    source_locale: &'a str,
+    target_locale: &'a str,
+    provider: &'a str,
+    context: &'a MessageContext,
+    wikitext: &'a str,
+    warnings: &'a [String],
+}
+
+impl Report<'_> {
+    /// Share of variants whose translation kept every placeholder anchor
+    fn confidence(&self) -> f64 {
+        let variants = &self.context.variants;
+        if variants.is_empty() {
+            return 1.0;
+        }
+        let intact = variants
+            .iter()
+            .filter(|v| anchors_in(&v.source_text).is_subset(&anchors_in(&v.translated_text)))
+            .count();
+        intact as f64 / variants.len() as f64
+    }
+
+    /// An array with one record per message, like `TranslationOutcome`
+    fn to_json(&self) -> serde_json::Value {
+        let variants: Vec<serde_json::Value> = self
+            .context
+            .variants
+            .iter()
+            .map(|variant| {
+                json!({
+                    "choices": self.context.describe_variant(variant),
+                    "source": variant.source_text,
+                    "translation": variant.translated_text,
+                })
+            })
+            .collect();
+        json!([{
+            "key": self.context.original_key,
+            "source": self.source,
+            "source_locale": self.source_locale,
+            "target_locale": self.target_locale,
+            "provider": self.provider,
+            "variants": variants,
+            "wikitext": self.wikitext,
+            "confidence": self.confidence(),
+            "warnings": self.warnings,
+        }])
+    }
+
+    fn write_pretty(&self, out: &mut impl Write, color: bool) -> std::io::Result<()> {
+        let paint = |code: &str, text: &str| {
+            if color {
+                format!("\x1b[{}m{}\x1b[0m", code, text)
+            } else {
+                text.to_string()
+            }
+        };
+        let confidence = self.confidence();
+
+        writeln!(
+            out,
+            "{} {} ({})",
+            paint("1", "Source"),
+            self.source,
+            self.source_locale
+        )?;
+        writeln!(
+            out,
+            "{} {} variants via {}",
+            paint("1", "Variants"),
+            self.context.variant_count(),
+            self.provider
+        )?;
+        for variant in &self.context.variants {
+            let choices = self.context.describe_variant(variant);
+            writeln!(
+                out,
+                "  {} {} → {}",
+                paint("2", &format!("[{}]", choices)),
+                variant.source_text,
+                paint("36", &variant.translated_text)
+            )?;
+        }
+        writeln!(
+            out,
+            "{} {} ({})",
+            paint("1", "Result"),
+            paint("32", self.wikitext),
+            self.target_locale
+        )?;
+        writeln!(
+            out,
+            "{} {}",
+            paint("1", "Confidence"),
+            paint(
+                if confidence < 1.0 { "33" } else { "32" },
+                &format!("{:.0}%", confidence * 100.0)
+            )
+        )?;
+        for warning in self.warnings {
+            writeln!(out, "{} {}", paint("33", "warning:"), warning)?;
+        }
+        Ok(())
+    }
+}
+
+/// Placeholder anchors (777001, 777002, ...) in a text
+fn anchors_in(text: &str) -> BTreeSet<String> {
+    find_anchors(text)
+        .into_iter()
+        .filter(|m| !m.is_link())
+        .map(|m| m.anchor.to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn run_cli(args: &[&str]) -> String {
+        let matches = cli().try_get_matches_from(args).unwrap();
+        let mut out = Vec::new();
+        run(&matches, &mut out, false).await.unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_json_format_with_mock() {
+        let output = run_cli(&[
+            "banana-mt",
+            "--mock",
+            "--format",
+            "json",
+            "--key",
+            "sent",
+            "{{GENDER:$1|He|She}} sent $2",
+            "fr",
+        ])
+        .await;
+
+        let records: serde_json::Value = serde_json::from_str(&output).unwrap();
+        let records = records.as_array().unwrap();
+        assert_eq!(records.len(), 1);
+
+        let record = &records[0];
+        assert_eq!(record["key"], "sent");
+        assert_eq!(record["source"], "{{GENDER:$1|He|She}} sent $2");
+        assert_eq!(record["source_locale"], "en");
+        assert_eq!(record["target_locale"], "fr");
+        assert_eq!(record["provider"], "Mock Translator");
+        assert_eq!(record["confidence"], 1.0);
+        assert!(record["warnings"].as_array().unwrap().is_empty());
+        assert!(
+            record["wikitext"]
+                .as_str()
+                .unwrap()
+                .contains("{{GENDER:$1|")
+        );
+
+        let variants = record["variants"].as_array().unwrap();
+        assert_eq!(variants.len(), 3);
+        assert_eq!(variants[1]["choices"], "$1=female");
+        assert_eq!(variants[1]["source"], "She sent 777002");
+        assert_eq!(variants[1]["translation"], "She sent 777002_fr");
+    }
+
+    #[tokio::test]
+    async fn test_pretty_format_without_color() {
+        let output = run_cli(&["banana-mt", "-m", "-f", "pretty", "Hello, $1!", "fr"]).await;
+
+        assert!(output.starts_with("Source Hello, $1! (en)\n"));
+        assert!(output.contains("  [] Hello, 777001! → Hello, 777001!_fr\n"));
+        assert!(output.contains("Confidence 100%"));
+        assert!(!output.contains('\x1b'));
+    }
+}
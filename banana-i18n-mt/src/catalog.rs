@@ -0,0 +1,746 @@
+//! Bridge between the core `I18n` catalog and the MT pipeline
+//!
+//! `I18nMtExt` adds MT methods directly to `I18n`, so a message can be machine
+//! translated by key without parsing it and wiring up expansion and
+//! reassembly by hand.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use banana_i18n::I18n;
+//! use banana_i18n_mt::{I18nMtExt, MockMode, MockTranslator};
+//!
+//! let provider = MockTranslator::new(MockMode::Suffix);
+//! let wikitext = i18n.translate_key("notification", "en", "fr", &provider).await?;
+//! ```
+//!
+//! # Batch Jobs
+//!
+//! `translate_keys()` translates many messages with per-message retries. A
+//! `JobBudget` caps the whole job: once its total duration or total number of
+//! retries is used up, the job stops and returns what it has so far, with
+//! `budget_exceeded` set.
+//!
+//! ```ignore
+//! let budget = JobBudget {
+//!     max_total_duration: Some(Duration::from_secs(600)),
+//!     max_total_retries: Some(50),
+//! };
+//! let job = i18n.translate_keys(&keys, "en", "fr", &provider, 3, &budget).await;
+//! if let Some(reason) = job.budget_exceeded {
+//!     eprintln!("Stopped early ({:?}); {} keys left", reason, job.skipped.len());
+//! }
+//! ```
+//!
+//! # Incremental Runs
+//!
+//! `translate_keys_cached()` keeps a `<out>.cache.json` sidecar next to the
+//! translated catalog, mapping each key to a hash of its source message and
+//! its translation. On the next run, keys whose source is unchanged reuse the
+//! stored translation and only new or edited messages reach the provider.
+//! Unlike a per-string translation cache, entries are keyed by catalog key, so
+//! editing one message never invalidates another.
+//!
+//! ```ignore
+//! let sidecar = CatalogCache::sidecar_path(Path::new("i18n/fr.json"));
+//! let job = i18n
+//!     .translate_keys_cached(&keys, "en", "fr", &provider, 3, &budget, &sidecar)
+//!     .await?;
+//! println!("{} keys reused from the previous run", job.cached);
+//! ```
+
+use crate::data::MessageContext;
+use crate::error::{MtError, MtResult};
+use crate::expansion::prepare_for_translation;
+use crate::pipeline::translate_context;
+use crate::translator::MachineTranslator;
+use async_trait::async_trait;
+use banana_i18n::I18n;
+use banana_i18n::parser::Parser;
+use serde_json::{Map, Value, json};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Limits shared by every message of a batch job
+///
+/// `None` means unlimited; the default budget is unlimited.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct JobBudget {
+    /// Wall-clock time after which no new translation or retry is started
+    pub max_total_duration: Option<Duration>,
+    /// Number of retries allowed across all messages of the job
+    pub max_total_retries: Option<usize>,
+}
+
+/// Which limit of a `JobBudget` stopped a job
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BudgetExceeded {
+    /// `max_total_duration` elapsed
+    Duration,
+    /// `max_total_retries` were used up
+    Retries,
+}
+
+/// Outcome of a batch job, complete or stopped by its budget
+#[derive(Debug, Clone, PartialEq)]
+pub struct CatalogJobResult {
+    /// Translated messages, as (key, wikitext)
+    pub translations: Vec<(String, String)>,
+    /// Messages that failed after their retries, as (key, last error)
+    pub failures: Vec<(String, MtError)>,
+    /// Keys not attempted because the budget ran out
+    pub skipped: Vec<String>,
+    /// Set if the job stopped early
+    pub budget_exceeded: Option<BudgetExceeded>,
+    /// Retries used across the job
+    pub retries: usize,
+    /// Translations reused from a `CatalogCache` instead of the provider
+    pub cached: usize,
+}
+
+/// Translation of one message from an earlier run
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CachedTranslation {
+    /// `source_hash()` of the source message that was translated
+    pub source_hash: String,
+    /// Translated wikitext
+    pub translation: String,
+}
+
+/// Translations from earlier runs of a batch job, keyed by message key
+///
+/// Stored as a JSON sidecar file next to the translated catalog:
+/// `{"key": {"source_hash": "...", "translation": "..."}}`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CatalogCache {
+    entries: BTreeMap<String, CachedTranslation>,
+}
+
+impl CatalogCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Path of the cache sidecar for a translated catalog: `<out>.cache.json`
+    pub fn sidecar_path(output: &Path) -> PathBuf {
+        let mut path = output.as_os_str().to_owned();
+        path.push(".cache.json");
+        PathBuf::from(path)
+    }
+
+    /// Read a cache sidecar file
+    ///
+    /// # Arguments
+    /// * `path` - Sidecar file path; a missing file is an empty cache
+    ///
+    /// # Returns
+    /// * `Ok(CatalogCache)` - The stored translations
+    /// * `Err(MtError)` - If the file cannot be read or is not a cache
+    pub fn load(path: &Path) -> MtResult<Self> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::new()),
+            Err(e) => {
+                return Err(MtError::Other(
+                    format!("Cannot read cache '{}': {}", path.display(), e).into(),
+                )
+                .caused_by(e));
+            }
+        };
+
+        let invalid = || MtError::Other(format!("Invalid cache file '{}'", path.display()).into());
+        let value: Value = serde_json::from_str(&content)?;
+        let mut cache = Self::new();
+        for (key, entry) in value.as_object().ok_or_else(invalid)? {
+            let field = |name: &str| entry.get(name).and_then(Value::as_str).map(String::from);
+            let (Some(source_hash), Some(translation)) =
+                (field("source_hash"), field("translation"))
+            else {
+                return Err(invalid().in_context(key));
+            };
+            cache.entries.insert(
+                key.clone(),
+                CachedTranslation {
+                    source_hash,
+                    translation,
+                },
+            );
+        }
+        Ok(cache)
+    }
+
+    /// Write the cache to a sidecar file, with keys sorted
+    ///
+    /// # Errors
+    /// - File write errors
+    pub fn save(&self, path: &Path) -> MtResult<()> {
+        let entries: Map<String, Value> = self
+            .entries
+            .iter()
+            .map(|(key, entry)| {
+                (
+                    key.clone(),
+                    json!({
+                        "source_hash": entry.source_hash,
+                        "translation": entry.translation,
+                    }),
+                )
+            })
+            .collect();
+        let mut content = serde_json::to_string_pretty(&Value::Object(entries))?;
+        content.push('\n');
+        fs::write(path, content).map_err(|e| {
+            MtError::Other(format!("Cannot write cache '{}': {}", path.display(), e).into())
+                .caused_by(e)
+        })
+    }
+
+    /// Stored translation of `key`, if it was translated from this exact `source`
+    pub fn get(&self, key: &str, source: &str) -> Option<&str> {
+        self.entries
+            .get(key)
+            .filter(|entry| entry.source_hash == source_hash(source))
+            .map(|entry| entry.translation.as_str())
+    }
+
+    /// Record the translation of `key` from `source`
+    pub fn insert(&mut self, key: &str, source: &str, translation: &str) {
+        self.entries.insert(
+            key.to_string(),
+            CachedTranslation {
+                source_hash: source_hash(source),
+                translation: translation.to_string(),
+            },
+        );
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// Hash of a source message as stored in a `CatalogCache`
+///
+/// 64-bit FNV-1a over the UTF-8 bytes, as 16 hex digits. Unlike
+/// `DefaultHasher`, it is stable across Rust releases, so sidecar files stay
+/// valid after a toolchain upgrade.
+pub fn source_hash(source: &str) -> String {
+    let hash = source.bytes().fold(0xcbf29ce484222325u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+    });
+    format!("{:016x}", hash)
+}
+
+/// MT extension methods for the core `I18n` catalog
+#[async_trait]
+pub trait I18nMtExt {
+    /// Expand one message of the catalog for translation
+    ///
+    /// Looks up `key` in `source_locale` like `translate_key()` and expands
+    /// it for `target_locale`. The context carries the key and, if the
+    /// catalog has `qqq` documentation for it, the documentation.
+    ///
+    /// # Arguments
+    /// * `key` - Message key
+    /// * `source_locale` - Locale to read the source message from
+    /// * `target_locale` - Language to translate into
+    ///
+    /// # Returns
+    /// * `Ok(MessageContext)` - The expanded message
+    /// * `Err(MtError)` - If the message does not exist or cannot be
+    ///   expanded; the error carries the message key
+    fn prepare_key(
+        &self,
+        key: &str,
+        source_locale: &str,
+        target_locale: &str,
+    ) -> MtResult<MessageContext>;
+
+    /// Machine translate one message of the catalog
+    ///
+    /// Looks up `key` in `source_locale` (following the locale fallback
+    /// chain, like `I18n::get_message`), then expands, translates and
+    /// reassembles it. The message's `qqq` documentation, if any, is passed
+    /// to the provider as context.
+    ///
+    /// # Arguments
+    /// * `key` - Message key
+    /// * `source_locale` - Locale to read the source message from
+    /// * `target_locale` - Language to translate into
+    /// * `provider` - Machine translation provider
+    ///
+    /// # Returns
+    /// * `Ok(String)` - Translated wikitext
+    /// * `Err(MtError)` - If the message does not exist or the pipeline fails;
+    ///   the error carries the message key
+    async fn translate_key(
+        &self,
+        key: &str,
+        source_locale: &str,
+        target_locale: &str,
+        provider: &dyn MachineTranslator,
+    ) -> MtResult<String>;
+
+    /// Machine translate several messages under a shared budget
+    ///
+    /// Keys are translated in order with `translate_key()`. A failed message
+    /// is retried up to `retries` times, but before every retry (and before
+    /// every new message) the shared `budget` is checked; once it is used up
+    /// the job stops, the message being retried is recorded as a failure and
+    /// the remaining keys are returned as skipped.
+    ///
+    /// # Arguments
+    /// * `keys` - Message keys to translate
+    /// * `source_locale` - Locale to read the source messages from
+    /// * `target_locale` - Language to translate into
+    /// * `provider` - Machine translation provider
+    /// * `retries` - Retries allowed per message
+    /// * `budget` - Limits for the whole job
+    ///
+    /// # Returns
+    /// Translations, failures and skipped keys, with `budget_exceeded` set if
+    /// the job stopped early
+    async fn translate_keys(
+        &self,
+        keys: &[&str],
+        source_locale: &str,
+        target_locale: &str,
+        provider: &dyn MachineTranslator,
+        retries: usize,
+        budget: &JobBudget,
+    ) -> CatalogJobResult;
+
+    /// Machine translate several messages, reusing translations of unchanged ones
+    ///
+    /// Reads the cache sidecar at `cache_path` (see
+    /// `CatalogCache::sidecar_path()`), takes the translation of every key
+    /// whose source message is byte-identical to the cached one, and
+    /// translates only the other keys with `translate_keys()`. New
+    /// translations are added to the sidecar, which is then written back.
+    ///
+    /// # Arguments
+    /// * `keys` - Message keys to translate
+    /// * `source_locale` - Locale to read the source messages from
+    /// * `target_locale` - Language to translate into
+    /// * `provider` - Machine translation provider
+    /// * `retries` - Retries allowed per message
+    /// * `budget` - Limits for the translated (not cached) messages
+    /// * `cache_path` - Cache sidecar file
+    ///
+    /// # Returns
+    /// * `Ok(CatalogJobResult)` - As `translate_keys()`, with translations in
+    ///   key order and `cached` counting the reused ones
+    /// * `Err(MtError)` - If the sidecar cannot be read or written
+    #[allow(clippy::too_many_arguments)]
+    async fn translate_keys_cached(
+        &self,
+        keys: &[&str],
+        source_locale: &str,
+        target_locale: &str,
+        provider: &dyn MachineTranslator,
+        retries: usize,
+        budget: &JobBudget,
+        cache_path: &Path,
+    ) -> MtResult<CatalogJobResult>;
+}
+
+#[async_trait]
+impl I18nMtExt for I18n {
+    fn prepare_key(
+        &self,
+        key: &str,
+        source_locale: &str,
+        target_locale: &str,
+    ) -> MtResult<MessageContext> {
+        // get_message returns the key itself when no message exists
+        let source = self.get_message(source_locale, key);
+        if source == key {
+            return Err(MtError::Other(
+                format!("No message found in locale '{}'", source_locale).into(),
+            )
+            .in_context(key));
+        }
+
+        let ast = Parser::new(&source).parse();
+        let mut context =
+            prepare_for_translation(&ast, target_locale, key).map_err(|e| e.in_context(key))?;
+        context.documentation = self.get_documentation(key).map(str::to_string);
+        Ok(context)
+    }
+
+    async fn translate_key(
+        &self,
+        key: &str,
+        source_locale: &str,
+        target_locale: &str,
+        provider: &dyn MachineTranslator,
+    ) -> MtResult<String> {
+        let context = self.prepare_key(key, source_locale, target_locale)?;
+        translate_context(context, provider, source_locale, target_locale)
+            .await
+            .map(|outcome| outcome.wikitext)
+            .map_err(|e| e.in_context(key))
+    }
+
+    async fn translate_keys(
+        &self,
+        keys: &[&str],
+        source_locale: &str,
+        target_locale: &str,
+        provider: &dyn MachineTranslator,
+        retries: usize,
+        budget: &JobBudget,
+    ) -> CatalogJobResult {
+        let started = Instant::now();
+        let out_of_time = || {
+            budget
+                .max_total_duration
+                .is_some_and(|limit| started.elapsed() >= limit)
+        };
+
+        let mut job = CatalogJobResult {
+            translations: Vec::new(),
+            failures: Vec::new(),
+            skipped: Vec::new(),
+            budget_exceeded: None,
+            retries: 0,
+            cached: 0,
+        };
+
+        for (i, key) in keys.iter().enumerate() {
+            if out_of_time() {
+                job.budget_exceeded = Some(BudgetExceeded::Duration);
+            }
+            if job.budget_exceeded.is_some() {
+                job.skipped.extend(keys[i..].iter().map(|k| k.to_string()));
+                break;
+            }
+
+            let mut attempt = 0;
+            loop {
+                let error = match self
+                    .translate_key(key, source_locale, target_locale, provider)
+                    .await
+                {
+                    Ok(wikitext) => {
+                        job.translations.push((key.to_string(), wikitext));
+                        break;
+                    }
+                    Err(e) => e,
+                };
+
+                if attempt < retries {
+                    if budget
+                        .max_total_retries
+                        .is_some_and(|max| job.retries >= max)
+                    {
+                        job.budget_exceeded = Some(BudgetExceeded::Retries);
+                    } else if out_of_time() {
+                        job.budget_exceeded = Some(BudgetExceeded::Duration);
+                    } else {
+                        attempt += 1;
+                        job.retries += 1;
+                        continue;
+                    }
+                }
+                job.failures.push((key.to_string(), error));
+                break;
+            }
+        }
+
+        job
+    }
+
+    async fn translate_keys_cached(
+        &self,
+        keys: &[&str],
+        source_locale: &str,
+        target_locale: &str,
+        provider: &dyn MachineTranslator,
+        retries: usize,
+        budget: &JobBudget,
+        cache_path: &Path,
+    ) -> MtResult<CatalogJobResult> {
+        let mut cache = CatalogCache::load(cache_path)?;
+
+        let mut cached = BTreeMap::new();
+        let mut pending = Vec::new();
+        for key in keys {
+            let source = self.get_message(source_locale, key);
+            match cache.get(key, &source) {
+                Some(translation) => {
+                    cached.insert(key.to_string(), translation.to_string());
+                }
+                None => pending.push(*key),
+            }
+        }
+
+        let mut job = self
+            .translate_keys(
+                &pending,
+                source_locale,
+                target_locale,
+                provider,
+                retries,
+                budget,
+            )
+            .await;
+
+        for (key, translation) in &job.translations {
+            let source = self.get_message(source_locale, key);
+            cache.insert(key, &source, translation);
+        }
+        cache.save(cache_path)?;
+
+        job.cached = cached.len();
+        let mut translated: BTreeMap<String, String> = job.translations.drain(..).collect();
+        job.translations = keys
+            .iter()
+            .filter_map(|key| {
+                let translation = cached.remove(*key).or_else(|| translated.remove(*key))?;
+                Some((key.to_string(), translation))
+            })
+            .collect();
+        Ok(job)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{MockMode, MockTranslator};
+    use banana_i18n::LocalizedMessages;
+
+    fn catalog() -> I18n {
+        let mut messages = LocalizedMessages::new();
+        messages
+            .with_message("greeting", "Hello, $1!")
+            .with_message(
+                "sent",
+                "$1 sent {{PLURAL:$2|a message|$2 messages}} to the team",
+            );
+        let mut i18n = I18n::new();
+        i18n.with_messages_for_locale("en", messages);
+        i18n
+    }
+
+    #[tokio::test]
+    async fn test_translate_key() {
+        let i18n = catalog();
+        let provider = MockTranslator::new(MockMode::Suffix);
+
+        let greeting = i18n
+            .translate_key("greeting", "en", "fr", &provider)
+            .await
+            .unwrap();
+        assert_eq!(greeting, "Hello, $1!_fr");
+
+        let sent = i18n
+            .translate_key("sent", "en", "fr", &provider)
+            .await
+            .unwrap();
+        assert!(sent.contains("{{PLURAL:$2|"));
+        assert!(sent.ends_with("to the team_fr"));
+    }
+
+    /// Provider that records the context of every request
+    #[derive(Default)]
+    struct ContextRecorder {
+        contexts: std::sync::Mutex<Vec<Option<String>>>,
+    }
+
+    #[async_trait]
+    impl MachineTranslator for ContextRecorder {
+        async fn translate(&self, text: &str, _: &str, _: &str) -> MtResult<String> {
+            Ok(text.to_string())
+        }
+
+        async fn translate_batch(
+            &self,
+            texts: &[String],
+            _: &str,
+            _: &str,
+        ) -> MtResult<Vec<String>> {
+            self.contexts.lock().unwrap().push(None);
+            Ok(texts.to_vec())
+        }
+
+        async fn translate_batch_with_options(
+            &self,
+            texts: &[String],
+            _: &str,
+            _: &str,
+            options: &crate::translator::TranslationOptions,
+        ) -> MtResult<Vec<String>> {
+            self.contexts.lock().unwrap().push(options.context.clone());
+            Ok(texts.to_vec())
+        }
+
+        fn provider_name(&self) -> &str {
+            "Context recorder"
+        }
+
+        fn supports_native_batch(&self) -> bool {
+            true
+        }
+    }
+
+    #[tokio::test]
+    async fn test_documentation_reaches_context_and_provider() {
+        let mut i18n = catalog();
+        let mut docs = LocalizedMessages::new();
+        docs.with_message("sent", "Notification. $1 is a user name, $2 a count.");
+        i18n.with_documentation(docs);
+
+        let context = i18n.prepare_key("sent", "en", "fr").unwrap();
+        assert_eq!(context.original_key, "sent");
+        assert_eq!(
+            context.documentation.as_deref(),
+            Some("Notification. $1 is a user name, $2 a count.")
+        );
+        let context = i18n.prepare_key("greeting", "en", "fr").unwrap();
+        assert_eq!(context.documentation, None);
+
+        let provider = ContextRecorder::default();
+        i18n.translate_key("sent", "en", "fr", &provider)
+            .await
+            .unwrap();
+        assert_eq!(
+            *provider.contexts.lock().unwrap(),
+            vec![Some(
+                "Notification. $1 is a user name, $2 a count.".to_string()
+            )]
+        );
+    }
+
+    // ========== Job Budget Tests ==========
+
+    #[tokio::test]
+    async fn test_translate_keys_stops_at_retry_budget() {
+        let i18n = catalog();
+        let provider = MockTranslator::new(MockMode::Error("service unavailable".to_string()));
+        let budget = JobBudget {
+            max_total_retries: Some(4),
+            ..Default::default()
+        };
+
+        let job = i18n
+            .translate_keys(&["greeting", "sent"], "en", "fr", &provider, 10, &budget)
+            .await;
+
+        // One attempt plus the 4 retries the whole job may use
+        assert_eq!(provider.call_count(), 5);
+        assert_eq!(job.retries, 4);
+        assert_eq!(job.budget_exceeded, Some(BudgetExceeded::Retries));
+        assert!(job.translations.is_empty());
+        assert_eq!(job.failures.len(), 1);
+        assert_eq!(job.failures[0].0, "greeting");
+        assert_eq!(job.skipped, vec!["sent"]);
+    }
+
+    #[tokio::test]
+    async fn test_translate_keys_within_budget() {
+        let i18n = catalog();
+        let provider = MockTranslator::new(MockMode::Suffix);
+
+        let job = i18n
+            .translate_keys(
+                &["greeting", "missing", "sent"],
+                "en",
+                "fr",
+                &provider,
+                1,
+                &JobBudget::default(),
+            )
+            .await;
+
+        assert_eq!(job.budget_exceeded, None);
+        assert_eq!(job.translations.len(), 2);
+        assert_eq!(job.failures.len(), 1);
+        assert_eq!(job.failures[0].0, "missing");
+        assert!(job.skipped.is_empty());
+
+        let expired = JobBudget {
+            max_total_duration: Some(Duration::ZERO),
+            ..Default::default()
+        };
+        let job = i18n
+            .translate_keys(&["greeting"], "en", "fr", &provider, 1, &expired)
+            .await;
+        assert_eq!(job.budget_exceeded, Some(BudgetExceeded::Duration));
+        assert_eq!(job.skipped, vec!["greeting"]);
+    }
+
+    // ========== Catalog Cache Tests ==========
+
+    #[tokio::test]
+    async fn test_translate_keys_cached_skips_unchanged_keys() {
+        let sidecar = CatalogCache::sidecar_path(
+            &std::env::temp_dir().join(format!("banana-mt-catalog-{}.json", std::process::id())),
+        );
+        let _ = fs::remove_file(&sidecar);
+        let mut i18n = catalog();
+        let keys = ["greeting", "sent"];
+        let budget = JobBudget::default();
+
+        let provider = MockTranslator::new(MockMode::Suffix);
+        let first = i18n
+            .translate_keys_cached(&keys, "en", "fr", &provider, 0, &budget, &sidecar)
+            .await
+            .unwrap();
+        assert!(provider.call_count() > 0);
+        assert_eq!(first.cached, 0);
+        assert_eq!(CatalogCache::load(&sidecar).unwrap().len(), 2);
+
+        // Nothing changed: every translation comes from the sidecar
+        let provider = MockTranslator::new(MockMode::Suffix);
+        let second = i18n
+            .translate_keys_cached(&keys, "en", "fr", &provider, 0, &budget, &sidecar)
+            .await
+            .unwrap();
+        assert_eq!(provider.call_count(), 0);
+        assert_eq!(second.cached, 2);
+        assert_eq!(second.translations, first.translations);
+
+        // Only the edited message is translated again
+        let mut edited = LocalizedMessages::new();
+        edited.with_message("greeting", "Hi, $1!").with_message(
+            "sent",
+            "$1 sent {{PLURAL:$2|a message|$2 messages}} to the team",
+        );
+        i18n.with_messages_for_locale("en", edited);
+        let provider = MockTranslator::new(MockMode::Suffix);
+        let third = i18n
+            .translate_keys_cached(&keys, "en", "fr", &provider, 0, &budget, &sidecar)
+            .await
+            .unwrap();
+        assert_eq!(provider.call_count(), 1);
+        assert_eq!(third.cached, 1);
+        assert_eq!(
+            third.translations[0],
+            ("greeting".to_string(), "Hi, $1!_fr".to_string())
+        );
+        assert_eq!(third.translations[1], first.translations[1]);
+
+        fs::remove_file(&sidecar).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_translate_missing_key() {
+        let i18n = catalog();
+        let provider = MockTranslator::new(MockMode::Suffix);
+
+        let err = i18n
+            .translate_key("missing", "en", "fr", &provider)
+            .await
+            .unwrap_err();
+        assert_eq!(err.message_key(), Some("missing"));
+        assert_eq!(provider.call_count(), 0);
+    }
+}
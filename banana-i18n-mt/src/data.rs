@@ -3,8 +3,14 @@
 //! This module defines the fundamental data types used throughout the MT pipeline,
 //! closely matching the Python reference implementation design for simplicity.
 
+use crate::anchors::replace_anchors;
+use crate::expansion::{IF_BRANCHES, plural_category_name};
+use icu_plurals::PluralCategory;
 use std::collections::HashMap;
 
+/// Labels of the GENDER options, by choice index
+const GENDER_LABELS: [&str; 3] = ["male", "female", "unknown"];
+
 /// Represents a single permutation of the message with a specific state
 ///
 /// Each variant corresponds to one specific combination of choices for all
@@ -38,6 +44,10 @@ pub struct TranslationVariant {
     /// The translated string returned by MT (initially empty)
     /// Will be populated during translation phase
     pub translated_text: String,
+
+    /// Whether the translation was set by hand and must not be replaced by MT
+    /// See `MessageContext::set_variant_translation()`
+    pub pinned: bool,
 }
 
 impl TranslationVariant {
@@ -47,6 +57,7 @@ impl TranslationVariant {
             state,
             source_text,
             translated_text: String::new(),
+            pinned: false,
         }
     }
 
@@ -60,6 +71,7 @@ impl TranslationVariant {
             state,
             source_text,
             translated_text,
+            pinned: false,
         }
     }
 
@@ -103,6 +115,34 @@ pub struct MessageContext {
 
     /// The list of all variants (cartesian product of all choices)
     pub variants: Vec<TranslationVariant>,
+
+    /// Link targets and URLs, held constant during translation
+    /// The Nth entry is protected by the anchor 888000 + N
+    pub link_targets: Vec<String>,
+
+    /// Variable IDs in order of first appearance in the source AST
+    /// Reassembly collapses axes in this order, so output is reproducible
+    pub variable_order: Vec<String>,
+
+    /// Message documentation (from the `qqq` pseudo-locale), if available
+    /// Providers that accept context can pass it along to improve translations
+    pub documentation: Option<String>,
+
+    /// CLDR plural category of each option index, per PLURAL variable
+    /// Example: {"$1": [One, Few, Many, Other]} for Russian
+    pub plural_categories: HashMap<String, Vec<PluralCategory>>,
+
+    /// Magic word names as written in the source, per variable
+    /// Example: {"$1": "plural"} for `{{plural:$1|...}}`
+    pub magic_word_names: HashMap<String, String>,
+
+    /// Placeholders of the source sent to MT as sample text (`PlaceholderPolicy::Translate`)
+    /// Their translation stands in for `$N`, so reassembly warns when `$N` is missing
+    pub translated_placeholders: Vec<usize>,
+
+    /// Problems noticed while preparing the message, for the caller to report
+    /// Example: plural rules borrowed from a fallback of an unknown locale code
+    pub warnings: Vec<String>,
 }
 
 impl MessageContext {
@@ -112,7 +152,44 @@ impl MessageContext {
             original_key,
             variable_types: HashMap::new(),
             variants: Vec::new(),
+            link_targets: Vec::new(),
+            variable_order: Vec::new(),
+            documentation: None,
+            plural_categories: HashMap::new(),
+            magic_word_names: HashMap::new(),
+            translated_placeholders: Vec::new(),
+            warnings: Vec::new(),
+        }
+    }
+
+    /// Create a context from variants expanded elsewhere
+    ///
+    /// For callers that expand messages with their own logic but reassemble
+    /// with `Reassembler`. Variables are recorded in the order given, which is
+    /// the order reassembly collapses them in.
+    ///
+    /// # Arguments
+    /// * `original_key` - Message key
+    /// * `variable_types` - (variable ID, magic word type) pairs, e.g. `("$1", "GENDER")`
+    /// * `variants` - Expanded (and possibly translated) variants
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let context = MessageContext::from_parts("sent", &[("$1", "GENDER")], variants);
+    /// let wikitext = reassemble_from_context(&context)?;
+    /// ```
+    pub fn from_parts(
+        original_key: &str,
+        variable_types: &[(&str, &str)],
+        variants: Vec<TranslationVariant>,
+    ) -> Self {
+        let mut context = Self::new(original_key.to_string());
+        for (var_id, var_type) in variable_types {
+            context.add_variable(var_id.to_string(), var_type.to_string());
         }
+        context.variants = variants;
+        context
     }
 
     /// Add a variable type mapping for reassembly
@@ -121,6 +198,9 @@ impl MessageContext {
     /// * `var_id` - Variable identifier (e.g., "$1", "$2")
     /// * `var_type` - Magic word type ("PLURAL", "GENDER")
     pub fn add_variable(&mut self, var_id: String, var_type: String) {
+        if !self.variable_order.contains(&var_id) {
+            self.variable_order.push(var_id.clone());
+        }
         self.variable_types.insert(var_id, var_type);
     }
 
@@ -149,6 +229,9 @@ impl MessageContext {
 
     /// Update all variants with translated texts
     ///
+    /// Pinned variants (see `set_variant_translation()`) keep their
+    /// translation; their entry in `translated_texts` is ignored.
+    ///
     /// # Arguments
     /// * `translated_texts` - Translated texts in same order as variants
     ///
@@ -162,19 +245,146 @@ impl MessageContext {
         );
 
         for (variant, translated) in self.variants.iter_mut().zip(translated_texts.into_iter()) {
-            variant.translated_text = translated;
+            if !variant.pinned {
+                variant.translated_text = translated;
+            }
+        }
+    }
+
+    /// Override the translation of one variant and pin it
+    ///
+    /// For human corrections of a single form: the pinned text survives later
+    /// `update_translations()` calls, so reassembly mixes it with the machine
+    /// translated forms. The text uses the same anchors as the MT output
+    /// (e.g. "777001" for $1).
+    ///
+    /// # Arguments
+    /// * `state` - Choices identifying the variant, e.g. {"$1": 1} for the female form
+    /// * `text` - Translated text for that variant
+    ///
+    /// # Returns
+    /// `true` if a variant with that state exists, `false` otherwise
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let female = HashMap::from([("$1".to_string(), 1)]);
+    /// context.set_variant_translation(&female, "777001 a envoyé un message".to_string());
+    /// context.update_translations(machine_translations); // female form is kept
+    /// ```
+    pub fn set_variant_translation(
+        &mut self,
+        state: &HashMap<String, usize>,
+        text: String,
+    ) -> bool {
+        match self.variants.iter_mut().find(|v| &v.state == state) {
+            Some(variant) => {
+                variant.translated_text = text;
+                variant.pinned = true;
+                true
+            }
+            None => false,
         }
     }
 
-    /// Get variables used in this message context
+    /// Get variables used in this message context, in order of first appearance
     pub fn variable_ids(&self) -> Vec<String> {
-        self.variable_types.keys().cloned().collect()
+        self.variable_order.clone()
     }
 
     /// Get the magic word type for a variable
     pub fn get_variable_type(&self, var_id: &str) -> Option<&String> {
         self.variable_types.get(var_id)
     }
+
+    /// Get the CLDR plural category a PLURAL variable's option index stands for
+    ///
+    /// # Returns
+    /// `None` if the variable is not a PLURAL variable or the index is out of range
+    pub fn plural_category(&self, var_id: &str, index: usize) -> Option<PluralCategory> {
+        self.plural_categories
+            .get(var_id)
+            .and_then(|categories| categories.get(index))
+            .copied()
+    }
+
+    /// Render each variant's source text for translators, with labeled placeholders
+    ///
+    /// For display only: the text sent to MT keeps its anchors. Placeholder
+    /// anchors become `⟦label⟧` using `labels` (keyed by "$1", "$2", ...), or
+    /// `⟦$N⟧` when no label is given; link anchors become the link target.
+    ///
+    /// # Arguments
+    /// * `labels` - Human-readable names of the placeholders, e.g. "$1" → "sender name"
+    ///
+    /// # Returns
+    /// One preview per variant, in variant order
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let labels = HashMap::from([("$1".to_string(), "sender name".to_string())]);
+    /// // "777001 sent a message" → "⟦sender name⟧ sent a message"
+    /// let previews = context.labeled_previews(&labels);
+    /// ```
+    pub fn labeled_previews(&self, labels: &HashMap<String, String>) -> Vec<String> {
+        self.variants
+            .iter()
+            .map(|variant| {
+                let source = &variant.source_text;
+                replace_anchors(source, |anchor| {
+                    if anchor.is_link() {
+                        anchor
+                            .index()
+                            .checked_sub(1)
+                            .and_then(|index| self.link_targets.get(index))
+                            .cloned()
+                            .unwrap_or_else(|| source[anchor.start..anchor.end].to_string())
+                    } else {
+                        let var_id = format!("${}", anchor.index());
+                        let label = labels.get(&var_id).unwrap_or(&var_id);
+                        format!("⟦{}⟧", label)
+                    }
+                })
+            })
+            .collect()
+    }
+
+    /// Describe the choices a variant makes, e.g. "$1=female, $2=few"
+    ///
+    /// PLURAL choices are named by CLDR category and GENDER choices by
+    /// gender; other choices fall back to the option index.
+    pub fn describe_variant(&self, variant: &TranslationVariant) -> String {
+        let mut var_ids: Vec<&String> = self
+            .variable_order
+            .iter()
+            .filter(|id| variant.state.contains_key(*id))
+            .collect();
+        let mut rest: Vec<&String> = variant
+            .state
+            .keys()
+            .filter(|id| !self.variable_order.contains(id))
+            .collect();
+        rest.sort();
+        var_ids.extend(rest);
+
+        var_ids
+            .into_iter()
+            .map(|var_id| {
+                let index = variant.state[var_id];
+                let label = match self.get_variable_type(var_id).map(String::as_str) {
+                    Some("PLURAL" | "PLURAL-ORDINAL") => self
+                        .plural_category(var_id, index)
+                        .map(|category| plural_category_name(category).to_string()),
+                    Some("GENDER") => GENDER_LABELS.get(index).map(|label| label.to_string()),
+                    Some("#if") => IF_BRANCHES.get(index).map(|label| label.to_string()),
+                    _ => None,
+                };
+                format!("{}={}", var_id, label.unwrap_or_else(|| index.to_string()))
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
 }
 
 #[cfg(test)]
@@ -236,6 +446,61 @@ mod tests {
         assert_eq!(context.get_variable_type("$3"), None);
     }
 
+    #[test]
+    fn test_message_context_from_parts() {
+        let variant = |gender: usize, source: &str, translated: &str| {
+            TranslationVariant::with_translation(
+                HashMap::from([("$1".to_string(), gender)]),
+                source.to_string(),
+                translated.to_string(),
+            )
+        };
+        let context = MessageContext::from_parts(
+            "sent",
+            &[("$1", "GENDER")],
+            vec![
+                variant(0, "He sent a message", "Il a envoyé un message"),
+                variant(1, "She sent a message", "Elle a envoyé un message"),
+                variant(2, "They sent a message", "Iel a envoyé un message"),
+            ],
+        );
+
+        assert_eq!(context.original_key, "sent");
+        assert_eq!(context.variable_ids(), vec!["$1"]);
+        assert!(context.is_fully_translated());
+        assert_eq!(
+            crate::reassembly::reassemble_from_context(&context).unwrap(),
+            "{{GENDER:$1|Il|Elle|Iel}} a envoyé un message"
+        );
+    }
+
+    #[test]
+    fn test_labeled_previews() {
+        let ast = banana_i18n::parser::Parser::new(
+            "$1 sent {{PLURAL:$3|a message|$3 messages}} to $2 via [[Special:Inbox|the inbox]]",
+        )
+        .parse();
+        let context = crate::expansion::prepare_for_translation(&ast, "en", "sent").unwrap();
+        let labels = HashMap::from([
+            ("$1".to_string(), "sender name".to_string()),
+            ("$2".to_string(), "recipient".to_string()),
+        ]);
+
+        let previews = context.labeled_previews(&labels);
+        assert_eq!(
+            previews,
+            vec![
+                "⟦sender name⟧ sent a message to ⟦recipient⟧ via [[Special:Inbox|the inbox]]",
+                "⟦sender name⟧ sent ⟦$3⟧ messages to ⟦recipient⟧ via [[Special:Inbox|the inbox]]",
+            ]
+        );
+        // The MT-bound text keeps its anchors
+        assert_eq!(
+            context.variants[0].source_text,
+            "777001 sent a message to 777002 via [[888001|the inbox]]"
+        );
+    }
+
     #[test]
     fn test_message_context_variants() {
         let mut context = MessageContext::new("test".to_string());
@@ -290,6 +555,39 @@ mod tests {
         context.update_translations(translations);
     }
 
+    #[test]
+    fn test_pinned_variant_survives_update_and_reassembly() {
+        let ast =
+            banana_i18n::parser::Parser::new("{{GENDER:$1|He|She|They}} sent a message").parse();
+        let mut context = crate::expansion::prepare_for_translation(&ast, "en", "sent").unwrap();
+
+        let female = HashMap::from([("$1".to_string(), 1)]);
+        assert!(context.set_variant_translation(&female, "Elle a envoyé un message".to_string()));
+        assert!(
+            !context.set_variant_translation(
+                &HashMap::from([("$1".to_string(), 7)]),
+                "Nope".to_string()
+            )
+        );
+
+        context.update_translations(vec![
+            "Il a envoyé un message".to_string(),
+            "Elle envoyé message".to_string(),
+            "Iel a envoyé un message".to_string(),
+        ]);
+
+        assert_eq!(
+            context.variants[1].translated_text,
+            "Elle a envoyé un message"
+        );
+        assert!(context.variants[1].pinned);
+        assert!(!context.variants[0].pinned);
+        assert_eq!(
+            crate::reassembly::reassemble_from_context(&context).unwrap(),
+            "{{GENDER:$1|Il|Elle|Iel}} a envoyé un message"
+        );
+    }
+
     #[test]
     fn test_variable_ids() {
         let mut context = MessageContext::new("test".to_string());
@@ -0,0 +1,513 @@
+//! DeepL API provider for machine translation
+//!
+//! This module integrates with the DeepL API v2. Unlike Google and Yandex,
+//! DeepL can be asked for formal or informal address (German Sie/du, French
+//! vous/tu), which is passed through `TranslationOptions`.
+//!
+//! # Authentication
+//!
+//! The provider loads the API key from the `DEEPL_API_KEY` environment
+//! variable. Keys of the free plan end in `:fx` and are sent to the free API
+//! endpoint automatically. See: https://developers.deepl.com/docs
+//!
+//! # Example
+//!
+//! ```ignore
+//! use banana_i18n_mt::{DeepLProvider, Formality, MachineTranslator, TranslationOptions};
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!     let provider = DeepLProvider::from_env()?;
+//!
+//!     let options = TranslationOptions {
+//!         formality: Some(Formality::Informal),
+//!         ..Default::default()
+//!     };
+//!     let result = provider
+//!         .translate_with_options("How are you?", "en", "de", &options)
+//!         .await?;
+//!     println!("{}", result); // "Wie geht es dir?"
+//!
+//!     Ok(())
+//! }
+//! ```
+
+use crate::error::{MtError, MtResult};
+use crate::translator::{
+    Formality, LanguageMatrix, MachineTranslator, TranslationOptions, validate_locale,
+};
+use async_trait::async_trait;
+use serde_json::json;
+
+/// Languages DeepL translates from and into
+///
+/// Regional targets (EN-GB, PT-BR, ZH-HANT) are covered by their base language.
+const DEEPL_LANGUAGES: &[&str] = &[
+    "ar", "bg", "cs", "da", "de", "el", "en", "es", "et", "fi", "fr", "hu", "id", "it", "ja", "ko",
+    "lt", "lv", "nb", "nl", "pl", "pt", "ro", "ru", "sk", "sl", "sv", "tr", "uk", "zh",
+];
+
+/// DeepL API v2 provider
+///
+/// Sends all texts of a batch in a single `text[]` request, chunking
+/// transparently at DeepL's limit of 50 texts per request.
+#[derive(Clone)]
+pub struct DeepLProvider {
+    /// API key for authentication
+    api_key: String,
+    /// HTTP client for async requests
+    client: reqwest::Client,
+    /// Base URL for the DeepL translate endpoint
+    base_url: String,
+}
+
+impl DeepLProvider {
+    /// Maximum number of texts per API request
+    const MAX_BATCH_SIZE: usize = 50;
+
+    /// Create a new DeepLProvider with an explicit API key
+    ///
+    /// # Arguments
+    ///
+    /// * `api_key` - DeepL API key (free plan keys end in `:fx`)
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Self)` - New provider instance
+    /// * `Err(MtError)` - If the API key is empty or HTTP client creation fails
+    pub fn new(api_key: String) -> MtResult<Self> {
+        if api_key.trim().is_empty() {
+            return Err(MtError::ConfigError("API key cannot be empty".into()));
+        }
+
+        let client = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(30))
+            .build()
+            .map_err(|e| {
+                MtError::NetworkError(format!("Failed to create HTTP client: {}", e).into())
+                    .caused_by(e)
+            })?;
+
+        let base_url = if api_key.ends_with(":fx") {
+            "https://api-free.deepl.com/v2/translate"
+        } else {
+            "https://api.deepl.com/v2/translate"
+        };
+
+        Ok(Self {
+            api_key,
+            client,
+            base_url: base_url.to_string(),
+        })
+    }
+
+    /// Create a DeepLProvider from the `DEEPL_API_KEY` environment variable
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Self)` - New provider instance
+    /// * `Err(MtError)` - If the environment variable is not set or creation fails
+    pub fn from_env() -> MtResult<Self> {
+        let api_key = std::env::var("DEEPL_API_KEY").map_err(|e| {
+            MtError::ConfigError("DEEPL_API_KEY environment variable not set".into()).caused_by(e)
+        })?;
+
+        Self::new(api_key)
+    }
+
+    /// Map a BCP 47 locale code to a DeepL source language code
+    ///
+    /// Source languages are given without region: `en-US` → `EN`.
+    pub fn deepl_source_locale(locale: &str) -> String {
+        let lower = locale.to_lowercase().replace('_', "-");
+        lower.split('-').next().unwrap_or(&lower).to_uppercase()
+    }
+
+    /// Map a BCP 47 locale code to a DeepL target language code
+    ///
+    /// DeepL requires a variant for English and Portuguese targets:
+    /// - `en` → `EN-US`, `en-GB` → `EN-GB`
+    /// - `pt` → `PT-PT`, `pt-BR` → `PT-BR`
+    /// - `zh-Hant` → `ZH-HANT`
+    /// - `de-AT` → `DE`
+    pub fn deepl_target_locale(locale: &str) -> String {
+        let lower = locale.to_lowercase().replace('_', "-");
+        match lower.as_str() {
+            "en" | "en-us" => "EN-US".to_string(),
+            "en-gb" => "EN-GB".to_string(),
+            "pt" | "pt-pt" => "PT-PT".to_string(),
+            "pt-br" => "PT-BR".to_string(),
+            "zh-hans" => "ZH-HANS".to_string(),
+            "zh-hant" | "zh-tw" => "ZH-HANT".to_string(),
+            _ => Self::deepl_source_locale(&lower),
+        }
+    }
+
+    /// Build the JSON request body for a chunk of texts
+    ///
+    /// Formality is sent as `prefer_more`/`prefer_less`, so target languages
+    /// without a formal register fall back to the default instead of failing.
+    /// The caller's context, domain and subject gender are sent as `context`,
+    /// which DeepL uses to disambiguate but does not translate or bill. Each
+    /// part is ended as a sentence, so they do not run together into one phrase.
+    fn build_request_body(
+        texts: &[String],
+        source_locale: &str,
+        target_locale: &str,
+        options: &TranslationOptions,
+    ) -> serde_json::Value {
+        let mut body = json!({
+            "text": texts,
+            "source_lang": Self::deepl_source_locale(source_locale),
+            "target_lang": Self::deepl_target_locale(target_locale),
+        });
+
+        if let Some(formality) = options.formality {
+            body["formality"] = json!(match formality {
+                Formality::Formal => "prefer_more",
+                Formality::Informal => "prefer_less",
+            });
+        }
+        let context: Vec<&str> = options
+            .context
+            .as_deref()
+            .into_iter()
+            .chain(options.domain.as_deref())
+            .chain(options.subject_gender.map(|g| g.description()))
+            .collect();
+        if !context.is_empty() {
+            body["context"] = json!(join_sentences(&context));
+        }
+
+        body
+    }
+
+    /// Extract the translated texts from a DeepL API response
+    ///
+    /// # Arguments
+    ///
+    /// * `json` - Parsed response body
+    /// * `expected` - Number of texts sent in the request
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<String>)` - Translated texts in request order
+    /// * `Err(MtError)` - If the response is malformed or the count differs
+    fn parse_response(json: &serde_json::Value, expected: usize) -> MtResult<Vec<String>> {
+        let translations = json["translations"].as_array().ok_or_else(|| {
+            MtError::TranslationError("Invalid API response: missing 'translations' array".into())
+        })?;
+
+        let results: Vec<String> = translations
+            .iter()
+            .map(|t| {
+                t["text"].as_str().map(|s| s.to_string()).ok_or_else(|| {
+                    MtError::TranslationError("Invalid API response: missing 'text' field".into())
+                })
+            })
+            .collect::<MtResult<_>>()?;
+
+        if results.len() != expected {
+            return Err(MtError::TranslationError(
+                format!(
+                    "API returned {} translations for {} texts",
+                    results.len(),
+                    expected
+                )
+                .into(),
+            ));
+        }
+
+        Ok(results)
+    }
+
+    /// Translate a single chunk of texts via the API
+    async fn translate_chunk(
+        &self,
+        texts: &[String],
+        source_locale: &str,
+        target_locale: &str,
+        options: &TranslationOptions,
+    ) -> MtResult<Vec<String>> {
+        let body = Self::build_request_body(texts, source_locale, target_locale, options);
+
+        let response = self
+            .client
+            .post(&self.base_url)
+            .header("Authorization", format!("DeepL-Auth-Key {}", self.api_key))
+            .json(&body)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Unknown error".to_string());
+
+            return Err(if status.is_client_error() {
+                MtError::ConfigError(
+                    format!("API client error ({}): {}", status, error_text).into(),
+                )
+            } else {
+                MtError::TranslationError(
+                    format!("API server error ({}): {}", status, error_text).into(),
+                )
+            });
+        }
+
+        let json: serde_json::Value = response.json().await?;
+
+        Self::parse_response(&json, texts.len())
+    }
+}
+
+/// Join context parts into sentences ("Show messages", "Mail" → "Show messages. Mail")
+///
+/// A full stop is added to every part but the last unless it already ends a sentence.
+fn join_sentences(parts: &[&str]) -> String {
+    let last = parts.len().saturating_sub(1);
+    parts
+        .iter()
+        .enumerate()
+        .map(|(i, part)| {
+            let part = part.trim();
+            if i < last && !part.ends_with(['.', '!', '?', '…']) {
+                format!("{}.", part)
+            } else {
+                part.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+impl std::fmt::Debug for DeepLProvider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DeepLProvider")
+            .field("api_key", &"***")
+            .field("base_url", &self.base_url)
+            .finish()
+    }
+}
+
+#[async_trait]
+impl MachineTranslator for DeepLProvider {
+    async fn translate(
+        &self,
+        text: &str,
+        source_locale: &str,
+        target_locale: &str,
+    ) -> MtResult<String> {
+        self.translate_with_options(
+            text,
+            source_locale,
+            target_locale,
+            &TranslationOptions::default(),
+        )
+        .await
+    }
+
+    async fn translate_batch(
+        &self,
+        texts: &[String],
+        source_locale: &str,
+        target_locale: &str,
+    ) -> MtResult<Vec<String>> {
+        self.translate_batch_with_options(
+            texts,
+            source_locale,
+            target_locale,
+            &TranslationOptions::default(),
+        )
+        .await
+    }
+
+    async fn translate_with_options(
+        &self,
+        text: &str,
+        source_locale: &str,
+        target_locale: &str,
+        options: &TranslationOptions,
+    ) -> MtResult<String> {
+        if text.is_empty() {
+            return Ok(String::new());
+        }
+
+        let results = self
+            .translate_batch_with_options(
+                &[text.to_string()],
+                source_locale,
+                target_locale,
+                options,
+            )
+            .await?;
+
+        Ok(results.into_iter().next().unwrap_or_default())
+    }
+
+    async fn translate_batch_with_options(
+        &self,
+        texts: &[String],
+        source_locale: &str,
+        target_locale: &str,
+        options: &TranslationOptions,
+    ) -> MtResult<Vec<String>> {
+        validate_locale(source_locale)?;
+        validate_locale(target_locale)?;
+
+        if texts.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut all_results = Vec::new();
+        for chunk in texts.chunks(Self::MAX_BATCH_SIZE) {
+            let chunk_results = self
+                .translate_chunk(chunk, source_locale, target_locale, options)
+                .await?;
+            all_results.extend(chunk_results);
+        }
+
+        Ok(all_results)
+    }
+
+    fn provider_name(&self) -> &str {
+        "DeepL"
+    }
+
+    fn supports_native_batch(&self) -> bool {
+        true
+    }
+
+    fn supported_pairs(&self) -> Option<LanguageMatrix> {
+        Some(LanguageMatrix::new(DEEPL_LANGUAGES, DEEPL_LANGUAGES))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::translator::SubjectGender;
+
+    // ========== Initialization Tests ==========
+
+    #[test]
+    fn test_free_key_uses_free_endpoint() {
+        let free = DeepLProvider::new("abc:fx".to_string()).unwrap();
+        assert!(free.base_url.starts_with("https://api-free.deepl.com"));
+
+        let pro = DeepLProvider::new("abc".to_string()).unwrap();
+        assert!(pro.base_url.starts_with("https://api.deepl.com"));
+
+        assert!(matches!(
+            DeepLProvider::new(" ".to_string()),
+            Err(MtError::ConfigError(_))
+        ));
+    }
+
+    #[test]
+    fn test_debug_output_masks_key() {
+        let debug_str = format!("{:?}", DeepLProvider::new("secret:fx".to_string()).unwrap());
+        assert!(debug_str.contains("***"));
+        assert!(!debug_str.contains("secret"));
+    }
+
+    // ========== Locale Mapping Tests ==========
+
+    #[test]
+    fn test_deepl_locale_mapping() {
+        assert_eq!(DeepLProvider::deepl_source_locale("en-US"), "EN");
+        assert_eq!(DeepLProvider::deepl_target_locale("en"), "EN-US");
+        assert_eq!(DeepLProvider::deepl_target_locale("en-GB"), "EN-GB");
+        assert_eq!(DeepLProvider::deepl_target_locale("pt_br"), "PT-BR");
+        assert_eq!(DeepLProvider::deepl_target_locale("zh-Hant"), "ZH-HANT");
+        assert_eq!(DeepLProvider::deepl_target_locale("de-AT"), "DE");
+    }
+
+    // ========== Request Construction Tests ==========
+
+    #[test]
+    fn test_request_body_carries_formality() {
+        let texts = vec!["How are you, 777001?".to_string()];
+        let options = TranslationOptions {
+            formality: Some(Formality::Formal),
+            domain: Some("software user interface".to_string()),
+            subject_gender: None,
+            context: None,
+        };
+        let body = DeepLProvider::build_request_body(&texts, "en", "de", &options);
+
+        assert_eq!(
+            body,
+            json!({
+                "text": ["How are you, 777001?"],
+                "source_lang": "EN",
+                "target_lang": "DE",
+                "formality": "prefer_more",
+                "context": "software user interface"
+            })
+        );
+
+        let informal = TranslationOptions {
+            formality: Some(Formality::Informal),
+            ..Default::default()
+        };
+        let body = DeepLProvider::build_request_body(&texts, "en", "fr", &informal);
+        assert_eq!(body["formality"], "prefer_less");
+
+        let gendered = TranslationOptions {
+            domain: Some("Greeting".to_string()),
+            subject_gender: Some(SubjectGender::Female),
+            ..Default::default()
+        };
+        let body = DeepLProvider::build_request_body(&texts, "en", "fr", &gendered);
+        assert_eq!(
+            body["context"],
+            "Greeting. The person referred to is female."
+        );
+
+        let in_context = TranslationOptions {
+            context: Some("Show messages".to_string()),
+            domain: Some("Mail".to_string()),
+            ..Default::default()
+        };
+        let body = DeepLProvider::build_request_body(&texts, "en", "fr", &in_context);
+        assert_eq!(body["context"], "Show messages. Mail");
+
+        let in_context = TranslationOptions {
+            context: Some("Are you sure?".to_string()),
+            domain: Some("Mail".to_string()),
+            ..Default::default()
+        };
+        let body = DeepLProvider::build_request_body(&texts, "en", "fr", &in_context);
+        assert_eq!(body["context"], "Are you sure? Mail");
+        assert_eq!(body["text"], json!(["How are you, 777001?"]));
+    }
+
+    #[test]
+    fn test_request_body_without_options() {
+        let texts = vec!["Hello".to_string()];
+        let body =
+            DeepLProvider::build_request_body(&texts, "en", "fr", &TranslationOptions::default());
+        assert!(body.get("formality").is_none());
+        assert!(body.get("context").is_none());
+    }
+
+    // ========== Response Parsing Tests ==========
+
+    #[test]
+    fn test_parse_recorded_response() {
+        let recorded = r#"{
+            "translations": [
+                {"detected_source_language": "EN", "text": "Wie geht es Ihnen, 777001?"}
+            ]
+        }"#;
+        let json: serde_json::Value = serde_json::from_str(recorded).unwrap();
+        assert_eq!(
+            DeepLProvider::parse_response(&json, 1).unwrap(),
+            vec!["Wie geht es Ihnen, 777001?".to_string()]
+        );
+        assert!(DeepLProvider::parse_response(&json, 2).is_err());
+    }
+}
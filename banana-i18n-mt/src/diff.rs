@@ -0,0 +1,329 @@
+//! Structural diff between two versions of a message
+//!
+//! When a message is machine translated again, a character diff of the
+//! wikitext mixes up what the reviewer cares about. `diff_messages()` parses
+//! both versions and reports changes node by node: edits to the text outside
+//! magic words, changed magic word options, and placeholders that were added
+//! or removed.
+//!
+//! # Example
+//!
+//! ```ignore
+//! let diff = diff_messages(
+//!     "$1 a envoyé {{PLURAL:$2|un message|$2 messages}}",
+//!     "$1 a envoyé {{PLURAL:$2|un courriel|$2 messages}}",
+//! );
+//! // PLURAL:$2 option 1: "un message" → "un courriel"
+//! for change in &diff.changes {
+//!     println!("{}", change);
+//! }
+//! ```
+
+use banana_i18n::ast::{AstNode, AstNodeList, Transclusion, ast_to_wikitext};
+use banana_i18n::parser::Parser;
+use std::collections::BTreeSet;
+
+/// A single change between two versions of a message
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NodeChange {
+    /// A run of text outside magic words was edited, added (`old` empty) or
+    /// removed (`new` empty)
+    TextChanged { old: String, new: String },
+    /// One option of a magic word present in both versions changed; `None`
+    /// means the option does not exist in that version
+    OptionChanged {
+        magic_word: String,
+        param: String,
+        /// 1-based option number
+        option: usize,
+        old: Option<String>,
+        new: Option<String>,
+    },
+    /// A magic word appears only in the new version
+    MagicWordAdded { magic_word: String, param: String },
+    /// A magic word appears only in the old version
+    MagicWordRemoved { magic_word: String, param: String },
+    /// A placeholder is used only in the new version
+    PlaceholderAdded(usize),
+    /// A placeholder is used only in the old version
+    PlaceholderRemoved(usize),
+}
+
+impl std::fmt::Display for NodeChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NodeChange::TextChanged { old, new } => write!(f, "text: \"{}\" → \"{}\"", old, new),
+            NodeChange::OptionChanged {
+                magic_word,
+                param,
+                option,
+                old,
+                new,
+            } => write!(
+                f,
+                "{}:{} option {}: {} → {}",
+                magic_word,
+                param,
+                option,
+                old.as_deref()
+                    .map_or("(none)".to_string(), |o| format!("\"{}\"", o)),
+                new.as_deref()
+                    .map_or("(none)".to_string(), |o| format!("\"{}\"", o))
+            ),
+            NodeChange::MagicWordAdded { magic_word, param } => {
+                write!(f, "added {{{{{}:{}}}}}", magic_word, param)
+            }
+            NodeChange::MagicWordRemoved { magic_word, param } => {
+                write!(f, "removed {{{{{}:{}}}}}", magic_word, param)
+            }
+            NodeChange::PlaceholderAdded(index) => write!(f, "added ${}", index),
+            NodeChange::PlaceholderRemoved(index) => write!(f, "removed ${}", index),
+        }
+    }
+}
+
+/// Node-level differences between two versions of a message
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MessageDiff {
+    /// Text changes in message order, then magic word changes in message
+    /// order, then placeholder changes by index
+    pub changes: Vec<NodeChange>,
+}
+
+impl MessageDiff {
+    /// Whether the two versions are structurally identical
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+/// Compare two versions of a message node by node
+///
+/// Text outside magic words is split into runs at each magic word and runs
+/// are compared by position. Magic words are matched by name and parameter,
+/// in order of occurrence, and their options compared one by one.
+/// Placeholders are compared as sets, including those controlling or inside
+/// magic words.
+///
+/// # Arguments
+/// * `old` - Previous wikitext
+/// * `new` - Updated wikitext
+///
+/// # Returns
+/// The changes; empty if nothing but whitespace inside options differs
+pub fn diff_messages(old: &str, new: &str) -> MessageDiff {
+    let old_ast = Parser::new(old).parse();
+    let new_ast = Parser::new(new).parse();
+    let mut changes = Vec::new();
+
+    // Stable text, as runs between magic words
+    let old_runs = text_runs(&old_ast);
+    let new_runs = text_runs(&new_ast);
+    for i in 0..old_runs.len().max(new_runs.len()) {
+        let old_run = old_runs.get(i).cloned().unwrap_or_default();
+        let new_run = new_runs.get(i).cloned().unwrap_or_default();
+        if old_run != new_run {
+            changes.push(NodeChange::TextChanged {
+                old: old_run,
+                new: new_run,
+            });
+        }
+    }
+
+    // Magic words, matched by name and parameter
+    let old_words = magic_words(&old_ast);
+    let mut new_words: Vec<Option<&Transclusion>> =
+        magic_words(&new_ast).into_iter().map(Some).collect();
+    for old_word in old_words {
+        let matched = new_words
+            .iter_mut()
+            .find(|w| w.is_some_and(|w| same_magic_word(old_word, w)))
+            .and_then(Option::take);
+        match matched {
+            Some(new_word) => diff_options(old_word, new_word, &mut changes),
+            None => changes.push(NodeChange::MagicWordRemoved {
+                magic_word: old_word.name.to_uppercase(),
+                param: old_word.param.clone(),
+            }),
+        }
+    }
+    for new_word in new_words.into_iter().flatten() {
+        changes.push(NodeChange::MagicWordAdded {
+            magic_word: new_word.name.to_uppercase(),
+            param: new_word.param.clone(),
+        });
+    }
+
+    // Placeholders, wherever they occur
+    let old_placeholders = placeholders(&old_ast);
+    let new_placeholders = placeholders(&new_ast);
+    for index in new_placeholders.difference(&old_placeholders) {
+        changes.push(NodeChange::PlaceholderAdded(*index));
+    }
+    for index in old_placeholders.difference(&new_placeholders) {
+        changes.push(NodeChange::PlaceholderRemoved(*index));
+    }
+
+    MessageDiff { changes }
+}
+
+/// Serialized text between magic words, including placeholders and links
+fn text_runs(ast: &AstNodeList) -> Vec<String> {
+    let mut runs = vec![String::new()];
+    for node in ast {
+        match node {
+            AstNode::Transclusion(_) => runs.push(String::new()),
+            _ => runs
+                .last_mut()
+                .unwrap()
+                .push_str(&ast_to_wikitext(&vec![node.clone()])),
+        }
+    }
+    runs
+}
+
+fn magic_words(ast: &AstNodeList) -> Vec<&Transclusion> {
+    ast.iter()
+        .filter_map(|node| match node {
+            AstNode::Transclusion(trans) => Some(trans),
+            _ => None,
+        })
+        .collect()
+}
+
+fn same_magic_word(a: &Transclusion, b: &Transclusion) -> bool {
+    a.name.trim().eq_ignore_ascii_case(b.name.trim()) && a.param.trim() == b.param.trim()
+}
+
+/// Compare options one by one, ignoring leading, trailing and repeated whitespace
+fn diff_options(old: &Transclusion, new: &Transclusion, changes: &mut Vec<NodeChange>) {
+    for i in 0..old.options.len().max(new.options.len()) {
+        let old_option = old.options.get(i);
+        let new_option = new.options.get(i);
+        let differs = old_option.map(|o| collapse_whitespace(o))
+            != new_option.map(|o| collapse_whitespace(o));
+        if differs {
+            changes.push(NodeChange::OptionChanged {
+                magic_word: old.name.to_uppercase(),
+                param: old.param.clone(),
+                option: i + 1,
+                old: old_option.cloned(),
+                new: new_option.cloned(),
+            });
+        }
+    }
+}
+
+/// Trim a text and reduce each run of whitespace inside it to one space
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Placeholder indices used anywhere in the message, including in magic words
+fn placeholders(ast: &AstNodeList) -> BTreeSet<usize> {
+    let mut indices = BTreeSet::new();
+    for node in ast {
+        match node {
+            AstNode::Placeholder(placeholder) => {
+                indices.insert(placeholder.index);
+            }
+            AstNode::Transclusion(trans) => {
+                indices.extend(placeholders(&Parser::new(&trans.param).parse()));
+                for option in &trans.options {
+                    indices.extend(placeholders(&Parser::new(option).parse()));
+                }
+            }
+            _ => {}
+        }
+    }
+    indices
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identical_messages() {
+        let message = "{{GENDER:$1|Il|Elle}} a envoyé {{PLURAL:$2|un message|$2 messages}}";
+        assert!(diff_messages(message, message).is_empty());
+    }
+
+    #[test]
+    fn test_whitespace_inside_options_is_ignored() {
+        let diff = diff_messages(
+            "$1 a envoyé {{PLURAL:$2|un message|$2 messages}}",
+            "$1 a envoyé {{PLURAL:$2| un  message |$2\tmessages}}",
+        );
+        assert!(diff.is_empty(), "{:?}", diff.changes);
+    }
+
+    #[test]
+    fn test_changed_plural_option() {
+        let diff = diff_messages(
+            "$1 a envoyé {{PLURAL:$2|un message|$2 messages}}",
+            "$1 a envoyé {{PLURAL:$2|un courriel|$2 courriels}}",
+        );
+        assert_eq!(
+            diff.changes,
+            vec![
+                NodeChange::OptionChanged {
+                    magic_word: "PLURAL".to_string(),
+                    param: "$2".to_string(),
+                    option: 1,
+                    old: Some("un message".to_string()),
+                    new: Some("un courriel".to_string()),
+                },
+                NodeChange::OptionChanged {
+                    magic_word: "PLURAL".to_string(),
+                    param: "$2".to_string(),
+                    option: 2,
+                    old: Some("$2 messages".to_string()),
+                    new: Some("$2 courriels".to_string()),
+                },
+            ]
+        );
+        assert_eq!(
+            diff.changes[0].to_string(),
+            "PLURAL:$2 option 1: \"un message\" → \"un courriel\""
+        );
+    }
+
+    #[test]
+    fn test_added_placeholder() {
+        let diff = diff_messages(
+            "Fichier envoyé {{PLURAL:$1|une fois|$1 fois}}",
+            "Fichier $2 envoyé {{PLURAL:$1|une fois|$1 fois}}",
+        );
+        assert_eq!(
+            diff.changes,
+            vec![
+                NodeChange::TextChanged {
+                    old: "Fichier envoyé ".to_string(),
+                    new: "Fichier $2 envoyé ".to_string(),
+                },
+                NodeChange::PlaceholderAdded(2),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_added_and_removed_magic_words() {
+        let diff = diff_messages(
+            "{{GENDER:$1|Il|Elle}} est parti",
+            "{{PLURAL:$1|Un utilisateur est parti|$1 utilisateurs sont partis}}",
+        );
+        assert!(diff.changes.contains(&NodeChange::MagicWordRemoved {
+            magic_word: "GENDER".to_string(),
+            param: "$1".to_string(),
+        }));
+        assert!(diff.changes.contains(&NodeChange::MagicWordAdded {
+            magic_word: "PLURAL".to_string(),
+            param: "$1".to_string(),
+        }));
+        assert!(diff.changes.contains(&NodeChange::TextChanged {
+            old: " est parti".to_string(),
+            new: String::new(),
+        }));
+    }
+}
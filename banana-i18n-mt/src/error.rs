@@ -1,30 +1,202 @@
+use std::sync::Arc;
+
 /// Error types for the Machine Translation module
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum MtError {
     /// Error during anchor token operations
-    AnchorTokenError(String),
+    AnchorTokenError(ErrorMessage),
     /// Error during expansion phase
-    ExpansionError(String),
+    ExpansionError(ErrorMessage),
     /// Error during plural expansion (specific case of expansion)
-    PluralExpansionError(String),
+    PluralExpansionError(ErrorMessage),
     /// Error during translation phase (API failures, invalid responses)
-    TranslationError(String),
+    TranslationError(ErrorMessage),
     /// Error during reassembly phase
-    ReassemblyError(String),
+    ReassemblyError(ErrorMessage),
     /// Inconsistent machine translations (same source translated differently)
-    InconsistentVariants(String),
+    InconsistentVariants(ErrorMessage),
     /// MT consistency check failed - translations too different to reassemble safely
-    ConsistencyError(String),
+    ConsistencyError(ErrorMessage),
     /// Error during scope detection
-    ScopeDetectionError(String),
+    ScopeDetectionError(ErrorMessage),
     /// Invalid API configuration (missing keys, invalid credentials)
-    ConfigError(String),
+    ConfigError(ErrorMessage),
     /// Network or HTTP error (timeouts, connection failures)
-    NetworkError(String),
+    NetworkError(ErrorMessage),
     /// Invalid locale code or unsupported language
-    InvalidLocale(String),
+    InvalidLocale(ErrorMessage),
     /// General error with context
-    Other(String),
+    Other(ErrorMessage),
+    /// An error raised while processing a specific message
+    InContext {
+        /// Key of the message being processed
+        key: String,
+        /// The underlying error
+        source: Box<MtError>,
+    },
+}
+
+/// Message of an `MtError`, with the library error that caused it if any
+///
+/// Built from a `String` or `&str` with `.into()`; it dereferences to `str`
+/// and displays as the message. The cause is what `MtError::source()`
+/// returns.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ErrorMessage {
+    text: String,
+    cause: Option<ErrorCause>,
+}
+
+impl ErrorMessage {
+    /// The message text
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+}
+
+impl From<String> for ErrorMessage {
+    fn from(text: String) -> Self {
+        ErrorMessage { text, cause: None }
+    }
+}
+
+impl From<&str> for ErrorMessage {
+    fn from(text: &str) -> Self {
+        text.to_string().into()
+    }
+}
+
+impl std::ops::Deref for ErrorMessage {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.text
+    }
+}
+
+impl PartialEq<str> for ErrorMessage {
+    fn eq(&self, other: &str) -> bool {
+        self.text == other
+    }
+}
+
+impl PartialEq<&str> for ErrorMessage {
+    fn eq(&self, other: &&str) -> bool {
+        self.text == *other
+    }
+}
+
+impl std::fmt::Display for ErrorMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+/// A library error kept as the cause of an `ErrorMessage`
+///
+/// Shared, so `MtError` stays cheap to clone; two causes are equal when
+/// their messages are.
+#[derive(Debug, Clone)]
+struct ErrorCause(Arc<dyn std::error::Error + Send + Sync>);
+
+impl PartialEq for ErrorCause {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0) || self.0.to_string() == other.0.to_string()
+    }
+}
+
+impl Eq for ErrorCause {}
+
+impl MtError {
+    /// Attach the key of the message being processed to this error
+    ///
+    /// Errors that already carry a key are returned unchanged, so the
+    /// innermost (most specific) key wins when contexts are nested.
+    pub fn in_context(self, key: &str) -> MtError {
+        match self {
+            MtError::InContext { .. } => self,
+            other => MtError::InContext {
+                key: key.to_string(),
+                source: Box::new(other),
+            },
+        }
+    }
+
+    /// Record the library error that caused this error, for `source()`
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let key = std::env::var("DEEPL_API_KEY")
+    ///     .map_err(|e| MtError::ConfigError("DEEPL_API_KEY not set".into()).caused_by(e))?;
+    /// ```
+    ///
+    /// For an error with message context, the cause is recorded on the
+    /// underlying error.
+    pub fn caused_by(mut self, cause: impl std::error::Error + Send + Sync + 'static) -> MtError {
+        if let Some(message) = self.message_mut() {
+            message.cause = Some(ErrorCause(Arc::new(cause)));
+        }
+        self
+    }
+
+    /// The message of this error or of the error it wraps
+    fn message_mut(&mut self) -> Option<&mut ErrorMessage> {
+        match self {
+            MtError::AnchorTokenError(msg)
+            | MtError::ExpansionError(msg)
+            | MtError::PluralExpansionError(msg)
+            | MtError::TranslationError(msg)
+            | MtError::ReassemblyError(msg)
+            | MtError::InconsistentVariants(msg)
+            | MtError::ConsistencyError(msg)
+            | MtError::ScopeDetectionError(msg)
+            | MtError::ConfigError(msg)
+            | MtError::NetworkError(msg)
+            | MtError::InvalidLocale(msg)
+            | MtError::Other(msg) => Some(msg),
+            MtError::InContext { source, .. } => source.message_mut(),
+        }
+    }
+
+    /// Whether the same request may succeed if sent again
+    ///
+    /// Network errors (timeouts, connection failures, throttling) are
+    /// transient, as are HTTP 5xx and 429 responses kept as the `source()`.
+    /// Configuration, authentication and response format errors are not.
+    pub fn is_transient(&self) -> bool {
+        if matches!(self.root(), MtError::NetworkError(_)) {
+            return true;
+        }
+        let mut source = std::error::Error::source(self);
+        while let Some(err) = source {
+            if let Some(status) = err
+                .downcast_ref::<reqwest::Error>()
+                .and_then(reqwest::Error::status)
+            {
+                return status.is_server_error()
+                    || status == reqwest::StatusCode::TOO_MANY_REQUESTS;
+            }
+            source = err.source();
+        }
+        false
+    }
+
+    /// Key of the message this error was raised for, if known
+    pub fn message_key(&self) -> Option<&str> {
+        match self {
+            MtError::InContext { key, .. } => Some(key),
+            _ => None,
+        }
+    }
+
+    /// The underlying error, with any message context stripped
+    pub fn root(&self) -> &MtError {
+        match self {
+            MtError::InContext { source, .. } => source.root(),
+            other => other,
+        }
+    }
 }
 
 impl std::fmt::Display for MtError {
@@ -42,28 +214,131 @@ impl std::fmt::Display for MtError {
             MtError::NetworkError(msg) => write!(f, "Network error: {}", msg),
             MtError::InvalidLocale(msg) => write!(f, "Invalid locale: {}", msg),
             MtError::Other(msg) => write!(f, "{}", msg),
+            MtError::InContext { key, source } => write!(f, "[{}] {}", key, source),
         }
     }
 }
 
-impl std::error::Error for MtError {}
+impl std::error::Error for MtError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            MtError::InContext { source, .. } => Some(source.as_ref()),
+            MtError::AnchorTokenError(msg)
+            | MtError::ExpansionError(msg)
+            | MtError::PluralExpansionError(msg)
+            | MtError::TranslationError(msg)
+            | MtError::ReassemblyError(msg)
+            | MtError::InconsistentVariants(msg)
+            | MtError::ConsistencyError(msg)
+            | MtError::ScopeDetectionError(msg)
+            | MtError::ConfigError(msg)
+            | MtError::NetworkError(msg)
+            | MtError::InvalidLocale(msg)
+            | MtError::Other(msg) => msg
+                .cause
+                .as_ref()
+                .map(|cause| cause.0.as_ref() as &(dyn std::error::Error + 'static)),
+        }
+    }
+}
 
 /// Implement conversion from reqwest::Error to MtError
+///
+/// The `reqwest::Error` is kept as the `source()`.
 impl From<reqwest::Error> for MtError {
     fn from(err: reqwest::Error) -> Self {
-        if err.is_timeout() {
-            MtError::NetworkError(format!("Request timeout: {}", err))
+        let error = if err.is_timeout() {
+            MtError::NetworkError(format!("Request timeout: {}", err).into())
         } else if err.is_connect() {
-            MtError::NetworkError(format!("Connection failed: {}", err))
+            MtError::NetworkError(format!("Connection failed: {}", err).into())
         } else if err.status().map_or(false, |s| s.is_client_error()) {
-            MtError::ConfigError(format!("HTTP client error: {}", err))
+            MtError::ConfigError(format!("HTTP client error: {}", err).into())
         } else if err.status().map_or(false, |s| s.is_server_error()) {
-            MtError::TranslationError(format!("HTTP server error: {}", err))
+            MtError::TranslationError(format!("HTTP server error: {}", err).into())
+        } else if err.is_decode() {
+            MtError::TranslationError(format!("Failed to parse API response: {}", err).into())
         } else {
-            MtError::NetworkError(format!("HTTP error: {}", err))
-        }
+            MtError::NetworkError(format!("HTTP error: {}", err).into())
+        };
+        error.caused_by(err)
+    }
+}
+
+/// Implement conversion from serde_json::Error to MtError
+impl From<serde_json::Error> for MtError {
+    fn from(err: serde_json::Error) -> Self {
+        MtError::TranslationError(format!("Failed to parse API response: {}", err).into())
+            .caused_by(err)
+    }
+}
+
+/// Implement conversion from std::env::VarError to MtError
+impl From<std::env::VarError> for MtError {
+    fn from(err: std::env::VarError) -> Self {
+        MtError::ConfigError(format!("Environment variable error: {}", err).into()).caused_by(err)
     }
 }
 
 /// Result type for MT operations
 pub type MtResult<T> = Result<T, MtError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error;
+
+    #[tokio::test]
+    async fn test_network_error_has_reqwest_source() {
+        // Nothing listens on port 1
+        let err = reqwest::Client::new()
+            .get("http://127.0.0.1:1/")
+            .send()
+            .await
+            .unwrap_err();
+        let err = MtError::from(err).in_context("greeting");
+
+        assert!(matches!(err.root(), MtError::NetworkError(_)));
+        assert!(err.to_string().starts_with("[greeting] Network error:"));
+        let cause = err.source().unwrap().source().unwrap();
+        assert!(cause.downcast_ref::<reqwest::Error>().is_some());
+    }
+
+    #[test]
+    fn test_json_and_env_errors_have_source() {
+        let json_err = serde_json::from_str::<serde_json::Value>("{").unwrap_err();
+        let err = MtError::from(json_err);
+        assert!(matches!(err, MtError::TranslationError(_)));
+        assert!(err.source().unwrap().is::<serde_json::Error>());
+        assert_eq!(err.clone(), err);
+
+        let err = MtError::from(std::env::VarError::NotPresent);
+        assert!(matches!(err, MtError::ConfigError(_)));
+        assert!(err.source().unwrap().is::<std::env::VarError>());
+        assert!(MtError::Other("plain".into()).source().is_none());
+    }
+
+    #[test]
+    fn test_caused_by_keeps_variant_and_message() {
+        let err =
+            MtError::ConfigError("KEY not set".into()).caused_by(std::env::VarError::NotPresent);
+        match &err {
+            MtError::ConfigError(msg) => assert_eq!(msg, "KEY not set"),
+            other => panic!("Expected ConfigError, got {:?}", other),
+        }
+        assert_eq!(err.to_string(), "Configuration error: KEY not set");
+        assert!(err.source().unwrap().is::<std::env::VarError>());
+
+        // The cause is recorded below any message context
+        let err = MtError::Other("failed".into())
+            .in_context("greeting")
+            .caused_by(std::env::VarError::NotPresent);
+        assert_eq!(err.message_key(), Some("greeting"));
+        assert!(
+            err.source()
+                .unwrap()
+                .source()
+                .unwrap()
+                .is::<std::env::VarError>()
+        );
+    }
+}
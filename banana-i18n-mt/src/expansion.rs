@@ -27,9 +27,12 @@
 //! // ]
 //! ```
 
+use super::anchors::{ANCHOR_MARK, join_anchors};
 use super::data::{MessageContext, TranslationVariant};
 use super::error::{MtError, MtResult};
-use banana_i18n::ast::{AstNode, AstNodeList};
+use banana_i18n::ast::{AstNode, AstNodeList, Transclusion, ast_to_wikitext};
+use banana_i18n::fallbacks::resolve_locale_chain;
+use banana_i18n::magic_words::{MagicWordRegistry, MagicWordType};
 use std::collections::HashMap;
 
 // ICU dependencies for plural rules (kept from original implementation)
@@ -39,13 +42,34 @@ use icu_plurals::{PluralCategory, PluralRuleType, PluralRules};
 /// Maximum number of variants allowed to prevent combinatorial explosion
 const MAX_VARIANTS: usize = 64;
 
+/// Base for placeholder anchors: `$N` becomes 777000 + N
+///
+/// Anchors are always six digits, which is what lets adjacent anchors
+/// ("777001777002") be split and restored unambiguously.
+pub const PLACEHOLDER_ANCHOR_BASE: usize = 777000;
+
+/// Largest placeholder index (or link count) that fits in a six-digit anchor
+///
+/// `$1000` would become 778000, which no longer reads as a placeholder anchor
+/// and would be left in the translation as a plain number.
+pub const MAX_ANCHOR_INDEX: usize = 999;
+
+/// Base for link target anchors: the Nth link target becomes 888000 + N
+///
+/// Link targets and URLs must never be translated, so they are replaced by a
+/// numeric anchor (like placeholders) while the display text stays translatable.
+pub const LINK_ANCHOR_BASE: usize = 888000;
+
+/// Labels of the two branches of an `{{#if:...}}` parser function
+pub(crate) const IF_BRANCHES: [&str; 2] = ["set", "empty"];
+
 /// Information about a magic word found in the AST
 #[derive(Debug, Clone)]
 struct ChoiceInfo {
     /// Variable ID (e.g., "$1", "$2")
     var_id: String,
-    /// Magic word type ("PLURAL" or "GENDER") - stored for consistency but not used in current logic
-    #[allow(dead_code)]
+    /// Magic word type ("PLURAL", "GENDER" or "#if"), used to reject conflicting
+    /// magic words on the same parameter
     magic_type: String,
     /// Number of options available
     option_count: usize,
@@ -58,6 +82,17 @@ pub struct PluralForm {
     pub test_value: u32,
 }
 
+/// Plural forms chosen with caller-preferred test values
+///
+/// Returned by `get_plural_forms_with_preferred_values()`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PreferredPluralForms {
+    /// One form per plural category of the language
+    pub forms: Vec<PluralForm>,
+    /// Preferred values that were rejected, and plural rules borrowed from a fallback locale
+    pub warnings: Vec<String>,
+}
+
 /// Representative test values for gender selection (language-independent)
 #[derive(Debug, Clone, PartialEq)]
 pub struct GenderForm {
@@ -65,6 +100,248 @@ pub struct GenderForm {
     pub test_value: String, // For expansion, same as label
 }
 
+/// Explanation of a single magic word found during expansion
+#[derive(Debug, Clone, PartialEq)]
+pub struct MagicWordExplanation {
+    /// Magic word type ("PLURAL", "GENDER" or "#if")
+    pub magic_type: String,
+    /// Controlling parameter (e.g., "$1")
+    pub var_id: String,
+    /// Number of options written in the source message
+    pub source_option_count: usize,
+    /// Labels of the forms expanded for this magic word
+    /// (CLDR categories such as "one", "few", "many" for PLURAL; "male", "female", "unknown" for GENDER)
+    pub forms: Vec<String>,
+    /// Whether an earlier magic word is controlled by the same parameter
+    ///
+    /// Magic words on the same parameter share one form selection, so a
+    /// shared magic word adds no variants of its own.
+    pub shares_selector: bool,
+}
+
+impl MagicWordExplanation {
+    /// Number of forms expanded for this magic word
+    pub fn form_count(&self) -> usize {
+        self.forms.len()
+    }
+
+    /// Number of variants this magic word contributes to the product
+    /// (1 if it shares its selector with an earlier magic word)
+    pub fn variant_factor(&self) -> usize {
+        if self.shares_selector {
+            1
+        } else {
+            self.form_count()
+        }
+    }
+}
+
+/// Dry-run description of how a message expands for a locale
+///
+/// Returned by `explain_expansion()`. Unlike `expand_to_variants()`, building an
+/// explanation never fails because of the variant limit, so it can be used to
+/// understand why a message expands to an unexpected number of variants.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExpansionExplanation {
+    /// Locale the expansion was explained for
+    pub locale: String,
+    /// Every PLURAL/GENDER magic word, in message order
+    pub magic_words: Vec<MagicWordExplanation>,
+    /// Product of all form counts (1 for messages without magic words)
+    pub variant_count: usize,
+    /// Whether `variant_count` exceeds MAX_VARIANTS
+    pub exceeds_limit: bool,
+}
+
+impl std::fmt::Display for ExpansionExplanation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Expansion for locale '{}':", self.locale)?;
+        for word in &self.magic_words {
+            write!(
+                f,
+                "  {{{{{}:{}}}}} → {} forms [{}] ({} options in source)",
+                word.magic_type,
+                word.var_id,
+                word.form_count(),
+                word.forms.join(", "),
+                word.source_option_count
+            )?;
+            if word.shares_selector {
+                write!(f, " (shares selector {})", word.var_id)?;
+            }
+            writeln!(f)?;
+        }
+        let factors: Vec<String> = axis_form_counts(&self.magic_words)
+            .iter()
+            .map(|count| count.to_string())
+            .collect();
+        write!(
+            f,
+            "  Total: {} = {} variants",
+            if factors.is_empty() {
+                "1".to_string()
+            } else {
+                factors.join(" × ")
+            },
+            self.variant_count
+        )?;
+        if self.exceeds_limit {
+            write!(f, " (exceeds limit of {})", MAX_VARIANTS)?;
+        }
+        Ok(())
+    }
+}
+
+/// How a placeholder is presented to the MT engine
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum PlaceholderPolicy {
+    /// Hidden behind an anchor token and restored verbatim (the default)
+    #[default]
+    Protect,
+    /// Replaced by sample text that is translated with the rest of the
+    /// message, for placeholders carrying a pre-localized label rather than
+    /// an opaque value
+    Translate(String),
+}
+
+/// Number of GENDER forms expanded when a locale has no override
+pub const DEFAULT_GENDER_FORM_COUNT: usize = 3;
+
+/// Target-language settings that control how messages are expanded
+///
+/// Some languages only distinguish two genders in practice; expanding the
+/// third (unknown) form for them costs translation calls whose results then
+/// collapse anyway. A per-locale gender form count avoids that. Locales are
+/// matched exactly first, then by base language ("pt-BR" → "pt").
+///
+/// # Example
+/// ```ignore
+/// let mut config = ExpansionConfig::new();
+/// config.with_gender_forms("xx", 2);
+/// let variants = expand_to_variants_with_config(&ast, "xx", &config)?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct ExpansionConfig {
+    /// Locale → number of GENDER forms to expand
+    gender_form_counts: HashMap<String, usize>,
+    /// Collapse runs of spaces in generated source variants
+    collapse_whitespace: bool,
+    /// Magic word names and aliases treated as PLURAL/GENDER
+    magic_words: MagicWordRegistry,
+    /// Expand every target form even when the source has fewer options
+    pad_missing_forms: bool,
+    /// Placeholder index → policy, for placeholders not anchored
+    placeholder_policies: HashMap<usize, PlaceholderPolicy>,
+}
+
+impl Default for ExpansionConfig {
+    fn default() -> Self {
+        Self {
+            gender_form_counts: HashMap::new(),
+            collapse_whitespace: false,
+            magic_words: MagicWordRegistry::default(),
+            pad_missing_forms: true,
+            placeholder_policies: HashMap::new(),
+        }
+    }
+}
+
+impl ExpansionConfig {
+    /// Create a config with no overrides (every locale expands 3 GENDER forms)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Expand GENDER to `count` forms (clamped to 1..=3) for a locale
+    pub fn with_gender_forms(&mut self, locale: &str, count: usize) -> &mut Self {
+        self.gender_form_counts.insert(
+            locale.to_lowercase(),
+            count.clamp(1, DEFAULT_GENDER_FORM_COUNT),
+        );
+        self
+    }
+
+    /// Collapse runs of spaces in generated source variants (off by default)
+    ///
+    /// A magic word whose selected option is empty leaves two spaces behind
+    /// ("sent  a message"), which MT engines handle poorly. Every variant is
+    /// normalized the same way, so reassembly still aligns; the reassembled
+    /// message then has single spaces where the source had runs.
+    pub fn with_collapse_whitespace(&mut self, enabled: bool) -> &mut Self {
+        self.collapse_whitespace = enabled;
+        self
+    }
+
+    /// Recognize magic word aliases from a registry (e.g. a localized PLURAL)
+    ///
+    /// Aliased magic words expand like their canonical type and are recorded
+    /// under the canonical name, so reassembly emits `{{PLURAL:...}}`.
+    pub fn with_magic_words(&mut self, registry: MagicWordRegistry) -> &mut Self {
+        self.magic_words = registry;
+        self
+    }
+
+    /// Expand every target form even when the source has fewer options (on by default)
+    ///
+    /// A `{{GENDER:$1|he|she}}` still expands to three GENDER variants, the
+    /// third repeating the last option, and a two-option PLURAL expands to
+    /// every plural form of the target locale. Turning this off expands
+    /// exactly the source options (capped at the target form count), saving
+    /// the MT calls spent on the repeats; reassembly then emits the source
+    /// option count and, as in MediaWiki, the last option covers the rest.
+    pub fn with_pad_missing_forms(&mut self, enabled: bool) -> &mut Self {
+        self.pad_missing_forms = enabled;
+        self
+    }
+
+    /// Set how placeholder `$index` is sent to MT (`Protect` by default)
+    ///
+    /// A `Translate` placeholder is replaced by its sample text in every
+    /// variant, so the translation (and the reassembled message) contains the
+    /// translated text instead of `$index`.
+    pub fn with_placeholder_policy(
+        &mut self,
+        index: usize,
+        policy: PlaceholderPolicy,
+    ) -> &mut Self {
+        self.placeholder_policies.insert(index, policy);
+        self
+    }
+
+    /// The policy for placeholder `$index`
+    pub fn placeholder_policy(&self, index: usize) -> &PlaceholderPolicy {
+        self.placeholder_policies
+            .get(&index)
+            .unwrap_or(&PlaceholderPolicy::Protect)
+    }
+
+    /// Whether magic words with fewer options than target forms are padded
+    pub fn pad_missing_forms(&self) -> bool {
+        self.pad_missing_forms
+    }
+
+    /// The magic word registry used for expansion
+    pub fn magic_words(&self) -> &MagicWordRegistry {
+        &self.magic_words
+    }
+
+    /// Whether runs of spaces are collapsed in generated source variants
+    pub fn collapse_whitespace(&self) -> bool {
+        self.collapse_whitespace
+    }
+
+    /// Number of GENDER forms to expand for a locale
+    pub fn gender_form_count(&self, locale: &str) -> usize {
+        let locale = locale.to_lowercase();
+        let language = locale.split(['-', '_']).next().unwrap_or(&locale);
+        self.gender_form_counts
+            .get(&locale)
+            .or_else(|| self.gender_form_counts.get(language))
+            .copied()
+            .unwrap_or(DEFAULT_GENDER_FORM_COUNT)
+    }
+}
+
 /// Main expansion function: converts AST to all variant combinations
 ///
 /// This function matches the Python `expand_to_variants()` design, creating
@@ -85,13 +362,34 @@ pub struct GenderForm {
 /// assert_eq!(variants.len(), 6); // 2 PLURAL × 3 GENDER
 /// ```
 pub fn expand_to_variants(ast: &AstNodeList, locale: &str) -> MtResult<Vec<TranslationVariant>> {
+    expand_to_variants_with_config(ast, locale, &ExpansionConfig::default())
+}
+
+/// Expand an AST to all variant combinations using target-language settings
+///
+/// Same as `expand_to_variants()`, but GENDER expands to the form count
+/// configured for `locale` (the first N source options are used).
+///
+/// # Arguments
+/// * `ast` - The parsed AST of the message containing magic words
+/// * `locale` - The target locale for plural form selection
+/// * `config` - Target-language expansion settings
+///
+/// # Returns
+/// * `Ok(Vec<TranslationVariant>)` - All variants with anchor tokens
+/// * `Err(MtError)` - If variant count exceeds MAX_VARIANTS or expansion fails
+pub fn expand_to_variants_with_config(
+    ast: &AstNodeList,
+    locale: &str,
+    config: &ExpansionConfig,
+) -> MtResult<Vec<TranslationVariant>> {
     // 1. Collect all magic words (PLURAL/GENDER) and their option counts
-    let choices = collect_choices(ast, locale)?;
+    let choices = collect_choices(ast, locale, config)?;
 
     // Check for empty case
     if choices.is_empty() {
         // No magic words - create single variant with anchor tokens applied
-        let text = resolve_ast_with_anchors(ast, &HashMap::new())?;
+        let text = resolve_with_config(ast, &HashMap::new(), config)?;
         return Ok(vec![TranslationVariant::new(HashMap::new(), text)]);
     }
 
@@ -103,7 +401,7 @@ pub fn expand_to_variants(ast: &AstNodeList, locale: &str) -> MtResult<Vec<Trans
             variant_count,
             MAX_VARIANTS,
             choices.len()
-        )));
+        ).into()));
     }
 
     // 3. Generate all combinations (cartesian product)
@@ -112,7 +410,7 @@ pub fn expand_to_variants(ast: &AstNodeList, locale: &str) -> MtResult<Vec<Trans
     // 4. Resolve each state to a variant with anchor tokens
     let mut variants = Vec::new();
     for state in state_combinations {
-        let source_text = resolve_ast_with_anchors(ast, &state)?;
+        let source_text = resolve_with_config(ast, &state, config)?;
         variants.push(TranslationVariant::new(state, source_text));
     }
 
@@ -136,14 +434,81 @@ pub fn prepare_for_translation(
     ast: &AstNodeList,
     locale: &str,
     message_key: &str,
+) -> MtResult<MessageContext> {
+    prepare_for_translation_with_config(ast, locale, message_key, &ExpansionConfig::default())
+}
+
+/// Prepare message for translation using target-language expansion settings
+///
+/// Same as `prepare_for_translation()`, with GENDER form counts taken from
+/// `config`. Reassembly reconstructs as many options as were expanded.
+///
+/// # Arguments
+/// * `ast` - The parsed AST of the message
+/// * `locale` - The target locale for plural form selection
+/// * `message_key` - Original message key for reference
+/// * `config` - Target-language expansion settings
+///
+/// # Returns
+/// * `Ok(MessageContext)` - Context with all variants and metadata
+/// * `Err(MtError)` - If expansion fails
+pub fn prepare_for_translation_with_config(
+    ast: &AstNodeList,
+    locale: &str,
+    message_key: &str,
+    config: &ExpansionConfig,
 ) -> MtResult<MessageContext> {
     let mut context = MessageContext::new(message_key.to_string());
 
     // Analyze AST to extract variable types
-    analyze_ast_for_variables(ast, &mut context)?;
+    analyze_ast_for_variables(ast, &mut context, config.magic_words())?;
+
+    // Remember link targets so reassembly can put them back
+    context.link_targets = collect_link_targets(ast);
+
+    // Record which CLDR category each PLURAL (or PLURAL-ORDINAL) option
+    // index stands for
+    for (var_type, rule_type) in [
+        ("PLURAL", PluralRuleType::Cardinal),
+        ("PLURAL-ORDINAL", PluralRuleType::Ordinal),
+    ] {
+        let plural_vars: Vec<String> = context
+            .variable_types
+            .iter()
+            .filter(|(_, t)| t.as_str() == var_type)
+            .map(|(var_id, _)| var_id.clone())
+            .collect();
+        if plural_vars.is_empty() {
+            continue;
+        }
+        // A fallback to another locale's rules is reported with the context
+        let categories: Vec<PluralCategory> =
+            plural_forms(locale, rule_type, &HashMap::new(), &mut context.warnings)?
+                .iter()
+                .map(|form| form.category)
+                .collect();
+        for var_id in plural_vars {
+            context.plural_categories.insert(var_id, categories.clone());
+        }
+    }
+
+    // Remember placeholders replaced by sample text, so reassembly can
+    // report those missing from the output
+    let mut translated: Vec<usize> = placeholder_indices(&ast_to_wikitext(ast))
+        .into_iter()
+        .filter(|index| {
+            matches!(
+                config.placeholder_policy(*index),
+                PlaceholderPolicy::Translate(_)
+            )
+        })
+        .collect();
+    translated.sort_unstable();
+    translated.dedup();
+    context.translated_placeholders = translated;
 
     // Generate all variants
-    let variants = expand_to_variants(ast, locale)?;
+    let variants = expand_to_variants_with_config(ast, locale, config)?;
     for variant in variants {
         context.add_variant(variant);
     }
@@ -151,29 +516,283 @@ pub fn prepare_for_translation(
     Ok(context)
 }
 
+/// Explain how a message will be expanded, without expanding it
+///
+/// This is the non-erroring introspection companion to `expand_to_variants()`:
+/// it lists each detected magic word, its controlling parameter, the forms it
+/// expands to for `locale` (with CLDR categories for PLURAL), and the product.
+/// It explains, e.g., why the same message yields 6 variants for English
+/// (3 GENDER × 2 PLURAL) but 9 for Russian (3 GENDER × 3 PLURAL).
+///
+/// # Arguments
+/// * `ast` - The parsed AST of the message
+/// * `locale` - The target locale for plural form selection
+/// * `config` - Expansion settings; magic word aliases, GENDER form counts and
+///   padding are honoured exactly as `expand_to_variants_with_config()` does
+///
+/// # Returns
+/// * `Ok(ExpansionExplanation)` - Explanation, even if the variant limit is exceeded
+/// * `Err(MtError)` - Only if the locale's plural rules cannot be loaded
+pub fn explain_expansion(
+    ast: &AstNodeList,
+    locale: &str,
+    config: &ExpansionConfig,
+) -> MtResult<ExpansionExplanation> {
+    let mut magic_words = Vec::new();
+    let mut positional = 0;
+
+    for node in ast.iter() {
+        if let AstNode::Transclusion(trans) = node {
+            let Some(magic_type) = config.magic_words().resolve(&trans.name) else {
+                continue;
+            };
+            let var_id = choice_key(
+                magic_type.canonical_name(),
+                selector_key(&trans.param, &mut positional),
+            );
+
+            let mut forms: Vec<String> = match magic_type {
+                MagicWordType::Plural => get_plural_forms_for_language(locale)?
+                    .iter()
+                    .map(|form| plural_category_name(form.category).to_string())
+                    .collect(),
+                MagicWordType::PluralOrdinal => get_ordinal_forms_for_language(locale)?
+                    .iter()
+                    .map(|form| plural_category_name(form.category).to_string())
+                    .collect(),
+                MagicWordType::Gender => get_gender_forms()
+                    .into_iter()
+                    .take(config.gender_form_count(locale))
+                    .map(|form| form.label)
+                    .collect(),
+                MagicWordType::If => IF_BRANCHES.iter().map(|b| b.to_string()).collect(),
+            };
+            if !config.pad_missing_forms() && magic_type != MagicWordType::If {
+                forms.truncate(unpadded_count(trans, forms.len()));
+            }
+
+            let shares_selector = magic_words
+                .iter()
+                .any(|w: &MagicWordExplanation| w.var_id == var_id);
+            magic_words.push(MagicWordExplanation {
+                magic_type: magic_type.canonical_name().to_string(),
+                var_id,
+                source_option_count: trans.options.len(),
+                forms,
+                shares_selector,
+            });
+        }
+    }
+
+    // Saturate instead of erroring: an explanation should always be available
+    let variant_count = axis_form_counts(&magic_words)
+        .iter()
+        .fold(1usize, |total, count| total.saturating_mul(*count));
+
+    Ok(ExpansionExplanation {
+        locale: locale.to_string(),
+        magic_words,
+        variant_count,
+        exceeds_limit: variant_count > MAX_VARIANTS,
+    })
+}
+
+/// Number of forms expanded per selector, in order of first appearance
+///
+/// Unpadded, a shared selector expands as many forms as its longest word.
+fn axis_form_counts(words: &[MagicWordExplanation]) -> Vec<usize> {
+    let mut axes: Vec<(&str, usize)> = Vec::new();
+    for word in words {
+        match axes.iter_mut().find(|(var_id, _)| *var_id == word.var_id) {
+            Some((_, count)) => *count = (*count).max(word.form_count()),
+            None => axes.push((&word.var_id, word.form_count())),
+        }
+    }
+    axes.into_iter().map(|(_, count)| count).collect()
+}
+
+/// CLDR name of a plural category ("zero", "one", "two", "few", "many", "other")
+pub fn plural_category_name(category: PluralCategory) -> &'static str {
+    match category {
+        PluralCategory::Zero => "zero",
+        PluralCategory::One => "one",
+        PluralCategory::Two => "two",
+        PluralCategory::Few => "few",
+        PluralCategory::Many => "many",
+        PluralCategory::Other => "other",
+    }
+}
+
+/// First character of the selector of a magic word without a parameter
+///
+/// `{{GENDER:|he|she}}` refers to the reader rather than a parameter, so two
+/// such words in a message are independent. Each is keyed by its position
+/// among them instead (`#1`, `#2`, ...); reassembly writes the parameter back
+/// as empty.
+pub const POSITIONAL_SELECTOR_PREFIX: char = '#';
+
+/// Variable id of a magic word's selector: its parameter, or a positional
+/// key if the parameter is empty
+///
+/// `positional` counts the parameterless magic words seen so far; callers
+/// walk the top-level magic words in order so every pass assigns the same keys.
+pub(crate) fn selector_key(param: &str, positional: &mut usize) -> String {
+    if param.trim().is_empty() {
+        *positional += 1;
+        format!("{}{}", POSITIONAL_SELECTOR_PREFIX, positional)
+    } else {
+        param.to_string()
+    }
+}
+
+/// Whether a variable id is a positional key from `selector_key()`
+pub fn is_positional_selector(var_id: &str) -> bool {
+    var_id
+        .strip_prefix(POSITIONAL_SELECTOR_PREFIX)
+        .is_some_and(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()))
+}
+
+/// Magic words that choose independently of others on the same parameter
+///
+/// `{{#if:$1|...}}` only asks whether `$1` is empty and PLURAL-ORDINAL picks
+/// an ordinal form (1st, 2nd) rather than a cardinal one, so neither shares a
+/// selector with a PLURAL or GENDER on `$1`. Their variable ids carry the
+/// magic word as a prefix (`#if:$1`, `PLURAL-ORDINAL:$1`).
+const INDEPENDENT_MAGIC_WORDS: &[&str] = &["#if", "PLURAL-ORDINAL"];
+
+/// Variable id of a magic word: its selector (see `selector_key()`), prefixed
+/// with the magic word's canonical name for `INDEPENDENT_MAGIC_WORDS`
+///
+/// # Arguments
+/// * `magic_type` - Canonical name of the magic word ("PLURAL", "#if", ...),
+///   matched case-insensitively
+/// * `selector` - Key from `selector_key()`
+pub(crate) fn choice_key(magic_type: &str, selector: String) -> String {
+    match INDEPENDENT_MAGIC_WORDS
+        .iter()
+        .find(|name| name.eq_ignore_ascii_case(magic_type))
+    {
+        Some(name) => format!("{}:{}", name, selector),
+        None => selector,
+    }
+}
+
+/// Selector a variable id from `choice_key()` stands for: `$1` for both
+/// `$1` and `#if:$1`
+pub fn selector_of(var_id: &str) -> &str {
+    INDEPENDENT_MAGIC_WORDS
+        .iter()
+        .find_map(|name| var_id.strip_prefix(name)?.strip_prefix(':'))
+        .unwrap_or(var_id)
+}
+
 /// Collect all magic words in AST and determine their option counts
-fn collect_choices(ast: &AstNodeList, locale: &str) -> MtResult<Vec<ChoiceInfo>> {
-    let mut choices = Vec::new();
+///
+/// As in MediaWiki, magic words on the same parameter share one selector:
+/// `{{GENDER:$1|his|her}} ... {{GENDER:$1|he|she}}` always picks the same
+/// form in both places, so the parameter is one axis, not two. Using one
+/// parameter for both PLURAL and GENDER is an error; `#if` and PLURAL-ORDINAL
+/// get axes of their own (see `choice_key()`). Magic words without a
+/// parameter are independent (see `selector_key()`).
+fn collect_choices(
+    ast: &AstNodeList,
+    locale: &str,
+    config: &ExpansionConfig,
+) -> MtResult<Vec<ChoiceInfo>> {
+    let mut choices: Vec<ChoiceInfo> = Vec::new();
+    let mut positional = 0;
 
     for node in ast.iter() {
         if let AstNode::Transclusion(trans) = node {
-            let name_upper = trans.name.to_uppercase();
-
-            if name_upper == "PLURAL" {
-                // Get plural forms for this locale using ICU
-                let plural_forms = get_plural_forms_for_language(locale)?;
-                choices.push(ChoiceInfo {
-                    var_id: trans.param.clone(),
-                    magic_type: "PLURAL".to_string(),
-                    option_count: plural_forms.len(),
-                });
-            } else if name_upper == "GENDER" {
-                // Gender always has 3 forms: male, female, unknown
-                choices.push(ChoiceInfo {
-                    var_id: trans.param.clone(),
-                    magic_type: "GENDER".to_string(),
-                    option_count: 3, // Always 3 gender forms
-                });
+            let magic_type = config.magic_words().resolve(&trans.name);
+            let var_id = match magic_type {
+                Some(magic_type) => choice_key(
+                    magic_type.canonical_name(),
+                    selector_key(&trans.param, &mut positional),
+                ),
+                None => trans.param.clone(),
+            };
+            if let Some(magic_type) = magic_type
+                && let Some(existing) = choices.iter_mut().find(|c| c.var_id == var_id)
+            {
+                if existing.magic_type != magic_type.canonical_name() {
+                    return Err(MtError::ExpansionError(
+                        format!(
+                            "{} controls both {} and {}",
+                            var_id,
+                            existing.magic_type,
+                            magic_type.canonical_name()
+                        )
+                        .into(),
+                    ));
+                }
+                // Unpadded, a shared selector needs the most options any of its words has
+                if !config.pad_missing_forms() && magic_type != MagicWordType::If {
+                    let form_count = match magic_type {
+                        MagicWordType::Plural => get_plural_forms_for_language(locale)?.len(),
+                        MagicWordType::PluralOrdinal => {
+                            get_ordinal_forms_for_language(locale)?.len()
+                        }
+                        _ => config.gender_form_count(locale),
+                    };
+                    existing.option_count =
+                        existing.option_count.max(unpadded_count(trans, form_count));
+                }
+                continue;
+            }
+
+            match magic_type {
+                Some(MagicWordType::Plural) => {
+                    // Get plural forms for this locale using ICU
+                    let plural_forms = get_plural_forms_for_language(locale)?;
+                    let option_count = if config.pad_missing_forms() {
+                        plural_forms.len()
+                    } else {
+                        unpadded_count(trans, plural_forms.len())
+                    };
+                    choices.push(ChoiceInfo {
+                        var_id: var_id.clone(),
+                        magic_type: "PLURAL".to_string(),
+                        option_count,
+                    });
+                }
+                Some(MagicWordType::PluralOrdinal) => {
+                    // Ordinal categories differ from cardinal ones (English: 4 vs 2)
+                    let ordinal_forms = get_ordinal_forms_for_language(locale)?;
+                    let option_count = if config.pad_missing_forms() {
+                        ordinal_forms.len()
+                    } else {
+                        unpadded_count(trans, ordinal_forms.len())
+                    };
+                    choices.push(ChoiceInfo {
+                        var_id: var_id.clone(),
+                        magic_type: "PLURAL-ORDINAL".to_string(),
+                        option_count,
+                    });
+                }
+                Some(MagicWordType::Gender) => {
+                    // male, female, unknown - unless the target locale needs fewer
+                    let form_count = config.gender_form_count(locale);
+                    let option_count = if config.pad_missing_forms() {
+                        form_count
+                    } else {
+                        unpadded_count(trans, form_count)
+                    };
+                    choices.push(ChoiceInfo {
+                        var_id: var_id.clone(),
+                        magic_type: "GENDER".to_string(),
+                        option_count,
+                    });
+                }
+                Some(MagicWordType::If) => {
+                    // The branch for a non-empty value, then the one for an empty value
+                    choices.push(ChoiceInfo {
+                        var_id: var_id.clone(),
+                        magic_type: "#if".to_string(),
+                        option_count: IF_BRANCHES.len(),
+                    });
+                }
+                None => {}
             }
         }
     }
@@ -181,6 +800,14 @@ fn collect_choices(ast: &AstNodeList, locale: &str) -> MtResult<Vec<ChoiceInfo>>
     Ok(choices)
 }
 
+/// Number of forms to expand for a magic word when padding is off
+///
+/// The source options, capped at the target form count; at least one, so a
+/// magic word without options still yields a variant.
+fn unpadded_count(trans: &Transclusion, form_count: usize) -> usize {
+    trans.options.len().clamp(1, form_count.max(1))
+}
+
 /// Calculate total number of variants (product of all option counts)
 fn calculate_total_variants(choices: &[ChoiceInfo]) -> MtResult<usize> {
     if choices.is_empty() {
@@ -191,7 +818,7 @@ fn calculate_total_variants(choices: &[ChoiceInfo]) -> MtResult<usize> {
     for choice in choices {
         total = total
             .checked_mul(choice.option_count)
-            .ok_or_else(|| MtError::ExpansionError("Variant count overflow".to_string()))?;
+            .ok_or_else(|| MtError::ExpansionError("Variant count overflow".into()))?;
     }
 
     Ok(total)
@@ -245,9 +872,49 @@ fn cartesian_product_recursive(
     }
 }
 
+/// Resolve a state to source text, applying config normalization
+fn resolve_with_config(
+    ast: &AstNodeList,
+    state: &HashMap<String, usize>,
+    config: &ExpansionConfig,
+) -> MtResult<String> {
+    let text = resolve_ast_with_anchors(ast, state, config)?;
+    if config.collapse_whitespace {
+        Ok(collapse_spaces(&text))
+    } else {
+        Ok(text)
+    }
+}
+
+/// Collapse runs of spaces into a single space
+///
+/// Only spaces are touched; anchors are digit runs and newlines are kept.
+fn collapse_spaces(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for c in text.chars() {
+        if c == ' ' && result.ends_with(' ') {
+            continue;
+        }
+        result.push(c);
+    }
+    result
+}
+
 /// Resolve AST with specific state to plain text with anchor tokens
-fn resolve_ast_with_anchors(ast: &AstNodeList, state: &HashMap<String, usize>) -> MtResult<String> {
+///
+/// Placeholders with a `PlaceholderPolicy::Translate` policy in `config` are
+/// replaced by their sample text instead of an anchor.
+fn resolve_ast_with_anchors(
+    ast: &AstNodeList,
+    state: &HashMap<String, usize>,
+    config: &ExpansionConfig,
+) -> MtResult<String> {
+    let magic_words = config.magic_words();
+    let policies = &config.placeholder_policies;
+    let mut positional = 0;
     let mut result = String::new();
+    // Links are numbered in AST order, matching collect_link_targets()
+    let mut link_count = 0;
 
     for node in ast {
         match node {
@@ -255,22 +922,28 @@ fn resolve_ast_with_anchors(ast: &AstNodeList, state: &HashMap<String, usize>) -
                 result.push_str(text);
             }
             AstNode::Placeholder(placeholder) => {
-                // Replace $1, $2, etc. with anchor tokens 777001, 777002 (777000 + index)
-                result.push_str(&format!("{}", 777000 + placeholder.index));
+                // Replace $1, $2, etc. with anchor tokens 777001, 777002
+                result.push_str(&render_placeholder(placeholder.index, policies)?);
             }
             AstNode::Transclusion(trans) => {
-                let name_upper = trans.name.to_uppercase();
-
-                if name_upper == "PLURAL" || name_upper == "GENDER" {
+                if let Some(magic_type) = magic_words.resolve(&trans.name) {
                     // Get the selected option index from state
-                    let option_idx = state.get(&trans.param).copied().unwrap_or(0);
-
-                    // Use the selected option (or last option if index out of bounds)
-                    let actual_idx = option_idx.min(trans.options.len().saturating_sub(1));
+                    let var_id = choice_key(
+                        magic_type.canonical_name(),
+                        selector_key(&trans.param, &mut positional),
+                    );
+                    let option_idx = state.get(&var_id).copied().unwrap_or(0);
+
+                    // Use the selected option (or last option if index out of
+                    // bounds); a missing #if branch is empty
+                    let actual_idx = match magic_type {
+                        MagicWordType::If => option_idx,
+                        _ => option_idx.min(trans.options.len().saturating_sub(1)),
+                    };
 
                     if let Some(option) = trans.options.get(actual_idx) {
                         // Replace placeholders in the option with anchor tokens
-                        let option_with_anchors = replace_placeholders_with_anchors(option)?;
+                        let option_with_anchors = replace_placeholders(option, policies)?;
                         result.push_str(&option_with_anchors);
                     }
                 } else {
@@ -279,34 +952,107 @@ fn resolve_ast_with_anchors(ast: &AstNodeList, state: &HashMap<String, usize>) -
                 }
             }
             AstNode::InternalLink(link) => {
+                // Target and parameters are held constant behind an anchor,
+                // display text is translated
+                link_count += 1;
                 result.push_str("[[");
-                result.push_str(&link.target);
+                result.push_str(&link_anchor(link_count)?);
                 if let Some(ref display_text) = link.display_text {
                     result.push('|');
-                    result.push_str(display_text);
+                    result.push_str(&replace_placeholders(display_text, policies)?);
                 }
                 result.push_str("]]");
             }
             AstNode::ExternalLink(link) => {
+                // URL is held constant behind an anchor, link text is translated
+                link_count += 1;
                 result.push('[');
-                result.push_str(&link.url);
+                result.push_str(&link_anchor(link_count)?);
                 if let Some(ref text) = link.text {
                     result.push(' ');
-                    result.push_str(text);
+                    result.push_str(&replace_placeholders(text, policies)?);
                 }
                 result.push(']');
             }
+            // Comments are never sent to MT
+            AstNode::Comment(_) => {}
         }
     }
 
-    Ok(result)
+    Ok(join_anchors(&result))
+}
+
+/// Indices of the `$N` placeholders in a text, in order of appearance
+pub(crate) fn placeholder_indices(text: &str) -> Vec<usize> {
+    let re = regex::Regex::new(r"\$(\d+)").unwrap();
+    re.captures_iter(text)
+        .filter_map(|cap| cap[1].parse().ok())
+        .collect()
+}
+
+/// Anchor token for placeholder `$index`
+///
+/// # Returns
+/// * `Ok(String)` - The six-digit anchor (e.g. "777001" for `$1`)
+/// * `Err(MtError::AnchorTokenError)` - If `index` exceeds `MAX_ANCHOR_INDEX`
+pub fn placeholder_anchor(index: usize) -> MtResult<String> {
+    if index > MAX_ANCHOR_INDEX {
+        return Err(MtError::AnchorTokenError(
+            format!(
+                "Placeholder ${} is out of anchor range (at most ${})",
+                index, MAX_ANCHOR_INDEX
+            )
+            .into(),
+        ));
+    }
+    Ok(format!("{}", PLACEHOLDER_ANCHOR_BASE + index))
+}
+
+/// Text sent to MT for placeholder `$index`: its anchor, or sample text
+///
+/// Anchors come wrapped in `ANCHOR_MARK`s, settled by `join_anchors()` once
+/// the whole text is built.
+fn render_placeholder(
+    index: usize,
+    policies: &HashMap<usize, PlaceholderPolicy>,
+) -> MtResult<String> {
+    match policies.get(&index) {
+        Some(PlaceholderPolicy::Translate(sample)) => Ok(sample.clone()),
+        _ => Ok(format!(
+            "{ANCHOR_MARK}{}{ANCHOR_MARK}",
+            placeholder_anchor(index)?
+        )),
+    }
+}
+
+/// Anchor token for the `count`th link target
+fn link_anchor(count: usize) -> MtResult<String> {
+    if count > MAX_ANCHOR_INDEX {
+        return Err(MtError::AnchorTokenError(
+            format!(
+                "Too many links to anchor ({} > {})",
+                count, MAX_ANCHOR_INDEX
+            )
+            .into(),
+        ));
+    }
+    Ok(format!("{}", LINK_ANCHOR_BASE + count))
 }
 
 /// Replace placeholders with anchor tokens in a text string
-fn replace_placeholders_with_anchors(text: &str) -> MtResult<String> {
+#[cfg(test)]
+pub(crate) fn replace_placeholders_with_anchors(text: &str) -> MtResult<String> {
+    Ok(join_anchors(&replace_placeholders(text, &HashMap::new())?))
+}
+
+/// Replace placeholders with their anchors or, per `policies`, sample text
+fn replace_placeholders(
+    text: &str,
+    policies: &HashMap<usize, PlaceholderPolicy>,
+) -> MtResult<String> {
     use regex::Regex;
 
-    // Replace $1, $2, etc. with 777001, 777002, etc. (777000 + index)
+    // Replace $1, $2, etc. with 777001, 777002, etc.
     // Sort by index in descending order to handle $10 before $1 (avoid conflicts)
     let re = Regex::new(r"\$(\d+)").unwrap();
 
@@ -314,7 +1060,7 @@ fn replace_placeholders_with_anchors(text: &str) -> MtResult<String> {
     let mut matches: Vec<(usize, usize, usize)> = Vec::new(); // (start, end, placeholder_number)
     for cap in re.captures_iter(text) {
         let full_match = cap.get(0).unwrap();
-        let placeholder_num: usize = cap[1].parse().unwrap();
+        let placeholder_num: usize = cap[1].parse().unwrap_or(usize::MAX);
         matches.push((full_match.start(), full_match.end(), placeholder_num));
     }
 
@@ -323,21 +1069,55 @@ fn replace_placeholders_with_anchors(text: &str) -> MtResult<String> {
 
     let mut result = text.to_string();
     for (start, end, num) in matches {
-        let anchor = format!("{}", 777000 + num);
+        let anchor = render_placeholder(num, policies)?;
         result.replace_range(start..end, &anchor);
     }
 
     Ok(result)
 }
 
+/// Collect link targets (internal link pages and external URLs) in AST order
+///
+/// The Nth entry corresponds to the anchor `LINK_ANCHOR_BASE + N` emitted by
+/// `resolve_ast_with_anchors()`. Parameters of an internal link other than
+/// its display text (e.g. `thumb` in a file link) are part of the entry, so
+/// only the caption is translated.
+fn collect_link_targets(ast: &AstNodeList) -> Vec<String> {
+    ast.iter()
+        .filter_map(|node| match node {
+            AstNode::InternalLink(link) => Some(
+                std::iter::once(&link.target)
+                    .chain(&link.params)
+                    .cloned()
+                    .collect::<Vec<_>>()
+                    .join("|"),
+            ),
+            AstNode::ExternalLink(link) => Some(link.url.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
 /// Analyze AST to extract variable type information
-fn analyze_ast_for_variables(ast: &AstNodeList, context: &mut MessageContext) -> MtResult<()> {
+fn analyze_ast_for_variables(
+    ast: &AstNodeList,
+    context: &mut MessageContext,
+    magic_words: &MagicWordRegistry,
+) -> MtResult<()> {
+    let mut positional = 0;
     for node in ast.iter() {
-        if let AstNode::Transclusion(trans) = node {
-            let name_upper = trans.name.to_uppercase();
-            if name_upper == "PLURAL" || name_upper == "GENDER" {
-                context.add_variable(trans.param.clone(), name_upper);
-            }
+        if let AstNode::Transclusion(trans) = node
+            && let Some(magic_type) = magic_words.resolve(&trans.name)
+        {
+            let var_id = choice_key(
+                magic_type.canonical_name(),
+                selector_key(&trans.param, &mut positional),
+            );
+            context.add_variable(var_id.clone(), magic_type.canonical_name().to_string());
+            context
+                .magic_word_names
+                .entry(var_id)
+                .or_insert_with(|| trans.name.clone());
         }
     }
     Ok(())
@@ -349,24 +1129,108 @@ fn analyze_ast_for_variables(ast: &AstNodeList, context: &mut MessageContext) ->
 /// a language has, and provides representative numbers that will select each form.
 /// Preserved from the original plural_expansion.rs implementation.
 ///
+/// MediaWiki uses locale codes ICU does not accept, such as `zh-min-nan` or
+/// `zh-classical`. When the exact code is rejected, the MediaWiki fallback
+/// chain is walked (then the primary language subtag) and the rules of the
+/// first accepted ancestor are used. English is not used as a last resort,
+/// so an invalid code still fails. `prepare_for_translation()` reports such a
+/// fallback in `MessageContext::warnings`; use `supports_plural_rules()` to
+/// check a locale up front.
+///
+/// Test values are whole numbers. Categories only decimals select, such as
+/// Russian "other" (1.5), get no form of their own: MediaWiki messages do not
+/// write one, and at runtime a decimal count uses the option its category
+/// maps to.
+///
 /// # Arguments  
 /// * `locale_str` - Language code (e.g., "en", "ru", "ar", "de")
 ///
 /// # Returns
 /// Vec of PluralForm with category and test value for each form
 pub fn get_plural_forms_for_language(locale_str: &str) -> MtResult<Vec<PluralForm>> {
-    // Parse the locale
-    let locale: Locale = locale_str.parse().map_err(|e| {
-        MtError::PluralExpansionError(format!("Failed to parse locale '{}': {}", locale_str, e))
-    })?;
+    plural_forms(
+        locale_str,
+        PluralRuleType::Cardinal,
+        &HashMap::new(),
+        &mut Vec::new(),
+    )
+}
 
-    // Create plural rules for the locale (cardinal numbers)
-    let pr = PluralRules::try_new(locale.into(), PluralRuleType::Cardinal.into()).map_err(|e| {
-        MtError::PluralExpansionError(format!(
-            "Failed to create PluralRules for locale '{}': {}",
-            locale_str, e
-        ))
-    })?;
+/// Get all plural forms for a language, preferring caller-supplied test values
+///
+/// Like `get_plural_forms_for_language()`, but a preferred value is tried
+/// before the built-in ones for its category, e.g. `2` rather than `6` for
+/// English "other", which reads more naturally in the text sent to MT. A
+/// preferred value that selects a different category in this language is
+/// ignored in favour of the built-in values, and reported in the warnings.
+///
+/// # Arguments
+/// * `locale_str` - Language code (e.g., "en", "ru")
+/// * `preferred` - Preferred test value per plural category
+///
+/// # Returns
+/// The category and test value of each form, with any warnings
+///
+/// # Example
+///
+/// ```ignore
+/// let preferred = HashMap::from([(PluralCategory::Other, 2)]);
+/// let selection = get_plural_forms_with_preferred_values("en", &preferred)?;
+/// // selection.forms: [PluralForm { category: One, test_value: 1 }, PluralForm { category: Other, test_value: 2 }]
+/// ```
+pub fn get_plural_forms_with_preferred_values(
+    locale_str: &str,
+    preferred: &HashMap<PluralCategory, u32>,
+) -> MtResult<PreferredPluralForms> {
+    let mut warnings = Vec::new();
+    let forms = plural_forms(
+        locale_str,
+        PluralRuleType::Cardinal,
+        preferred,
+        &mut warnings,
+    )?;
+    Ok(PreferredPluralForms { forms, warnings })
+}
+
+/// Get all ordinal plural forms for a language, for PLURAL-ORDINAL
+///
+/// Like `get_plural_forms_for_language()`, but with CLDR ordinal rules:
+/// English has one (1st), two (2nd), few (3rd) and other (4th).
+///
+/// # Arguments
+/// * `locale_str` - Language code (e.g., "en", "cy")
+///
+/// # Returns
+/// Vec of PluralForm with category and test value for each ordinal form
+pub fn get_ordinal_forms_for_language(locale_str: &str) -> MtResult<Vec<PluralForm>> {
+    plural_forms(
+        locale_str,
+        PluralRuleType::Ordinal,
+        &HashMap::new(),
+        &mut Vec::new(),
+    )
+}
+
+/// Plural forms of a locale under cardinal or ordinal rules
+///
+/// A fallback to the rules of another locale, and preferred values rejected
+/// because they select another category, are added to `warnings`.
+fn plural_forms(
+    locale_str: &str,
+    rule_type: PluralRuleType,
+    preferred: &HashMap<PluralCategory, u32>,
+    warnings: &mut Vec<String>,
+) -> MtResult<Vec<PluralForm>> {
+    let pr = match resolve_plural_rules(locale_str, rule_type)? {
+        (pr, None) => pr,
+        (pr, Some((error, candidate))) => {
+            let warning = format!("{}; using plural rules of '{}'", error, candidate);
+            if !warnings.contains(&warning) {
+                warnings.push(warning);
+            }
+            pr
+        }
+    };
 
     // Map plural categories to specific test values
     // These test values are chosen to trigger each plural form in various languages
@@ -375,7 +1239,7 @@ pub fn get_plural_forms_for_language(locale_str: &str) -> MtResult<Vec<PluralFor
         (PluralCategory::One, vec![1u32, 21u32, 31u32, 41u32]),
         (PluralCategory::Two, vec![2u32, 22u32, 32u32]),
         (PluralCategory::Few, vec![3u32, 4u32, 23u32, 24u32]),
-        (PluralCategory::Many, vec![5u32, 11u32, 101u32]),
+        (PluralCategory::Many, vec![5u32, 11u32, 101u32, 8u32, 80u32]),
         (
             PluralCategory::Other,
             vec![6u32, 7u32, 8u32, 9u32, 10u32, 25u32, 100u32, 1000u32],
@@ -386,8 +1250,22 @@ pub fn get_plural_forms_for_language(locale_str: &str) -> MtResult<Vec<PluralFor
     let mut forms = Vec::new();
 
     for (expected_category, test_values) in test_values_by_category.iter() {
-        for &test_value in test_values {
-            let actual_category = pr.category_for(test_value as usize);
+        let preferred_value = preferred.get(expected_category).copied();
+        if let Some(value) = preferred_value
+            && pr.category_for(value) != *expected_category
+        {
+            warnings.push(format!(
+                "Preferred test value {} is not {} in '{}'; ignoring it",
+                value,
+                plural_category_name(*expected_category),
+                locale_str
+            ));
+        }
+        for test_value in preferred_value
+            .into_iter()
+            .chain(test_values.iter().copied())
+        {
+            let actual_category = pr.category_for(test_value);
             if actual_category == *expected_category {
                 forms.push(PluralForm {
                     category: *expected_category,
@@ -401,6 +1279,131 @@ pub fn get_plural_forms_for_language(locale_str: &str) -> MtResult<Vec<PluralFor
     Ok(forms)
 }
 
+/// Check whether CLDR plural rules are available for a locale
+///
+/// Uses the same candidates as `get_plural_forms_for_language()`: `_`
+/// separators are accepted, and the MediaWiki fallback chain and the primary
+/// language subtag are tried after the exact code. Lets callers warn about an
+/// unsupported target language before expanding and translating.
+///
+/// ICU accepts any well-formed tag, giving a language without CLDR data
+/// (`tlh`, `qaa`) the root rules, which only know "other". Such a locale is
+/// not supported: only CLDR rules for the language itself count.
+///
+/// # Arguments
+/// * `locale_str` - Language code (e.g., "en", "pt_BR", "zh-min-nan")
+///
+/// # Returns
+/// `true` if CLDR has plural rules for the locale or one of its fallbacks
+pub fn supports_plural_rules(locale_str: &str) -> bool {
+    plural_rule_candidates(locale_str)
+        .iter()
+        .any(|candidate| has_cldr_plural_rules(candidate))
+}
+
+/// Languages whose CLDR plural rules only have "other" (CLDR `plurals.xml`)
+///
+/// ICU's data leaves these out, as they match the root rules, so they cannot
+/// be told apart from languages CLDR has no rules for by a data lookup.
+const CLDR_OTHER_ONLY_PLURALS: &[&str] = &[
+    "bm", "bo", "dz", "hnj", "id", "ig", "ii", "in", "ja", "jbo", "jv", "jw", "kde", "kea", "km",
+    "ko", "lkt", "lo", "ms", "my", "nqo", "osa", "sah", "ses", "sg", "su", "th", "to", "tpi", "vi",
+    "wo", "yo", "yue", "zh",
+];
+
+/// Whether CLDR has cardinal plural rules for a locale, rather than ICU
+/// falling back to the root rules
+fn has_cldr_plural_rules(locale_str: &str) -> bool {
+    use icu_plurals::provider::{Baked, PluralsCardinalV1};
+    use icu_provider::prelude::*;
+
+    let Ok(locale) = locale_str.parse::<Locale>() else {
+        return false;
+    };
+    if CLDR_OTHER_ONLY_PLURALS.contains(&locale.id.language.as_str()) {
+        return true;
+    }
+    let locale = DataLocale::from(&locale);
+    let request = DataRequest {
+        id: DataIdentifierBorrowed::for_locale(&locale),
+        ..Default::default()
+    };
+    DataProvider::<PluralsCardinalV1>::load(&Baked, request).is_ok_and(|response| {
+        // The locale the data came from, if it was not the requested one
+        response
+            .metadata
+            .locale
+            .is_none_or(|found| !found.is_unknown())
+    })
+}
+
+/// Locale codes to load plural rules for, in order: the code itself (with
+/// `_` read as `-`), its MediaWiki fallbacks, then its primary language
+fn plural_rule_candidates(locale_str: &str) -> Vec<String> {
+    let normalized = locale_str.trim().replace('_', "-");
+    let primary = normalized
+        .split('-')
+        .next()
+        .unwrap_or(&normalized)
+        .to_lowercase();
+    // The chain always ends in "en"; an unrelated language must not
+    // silently get English rules
+    let mut candidates: Vec<String> = resolve_locale_chain(&normalized)
+        .into_iter()
+        .filter(|candidate| candidate != "en" || primary == "en")
+        .collect();
+    if !candidates.contains(&primary) {
+        candidates.push(primary);
+    }
+    candidates
+}
+
+/// Load plural rules for a locale, walking its fallbacks if needed
+///
+/// Returns the rules, plus the error for the exact code and the fallback
+/// used when the exact code was rejected.
+fn resolve_plural_rules(
+    locale_str: &str,
+    rule_type: PluralRuleType,
+) -> MtResult<(PluralRules, Option<(MtError, String)>)> {
+    let normalized = locale_str.trim().replace('_', "-");
+    let error = match plural_rules_for(&normalized, rule_type) {
+        Ok(pr) => return Ok((pr, None)),
+        Err(error) => error,
+    };
+
+    plural_rule_candidates(&normalized)
+        .into_iter()
+        .skip(1)
+        .find_map(|candidate| {
+            plural_rules_for(&candidate, rule_type)
+                .ok()
+                .map(|pr| (pr, candidate))
+        })
+        .map(|(pr, candidate)| (pr, Some((error.clone(), candidate))))
+        .ok_or(error)
+}
+
+/// Load cardinal or ordinal plural rules for exactly this locale code
+fn plural_rules_for(locale_str: &str, rule_type: PluralRuleType) -> MtResult<PluralRules> {
+    // Parse the locale
+    let locale: Locale = locale_str.parse().map_err(|e| {
+        MtError::PluralExpansionError(
+            format!("Failed to parse locale '{}': {}", locale_str, e).into(),
+        )
+    })?;
+
+    PluralRules::try_new(locale.into(), rule_type.into()).map_err(|e| {
+        MtError::PluralExpansionError(
+            format!(
+                "Failed to create PluralRules for locale '{}': {}",
+                locale_str, e
+            )
+            .into(),
+        )
+    })
+}
+
 /// Get all gender forms for expansion
 ///
 /// Gender expansion is language-independent. All languages use the same 3 forms:
@@ -512,6 +1515,378 @@ mod tests {
         assert_eq!(variants.len(), 9);
     }
 
+    // ========== Expansion Config Tests ==========
+
+    #[test]
+    fn test_gender_form_count_lookup() {
+        let mut config = ExpansionConfig::new();
+        config.with_gender_forms("pt", 2).with_gender_forms("xx", 9);
+
+        assert_eq!(config.gender_form_count("en"), 3);
+        assert_eq!(config.gender_form_count("pt"), 2);
+        assert_eq!(config.gender_form_count("pt-BR"), 2);
+        assert_eq!(config.gender_form_count("xx"), 3); // clamped
+    }
+
+    #[test]
+    fn test_two_gender_locale_expansion_and_reassembly() {
+        use crate::reassembly::reassemble_from_context;
+
+        let mut config = ExpansionConfig::new();
+        config.with_gender_forms("xx", 2);
+        let ast = parse("{{GENDER:$1|He|She|They}} sent a message");
+
+        let default_variants = expand_to_variants(&ast, "xx").unwrap();
+        assert_eq!(default_variants.len(), 3);
+
+        let mut context = prepare_for_translation_with_config(&ast, "xx", "test", &config).unwrap();
+        assert_eq!(context.variant_count(), 2);
+        assert_eq!(context.variants[0].source_text, "He sent a message");
+        assert_eq!(context.variants[1].source_text, "She sent a message");
+
+        context.update_translations(vec![
+            "Il a envoyé un message".to_string(),
+            "Elle a envoyé un message".to_string(),
+        ]);
+        let result = reassemble_from_context(&context).unwrap();
+        assert_eq!(result, "{{GENDER:$1|Il|Elle}} a envoyé un message");
+    }
+
+    #[test]
+    fn test_unpadded_variant_counts() {
+        let ast = parse("{{GENDER:$1|He|She}} sent {{PLURAL:$2|a message|$2 messages}}");
+
+        // Padded: 3 GENDER × 3 PLURAL for Russian, repeating the last options
+        let padded = expand_to_variants(&ast, "ru").unwrap();
+        assert_eq!(padded.len(), 9);
+
+        let mut config = ExpansionConfig::new();
+        config.with_pad_missing_forms(false);
+        let unpadded = expand_to_variants_with_config(&ast, "ru", &config).unwrap();
+        assert_eq!(unpadded.len(), 4);
+
+        // Never more than the target has forms
+        let ast = parse("{{PLURAL:$1|one|two|three}}");
+        let unpadded = expand_to_variants_with_config(&ast, "fr", &config).unwrap();
+        assert_eq!(unpadded.len(), 2);
+
+        // A shared selector takes the most options of its magic words
+        let ast = parse("{{GENDER:$1|his}} {{GENDER:$1|he|she}}");
+        let unpadded = expand_to_variants_with_config(&ast, "en", &config).unwrap();
+        assert_eq!(unpadded.len(), 2);
+    }
+
+    #[test]
+    fn test_unpadded_reassembles_to_source_option_count() {
+        use crate::reassembly::reassemble_from_context;
+
+        let mut config = ExpansionConfig::new();
+        config.with_pad_missing_forms(false);
+        let ast = parse("{{GENDER:$1|He|She}} sent a message");
+
+        let mut context = prepare_for_translation_with_config(&ast, "fr", "test", &config).unwrap();
+        assert_eq!(context.variant_count(), 2);
+
+        context.update_translations(vec![
+            "Il a envoyé un message".to_string(),
+            "Elle a envoyé un message".to_string(),
+        ]);
+        let result = reassemble_from_context(&context).unwrap();
+        assert_eq!(result, "{{GENDER:$1|Il|Elle}} a envoyé un message");
+    }
+
+    #[test]
+    fn test_collapse_whitespace_after_empty_option() {
+        // The GENDER word has no options, so it leaves a double space behind
+        let ast = parse("$1 sent {{GENDER:$1|}} a {{PLURAL:$2|message|messages}}");
+
+        let variants = expand_to_variants(&ast, "en").unwrap();
+        assert_eq!(variants[0].source_text, "777001 sent  a message");
+
+        let mut config = ExpansionConfig::new();
+        config.with_collapse_whitespace(true);
+        let variants = expand_to_variants_with_config(&ast, "en", &config).unwrap();
+        assert_eq!(variants[0].source_text, "777001 sent a message");
+        assert!(variants.iter().all(|v| !v.source_text.contains("  ")));
+
+        // Reassembly still aligns on the normalized variants
+        let mut context = prepare_for_translation_with_config(&ast, "en", "test", &config).unwrap();
+        let texts = context.source_texts();
+        context.update_translations(texts);
+        let result = crate::reassembly::reassemble_from_context(&context).unwrap();
+        assert_eq!(result, "$1 sent a {{PLURAL:$2|message|messages}}");
+    }
+
+    #[test]
+    fn test_magic_word_alias_expands_like_plural() {
+        let ast = parse("{{MEHRZAHL:$1|a file|$1 files}} deleted");
+
+        // Not a magic word by default: a single variant
+        assert_eq!(expand_to_variants(&ast, "en").unwrap().len(), 1);
+
+        let mut registry = MagicWordRegistry::new();
+        registry.with_alias("MEHRZAHL", MagicWordType::Plural);
+        let mut config = ExpansionConfig::new();
+        config.with_magic_words(registry);
+
+        let context = prepare_for_translation_with_config(&ast, "en", "test", &config).unwrap();
+        assert_eq!(context.variant_count(), 2);
+        assert_eq!(context.variants[0].source_text, "a file deleted");
+        assert_eq!(context.variants[1].source_text, "777001 files deleted");
+        assert_eq!(context.get_variable_type("$1"), Some(&"PLURAL".to_string()));
+    }
+
+    #[test]
+    fn test_if_expands_to_both_branches() {
+        let ast = parse("Blocked{{#if:$1|: $1|}}");
+        let context = prepare_for_translation(&ast, "en", "test").unwrap();
+        assert_eq!(
+            context.get_variable_type("#if:$1"),
+            Some(&"#if".to_string())
+        );
+
+        let texts = context.source_texts();
+        // The empty branch was dropped by the parser and expands to nothing
+        assert_eq!(texts, vec!["Blocked: 777001", "Blocked"]);
+        assert_eq!(
+            context.describe_variant(&context.variants[1]),
+            "#if:$1=empty"
+        );
+    }
+
+    #[test]
+    fn test_if_and_plural_on_the_same_parameter() {
+        use crate::reassembly::reassemble_from_context;
+
+        let message = "{{#if:$1|Selected:|Nothing selected}} {{PLURAL:$1|one file|$1 files}}";
+        let mut context = prepare_for_translation(&parse(message), "en", "test").unwrap();
+        assert_eq!(context.variant_count(), 4);
+        assert_eq!(context.get_variable_type("$1"), Some(&"PLURAL".to_string()));
+        assert_eq!(
+            context.get_variable_type("#if:$1"),
+            Some(&"#if".to_string())
+        );
+        assert!(
+            context
+                .source_texts()
+                .contains(&"Nothing selected 777001 files".to_string())
+        );
+
+        let translations = context.source_texts();
+        context.update_translations(translations);
+        assert_eq!(reassemble_from_context(&context).unwrap(), message);
+    }
+
+    #[test]
+    fn test_cardinal_and_ordinal_plural_on_the_same_parameter() {
+        use crate::reassembly::reassemble_from_context;
+
+        let message =
+            "{{PLURAL:$1|$1 lap|$1 laps}}, the {{PLURAL-ORDINAL:$1|$1st|$1nd|$1rd|$1th}} race";
+        let mut context = prepare_for_translation(&parse(message), "en", "test").unwrap();
+        // 2 cardinal forms × 4 ordinal forms in English
+        assert_eq!(context.variant_count(), 8);
+        assert_eq!(
+            context.get_variable_type("PLURAL-ORDINAL:$1"),
+            Some(&"PLURAL-ORDINAL".to_string())
+        );
+        assert_eq!(context.plural_categories["PLURAL-ORDINAL:$1"].len(), 4);
+
+        let translations = context.source_texts();
+        context.update_translations(translations);
+        assert_eq!(
+            reassemble_from_context(&context).unwrap(),
+            "$1 {{PLURAL:$1|lap,|laps,}} the {{PLURAL-ORDINAL:$1|$1st|$1nd|$1rd|$1th}} race"
+        );
+    }
+
+    #[test]
+    fn test_translatable_placeholder_passes_sample_text_to_mt() {
+        use crate::reassembly::{Reassembler, reassemble_from_context};
+
+        let mut config = ExpansionConfig::new();
+        config.with_placeholder_policy(2, PlaceholderPolicy::Translate("Watchlist".to_string()));
+        assert_eq!(config.placeholder_policy(1), &PlaceholderPolicy::Protect);
+
+        let ast = parse("$1 added {{PLURAL:$3|a page|$3 pages}} to $2");
+        let mut context = prepare_for_translation_with_config(&ast, "en", "test", &config).unwrap();
+        assert_eq!(
+            context.source_texts(),
+            vec![
+                "777001 added a page to Watchlist",
+                "777001 added 777003 pages to Watchlist",
+            ]
+        );
+
+        context.update_translations(vec![
+            "777001 a ajouté une page à la liste de suivi".to_string(),
+            "777001 a ajouté 777003 pages à la liste de suivi".to_string(),
+        ]);
+        let result = reassemble_from_context(&context).unwrap();
+        assert_eq!(
+            result,
+            "$1 a ajouté {{PLURAL:$3|une page|$3 pages}} à la liste de suivi"
+        );
+
+        // $2 is gone from the output, which the detailed result reports
+        assert_eq!(context.translated_placeholders, vec![2]);
+        let output = Reassembler::from_context(&context)
+            .reassemble_detailed(context.variants.clone())
+            .unwrap();
+        assert_eq!(output.wikitext, result);
+        assert_eq!(
+            output.warnings,
+            vec!["Placeholder $2 was translated as sample text and is missing from the output"]
+        );
+    }
+
+    // ========== Parameterless Magic Word Tests ==========
+
+    #[test]
+    fn test_parameterless_genders_expand_independently() {
+        use crate::reassembly::{Reassembler, StructureCheck, reassemble_from_context};
+
+        let ast = parse("{{GENDER:|He|She|They}} is ready, {{GENDER:|he|she|they}} left");
+        let mut context = prepare_for_translation(&ast, "fr", "test").unwrap();
+        assert_eq!(context.variant_count(), 9);
+        assert_eq!(context.variable_ids(), vec!["#1", "#2"]);
+        assert_eq!(
+            explain_expansion(&ast, "fr", &ExpansionConfig::default())
+                .unwrap()
+                .variant_count,
+            9
+        );
+
+        let subjects = ["Il", "Elle", "Iel"];
+        let objects = ["lui", "elle", "iel"];
+        let translations = context
+            .variants
+            .iter()
+            .map(|v| {
+                format!(
+                    "{} est prêt, {} est parti",
+                    subjects[v.state["#1"]], objects[v.state["#2"]]
+                )
+            })
+            .collect();
+        context.update_translations(translations);
+
+        let result = reassemble_from_context(&context).unwrap();
+        assert_eq!(
+            result,
+            "{{GENDER:|Il|Elle|Iel}} est prêt, {{GENDER:|lui|elle|iel}} est parti"
+        );
+        let checked = Reassembler::from_context(&context)
+            .with_structure_check(StructureCheck::Strict)
+            .reassemble(context.variants);
+        assert_eq!(checked.unwrap(), result);
+    }
+
+    // ========== Expansion Explanation Tests ==========
+
+    #[test]
+    fn test_explain_expansion_english_and_russian() {
+        let ast = parse("{{GENDER:$1|He|She|They}} sent {{PLURAL:$2|a message|$2 messages}}");
+
+        let en = explain_expansion(&ast, "en", &ExpansionConfig::default()).unwrap();
+        assert_eq!(en.magic_words.len(), 2);
+        assert_eq!(en.magic_words[0].magic_type, "GENDER");
+        assert_eq!(en.magic_words[0].var_id, "$1");
+        assert_eq!(en.magic_words[0].forms, vec!["male", "female", "unknown"]);
+        assert_eq!(en.magic_words[1].magic_type, "PLURAL");
+        assert_eq!(en.magic_words[1].var_id, "$2");
+        assert_eq!(en.magic_words[1].source_option_count, 2);
+        assert_eq!(en.magic_words[1].forms, vec!["one", "other"]);
+        assert_eq!(en.variant_count, 6);
+        assert!(!en.exceeds_limit);
+
+        let ru = explain_expansion(&ast, "ru", &ExpansionConfig::default()).unwrap();
+        assert_eq!(ru.magic_words[0].form_count(), 3);
+        assert_eq!(ru.magic_words[1].forms, vec!["one", "few", "many"]);
+        assert_eq!(ru.variant_count, 9);
+
+        // The explanation agrees with the actual expansion
+        assert_eq!(
+            expand_to_variants(&ast, "ru").unwrap().len(),
+            ru.variant_count
+        );
+        assert!(ru.to_string().contains("Total: 3 × 3 = 9 variants"));
+    }
+
+    #[test]
+    fn test_explain_expansion_does_not_error_over_limit() {
+        let message = "{{PLURAL:$1|a|b}} {{PLURAL:$2|a|b}} {{PLURAL:$3|a|b}} {{PLURAL:$4|a|b}} {{PLURAL:$5|a|b}} {{PLURAL:$6|a|b}} {{PLURAL:$7|a|b}}";
+        let ast = parse(message);
+        assert!(expand_to_variants(&ast, "en").is_err());
+
+        let explanation = explain_expansion(&ast, "en", &ExpansionConfig::default()).unwrap();
+        assert_eq!(explanation.variant_count, 128);
+        assert!(explanation.exceeds_limit);
+    }
+
+    #[test]
+    fn test_explain_expansion_follows_config() {
+        let ast =
+            parse("{{GESCHLECHT:$1|Er|Sie}} hat {{PLURAL:$2|eine Datei|$2 Dateien}} gelöscht");
+
+        let mut registry = MagicWordRegistry::new();
+        registry.with_alias("GESCHLECHT", MagicWordType::Gender);
+        let mut config = ExpansionConfig::new();
+        config
+            .with_magic_words(registry)
+            .with_gender_forms("de", 2)
+            .with_pad_missing_forms(false);
+
+        let explanation = explain_expansion(&ast, "de", &config).unwrap();
+        assert_eq!(explanation.magic_words.len(), 2);
+        assert_eq!(explanation.magic_words[0].magic_type, "GENDER");
+        assert_eq!(explanation.magic_words[0].forms, vec!["male", "female"]);
+        assert_eq!(explanation.variant_count, 4);
+        assert_eq!(
+            expand_to_variants_with_config(&ast, "de", &config)
+                .unwrap()
+                .len(),
+            explanation.variant_count
+        );
+
+        // The default registry does not know the alias
+        let default = explain_expansion(&ast, "de", &ExpansionConfig::default()).unwrap();
+        assert_eq!(default.magic_words.len(), 1);
+    }
+
+    // ========== Shared Selector Tests ==========
+
+    #[test]
+    fn test_same_parameter_magic_words_share_selector() {
+        let ast = parse("{{GENDER:$1|He|She|They}} updated {{GENDER:$1|his|her|their}} profile");
+        let variants = expand_to_variants(&ast, "en").unwrap();
+
+        // One GENDER axis, not 3 × 3
+        assert_eq!(variants.len(), 3);
+        let texts: Vec<&str> = variants.iter().map(|v| v.source_text.as_str()).collect();
+        assert_eq!(
+            texts,
+            vec![
+                "He updated his profile",
+                "She updated her profile",
+                "They updated their profile"
+            ]
+        );
+
+        let explanation = explain_expansion(&ast, "en", &ExpansionConfig::default()).unwrap();
+        assert!(!explanation.magic_words[0].shares_selector);
+        assert!(explanation.magic_words[1].shares_selector);
+        assert_eq!(explanation.variant_count, 3);
+        assert!(explanation.to_string().contains("Total: 3 = 3 variants"));
+    }
+
+    #[test]
+    fn test_same_parameter_with_different_types_is_an_error() {
+        let ast = parse("{{GENDER:$1|He|She}} sent {{PLURAL:$1|a message|messages}}");
+        let result = expand_to_variants(&ast, "en");
+        assert!(matches!(result, Err(MtError::ExpansionError(msg)) if msg.contains("$1")));
+    }
+
     // ========== Variant Count Calculation Tests ==========
 
     #[test]
@@ -595,6 +1970,18 @@ mod tests {
         assert_eq!(result, "Hello, World!");
     }
 
+    #[test]
+    fn test_placeholder_anchor_range() {
+        assert_eq!(placeholder_anchor(999).unwrap(), "777999");
+        // 777000 + 1000 = 778000 would not read back as an anchor
+        assert!(matches!(
+            placeholder_anchor(1000),
+            Err(MtError::AnchorTokenError(_))
+        ));
+        assert!(replace_placeholders_with_anchors("$1000 files").is_err());
+        assert!(expand_to_variants(&parse("Deleted $1000 files"), "en").is_err());
+    }
+
     // ========== ICU Plural Form Tests ==========
 
     #[test]
@@ -626,6 +2013,103 @@ mod tests {
         assert!(forms.iter().any(|f| f.test_value == 1)); // one
         assert!(forms.iter().any(|f| f.test_value == 2 || f.test_value == 3)); // few
         assert!(forms.iter().any(|f| f.test_value == 5)); // many
+        // "other" only applies to decimals
+        assert!(
+            !forms
+                .iter()
+                .any(|f| f.category == icu_plurals::PluralCategory::Other)
+        );
+    }
+
+    #[test]
+    fn test_russian_plural_axis_is_labeled_with_categories() {
+        let mut parser =
+            Parser::new("{{GENDER:$1|He|She}} uploaded {{PLURAL:$2|$2 файл|$2 файла|$2 файлов}}");
+        let ast = parser.parse();
+        let context = prepare_for_translation(&ast, "ru", "upload").unwrap();
+
+        // Russian option indices map to one, few, many, ...
+        assert_eq!(
+            context.plural_category("$2", 0),
+            Some(icu_plurals::PluralCategory::One)
+        );
+        assert_eq!(
+            context.plural_category("$2", 1),
+            Some(icu_plurals::PluralCategory::Few)
+        );
+        assert_eq!(
+            context.plural_category("$2", 2),
+            Some(icu_plurals::PluralCategory::Many)
+        );
+        assert_eq!(context.plural_category("$1", 0), None);
+
+        let variant = context
+            .variants
+            .iter()
+            .find(|v| v.state["$1"] == 1 && v.state["$2"] == 1)
+            .unwrap();
+        assert_eq!(context.describe_variant(variant), "$1=female, $2=few");
+    }
+
+    #[test]
+    fn test_extended_locale_tags_fall_back_to_base_rules() {
+        let categories = |locale: &str| -> Vec<PluralCategory> {
+            get_plural_forms_for_language(locale)
+                .unwrap()
+                .iter()
+                .map(|form| form.category)
+                .collect()
+        };
+
+        assert_eq!(categories("de-formal"), categories("de"));
+        assert_eq!(categories("ru-x-wiki"), categories("ru"));
+        assert_eq!(categories("zh-min-nan"), categories("zh"));
+        assert_eq!(categories("be-x-old"), categories("be"));
+
+        let ast = parse("{{PLURAL:$1|one file|$1 files}}");
+        assert_eq!(expand_to_variants(&ast, "de-formal").unwrap().len(), 2);
+
+        // The fallback is reported with the prepared message, not printed
+        let context = prepare_for_translation(&ast, "zh-classical", "test").unwrap();
+        assert_eq!(context.warnings.len(), 1);
+        assert!(context.warnings[0].contains("'zh-classical'"));
+        assert!(context.warnings[0].ends_with("using plural rules of 'lzh'"));
+        let context = prepare_for_translation(&ast, "de-formal", "test").unwrap();
+        assert!(context.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_preferred_plural_test_values() {
+        let preferred = HashMap::from([(PluralCategory::Other, 2)]);
+        let selection = get_plural_forms_with_preferred_values("en", &preferred).unwrap();
+        assert!(selection.warnings.is_empty());
+        assert_eq!(
+            selection.forms,
+            vec![
+                PluralForm {
+                    category: PluralCategory::One,
+                    test_value: 1
+                },
+                PluralForm {
+                    category: PluralCategory::Other,
+                    test_value: 2
+                },
+            ]
+        );
+        let rules = plural_rules_for("en", PluralRuleType::Cardinal).unwrap();
+        assert_eq!(rules.category_for(2u32), PluralCategory::Other);
+
+        // 1 is not "other" in English, so the built-in value is kept
+        let preferred = HashMap::from([(PluralCategory::Other, 1)]);
+        let selection = get_plural_forms_with_preferred_values("en", &preferred).unwrap();
+        assert_eq!(
+            selection.forms,
+            get_plural_forms_for_language("en").unwrap()
+        );
+        assert_eq!(
+            selection.warnings,
+            vec!["Preferred test value 1 is not other in 'en'; ignoring it"]
+        );
     }
 
     #[test]
@@ -634,6 +2118,22 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_supports_plural_rules() {
+        assert!(supports_plural_rules("ru"));
+        assert!(supports_plural_rules("pt_BR"));
+        assert!(supports_plural_rules("zh-min-nan"));
+        assert!(!supports_plural_rules("invalid-locale"));
+        assert!(!supports_plural_rules(""));
+
+        // Well-formed tags that CLDR has no plural rules for
+        for locale in ["tlh", "qaa", "xyz", "zz"] {
+            assert!(!supports_plural_rules(locale), "{}", locale);
+        }
+        assert!(supports_plural_rules("en-US"));
+        assert!(supports_plural_rules("ja"));
+    }
+
     // ========== Gender Form Tests ==========
 
     #[test]
@@ -656,7 +2156,9 @@ mod tests {
         assert_eq!(variants.len(), 6); // 3 GENDER × 2 PLURAL
 
         for variant in &variants {
-            assert!(variant.source_text.contains("article"));
+            // Link target is held constant behind a link anchor
+            assert!(variant.source_text.contains("[[888001]]"));
+            assert!(!variant.source_text.contains("article"));
             // Note: $1 is used as GENDER control, no 777001 expected
             // Only 777002 appears in plural forms
         }
@@ -667,21 +2169,90 @@ mod tests {
         assert!(variants[5].source_text.contains("777002"));
     }
 
+    #[test]
+    fn test_link_display_text_is_translatable() {
+        let ast = parse("See [[Cat|the cat]] and [https://example.org $1 docs]");
+        let variants = expand_to_variants(&ast, "en").unwrap();
+        assert_eq!(variants.len(), 1);
+        assert_eq!(
+            variants[0].source_text,
+            "See [[888001|the cat]] and [888002 777001 docs]"
+        );
+
+        let context = prepare_for_translation(&ast, "en", "test").unwrap();
+        assert_eq!(context.link_targets, vec!["Cat", "https://example.org"]);
+    }
+
+    #[test]
+    fn test_file_link_caption_is_the_only_translatable_part() {
+        let ast = parse("[[File:Bar.png|thumb|upright|$1 at work]]");
+        let context = prepare_for_translation(&ast, "en", "test").unwrap();
+        assert_eq!(context.variants[0].source_text, "[[888001|777001 at work]]");
+        assert_eq!(context.link_targets, vec!["File:Bar.png|thumb|upright"]);
+
+        // Image options and sort keys alone leave nothing to translate
+        let ast = parse("See [[File:X.png|200px]] in [[Category:Foo|Sortkey]]");
+        let context = prepare_for_translation(&ast, "en", "test").unwrap();
+        assert_eq!(
+            context.variants[0].source_text,
+            "See [[888001]] in [[888002]]"
+        );
+        assert_eq!(
+            context.link_targets,
+            vec!["File:X.png|200px", "Category:Foo|Sortkey"]
+        );
+    }
+
+    #[test]
+    fn test_comments_are_not_expanded() {
+        let ast =
+            parse("<!-- $1 is a user name -->{{GENDER:$1|He|She|They}} left<!-- keep short -->");
+        let variants = expand_to_variants(&ast, "en").unwrap();
+        assert_eq!(variants.len(), 3);
+        for variant in &variants {
+            assert!(!variant.source_text.contains("<!--"));
+            assert!(!variant.source_text.contains("user name"));
+        }
+        assert_eq!(variants[0].source_text, "He left");
+
+        // Nor are comments inside magic word options
+        let ast = parse("Hi <!-- note -->{{PLURAL:$1|one<!-- x -->|many}}");
+        let texts: Vec<String> = expand_to_variants(&ast, "en")
+            .unwrap()
+            .into_iter()
+            .map(|variant| variant.source_text)
+            .collect();
+        assert_eq!(texts, vec!["Hi one", "Hi many"]);
+    }
+
     #[test]
     fn test_analyze_ast_for_variables() {
         let ast = parse("{{GENDER:$1|He|She}} sent {{PLURAL:$2|one|many}}");
         let mut context = MessageContext::new("test".to_string());
-        analyze_ast_for_variables(&ast, &mut context).unwrap();
+        analyze_ast_for_variables(&ast, &mut context, MagicWordRegistry::default_registry())
+            .unwrap();
 
         assert_eq!(context.get_variable_type("$1"), Some(&"GENDER".to_string()));
         assert_eq!(context.get_variable_type("$2"), Some(&"PLURAL".to_string()));
         assert_eq!(context.get_variable_type("$3"), None);
     }
 
+    #[test]
+    fn test_analyze_ast_records_source_magic_word_names() {
+        let ast = parse("{{gender:$1|He|She}} sent {{Plural:$2|one|many}}");
+        let mut context = MessageContext::new("test".to_string());
+        analyze_ast_for_variables(&ast, &mut context, MagicWordRegistry::default_registry())
+            .unwrap();
+
+        assert_eq!(context.get_variable_type("$2"), Some(&"PLURAL".to_string()));
+        assert_eq!(context.magic_word_names["$1"], "gender");
+        assert_eq!(context.magic_word_names["$2"], "Plural");
+    }
+
     #[test]
     fn test_empty_choices_collection() {
         let ast = parse("Plain message with $1");
-        let choices = collect_choices(&ast, "en").unwrap();
+        let choices = collect_choices(&ast, "en", &ExpansionConfig::default()).unwrap();
         assert!(choices.is_empty());
     }
 }
@@ -36,6 +36,7 @@ use crate::error::{MtError, MtResult};
 use crate::translator::{MachineTranslator, normalize_locale, validate_locale};
 use async_trait::async_trait;
 use serde_json::json;
+use std::time::Duration;
 
 /// Google Translate API v2 provider
 ///
@@ -49,6 +50,10 @@ pub struct GoogleTranslateProvider {
     client: reqwest::Client,
     /// Base URL for Google Translate API
     base_url: String,
+    /// How many times a failed chunk is retried by `translate_batch_partial`
+    chunk_retries: usize,
+    /// Delay before the first retry of a chunk, doubled for each further one
+    retry_delay: Duration,
 }
 
 impl GoogleTranslateProvider {
@@ -56,9 +61,15 @@ impl GoogleTranslateProvider {
     /// Google Translate v2 API accepts up to 128 texts per request
     const MAX_BATCH_SIZE: usize = 128;
 
-    /// Maximum characters per string (30KB per Google Translate API limits)
+    /// Maximum characters per string (per Google Translate API limits)
+    ///
+    /// Counted in Unicode code points, as Google counts (and bills) them, not
+    /// in UTF-8 bytes: 30,000 Malayalam characters are 90,000 bytes.
     const MAX_CHARS_PER_STRING: usize = 30_000;
 
+    /// Delay before the first retry of a failed chunk
+    const DEFAULT_RETRY_DELAY: Duration = Duration::from_millis(500);
+
     /// Create a new GoogleTranslateProvider with an explicit API key
     ///
     /// # Arguments
@@ -77,21 +88,56 @@ impl GoogleTranslateProvider {
     /// ```
     pub fn new(api_key: String) -> MtResult<Self> {
         if api_key.trim().is_empty() {
-            return Err(MtError::ConfigError("API key cannot be empty".to_string()));
+            return Err(MtError::ConfigError("API key cannot be empty".into()));
         }
 
         let client = reqwest::Client::builder()
             .timeout(std::time::Duration::from_secs(30))
             .build()
-            .map_err(|e| MtError::NetworkError(format!("Failed to create HTTP client: {}", e)))?;
+            .map_err(|e| {
+                MtError::NetworkError(format!("Failed to create HTTP client: {}", e).into())
+                    .caused_by(e)
+            })?;
 
         Ok(Self {
             api_key,
             client,
             base_url: "https://translation.googleapis.com/language/translate/v2".to_string(),
+            chunk_retries: 0,
+            retry_delay: Self::DEFAULT_RETRY_DELAY,
         })
     }
 
+    /// Use a different API endpoint (e.g. a proxy or a local test server)
+    ///
+    /// # Arguments
+    ///
+    /// * `base_url` - URL of the v2 translate endpoint, without query string
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Retry each failed chunk up to `retries` times in `translate_batch_partial`
+    ///
+    /// Only the failed chunk is resent, so chunks that already succeeded are
+    /// not paid for twice, and only after a transient failure (see
+    /// `MtError::is_transient()`): a rejected key fails the same way every
+    /// time. The default is no retries.
+    pub fn with_chunk_retries(mut self, retries: usize) -> Self {
+        self.chunk_retries = retries;
+        self
+    }
+
+    /// Set the delay before the first retry of a chunk (default: 500 ms)
+    ///
+    /// Each further retry of the same chunk waits twice as long as the one
+    /// before, giving an overloaded or throttling API time to recover.
+    pub fn with_retry_delay(mut self, delay: Duration) -> Self {
+        self.retry_delay = delay;
+        self
+    }
+
     /// Create a GoogleTranslateProvider from the `GOOGLE_TRANSLATE_API_KEY` environment variable
     ///
     /// # Returns
@@ -105,10 +151,9 @@ impl GoogleTranslateProvider {
     /// let provider = GoogleTranslateProvider::from_env()?;
     /// ```
     pub fn from_env() -> MtResult<Self> {
-        let api_key = std::env::var("GOOGLE_TRANSLATE_API_KEY").map_err(|_| {
-            MtError::ConfigError(
-                "GOOGLE_TRANSLATE_API_KEY environment variable not set".to_string(),
-            )
+        let api_key = std::env::var("GOOGLE_TRANSLATE_API_KEY").map_err(|e| {
+            MtError::ConfigError("GOOGLE_TRANSLATE_API_KEY environment variable not set".into())
+                .caused_by(e)
         })?;
 
         Self::new(api_key)
@@ -166,30 +211,39 @@ impl GoogleTranslateProvider {
         // Send POST request
         let response = self.client.post(&url).json(&body).send().await?;
 
-        // Check HTTP status
-        if !response.status().is_success() {
+        // Check HTTP status; the URL carries the API key, so it is left out
+        // of the status error kept as the source
+        if let Err(cause) = response.error_for_status_ref() {
+            let cause = cause.without_url();
             let status = response.status();
             let error_text = response
                 .text()
                 .await
                 .unwrap_or_else(|_| "Unknown error".to_string());
 
-            return Err(if status.is_client_error() {
-                MtError::ConfigError(format!("API client error ({}): {}", status, error_text))
+            let error = if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+                MtError::NetworkError(
+                    format!("API rate limit exceeded ({}): {}", status, error_text).into(),
+                )
+            } else if status.is_client_error() {
+                MtError::ConfigError(
+                    format!("API client error ({}): {}", status, error_text).into(),
+                )
             } else {
-                MtError::TranslationError(format!("API server error ({}): {}", status, error_text))
-            });
+                MtError::TranslationError(
+                    format!("API server error ({}): {}", status, error_text).into(),
+                )
+            };
+            return Err(error.caused_by(cause));
         }
 
         // Parse response JSON
-        let json: serde_json::Value = response.json().await.map_err(|e| {
-            MtError::TranslationError(format!("Failed to parse API response: {}", e))
-        })?;
+        let json: serde_json::Value = response.json().await?;
 
         // Extract translations from nested response
         let translations = json["data"]["translations"].as_array().ok_or_else(|| {
             MtError::TranslationError(
-                "Invalid API response: missing 'data.translations' array".to_string(),
+                "Invalid API response: missing 'data.translations' array".into(),
             )
         })?;
 
@@ -202,7 +256,7 @@ impl GoogleTranslateProvider {
                     .map(|s: &str| s.to_string())
                     .ok_or_else(|| {
                         MtError::TranslationError(
-                            "Invalid API response: missing 'translatedText' field".to_string(),
+                            "Invalid API response: missing 'translatedText' field".into(),
                         )
                     })
             })
@@ -211,112 +265,120 @@ impl GoogleTranslateProvider {
         results
     }
 
-    /// Translate multiple variants as a single block with numbering
+    /// Translate a batch, keeping the results of chunks that succeeded
     ///
-    /// This method implements the Python `translate_as_block()` approach,
-    /// joining all variants with numbered prefixes to force MT consistency.
-    /// All variants are translated in one API call, ensuring the MT system
-    /// sees the context and maintains consistency across related variants.
+    /// Like `translate_batch()`, texts are sent in chunks of up to 128. When a
+    /// chunk fails (after `with_chunk_retries()` attempts), its texts are marked
+    /// with the chunk's error and the remaining chunks are still translated,
+    /// instead of discarding all completed work.
     ///
     /// # Arguments
-    /// * `variants` - Vector of strings to translate as a block
+    ///
+    /// * `texts` - Texts to translate
     /// * `source_locale` - Source language code
     /// * `target_locale` - Target language code
     ///
     /// # Returns
-    /// * `Ok(Vec<String>)` - Translated variants in same order as input
-    /// * `Err(MtError)` - If translation fails or count mismatch occurs
     ///
-    /// # Algorithm (matches Python lines 145-186)
-    /// ```text
-    /// 1. Join variants with numbered prefixes: "1. text\n2. text\n..."
-    /// 2. Translate the entire block as single text
-    /// 3. Split back using regex to extract individual translations
-    /// 4. Clean up any anchor token spacing issues
-    /// ```
-    ///
-    /// # Example
-    /// ```ignore
-    /// let variants = vec![
-    ///     "777001 sent a message".to_string(),
-    ///     "777001 sent 777002 messages".to_string(),
-    /// ];
-    /// let results = provider.translate_as_block(&variants, "en", "fr").await?;
-    /// // Results maintain consistency: ["777001 a envoyé un message", "777001 a envoyé 777002 messages"]
-    /// ```
-    pub async fn translate_as_block(
+    /// * `Ok(Vec<MtResult<String>>)` - One result per input text, in input order
+    /// * `Err(MtError)` - If the input is invalid (bad locale, text too long);
+    ///   nothing is sent in that case
+    pub async fn translate_batch_partial(
         &self,
-        variants: &[String],
+        texts: &[String],
         source_locale: &str,
         target_locale: &str,
-    ) -> MtResult<Vec<String>> {
-        // Handle empty case
-        if variants.is_empty() {
-            return Ok(Vec::new());
-        }
-
-        // Handle single variant case
-        if variants.len() == 1 {
-            let result = self
-                .translate(&variants[0], source_locale, target_locale)
-                .await?;
-            return Ok(vec![result]);
+    ) -> MtResult<Vec<MtResult<String>>> {
+        validate_locale(source_locale)?;
+        validate_locale(target_locale)?;
+        self.validate_batch(texts)?;
+
+        let mut results = Vec::with_capacity(texts.len());
+        for chunk in Self::chunk_batch(texts) {
+            let mut attempt = 0;
+            let outcome = loop {
+                match self
+                    .translate_chunk(chunk, source_locale, target_locale)
+                    .await
+                {
+                    Ok(translations) if translations.len() == chunk.len() => {
+                        break Ok(translations);
+                    }
+                    Ok(translations) => {
+                        break Err(MtError::TranslationError(
+                            format!(
+                                "API returned {} translations for a chunk of {}",
+                                translations.len(),
+                                chunk.len()
+                            )
+                            .into(),
+                        ));
+                    }
+                    Err(e) if attempt < self.chunk_retries && e.is_transient() => {
+                        let backoff = 2u32.saturating_pow(attempt as u32);
+                        tokio::time::sleep(self.retry_delay.saturating_mul(backoff)).await;
+                        attempt += 1;
+                    }
+                    Err(e) => break Err(e),
+                }
+            };
+
+            match outcome {
+                Ok(translations) => results.extend(translations.into_iter().map(Ok)),
+                Err(e) => results.extend(chunk.iter().map(|_| Err(e.clone()))),
+            }
         }
 
-        // 1. Join with numbered prefixes (Python line 152-154)
-        let input_block: String = variants
-            .iter()
-            .enumerate()
-            .map(|(i, variant)| format!("{}. {}", i + 1, variant))
-            .collect::<Vec<_>>()
-            .join("\n");
-
-        // 2. Translate the entire block
-        let translated_block = self
-            .translate(&input_block, source_locale, target_locale)
-            .await?;
-
-        // 3. Split back using regex (Python lines 167-171)
-        use regex::Regex;
-        let re = Regex::new(r"\n?\d+\.\s").unwrap();
-
-        // Split and filter empty strings
-        let lines: Vec<String> = re
-            .split(translated_block.trim())
-            .map(|s| s.trim().to_string())
-            .filter(|s| !s.is_empty())
-            .collect();
+        Ok(results)
+    }
 
-        // 4. Safety check: same count (Python lines 173-175)
-        if lines.len() != variants.len() {
-            return Err(MtError::TranslationError(format!(
-                "Block translation count mismatch: expected {}, got {}. Block: '{}'",
-                variants.len(),
-                lines.len(),
-                translated_block
-            )));
+    /// Check every text of a batch against the per-string length limit
+    fn validate_batch(&self, texts: &[String]) -> MtResult<()> {
+        for (i, text) in texts.iter().enumerate() {
+            if text.chars().count() > Self::MAX_CHARS_PER_STRING {
+                return Err(MtError::TranslationError(
+                    format!(
+                        "Text at index {} exceeds maximum length of {} characters",
+                        i,
+                        Self::MAX_CHARS_PER_STRING
+                    )
+                    .into(),
+                ));
+            }
         }
-
-        // 5. Clean up anchor token mangling (Python lines 177-180)
-        // Sometimes MT systems add spaces: "777 001" instead of "777001"
-        let cleaned: Vec<String> = lines
-            .iter()
-            .map(|line| {
-                // Fix common anchor mangling patterns
-                line.replace("777 ", "777") // "777 001" → "777001"
-                    .replace(" 777", "777") // " 777001" → "777001"
-            })
-            .collect();
-
-        Ok(cleaned)
+        Ok(())
     }
 }
 
+/// Count the characters Google Translate bills for a batch of texts
+///
+/// Google bills per Unicode code point of the `q` values actually sent,
+/// including whitespace and anchor tokens (e.g. `777001` counts as 6
+/// characters), not the anchor-free source message. Multibyte characters
+/// count once each, regardless of their UTF-8 byte length.
+///
+/// # Arguments
+/// * `texts` - The exact texts that will be sent as `q` values
+///
+/// # Returns
+/// Number of billable characters
+///
+/// # Example
+/// ```ignore
+/// let texts = vec!["777001 sent a message".to_string()];
+/// assert_eq!(billable_chars(&texts), 21);
+/// ```
+pub fn billable_chars(texts: &[String]) -> usize {
+    texts.iter().map(|text| text.chars().count()).sum()
+}
+
 impl std::fmt::Debug for GoogleTranslateProvider {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("GoogleTranslateProvider")
             .field("api_key", &"***")
             .field("base_url", &self.base_url)
+            .field("chunk_retries", &self.chunk_retries)
+            .field("retry_delay", &self.retry_delay)
             .finish()
     }
 }
@@ -338,11 +400,14 @@ impl MachineTranslator for GoogleTranslateProvider {
         }
 
         // Check character limit
-        if text.len() > Self::MAX_CHARS_PER_STRING {
-            return Err(MtError::TranslationError(format!(
-                "Text exceeds maximum length of {} characters",
-                Self::MAX_CHARS_PER_STRING
-            )));
+        if text.chars().count() > Self::MAX_CHARS_PER_STRING {
+            return Err(MtError::TranslationError(
+                format!(
+                    "Text exceeds maximum length of {} characters",
+                    Self::MAX_CHARS_PER_STRING
+                )
+                .into(),
+            ));
         }
 
         // Translate as a single-item batch
@@ -368,15 +433,7 @@ impl MachineTranslator for GoogleTranslateProvider {
         }
 
         // Validate each text
-        for (i, text) in texts.iter().enumerate() {
-            if text.len() > Self::MAX_CHARS_PER_STRING {
-                return Err(MtError::TranslationError(format!(
-                    "Text at index {} exceeds maximum length of {} characters",
-                    i,
-                    Self::MAX_CHARS_PER_STRING
-                )));
-            }
-        }
+        self.validate_batch(texts)?;
 
         // Chunk texts for API limits
         let chunks = Self::chunk_batch(texts);
@@ -403,6 +460,38 @@ impl MachineTranslator for GoogleTranslateProvider {
     fn provider_name(&self) -> &str {
         "Google Translate"
     }
+
+    fn supports_native_batch(&self) -> bool {
+        true
+    }
+
+    /// Check the API key with the `languages` endpoint
+    ///
+    /// Lists supported languages instead of translating, so the check is
+    /// authenticated but does not count against the character quota.
+    async fn health_check(&self) -> MtResult<()> {
+        let url = format!("{}/languages?key={}", self.base_url, self.api_key);
+        let response = self.client.get(&url).send().await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error_text = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(if status.is_client_error() {
+                MtError::ConfigError(
+                    format!("API client error ({}): {}", status, error_text).into(),
+                )
+            } else {
+                MtError::NetworkError(
+                    format!("API server error ({}): {}", status, error_text).into(),
+                )
+            });
+        }
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -491,6 +580,29 @@ mod tests {
         assert_eq!(chunks.len(), 0);
     }
 
+    // ========== Billing Tests ==========
+
+    #[test]
+    fn test_billable_chars_counts_anchors_and_whitespace() {
+        let texts = vec![
+            "777001 sent a message".to_string(),
+            "  777001 sent 777002 messages ".to_string(),
+        ];
+        assert_eq!(billable_chars(&texts), 21 + 30);
+        assert_eq!(billable_chars(&[]), 0);
+    }
+
+    #[test]
+    fn test_billable_chars_counts_code_points_not_bytes() {
+        let texts = vec![
+            "Привет, 777001!".to_string(), // Cyrillic: 2 bytes per letter
+            "こんにちは".to_string(),      // Japanese: 3 bytes per character
+            "👋".to_string(),              // Emoji: 4 bytes, one code point
+        ];
+        assert_eq!(texts.iter().map(|t| t.len()).sum::<usize>(), 21 + 15 + 4);
+        assert_eq!(billable_chars(&texts), 15 + 5 + 1);
+    }
+
     // ========== Validation Tests ==========
 
     #[tokio::test]
@@ -543,6 +655,34 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_length_limit_counts_characters_not_bytes() {
+        let provider = GoogleTranslateProvider::new("test-key".to_string()).unwrap();
+        let max = GoogleTranslateProvider::MAX_CHARS_PER_STRING;
+
+        // 3 bytes per character: far over the limit in bytes, at it in characters
+        let at_limit = "മ".repeat(max);
+        assert_eq!(at_limit.len(), 3 * max);
+        assert!(provider.validate_batch(&[at_limit]).is_ok());
+
+        // Combining marks are code points of their own
+        let decomposed = format!("{}e\u{301}", "x".repeat(max - 2));
+        assert!(provider.validate_batch(&[decomposed]).is_ok());
+
+        let over_limit = "😀".repeat(max + 1);
+        assert!(provider.validate_batch(&[over_limit]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_translate_multibyte_text_too_long() {
+        let provider = GoogleTranslateProvider::new("test-key".to_string()).unwrap();
+        let long_text = "ü".repeat(GoogleTranslateProvider::MAX_CHARS_PER_STRING + 1);
+        match provider.translate(&long_text, "de", "en").await {
+            Err(MtError::TranslationError(msg)) => assert!(msg.contains("exceeds maximum")),
+            other => panic!("Expected TranslationError, got {:?}", other),
+        }
+    }
+
     // ========== Provider Name Test ==========
 
     #[test]
@@ -617,8 +757,185 @@ mod tests {
         }
     }
 
+    // ========== Partial Batch Tests ==========
+
+    /// Serve fake Google responses on a local port, failing the listed
+    /// requests with `failure_status` (e.g. "500 Internal Server Error")
+    ///
+    /// Returns the base URL and a counter of requests received.
+    fn spawn_fake_api(
+        failing_requests: &'static [usize],
+        failure_status: &'static str,
+    ) -> (String, std::sync::Arc<std::sync::atomic::AtomicUsize>) {
+        use std::io::{BufRead, BufReader, Read, Write};
+        use std::sync::atomic::Ordering;
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}/v2", listener.local_addr().unwrap());
+        let requests = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let counter = requests.clone();
+
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line == "\r\n" || line.is_empty() {
+                        break;
+                    }
+                    let lower = line.to_lowercase();
+                    if let Some(value) = lower.strip_prefix("content-length:") {
+                        content_length = value.trim().parse().unwrap();
+                    }
+                }
+                let mut body = vec![0; content_length];
+                reader.read_exact(&mut body).unwrap();
+
+                let n = counter.fetch_add(1, Ordering::SeqCst) + 1;
+                let (status, payload) = if failing_requests.contains(&n) {
+                    (failure_status, "backend error".to_string())
+                } else {
+                    let request: serde_json::Value = serde_json::from_slice(&body).unwrap();
+                    let translations: Vec<serde_json::Value> = request["q"]
+                        .as_array()
+                        .unwrap()
+                        .iter()
+                        .map(|q| json!({ "translatedText": format!("{}_fr", q.as_str().unwrap()) }))
+                        .collect();
+                    (
+                        "200 OK",
+                        json!({ "data": { "translations": translations } }).to_string(),
+                    )
+                };
+                write!(
+                    stream,
+                    "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    payload.len(),
+                    payload
+                )
+                .unwrap();
+            }
+        });
+
+        (base_url, requests)
+    }
+
+    fn numbered_texts(count: usize) -> Vec<String> {
+        (0..count).map(|i| format!("text {}", i)).collect()
+    }
+
+    #[tokio::test]
+    async fn test_batch_partial_keeps_successful_chunks() {
+        let (base_url, requests) = spawn_fake_api(&[2], "500 Internal Server Error");
+        let provider = GoogleTranslateProvider::new("test-key".to_string())
+            .unwrap()
+            .with_base_url(&base_url);
+
+        // Three chunks: 128 + 128 + 44; the second request fails
+        let texts = numbered_texts(300);
+        let results = provider
+            .translate_batch_partial(&texts, "en", "fr")
+            .await
+            .unwrap();
+
+        assert_eq!(results.len(), 300);
+        assert_eq!(results[0], Ok("text 0_fr".to_string()));
+        assert_eq!(results[127], Ok("text 127_fr".to_string()));
+        assert!(results[128..256].iter().all(|r| r.is_err()));
+        assert!(matches!(results[128], Err(MtError::TranslationError(_))));
+        assert_eq!(results[256], Ok("text 256_fr".to_string()));
+        assert_eq!(results[299], Ok("text 299_fr".to_string()));
+        assert_eq!(requests.load(std::sync::atomic::Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_batch_partial_retries_failed_chunk_only() {
+        let (base_url, requests) = spawn_fake_api(&[2], "500 Internal Server Error");
+        let provider = GoogleTranslateProvider::new("test-key".to_string())
+            .unwrap()
+            .with_base_url(&base_url)
+            .with_chunk_retries(1);
+
+        let texts = numbered_texts(300);
+        let results = provider
+            .translate_batch_partial(&texts, "en", "fr")
+            .await
+            .unwrap();
+
+        assert!(results.iter().all(|r| r.is_ok()));
+        assert_eq!(results[200], Ok("text 200_fr".to_string()));
+        // One extra request for the retried chunk
+        assert_eq!(requests.load(std::sync::atomic::Ordering::SeqCst), 4);
+    }
+
+    #[tokio::test]
+    async fn test_batch_partial_backs_off_between_retries() {
+        let (base_url, requests) = spawn_fake_api(&[2, 3], "429 Too Many Requests");
+        let provider = GoogleTranslateProvider::new("test-key".to_string())
+            .unwrap()
+            .with_base_url(&base_url)
+            .with_chunk_retries(2)
+            .with_retry_delay(Duration::from_millis(50));
+
+        let started = std::time::Instant::now();
+        let results = provider
+            .translate_batch_partial(&numbered_texts(300), "en", "fr")
+            .await
+            .unwrap();
+
+        assert!(results.iter().all(|r| r.is_ok()));
+        assert_eq!(requests.load(std::sync::atomic::Ordering::SeqCst), 5);
+        // 50 ms before the first retry, 100 ms before the second
+        assert!(started.elapsed() >= Duration::from_millis(150));
+    }
+
+    #[tokio::test]
+    async fn test_batch_partial_does_not_retry_rejected_key() {
+        let (base_url, requests) = spawn_fake_api(&[2], "403 Forbidden");
+        let provider = GoogleTranslateProvider::new("test-key".to_string())
+            .unwrap()
+            .with_base_url(&base_url)
+            .with_chunk_retries(2);
+
+        let results = provider
+            .translate_batch_partial(&numbered_texts(300), "en", "fr")
+            .await
+            .unwrap();
+
+        let error = results[128].as_ref().unwrap_err();
+        assert!(matches!(error, MtError::ConfigError(_)));
+        assert!(!error.is_transient());
+        let cause = std::error::Error::source(error).unwrap();
+        assert!(!cause.to_string().contains("test-key"));
+        assert_eq!(results[256], Ok("text 256_fr".to_string()));
+        assert_eq!(requests.load(std::sync::atomic::Ordering::SeqCst), 3);
+    }
+
     // ========== Integration Tests (require real API key) ==========
 
+    #[tokio::test]
+    #[ignore] // Run with: cargo test --ignored
+    async fn test_real_api_health_check() {
+        if std::env::var("GOOGLE_TRANSLATE_API_KEY").is_err() {
+            eprintln!("Skipping: GOOGLE_TRANSLATE_API_KEY not set");
+            return;
+        }
+
+        let provider = GoogleTranslateProvider::from_env().unwrap();
+        assert!(provider.health_check().await.is_ok());
+
+        let invalid = GoogleTranslateProvider::new("invalid-key".to_string()).unwrap();
+        assert!(matches!(
+            invalid.health_check().await,
+            Err(MtError::ConfigError(_))
+        ));
+    }
+
     #[tokio::test]
     #[ignore] // Run with: cargo test --ignored
     async fn test_real_api_single_translation() {
@@ -14,6 +14,7 @@
 mod tests {
     use super::super::*;
     use banana_i18n::parser::Parser;
+    use icu_plurals::PluralCategory;
     use std::time::Instant;
 
     /// Helper to format timing output
@@ -488,4 +489,266 @@ mod tests {
         println!("  - Final message: {} chars", result.len());
         println!("{}", "=".repeat(80));
     }
+
+    // ============================================================================
+    // TEST 7: Anchor Style A/B (Mock Translation)
+    // ============================================================================
+
+    /// Share of messages whose placeholders all survive translation in `style`
+    async fn anchor_preservation_rate(messages: &[&str], style: AnchorStyle) -> f64 {
+        let provider = MockTranslator::new(MockMode::Reorder).with_anchor_style(style);
+        let placeholder = regex::Regex::new(r"\$\d+").unwrap();
+
+        let mut preserved = 0;
+        for message in messages {
+            let ast = Parser::new(message).parse();
+            let Ok(outcome) = translate_message(&ast, &provider, "en", "ja").await else {
+                continue;
+            };
+            if placeholder
+                .find_iter(message)
+                .all(|m| outcome.wikitext.contains(m.as_str()))
+            {
+                preserved += 1;
+            }
+        }
+        preserved as f64 / messages.len() as f64
+    }
+
+    #[tokio::test]
+    async fn test_anchor_style_ab_with_reorder_mock() {
+        println!("\n{}", "=".repeat(80));
+        println!("TEST 7: Anchor Style A/B (Mock Translation)");
+        println!("{}", "=".repeat(80));
+        println!("Purpose: Compare placeholder preservation of _ID_ and bracketed anchors");
+
+        let messages = [
+            "Hello, $1!",
+            "$1 sent $2 messages",
+            "Total: $1$2",
+            "$10 replaced $1 in $11",
+            "{{GENDER:$1|He|She}} edited the page",
+            "{{PLURAL:$1|$1 file|$1 files}} uploaded by $2",
+            "$3 and $3 again",
+        ];
+
+        let underscore = anchor_preservation_rate(&messages, AnchorStyle::Underscore).await;
+        let bracketed = anchor_preservation_rate(&messages, AnchorStyle::Bracketed).await;
+
+        println!("\n📊 PRESERVATION RATES (Reorder mock):");
+        println!("  _ID_      : {:.0}%", underscore * 100.0);
+        println!("  Bracketed : {:.0}%", bracketed * 100.0);
+
+        assert_eq!(bracketed, 1.0, "Bracketed anchors must round-trip");
+        assert!(bracketed >= underscore);
+        println!("{}", "=".repeat(80));
+    }
+
+    // ============================================================================
+    // TEST 8: Cardinal and Ordinal PLURAL in One Message (Mock Translation)
+    // ============================================================================
+
+    #[tokio::test]
+    async fn test_cardinal_and_ordinal_plural_round_trip() {
+        println!("\n{}", "=".repeat(80));
+        println!("TEST 8: Cardinal and Ordinal PLURAL (Mock Translation)");
+        println!("{}", "=".repeat(80));
+        println!("Purpose: Each axis expands and reassembles with its own rule type");
+
+        let source_message = "{{PLURAL:$1|$1 file|$1 files}} uploaded on the \
+             {{PLURAL-ORDINAL:$2|$2st|$2nd|$2rd|$2th}} attempt";
+        let ast = Parser::new(source_message).parse();
+
+        // English: 2 cardinal forms (one, other) × 4 ordinal forms (one, two, few, other)
+        let context = prepare_for_translation(&ast, "en", "upload-attempt").unwrap();
+        assert_eq!(context.variant_count(), 8);
+        assert_eq!(
+            context.get_variable_type("PLURAL-ORDINAL:$2"),
+            Some(&"PLURAL-ORDINAL".to_string())
+        );
+        assert_eq!(
+            context.plural_categories["$1"],
+            vec![PluralCategory::One, PluralCategory::Other]
+        );
+        assert_eq!(
+            context.plural_categories["PLURAL-ORDINAL:$2"],
+            vec![
+                PluralCategory::One,
+                PluralCategory::Two,
+                PluralCategory::Few,
+                PluralCategory::Other
+            ]
+        );
+        let labels: Vec<String> = context
+            .variants
+            .iter()
+            .map(|variant| context.describe_variant(variant))
+            .collect();
+        assert!(labels.contains(&"$1=one, PLURAL-ORDINAL:$2=few".to_string()));
+        assert!(labels.contains(&"$1=other, PLURAL-ORDINAL:$2=two".to_string()));
+
+        let provider = MockTranslator::new(MockMode::Suffix);
+        let outcome = translate_message(&ast, &provider, "en", "en")
+            .await
+            .unwrap();
+        println!("\n🔧 RESULT: \"{}\"", outcome.wikitext);
+        // The shared "$1 " prefix is hoisted out of the cardinal options
+        assert!(outcome.wikitext.contains("{{PLURAL:$1|file|files}}"));
+        assert!(
+            outcome
+                .wikitext
+                .contains("{{PLURAL-ORDINAL:$2|$2st|$2nd|$2rd|$2th}}")
+        );
+
+        // The reassembled message renders with the right rule type per axis
+        let mut messages = banana_i18n::LocalizedMessages::new();
+        messages.with_message("upload-attempt", &outcome.wikitext);
+        let mut i18n = banana_i18n::I18n::new();
+        i18n.with_messages_for_locale("en", messages);
+        let rendered = i18n.localize(
+            "en",
+            "upload-attempt",
+            &vec!["1".to_string(), "22".to_string()],
+        );
+        assert_eq!(rendered, "1 file uploaded on the 22nd attempt_en");
+        println!("{}", "=".repeat(80));
+    }
+
+    // ============================================================================
+    // TEST 9: Right-to-Left Round Trip (Mock Translation)
+    // ============================================================================
+
+    /// Mock that wraps every numeric anchor in right-to-left marks, as some
+    /// engines do in Arabic and Hebrew output
+    #[derive(Debug)]
+    struct RtlMarkTranslator {
+        style: AnchorStyle,
+    }
+
+    #[async_trait::async_trait]
+    impl MachineTranslator for RtlMarkTranslator {
+        async fn translate(&self, text: &str, _source: &str, _target: &str) -> MtResult<String> {
+            let anchors = regex::Regex::new(r"777\d{3}|⟦\d+⟧").unwrap();
+            Ok(anchors
+                .replace_all(text, |caps: &regex::Captures| match &caps[0] {
+                    bracketed if bracketed.starts_with('⟦') => {
+                        bracketed.replace('⟦', "⟦\u{200F}")
+                    }
+                    numeric => format!("\u{200F}{}\u{200F}", numeric),
+                })
+                .to_string())
+        }
+
+        async fn translate_batch(
+            &self,
+            texts: &[String],
+            source: &str,
+            target: &str,
+        ) -> MtResult<Vec<String>> {
+            let mut translated = Vec::new();
+            for text in texts {
+                translated.push(self.translate(text, source, target).await?);
+            }
+            Ok(translated)
+        }
+
+        fn provider_name(&self) -> &str {
+            "RTL marks"
+        }
+
+        fn preferred_anchor_style(&self) -> AnchorStyle {
+            self.style
+        }
+    }
+
+    /// Options of the first magic word in reassembled wikitext
+    fn magic_word_options(wikitext: &str) -> Vec<String> {
+        Parser::new(wikitext)
+            .parse()
+            .into_iter()
+            .find_map(|node| match node {
+                banana_i18n::ast::AstNode::Transclusion(trans) => Some(trans.options),
+                _ => None,
+            })
+            .unwrap_or_default()
+    }
+
+    #[tokio::test]
+    async fn test_rtl_round_trip() {
+        println!("\n{}", "=".repeat(80));
+        println!("TEST 9: Right-to-Left Round Trip (Mock Translation)");
+        println!("{}", "=".repeat(80));
+        println!("Purpose: Arabic/Hebrew variants keep word boundaries, text and placeholders");
+
+        // (message, reassembled wikitext, options expanded for Arabic)
+        let cases = [
+            (
+                "{{GENDER:$1|أرسل|أرسلت}} $1 رسالة إلى $2.",
+                "{{GENDER:$1|أرسل|أرسلت|أرسلت}} $1 رسالة إلى $2.",
+                3,
+            ),
+            (
+                "{{GENDER:$1|הוא שלח|היא שלחה}} הודעה ל־$2",
+                "{{GENDER:$1|הוא שלח|היא שלחה|היא שלחה}} הודעה ל־$2",
+                3,
+            ),
+            (
+                "تم رفع {{PLURAL:$1|لا ملفات|ملف واحد|ملفين|$1 ملفات|$1 ملفًا|$1 ملف}} بواسطة $2",
+                "تم رفع {{PLURAL:$1|لا ملفات|ملف واحد|ملفين|$1 ملفات|$1 ملفًا|$1 ملف}} بواسطة $2",
+                6,
+            ),
+        ];
+
+        for (message, expected, option_count) in cases {
+            println!("\n📝 SOURCE: \"{}\"", message);
+            let ast = Parser::new(message).parse();
+
+            let suffix = MockTranslator::new(MockMode::Suffix);
+            let outcome = translate_message(&ast, &suffix, "ar", "ar").await.unwrap();
+            assert_eq!(outcome.wikitext, format!("{}_ar", expected));
+
+            // Whole words move, so options stay whole RTL words
+            let reorder = MockTranslator::new(MockMode::Reorder);
+            let outcome = translate_message(&ast, &reorder, "ar", "ar").await.unwrap();
+            println!("🔄 REORDER: \"{}\"", outcome.wikitext);
+            assert!(outcome.wikitext.contains("$1") && outcome.wikitext.contains("$2"));
+            let options = magic_word_options(&outcome.wikitext);
+            assert_eq!(options.len(), option_count);
+            for word in expected
+                .split(['{', '}', '|', ' '])
+                .filter(|w| !w.is_empty())
+            {
+                if !word.contains(':') {
+                    assert!(outcome.wikitext.contains(word), "lost '{}'", word);
+                }
+            }
+
+            // Marks around anchors are recovered, and do not leak into the output
+            for style in [AnchorStyle::Numeric, AnchorStyle::Bracketed] {
+                let outcome = translate_message(&ast, &RtlMarkTranslator { style }, "ar", "ar")
+                    .await
+                    .unwrap();
+                assert_eq!(outcome.wikitext, expected, "{:?}", style);
+                assert_eq!(outcome.retried, 0);
+            }
+
+            // An RTL target alone is enough for the marks to be dropped
+            let outcome = translate_message(
+                &ast,
+                &RtlMarkTranslator {
+                    style: AnchorStyle::Numeric,
+                },
+                "en",
+                "he",
+            )
+            .await
+            .unwrap();
+            assert!(
+                !outcome.wikitext.contains('\u{200F}'),
+                "{}",
+                outcome.wikitext
+            );
+        }
+        println!("{}", "=".repeat(80));
+    }
 }
@@ -33,26 +33,66 @@
 //! }
 //! ```
 
+pub mod anchors;
+pub mod azure_translate;
+pub mod catalog;
 pub mod data;
+pub mod deepl_translate;
+pub mod diff;
 pub mod error;
 pub mod expansion;
 pub mod google_translate;
+pub mod logging;
 pub mod mock;
+pub mod pipeline;
 pub mod reassembly;
+pub mod segments;
+pub mod tm;
 pub mod translator;
+pub mod typography;
+pub mod yandex_translate;
 
 // Integration tests (only available during testing)
 #[cfg(test)]
 mod integration_tests;
 
 // Re-export main types for convenient access
+pub use anchors::{AnchorStyle, AnchorToken, PrecheckWarning, precheck_source, protect, unprotect};
+pub use azure_translate::AzureTranslateProvider;
+pub use catalog::{
+    BudgetExceeded, CachedTranslation, CatalogCache, CatalogJobResult, I18nMtExt, JobBudget,
+    source_hash,
+};
 pub use data::{MessageContext, TranslationVariant};
-pub use error::{MtError, MtResult};
+pub use deepl_translate::DeepLProvider;
+pub use diff::{MessageDiff, NodeChange, diff_messages};
+pub use error::{ErrorMessage, MtError, MtResult};
 pub use expansion::{
-    GenderForm, PluralForm, expand_to_variants, get_gender_forms, get_plural_forms_for_language,
-    prepare_for_translation,
+    ExpansionConfig, ExpansionExplanation, GenderForm, MAX_ANCHOR_INDEX, MagicWordExplanation,
+    PLACEHOLDER_ANCHOR_BASE, POSITIONAL_SELECTOR_PREFIX, PlaceholderPolicy, PluralForm,
+    PreferredPluralForms, expand_to_variants, expand_to_variants_with_config, explain_expansion,
+    get_gender_forms, get_ordinal_forms_for_language, get_plural_forms_for_language,
+    get_plural_forms_with_preferred_values, is_positional_selector, placeholder_anchor,
+    prepare_for_translation, prepare_for_translation_with_config, selector_of,
+    supports_plural_rules,
 };
-pub use google_translate::GoogleTranslateProvider;
+pub use google_translate::{GoogleTranslateProvider, billable_chars};
+pub use logging::LoggingTranslator;
 pub use mock::{MockMode, MockTranslator};
-pub use reassembly::{Reassembler, get_similarity, reassemble_from_context};
-pub use translator::MachineTranslator;
+pub use pipeline::{
+    MultiTargetTranslation, ReviewPolicy, ReviewStatus, TranslationOutcome, classify,
+    introduce_gender_axis, retranslate, translate_anchored, translate_context, translate_message,
+    translate_message_in_context, translate_to_many, translate_variable_forms,
+};
+pub use reassembly::{
+    FixAction, FixSuggestion, GenderNeutralPlacement, Reassembler, ReassemblyOutput,
+    StructureCheck, get_similarity, reassemble_all, reassemble_from_context,
+};
+pub use segments::{Segment, SegmentBoundary, extract_segments};
+pub use tm::{export_tmx, import_tmx};
+pub use translator::{
+    DEFAULT_BLOCK_SEPARATOR, Formality, LanguageMatrix, MachineTranslator, SUPPORTED_PROVIDERS,
+    SubjectGender, TranslationOptions, from_config,
+};
+pub use typography::{apply_typography, normalize_quotes};
+pub use yandex_translate::YandexProvider;
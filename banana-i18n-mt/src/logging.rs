@@ -0,0 +1,451 @@
+//! Request/response logging for machine translation providers
+//!
+//! `LoggingTranslator` wraps any `MachineTranslator` and logs every request
+//! it forwards and the response it gets back, for debugging providers that
+//! misbehave (dropped anchors, mangled numbering, unexpected errors). It is
+//! transparent: results, errors and provider capabilities are those of the
+//! wrapped provider.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use banana_i18n_mt::{LoggingTranslator, MachineTranslator, MockMode, MockTranslator};
+//!
+//! let provider = LoggingTranslator::new(MockTranslator::new(MockMode::Suffix))
+//!     .with_redaction(true);
+//! provider.translate("Hello", "en", "fr").await?;
+//! // stderr:
+//! // [Mock Translator] translate en -> fr: 1 text, 5 chars
+//! // [Mock Translator] translate en -> fr: 1 result, 8 chars (0 ms)
+//! ```
+
+use crate::anchors::AnchorStyle;
+use crate::error::MtResult;
+use crate::translator::{
+    DEFAULT_BLOCK_SEPARATOR, LanguageMatrix, MachineTranslator, TranslationOptions, translate_block,
+};
+use async_trait::async_trait;
+use std::sync::Arc;
+use std::time::Instant;
+
+/// Destination of log lines, called once per line
+#[derive(Clone)]
+struct Sink(Arc<dyn Fn(&str) + Send + Sync>);
+
+impl std::fmt::Debug for Sink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Sink(<fn>)")
+    }
+}
+
+/// A provider wrapper that logs each request and response
+///
+/// Each call logs one line when the request is sent, with the locales and
+/// the texts, and one when it returns, with the results (or the error) and
+/// the time taken. With redaction enabled only counts and lengths are
+/// logged, so no message content leaves the process through the log.
+///
+/// Warnings are logged too, including a block translation falling back to
+/// translating its variants one by one.
+#[derive(Debug, Clone)]
+pub struct LoggingTranslator<T> {
+    inner: T,
+    sink: Sink,
+    redact: bool,
+}
+
+impl<T: MachineTranslator> LoggingTranslator<T> {
+    /// Wrap a provider, logging to stderr
+    pub fn new(inner: T) -> Self {
+        Self {
+            inner,
+            sink: Sink(Arc::new(|line| eprintln!("{}", line))),
+            redact: false,
+        }
+    }
+
+    /// Send log lines to `sink` instead of stderr
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let provider = LoggingTranslator::new(provider)
+    ///     .with_sink(|line| tracing::debug!("{}", line));
+    /// ```
+    pub fn with_sink<F>(mut self, sink: F) -> Self
+    where
+        F: Fn(&str) + Send + Sync + 'static,
+    {
+        self.sink = Sink(Arc::new(sink));
+        self
+    }
+
+    /// Log only the number and lengths of texts, not their content
+    pub fn with_redaction(mut self, enabled: bool) -> Self {
+        self.redact = enabled;
+        self
+    }
+
+    /// The wrapped provider
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    /// Unwrap the provider
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+
+    fn log(&self, line: String) {
+        (self.sink.0)(&format!("[{}] {}", self.inner.provider_name(), line));
+    }
+
+    /// Describe texts: their content, or only their count and lengths when redacting
+    fn describe(&self, texts: &[String], noun: &str) -> String {
+        let count = match texts.len() {
+            1 => format!("1 {}", noun),
+            n => format!("{} {}s", n, noun),
+        };
+        if !self.redact {
+            return format!("{} {:?}", count, texts);
+        }
+        let lengths: Vec<String> = texts
+            .iter()
+            .map(|text| text.chars().count().to_string())
+            .collect();
+        format!("{}, {} chars", count, lengths.join("/"))
+    }
+
+    /// Log a call around `call`, which performs it on the wrapped provider
+    async fn logged<F>(
+        &self,
+        method: &str,
+        texts: &[String],
+        source_locale: &str,
+        target_locale: &str,
+        call: F,
+    ) -> MtResult<Vec<String>>
+    where
+        F: Future<Output = MtResult<Vec<String>>>,
+    {
+        let request = format!("{} {} -> {}", method, source_locale, target_locale);
+        self.log(format!("{}: {}", request, self.describe(texts, "text")));
+
+        let started = Instant::now();
+        let result = call.await;
+        let elapsed = started.elapsed().as_millis();
+        match &result {
+            Ok(translated) => self.log(format!(
+                "{}: {} ({} ms)",
+                request,
+                self.describe(translated, "result"),
+                elapsed
+            )),
+            Err(e) => self.log(format!("{} failed: {} ({} ms)", request, e, elapsed)),
+        }
+        result
+    }
+}
+
+#[async_trait]
+impl<T: MachineTranslator> MachineTranslator for LoggingTranslator<T> {
+    async fn translate(
+        &self,
+        text: &str,
+        source_locale: &str,
+        target_locale: &str,
+    ) -> MtResult<String> {
+        let texts = [text.to_string()];
+        let call = async {
+            self.inner
+                .translate(text, source_locale, target_locale)
+                .await
+                .map(|result| vec![result])
+        };
+        let results = self
+            .logged("translate", &texts, source_locale, target_locale, call)
+            .await?;
+        Ok(results.into_iter().next().unwrap_or_default())
+    }
+
+    async fn translate_batch(
+        &self,
+        texts: &[String],
+        source_locale: &str,
+        target_locale: &str,
+    ) -> MtResult<Vec<String>> {
+        let call = self
+            .inner
+            .translate_batch(texts, source_locale, target_locale);
+        self.logged("translate_batch", texts, source_locale, target_locale, call)
+            .await
+    }
+
+    async fn translate_with_options(
+        &self,
+        text: &str,
+        source_locale: &str,
+        target_locale: &str,
+        options: &TranslationOptions,
+    ) -> MtResult<String> {
+        let texts = [text.to_string()];
+        let call = async {
+            self.inner
+                .translate_with_options(text, source_locale, target_locale, options)
+                .await
+                .map(|result| vec![result])
+        };
+        let results = self
+            .logged(
+                "translate_with_options",
+                &texts,
+                source_locale,
+                target_locale,
+                call,
+            )
+            .await?;
+        Ok(results.into_iter().next().unwrap_or_default())
+    }
+
+    async fn translate_batch_with_options(
+        &self,
+        texts: &[String],
+        source_locale: &str,
+        target_locale: &str,
+        options: &TranslationOptions,
+    ) -> MtResult<Vec<String>> {
+        let call =
+            self.inner
+                .translate_batch_with_options(texts, source_locale, target_locale, options);
+        self.logged(
+            "translate_batch_with_options",
+            texts,
+            source_locale,
+            target_locale,
+            call,
+        )
+        .await
+    }
+
+    async fn translate_as_block(
+        &self,
+        variants: &[String],
+        source_locale: &str,
+        target_locale: &str,
+    ) -> MtResult<Vec<String>> {
+        // Run the block algorithm here: the inner provider would not report its fallback
+        let report = |warning: &str| self.report_warning(warning);
+        let call = translate_block(
+            &self.inner,
+            variants,
+            DEFAULT_BLOCK_SEPARATOR,
+            source_locale,
+            target_locale,
+            &report,
+        );
+        self.logged(
+            "translate_as_block",
+            variants,
+            source_locale,
+            target_locale,
+            call,
+        )
+        .await
+    }
+
+    async fn translate_as_block_with_separator(
+        &self,
+        variants: &[String],
+        separator: &str,
+        source_locale: &str,
+        target_locale: &str,
+    ) -> MtResult<Vec<String>> {
+        let report = |warning: &str| self.report_warning(warning);
+        let call = translate_block(
+            &self.inner,
+            variants,
+            separator,
+            source_locale,
+            target_locale,
+            &report,
+        );
+        self.logged(
+            "translate_as_block_with_separator",
+            variants,
+            source_locale,
+            target_locale,
+            call,
+        )
+        .await
+    }
+
+    async fn health_check(&self) -> MtResult<()> {
+        self.log("health_check".to_string());
+
+        let started = Instant::now();
+        let result = self.inner.health_check().await;
+        let elapsed = started.elapsed().as_millis();
+        match &result {
+            Ok(()) => self.log(format!("health_check: ok ({} ms)", elapsed)),
+            Err(e) => self.log(format!("health_check failed: {} ({} ms)", e, elapsed)),
+        }
+        result
+    }
+
+    fn report_warning(&self, warning: &str) {
+        self.log(format!("warning: {}", warning));
+    }
+
+    fn provider_name(&self) -> &str {
+        self.inner.provider_name()
+    }
+
+    fn preferred_anchor_style(&self) -> AnchorStyle {
+        self.inner.preferred_anchor_style()
+    }
+
+    fn wants_plural_hints(&self) -> bool {
+        self.inner.wants_plural_hints()
+    }
+
+    fn supports_native_batch(&self) -> bool {
+        self.inner.supports_native_batch()
+    }
+
+    fn supported_pairs(&self) -> Option<LanguageMatrix> {
+        self.inner.supported_pairs()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{MockMode, MockTranslator};
+    use std::sync::Mutex;
+
+    /// Wrap a mock, collecting log lines
+    fn capture(
+        mode: MockMode,
+        redact: bool,
+    ) -> (LoggingTranslator<MockTranslator>, Arc<Mutex<Vec<String>>>) {
+        let lines = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&lines);
+        let provider = LoggingTranslator::new(MockTranslator::new(mode))
+            .with_redaction(redact)
+            .with_sink(move |line| sink.lock().unwrap().push(line.to_string()));
+        (provider, lines)
+    }
+
+    #[tokio::test]
+    async fn test_logs_batch_request_and_response() {
+        let (provider, lines) = capture(MockMode::Suffix, false);
+        let texts = vec!["Hello".to_string(), "777001 left".to_string()];
+
+        let result = provider.translate_batch(&texts, "en", "fr").await.unwrap();
+        assert_eq!(result, vec!["Hello_fr", "777001 left_fr"]);
+
+        let lines = lines.lock().unwrap();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(
+            lines[0],
+            r#"[Mock Translator] translate_batch en -> fr: 2 texts ["Hello", "777001 left"]"#
+        );
+        assert!(
+            lines[1].starts_with(
+                r#"[Mock Translator] translate_batch en -> fr: 2 results ["Hello_fr", "777001 left_fr"]"#
+            ),
+            "{}",
+            lines[1]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_redaction_logs_only_lengths() {
+        let (provider, lines) = capture(MockMode::Suffix, true);
+
+        let result = provider.translate("Secret", "en", "de").await.unwrap();
+        assert_eq!(result, "Secret_de");
+
+        let lines = lines.lock().unwrap();
+        assert_eq!(
+            lines[0],
+            "[Mock Translator] translate en -> de: 1 text, 6 chars"
+        );
+        assert!(lines[1].starts_with("[Mock Translator] translate en -> de: 1 result, 9 chars"));
+        assert!(lines.iter().all(|line| !line.contains("Secret")));
+    }
+
+    #[tokio::test]
+    async fn test_block_and_health_check_are_forwarded() {
+        let (provider, lines) = capture(MockMode::Suffix, false);
+        let texts = vec!["He left".to_string(), "She left".to_string()];
+
+        let result = provider
+            .translate_as_block(&texts, "en", "fr")
+            .await
+            .unwrap();
+        assert_eq!(result.len(), 2);
+        provider.health_check().await.unwrap();
+
+        // One request/response pair per call, not the inner translate() calls
+        let lines = lines.lock().unwrap();
+        assert_eq!(lines.len(), 4, "{:?}", lines);
+        assert!(lines[0].starts_with("[Mock Translator] translate_as_block en -> fr: 2 texts"));
+        assert_eq!(lines[2], "[Mock Translator] health_check");
+        assert!(lines[3].starts_with("[Mock Translator] health_check: ok"));
+        assert!(lines.iter().all(|line| !line.contains("] translate ")));
+    }
+
+    #[tokio::test]
+    async fn test_block_fallback_is_logged_as_warning() {
+        let map = [
+            ("Hello\n§§§\nGoodbye", "Bonjour\n§ §\nAu revoir"),
+            ("Hello", "Salut"),
+            ("Goodbye", "Au revoir"),
+        ]
+        .iter()
+        .map(|(en, fr)| ((en.to_string(), "fr".to_string()), fr.to_string()))
+        .collect();
+        let (provider, lines) = capture(MockMode::Mappings(map), false);
+        let texts = vec!["Hello".to_string(), "Goodbye".to_string()];
+
+        let result = provider
+            .translate_as_block(&texts, "en", "fr")
+            .await
+            .unwrap();
+        assert_eq!(result, vec!["Salut", "Au revoir"]);
+
+        let lines = lines.lock().unwrap();
+        assert_eq!(lines.len(), 3, "{:?}", lines);
+        assert_eq!(
+            lines[1],
+            "[Mock Translator] warning: block translation returned 1 parts for 2 variants; translating them one by one"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_logs_errors_and_delegates_capabilities() {
+        let (provider, lines) = capture(MockMode::Error("quota exceeded".to_string()), false);
+
+        assert!(provider.translate("Hello", "en", "fr").await.is_err());
+        assert!(lines.lock().unwrap()[1].contains("translate en -> fr failed:"));
+        assert!(lines.lock().unwrap()[1].contains("quota exceeded"));
+
+        assert_eq!(provider.provider_name(), "Mock Translator");
+        assert_eq!(
+            provider.supports_native_batch(),
+            provider.inner().supports_native_batch()
+        );
+    }
+
+    #[test]
+    fn test_supported_pairs_are_delegated() {
+        use crate::deepl_translate::DeepLProvider;
+
+        let deepl = DeepLProvider::new("key:fx".to_string()).unwrap();
+        let provider = LoggingTranslator::new(deepl).with_sink(|_| {});
+        let matrix = provider.supported_pairs().unwrap();
+        assert!(matrix.can_translate("en", "de"));
+        assert!(!matrix.can_translate("en", "ml"));
+    }
+}
@@ -16,11 +16,25 @@
 //! }
 //! ```
 
+use crate::anchors::AnchorStyle;
 use crate::error::MtResult;
 use crate::translator::MachineTranslator;
 use async_trait::async_trait;
 use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::time::Duration;
+use tokio::task::JoinSet;
+
+/// Per-item latency function used by `MockTranslator::with_latency`
+#[derive(Clone)]
+struct Latency(Arc<dyn Fn(&str) -> Duration + Send + Sync>);
+
+impl std::fmt::Debug for Latency {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Latency(<fn>)")
+    }
+}
 
 /// Mock translation modes for testing different scenarios
 #[derive(Debug, Clone)]
@@ -53,6 +67,18 @@ pub struct MockTranslator {
     mode: MockMode,
     /// Optional simulated network delay (in milliseconds)
     delay_ms: u64,
+    /// Optional simulated delay computed per text
+    latency: Option<Latency>,
+    /// Number of provider calls (translate or translate_batch), shared between clones
+    calls: Arc<AtomicUsize>,
+    /// Number of texts received across all calls, shared between clones
+    texts: Arc<AtomicUsize>,
+    /// Anchor style reported by `preferred_anchor_style()`
+    anchor_style: AnchorStyle,
+    /// Value reported by `wants_plural_hints()`
+    plural_hints: bool,
+    /// Value reported by `supports_native_batch()`
+    native_batch: bool,
 }
 
 impl MockTranslator {
@@ -68,7 +94,7 @@ impl MockTranslator {
     /// let mock = MockTranslator::new(MockMode::Suffix);
     /// ```
     pub fn new(mode: MockMode) -> Self {
-        Self { mode, delay_ms: 0 }
+        Self::with_delay(mode, 0)
     }
 
     /// Create a MockTranslator with simulated network delay
@@ -85,7 +111,150 @@ impl MockTranslator {
     /// // Each translation will have ~50ms delay
     /// ```
     pub fn with_delay(mode: MockMode, delay_ms: u64) -> Self {
-        Self { mode, delay_ms }
+        Self {
+            mode,
+            delay_ms,
+            latency: None,
+            calls: Arc::new(AtomicUsize::new(0)),
+            texts: Arc::new(AtomicUsize::new(0)),
+            anchor_style: AnchorStyle::Numeric,
+            plural_hints: false,
+            native_batch: true,
+        }
+    }
+
+    /// Create a MockTranslator whose delay varies per text
+    ///
+    /// `latency` computes the simulated delay for each text, e.g. longer for
+    /// longer strings. `translate_batch` translates items concurrently, each
+    /// after its own delay, so completion order can differ from input order
+    /// (observable through `translate_batch_unordered`).
+    ///
+    /// # Arguments
+    ///
+    /// * `mode` - The translation mode
+    /// * `latency` - Function returning the delay for a given text
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let mock = MockTranslator::with_latency(MockMode::NoOp, |text| {
+    ///     Duration::from_millis(10 * text.len() as u64)
+    /// });
+    /// ```
+    pub fn with_latency<F>(mode: MockMode, latency: F) -> Self
+    where
+        F: Fn(&str) -> Duration + Send + Sync + 'static,
+    {
+        let mut mock = Self::new(mode);
+        mock.latency = Some(Latency(Arc::new(latency)));
+        mock
+    }
+
+    /// Translate a batch concurrently, returning results in completion order
+    ///
+    /// Each result is paired with the index of its input text. Without a
+    /// latency function all items complete immediately.
+    ///
+    /// # Arguments
+    ///
+    /// * `texts` - Strings to translate
+    /// * `source_locale` - Source language code
+    /// * `target_locale` - Target language code
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<(usize, String)>)` - (input index, translation) in completion order
+    /// * `Err(MtError)` - If any item fails to translate
+    pub async fn translate_batch_unordered(
+        &self,
+        texts: &[String],
+        source_locale: &str,
+        target_locale: &str,
+    ) -> MtResult<Vec<(usize, String)>> {
+        self.record_call(texts.len());
+        self.apply_delay().await;
+
+        let mut tasks = JoinSet::new();
+        for (i, text) in texts.iter().enumerate() {
+            let mock = self.clone();
+            let text = text.clone();
+            let source = source_locale.to_string();
+            let target = target_locale.to_string();
+            tasks.spawn(async move {
+                mock.apply_item_delay(&text).await;
+                (i, mock.apply_translation(&text, &source, &target))
+            });
+        }
+
+        let mut results = Vec::with_capacity(texts.len());
+        while let Some(joined) = tasks.join_next().await {
+            let (i, translation) = joined.map_err(|e| {
+                crate::error::MtError::TranslationError(format!("Mock task failed: {}", e).into())
+            })?;
+            results.push((i, translation?));
+        }
+        Ok(results)
+    }
+
+    /// Declare an anchor style, to test providers that prefer non-numeric anchors
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let mock = MockTranslator::new(MockMode::Suffix).with_anchor_style(AnchorStyle::XmlTag);
+    /// ```
+    pub fn with_anchor_style(mut self, style: AnchorStyle) -> Self {
+        self.anchor_style = style;
+        self
+    }
+
+    /// Ask for plural hints, to test the pipeline's number substitution
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let mock = MockTranslator::new(MockMode::Suffix).with_plural_hints(true);
+    /// ```
+    pub fn with_plural_hints(mut self, enabled: bool) -> Self {
+        self.plural_hints = enabled;
+        self
+    }
+
+    /// Declare whether `translate_batch` is one request, to test how callers
+    /// treat providers without a batch API
+    ///
+    /// The mock translates a batch in a single call, so it reports `true`
+    /// unless told otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let mock = MockTranslator::new(MockMode::Suffix).with_native_batch(false);
+    /// ```
+    pub fn with_native_batch(mut self, enabled: bool) -> Self {
+        self.native_batch = enabled;
+        self
+    }
+
+    /// Number of times the provider was called (each `translate` or
+    /// `translate_batch` counts as one call)
+    ///
+    /// Clones share the counter, so a clone handed to the code under test
+    /// can be inspected through the original.
+    pub fn call_count(&self) -> usize {
+        self.calls.load(Ordering::SeqCst)
+    }
+
+    /// Total number of texts received across all calls
+    pub fn text_count(&self) -> usize {
+        self.texts.load(Ordering::SeqCst)
+    }
+
+    /// Internal helper to record a provider call with `text_count` texts
+    fn record_call(&self, text_count: usize) {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        self.texts.fetch_add(text_count, Ordering::SeqCst);
     }
 
     /// Internal helper to apply the simulated delay
@@ -95,6 +264,16 @@ impl MockTranslator {
         }
     }
 
+    /// Internal helper to apply the per-text delay, if configured
+    async fn apply_item_delay(&self, text: &str) {
+        if let Some(Latency(latency)) = &self.latency {
+            let delay = latency(text);
+            if !delay.is_zero() {
+                tokio::time::sleep(delay).await;
+            }
+        }
+    }
+
     /// Apply translation logic based on the mode
     fn apply_translation(&self, text: &str, _source: &str, target: &str) -> MtResult<String> {
         use crate::error::MtError;
@@ -118,7 +297,7 @@ impl MockTranslator {
                 let reversed = words.iter().rev().map(|&w| w).collect::<Vec<_>>().join(" ");
                 Ok(reversed)
             }
-            MockMode::Error(msg) => Err(MtError::TranslationError(msg.clone())),
+            MockMode::Error(msg) => Err(MtError::TranslationError(msg.clone().into())),
             MockMode::NoOp => Ok(text.to_string()),
         }
     }
@@ -132,8 +311,11 @@ impl MachineTranslator for MockTranslator {
         source_locale: &str,
         target_locale: &str,
     ) -> MtResult<String> {
+        self.record_call(1);
+
         // Apply simulated delay
         self.apply_delay().await;
+        self.apply_item_delay(text).await;
 
         // Apply translation
         self.apply_translation(text, source_locale, target_locale)
@@ -145,6 +327,17 @@ impl MachineTranslator for MockTranslator {
         source_locale: &str,
         target_locale: &str,
     ) -> MtResult<Vec<String>> {
+        if self.latency.is_some() {
+            // Per-item delays: translate concurrently, then restore input order
+            let mut results = self
+                .translate_batch_unordered(texts, source_locale, target_locale)
+                .await?;
+            results.sort_by_key(|(i, _)| *i);
+            return Ok(results.into_iter().map(|(_, text)| text).collect());
+        }
+
+        self.record_call(texts.len());
+
         // Apply simulated delay (per batch, not per string)
         self.apply_delay().await;
 
@@ -160,6 +353,18 @@ impl MachineTranslator for MockTranslator {
     fn provider_name(&self) -> &str {
         "Mock Translator"
     }
+
+    fn preferred_anchor_style(&self) -> AnchorStyle {
+        self.anchor_style
+    }
+
+    fn wants_plural_hints(&self) -> bool {
+        self.plural_hints
+    }
+
+    fn supports_native_batch(&self) -> bool {
+        self.native_batch
+    }
 }
 
 #[cfg(test)]
@@ -354,6 +559,27 @@ mod tests {
         assert!(elapsed.as_millis() < 10);
     }
 
+    #[tokio::test]
+    async fn test_latency_per_item_completion_order() {
+        let mock = MockTranslator::with_latency(MockMode::Suffix, |text| {
+            Duration::from_millis(20 * text.len() as u64)
+        });
+        let texts = vec!["slowest".to_string(), "mid".to_string(), "a".to_string()];
+
+        let completed = mock
+            .translate_batch_unordered(&texts, "en", "fr")
+            .await
+            .unwrap();
+        let order: Vec<usize> = completed.iter().map(|(i, _)| *i).collect();
+        assert_eq!(order, vec![2, 1, 0]);
+        assert_eq!(completed[0].1, "a_fr");
+
+        // translate_batch still returns results in input order
+        let results = mock.translate_batch(&texts, "en", "fr").await.unwrap();
+        assert_eq!(results, vec!["slowest_fr", "mid_fr", "a_fr"]);
+        assert_eq!(mock.call_count(), 2);
+    }
+
     // ========== Provider Name Test ==========
 
     #[test]
@@ -362,6 +588,21 @@ mod tests {
         assert_eq!(mock.provider_name(), "Mock Translator");
     }
 
+    // ========== Call Counting Tests ==========
+
+    #[tokio::test]
+    async fn test_call_count_shared_between_clones() {
+        let mock = MockTranslator::new(MockMode::Suffix);
+        let clone = mock.clone();
+
+        let _ = clone.translate("hello", "en", "fr").await.unwrap();
+        let texts = vec!["a".to_string(), "b".to_string()];
+        let _ = clone.translate_batch(&texts, "en", "fr").await.unwrap();
+
+        assert_eq!(mock.call_count(), 2);
+        assert_eq!(mock.text_count(), 3);
+    }
+
     // ========== Batch Consistency Tests ==========
 
     #[tokio::test]
@@ -0,0 +1,1827 @@
+//! High-level translation workflows built on expansion, translation and reassembly
+//!
+//! The lower-level modules each implement one phase of the pipeline. This module
+//! combines them into workflows that callers would otherwise wire up by hand.
+//!
+//! # Incremental Retranslation
+//!
+//! When a source message changes slightly, most of its expanded variants are
+//! unchanged. `retranslate()` diffs the new variants against a previously
+//! translated `MessageContext` (keyed by the anchor-protected source text) and
+//! only sends the changed variants to the provider.
+//!
+//! ```ignore
+//! let ast = Parser::new("{{GENDER:$1|He|She|Someone}} sent a message").parse();
+//! let context = retranslate(&previous, &ast, &provider, "en", "fr").await?;
+//! let wikitext = reassemble_from_context(&context)?;
+//! ```
+//!
+//! # Instrumented Translation
+//!
+//! `translate_message()` runs the whole expand → translate → reassemble flow
+//! for one message and reports what it cost alongside the result:
+//!
+//! ```ignore
+//! let outcome = translate_message(&ast, &provider, "en", "fr").await?;
+//! println!("{} via {} in {:?}", outcome.wikitext, outcome.provider, outcome.duration);
+//! ```
+//!
+//! # Filling Forms Into a Human Translation
+//!
+//! When a human has already translated the stable text of a message but left
+//! the PLURAL/GENDER options in the source language, `translate_variable_forms()`
+//! keeps the human text as is and machine translates only the option fragments:
+//!
+//! ```ignore
+//! let base = Parser::new("{{GENDER:$1|He|She|They}} a envoyé un message").parse();
+//! let wikitext = translate_variable_forms(&base, &provider, "en", "fr").await?;
+//! // "{{GENDER:$1|Il|Elle|Iel}} a envoyé un message"
+//! ```
+//!
+//! # Anchor Styles
+//!
+//! Every workflow here sends variants through `translate_anchored()`, which
+//! renders the numeric anchors from expansion in the provider's
+//! `preferred_anchor_style()` and recovers them from the translations, so
+//! reassembly always sees numeric anchors.
+//!
+//! # Introducing a GENDER Axis
+//!
+//! Some target languages need a GENDER distinction the source does not make,
+//! e.g. a verb that agrees with the user's gender. `introduce_gender_axis()`
+//! translates the message once per gender form, passing the gender to the
+//! provider as a `TranslationOptions::subject_gender` hint, and reassembles
+//! the results into a GENDER block on the given variable:
+//!
+//! ```ignore
+//! let ast = Parser::new("$1 joined the project").parse();
+//! let wikitext = introduce_gender_axis(&ast, "$1", &provider, "en", "fr").await?;
+//! // "$1 est {{GENDER:$1|arrivé|arrivée|arrivé·e}} dans le projet"
+//! ```
+
+use crate::anchors::{
+    AnchorStyle, find_anchors, has_bidi_controls, protect, strip_bidi_around_anchors, unprotect,
+};
+use crate::data::MessageContext;
+use crate::data::TranslationVariant;
+use crate::error::{MtError, MtResult};
+use crate::expansion::{
+    DEFAULT_GENDER_FORM_COUNT, PluralForm, get_ordinal_forms_for_language,
+    get_plural_forms_for_language, placeholder_anchor, prepare_for_translation,
+};
+use crate::reassembly::{Reassembler, StructureCheck};
+use crate::translator::{LanguageMatrix, MachineTranslator, SubjectGender, TranslationOptions};
+use async_trait::async_trait;
+use banana_i18n::ast::{AstNode, AstNodeList, ast_to_wikitext};
+use banana_i18n::magic_words::{MagicWordRegistry, MagicWordType};
+use banana_i18n::parser::Parser;
+use banana_i18n::{Direction, direction_of, preview_plain};
+use futures_util::future::try_join_all;
+use icu_plurals::PluralCategory;
+use regex::Regex;
+use std::collections::{BTreeSet, HashMap};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+/// Result of translating one message, with provenance and timing
+#[derive(Debug, Clone, PartialEq)]
+pub struct TranslationOutcome {
+    /// The translated wikitext
+    pub wikitext: String,
+    /// Name of the provider that translated the variants
+    pub provider: String,
+    /// Number of requests sent to the provider: one for the batch of variants
+    /// (one per variant without a native batch API), plus any anchor retry
+    pub api_calls: usize,
+    /// Wall-clock time for expansion, translation and reassembly
+    pub duration: Duration,
+    /// Non-fatal problems: lost placeholders, structure mismatches and plural
+    /// rules borrowed from a fallback locale
+    pub warnings: Vec<String>,
+    /// Fraction of variants (0.0 to 1.0) whose translation kept every
+    /// placeholder anchor of its source text
+    pub confidence: f32,
+    /// Number of variants whose translation changed the relative order of
+    /// placeholder anchors (expected for some language pairs, so not a warning)
+    pub reordered: usize,
+    /// Number of variants that lost an anchor and kept them all when
+    /// translated again
+    pub retried: usize,
+}
+
+/// Rules deciding whether a translated message needs human review
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReviewPolicy {
+    /// Lowest `TranslationOutcome::confidence` that may be auto-approved
+    pub min_confidence: f32,
+    /// Send messages whose placeholders were reordered to review
+    pub flag_on_reordering: bool,
+}
+
+impl Default for ReviewPolicy {
+    fn default() -> Self {
+        ReviewPolicy {
+            min_confidence: 1.0,
+            flag_on_reordering: false,
+        }
+    }
+}
+
+/// Review label for a translated message
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReviewStatus {
+    /// Safe to publish without review
+    AutoApproved,
+    /// A translator should check the message, for the given reasons
+    NeedsReview(Vec<String>),
+}
+
+impl ReviewStatus {
+    /// Label for a translation management system: `auto-approved` or `needs-review`
+    pub fn label(&self) -> &'static str {
+        match self {
+            ReviewStatus::AutoApproved => "auto-approved",
+            ReviewStatus::NeedsReview(_) => "needs-review",
+        }
+    }
+}
+
+impl std::fmt::Display for ReviewStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+/// Label a translated message as auto-approved or needing review
+///
+/// A message needs review if its confidence is below `policy.min_confidence`,
+/// if translation or reassembly produced any warning (lost anchors, structure
+/// mismatches), or if placeholders were reordered and the policy flags that.
+///
+/// # Arguments
+/// * `outcome` - Result of `translate_message()`
+/// * `policy` - Thresholds to apply
+///
+/// # Returns
+/// `ReviewStatus::AutoApproved`, or `ReviewStatus::NeedsReview` with the reasons
+pub fn classify(outcome: &TranslationOutcome, policy: &ReviewPolicy) -> ReviewStatus {
+    let mut reasons = Vec::new();
+    if outcome.confidence < policy.min_confidence {
+        reasons.push(format!(
+            "confidence {:.2} is below {:.2}",
+            outcome.confidence, policy.min_confidence
+        ));
+    }
+    reasons.extend(outcome.warnings.iter().cloned());
+    if policy.flag_on_reordering && outcome.reordered > 0 {
+        reasons.push(format!(
+            "{} variant(s) reordered placeholders",
+            outcome.reordered
+        ));
+    }
+
+    if reasons.is_empty() {
+        ReviewStatus::AutoApproved
+    } else {
+        ReviewStatus::NeedsReview(reasons)
+    }
+}
+
+/// Retranslate a changed message, reusing translations of unchanged variants
+///
+/// Expands `new_ast`, then looks up each variant's anchor-protected source text
+/// in `previous`. Variants whose source text was already translated reuse the
+/// previous translation; only the remaining variants are sent to the provider,
+/// as one batch. If nothing changed, the provider is not called at all.
+///
+/// # Arguments
+/// * `previous` - Context of the previous translation of this message
+/// * `new_ast` - Parsed AST of the updated source message
+/// * `provider` - Machine translation provider
+/// * `source_locale` - Source language code
+/// * `target_locale` - Target language code (also used for plural form selection)
+///
+/// # Returns
+/// * `Ok(MessageContext)` - Fully translated context for the new message
+/// * `Err(MtError)` - If expansion or translation fails
+pub async fn retranslate(
+    previous: &MessageContext,
+    new_ast: &AstNodeList,
+    provider: &dyn MachineTranslator,
+    source_locale: &str,
+    target_locale: &str,
+) -> MtResult<MessageContext> {
+    let mut context = prepare_for_translation(new_ast, target_locale, &previous.original_key)?;
+    context.documentation = previous.documentation.clone();
+
+    // Diff key is the anchor-protected source text
+    let known: HashMap<&str, &str> = previous
+        .variants
+        .iter()
+        .filter(|v| v.is_translated())
+        .map(|v| (v.source_text.as_str(), v.translated_text.as_str()))
+        .collect();
+
+    let mut pending_indices = Vec::new();
+    let mut pending_texts = Vec::new();
+    for (i, variant) in context.variants.iter_mut().enumerate() {
+        match known.get(variant.source_text.as_str()) {
+            Some(translated) => variant.translated_text = translated.to_string(),
+            None => {
+                pending_indices.push(i);
+                pending_texts.push(variant.source_text.clone());
+            }
+        }
+    }
+
+    if pending_texts.is_empty() {
+        return Ok(context);
+    }
+
+    let translated = translate_anchored(
+        provider,
+        &pending_texts,
+        source_locale,
+        target_locale,
+        &TranslationOptions::default(),
+    )
+    .await?;
+    if translated.len() != pending_texts.len() {
+        return Err(MtError::TranslationError(
+            format!(
+                "Provider returned {} translations for {} changed variants",
+                translated.len(),
+                pending_texts.len()
+            )
+            .into(),
+        ));
+    }
+
+    for (i, text) in pending_indices.into_iter().zip(translated) {
+        context.variants[i].translated_text = text;
+    }
+
+    Ok(context)
+}
+
+/// Translate a message end to end, recording provenance and timing
+///
+/// Expands `ast` for the target locale, sends all variants to the provider as
+/// one batch, and reassembles the result with `StructureCheck::Warn`, so
+/// structure mismatches are reported as warnings rather than errors.
+///
+/// Providers whose `wants_plural_hints()` is true see a representative number
+/// in place of each PLURAL parameter's anchor; the anchor is restored in the
+/// translation before reassembly. Hints change the text sent, not the number
+/// of requests.
+///
+/// Variants whose translation lost an anchor are translated once more, in a
+/// second batch with a different anchor style (bracketed instead of numeric,
+/// numeric instead of a provider's own style). A retried translation
+/// replaces the first one only if it keeps every anchor; otherwise the loss
+/// is reported as a warning.
+///
+/// A batch is one request for providers with a native batch API and one
+/// request per text otherwise, so `TranslationOutcome::api_calls` is 1 for
+/// a batching provider whose translations kept their anchors, 2 with a retry,
+/// and the number of variants (plus retried ones) for the others.
+///
+/// # Arguments
+/// * `ast` - Parsed AST of the source message
+/// * `provider` - Machine translation provider
+/// * `source_locale` - Source language code
+/// * `target_locale` - Target language code (also used for plural form selection)
+///
+/// # Returns
+/// * `Ok(TranslationOutcome)` - Wikitext together with provider, call count,
+///   duration, warnings and confidence
+/// * `Err(MtError)` - If expansion, translation or reassembly fails
+pub async fn translate_message(
+    ast: &AstNodeList,
+    provider: &dyn MachineTranslator,
+    source_locale: &str,
+    target_locale: &str,
+) -> MtResult<TranslationOutcome> {
+    translate_message_with_options(
+        ast,
+        provider,
+        source_locale,
+        target_locale,
+        &TranslationOptions::default(),
+    )
+    .await
+}
+
+/// Translate a message like `translate_message()`, with the source as context
+///
+/// An expanded variant is a complete sentence, but a short one such as
+/// "Show messages" can still be ambiguous. Here the whole source message,
+/// rendered as plain text with `preview_plain()` (magic words resolved, no
+/// wikitext syntax), is passed to the provider as `TranslationOptions::context`,
+/// so context-aware providers (e.g. DeepL) can disambiguate with it. The
+/// context is not translated and not billed as translatable text; other
+/// providers ignore it.
+///
+/// # Arguments
+/// * `ast` - Parsed AST of the source message
+/// * `provider` - Machine translation provider
+/// * `source_locale` - Source language code
+/// * `target_locale` - Target language code
+///
+/// # Returns
+/// Same as `translate_message()`
+pub async fn translate_message_in_context(
+    ast: &AstNodeList,
+    provider: &dyn MachineTranslator,
+    source_locale: &str,
+    target_locale: &str,
+) -> MtResult<TranslationOutcome> {
+    let options = TranslationOptions {
+        context: Some(preview_plain(&ast_to_wikitext(ast), source_locale)),
+        ..Default::default()
+    };
+    translate_message_with_options(ast, provider, source_locale, target_locale, &options).await
+}
+
+/// Translate and reassemble an already expanded message
+///
+/// Same as `translate_message()`, for callers that expanded the message
+/// themselves and filled in more of the context, such as
+/// `I18nMtExt::translate_key()` with the message key and its `qqq`
+/// documentation. The context's `documentation`, if any, is passed to the
+/// provider as `TranslationOptions::context`.
+///
+/// # Arguments
+/// * `context` - Context from `prepare_for_translation()` for `target_locale`
+/// * `provider` - Machine translation provider
+/// * `source_locale` - Source language code
+/// * `target_locale` - Target language code
+///
+/// # Returns
+/// Same as `translate_message()`
+pub async fn translate_context(
+    context: MessageContext,
+    provider: &dyn MachineTranslator,
+    source_locale: &str,
+    target_locale: &str,
+) -> MtResult<TranslationOutcome> {
+    let options = TranslationOptions {
+        context: context.documentation.clone(),
+        ..Default::default()
+    };
+    translate_prepared(context, provider, source_locale, target_locale, &options).await
+}
+
+/// Expand, translate and reassemble a message, passing `options` to the provider
+async fn translate_message_with_options(
+    ast: &AstNodeList,
+    provider: &dyn MachineTranslator,
+    source_locale: &str,
+    target_locale: &str,
+    options: &TranslationOptions,
+) -> MtResult<TranslationOutcome> {
+    let context = prepare_for_translation(ast, target_locale, "message")?;
+    translate_prepared(context, provider, source_locale, target_locale, options).await
+}
+
+/// Provider wrapper that counts the requests sent through it
+///
+/// Each `translate` or batch call made on the wrapper is one request to the
+/// inner provider. Everything else is delegated unchanged.
+struct CountingTranslator<'a> {
+    inner: &'a dyn MachineTranslator,
+    requests: AtomicUsize,
+}
+
+impl<'a> CountingTranslator<'a> {
+    fn new(inner: &'a dyn MachineTranslator) -> Self {
+        CountingTranslator {
+            inner,
+            requests: AtomicUsize::new(0),
+        }
+    }
+
+    /// Number of requests sent so far
+    fn requests(&self) -> usize {
+        self.requests.load(Ordering::SeqCst)
+    }
+
+    fn record_request(&self) {
+        self.requests.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[async_trait]
+impl MachineTranslator for CountingTranslator<'_> {
+    async fn translate(
+        &self,
+        text: &str,
+        source_locale: &str,
+        target_locale: &str,
+    ) -> MtResult<String> {
+        self.record_request();
+        self.inner
+            .translate(text, source_locale, target_locale)
+            .await
+    }
+
+    async fn translate_batch(
+        &self,
+        texts: &[String],
+        source_locale: &str,
+        target_locale: &str,
+    ) -> MtResult<Vec<String>> {
+        self.record_request();
+        self.inner
+            .translate_batch(texts, source_locale, target_locale)
+            .await
+    }
+
+    async fn translate_with_options(
+        &self,
+        text: &str,
+        source_locale: &str,
+        target_locale: &str,
+        options: &TranslationOptions,
+    ) -> MtResult<String> {
+        self.record_request();
+        self.inner
+            .translate_with_options(text, source_locale, target_locale, options)
+            .await
+    }
+
+    async fn translate_batch_with_options(
+        &self,
+        texts: &[String],
+        source_locale: &str,
+        target_locale: &str,
+        options: &TranslationOptions,
+    ) -> MtResult<Vec<String>> {
+        self.record_request();
+        self.inner
+            .translate_batch_with_options(texts, source_locale, target_locale, options)
+            .await
+    }
+
+    async fn translate_with_context(
+        &self,
+        texts: &[String],
+        context: &str,
+        source_locale: &str,
+        target_locale: &str,
+    ) -> MtResult<Vec<String>> {
+        self.record_request();
+        self.inner
+            .translate_with_context(texts, context, source_locale, target_locale)
+            .await
+    }
+
+    fn provider_name(&self) -> &str {
+        self.inner.provider_name()
+    }
+
+    fn preferred_anchor_style(&self) -> AnchorStyle {
+        self.inner.preferred_anchor_style()
+    }
+
+    fn wants_plural_hints(&self) -> bool {
+        self.inner.wants_plural_hints()
+    }
+
+    fn supports_native_batch(&self) -> bool {
+        self.inner.supports_native_batch()
+    }
+
+    fn supported_pairs(&self) -> Option<LanguageMatrix> {
+        self.inner.supported_pairs()
+    }
+}
+
+/// Translate and reassemble an expanded message, passing `options` to the provider
+async fn translate_prepared(
+    mut context: MessageContext,
+    provider: &dyn MachineTranslator,
+    source_locale: &str,
+    target_locale: &str,
+    options: &TranslationOptions,
+) -> MtResult<TranslationOutcome> {
+    let started = Instant::now();
+    let counter = CountingTranslator::new(provider);
+    let provider: &dyn MachineTranslator = &counter;
+
+    let source_texts = context.source_texts();
+    let hints = if provider.wants_plural_hints() {
+        plural_hints(&context, target_locale)?
+    } else {
+        vec![Vec::new(); source_texts.len()]
+    };
+    let hinted_texts: Vec<String> = source_texts
+        .iter()
+        .zip(&hints)
+        .map(|(text, swaps)| {
+            swaps.iter().fold(text.clone(), |text, (number, anchor)| {
+                text.replace(anchor.as_str(), number)
+            })
+        })
+        .collect();
+    let translated = translate_anchored(
+        provider,
+        &hinted_texts,
+        source_locale,
+        target_locale,
+        options,
+    )
+    .await?;
+    if translated.len() != source_texts.len() {
+        return Err(MtError::TranslationError(
+            format!(
+                "Provider returned {} translations for {} variants",
+                translated.len(),
+                source_texts.len()
+            )
+            .into(),
+        ));
+    }
+    let mut translated: Vec<String> = translated
+        .into_iter()
+        .zip(&hints)
+        .map(|(text, swaps)| restore_plural_hints(&text, swaps))
+        .collect();
+
+    // Give variants that dropped an anchor one more try in another anchor
+    // style; the retry is kept only if it preserves every anchor
+    let mut retried = 0;
+    let lossy: Vec<usize> = (0..source_texts.len())
+        .filter(|&i| !lost_anchors(&source_texts[i], &translated[i]).is_empty())
+        .collect();
+    if !lossy.is_empty() {
+        let retry_texts: Vec<String> = lossy.iter().map(|&i| hinted_texts[i].clone()).collect();
+        let style = retry_anchor_style(provider.preferred_anchor_style());
+        if let Ok(retranslated) = translate_in_style(
+            provider,
+            &retry_texts,
+            style,
+            source_locale,
+            target_locale,
+            options,
+        )
+        .await
+        {
+            for (&i, text) in lossy.iter().zip(retranslated) {
+                let text = restore_plural_hints(&text, &hints[i]);
+                if lost_anchors(&source_texts[i], &text).is_empty() {
+                    translated[i] = text;
+                    retried += 1;
+                }
+            }
+        }
+    }
+
+    // Variants that dropped an anchor cannot be reassembled faithfully
+    let mut warnings = context.warnings.clone();
+    let mut intact = 0;
+    let mut reordered = 0;
+    for (source, target) in source_texts.iter().zip(&translated) {
+        if anchors_reordered(source, target) {
+            reordered += 1;
+        }
+        let lost = lost_anchors(source, target);
+        if lost.is_empty() {
+            intact += 1;
+        } else {
+            warnings.push(format!(
+                "Translation of \"{}\" lost anchors {}",
+                source,
+                lost.join(", ")
+            ));
+        }
+    }
+    let confidence = if source_texts.is_empty() {
+        1.0
+    } else {
+        intact as f32 / source_texts.len() as f32
+    };
+
+    context.update_translations(translated);
+    let output = Reassembler::from_context(&context)
+        .with_target_locale(target_locale)
+        .with_structure_check(StructureCheck::Warn)
+        .reassemble_detailed(context.variants)?;
+    warnings.extend(output.warnings);
+
+    Ok(TranslationOutcome {
+        wikitext: output.wikitext,
+        provider: provider.provider_name().to_string(),
+        api_calls: counter.requests(),
+        duration: started.elapsed(),
+        warnings,
+        confidence,
+        reordered,
+        retried,
+    })
+}
+
+/// Translations of one message into several target languages
+#[derive(Debug, Clone, PartialEq)]
+pub struct MultiTargetTranslation {
+    /// Translated wikitext per target locale, in the order requested
+    pub translations: Vec<(String, MtResult<String>)>,
+    /// Number of times the message was expanded
+    pub expansions: usize,
+}
+
+/// Cardinal and ordinal plural categories of a target language
+type PluralKey = (Vec<PluralCategory>, Vec<PluralCategory>);
+
+/// Translate a message into several target languages, expanding it sparingly
+///
+/// Expanded variants only depend on the target language through its plural
+/// forms, so targets with the same plural categories (e.g. French and German)
+/// share one expansion; a message without PLURAL or PLURAL-ORDINAL is expanded once for all
+/// targets. Each target still gets its own batch of variants and is
+/// reassembled with its own locale. A failing target does not stop the others.
+///
+/// # Arguments
+/// * `ast` - Parsed AST of the source message
+/// * `provider` - Machine translation provider
+/// * `source_locale` - Source language code
+/// * `target_locales` - Target language codes
+///
+/// # Returns
+/// Per-target wikitext or error, and the number of expansions performed
+pub async fn translate_to_many(
+    ast: &AstNodeList,
+    provider: &dyn MachineTranslator,
+    source_locale: &str,
+    target_locales: &[&str],
+) -> MultiTargetTranslation {
+    let uses = |magic_type: MagicWordType| {
+        ast.iter().any(|node| {
+            matches!(node, AstNode::Transclusion(trans)
+                if MagicWordRegistry::default_registry().resolve(&trans.name) == Some(magic_type))
+        })
+    };
+    let has_plural = uses(MagicWordType::Plural);
+    let has_ordinal = uses(MagicWordType::PluralOrdinal);
+
+    // Expansions keyed by cardinal and ordinal plural categories (empty: not used)
+    let mut expansions: Vec<(PluralKey, MessageContext)> = Vec::new();
+    let mut translations = Vec::new();
+
+    for &target in target_locales {
+        let categories = |used: bool, forms_for: fn(&str) -> MtResult<Vec<PluralForm>>| {
+            if !used {
+                return Ok(Vec::new());
+            }
+            forms_for(target).map(|forms| forms.iter().map(|form| form.category).collect())
+        };
+        let key = match (
+            categories(has_plural, get_plural_forms_for_language),
+            categories(has_ordinal, get_ordinal_forms_for_language),
+        ) {
+            (Ok(cardinal), Ok(ordinal)) => (cardinal, ordinal),
+            (Err(e), _) | (_, Err(e)) => {
+                translations.push((target.to_string(), Err(e)));
+                continue;
+            }
+        };
+
+        let context = match expansions.iter().find(|(k, _)| *k == key) {
+            Some((_, context)) => context.clone(),
+            None => match prepare_for_translation(ast, target, "message") {
+                Ok(context) => {
+                    expansions.push((key, context.clone()));
+                    context
+                }
+                Err(e) => {
+                    translations.push((target.to_string(), Err(e)));
+                    continue;
+                }
+            },
+        };
+
+        let result = translate_expanded(context, provider, source_locale, target).await;
+        translations.push((target.to_string(), result));
+    }
+
+    MultiTargetTranslation {
+        translations,
+        expansions: expansions.len(),
+    }
+}
+
+/// Translate and reassemble an already expanded message for one target
+async fn translate_expanded(
+    mut context: MessageContext,
+    provider: &dyn MachineTranslator,
+    source_locale: &str,
+    target_locale: &str,
+) -> MtResult<String> {
+    let source_texts = context.source_texts();
+    let translated = translate_anchored(
+        provider,
+        &source_texts,
+        source_locale,
+        target_locale,
+        &TranslationOptions::default(),
+    )
+    .await?;
+    if translated.len() != source_texts.len() {
+        return Err(MtError::TranslationError(
+            format!(
+                "Provider returned {} translations for {} variants",
+                translated.len(),
+                source_texts.len()
+            )
+            .into(),
+        ));
+    }
+
+    context.update_translations(translated);
+    Reassembler::from_context(&context)
+        .with_target_locale(target_locale)
+        .reassemble(context.variants)
+}
+
+/// Numbers to show the provider in place of PLURAL anchors, per variant
+///
+/// For each variant, the anchor of every PLURAL parameter is paired with the
+/// test value of the variant's plural category, as (number, anchor). A
+/// parameter is left alone if its anchor is absent or its number already
+/// occurs in the text, since it could not be swapped back unambiguously.
+fn plural_hints(context: &MessageContext, locale: &str) -> MtResult<Vec<Vec<(String, String)>>> {
+    let forms = get_plural_forms_for_language(locale)?;
+    let mut hints = Vec::new();
+
+    for variant in &context.variants {
+        let mut text = variant.source_text.clone();
+        let mut swaps = Vec::new();
+        for var_id in context.variable_ids() {
+            if context.get_variable_type(&var_id).map(String::as_str) != Some("PLURAL") {
+                continue;
+            }
+            let Some(form) = variant
+                .state
+                .get(&var_id)
+                .and_then(|&index| forms.get(index))
+            else {
+                continue;
+            };
+            let Some(anchor) = var_id
+                .strip_prefix('$')
+                .and_then(|index| index.parse().ok())
+                .and_then(|index| placeholder_anchor(index).ok())
+            else {
+                continue;
+            };
+
+            let number = form.test_value.to_string();
+            if !text.contains(&anchor) || number_in(&text, &number) {
+                continue;
+            }
+            text = text.replace(&anchor, &number);
+            swaps.push((number, anchor));
+        }
+        hints.push(swaps);
+    }
+
+    Ok(hints)
+}
+
+/// Put the anchors replaced by `plural_hints()` back into a translation
+fn restore_plural_hints(text: &str, swaps: &[(String, String)]) -> String {
+    swaps
+        .iter()
+        .fold(text.to_string(), |text, (number, anchor)| {
+            let re = Regex::new(&format!(r"\b{}\b", number)).unwrap();
+            re.replace_all(&text, anchor.as_str()).to_string()
+        })
+}
+
+/// Whether `number` occurs in `text` as a whole number
+fn number_in(text: &str, number: &str) -> bool {
+    Regex::new(&format!(r"\b{}\b", number))
+        .unwrap()
+        .is_match(text)
+}
+
+/// Translate anchor-protected texts in the provider's preferred anchor style
+///
+/// Numeric anchors in `texts` are rendered with the provider's
+/// `preferred_anchor_style()`, the texts are sent as one batch (a single
+/// request with a native batch API), and the anchors in the translations
+/// are converted back to numeric anchors. For providers using the default
+/// `AnchorStyle::Numeric` this is a plain batch call.
+///
+/// Empty and whitespace-only texts have nothing to translate and are returned
+/// unchanged without being sent; if all texts are blank the provider is not
+/// called at all.
+///
+/// # Arguments
+/// * `provider` - Machine translation provider
+/// * `texts` - Texts with numeric anchors, as produced by expansion
+/// * `source_locale` - Source language code
+/// * `target_locale` - Target language code
+/// * `options` - Per-request hints passed through to the provider
+///
+/// # Returns
+/// * `Ok(Vec<String>)` - Translations with numeric anchors, in input order
+/// * `Err(MtError)` - If translation fails
+pub async fn translate_anchored(
+    provider: &dyn MachineTranslator,
+    texts: &[String],
+    source_locale: &str,
+    target_locale: &str,
+    options: &TranslationOptions,
+) -> MtResult<Vec<String>> {
+    let pending: Vec<usize> = (0..texts.len())
+        .filter(|&i| !texts[i].trim().is_empty())
+        .collect();
+    if pending.is_empty() {
+        return Ok(texts.to_vec());
+    }
+
+    let pending_texts: Vec<String> = pending.iter().map(|&i| texts[i].clone()).collect();
+    let translated = translate_in_style(
+        provider,
+        &pending_texts,
+        provider.preferred_anchor_style(),
+        source_locale,
+        target_locale,
+        options,
+    )
+    .await?;
+    if pending.len() == texts.len() {
+        return Ok(translated);
+    }
+
+    if translated.len() != pending.len() {
+        return Err(MtError::TranslationError(
+            format!(
+                "Provider returned {} translations for {} texts",
+                translated.len(),
+                pending.len()
+            )
+            .into(),
+        ));
+    }
+    let mut merged = texts.to_vec();
+    for (i, text) in pending.into_iter().zip(translated) {
+        merged[i] = text;
+    }
+    Ok(merged)
+}
+
+/// Send texts to the provider with their anchors rendered in `style`
+async fn translate_in_style(
+    provider: &dyn MachineTranslator,
+    texts: &[String],
+    style: AnchorStyle,
+    source_locale: &str,
+    target_locale: &str,
+    options: &TranslationOptions,
+) -> MtResult<Vec<String>> {
+    let styled: Vec<String> = texts.iter().map(|t| style.apply(t)).collect();
+    let translated = send_batch(provider, &styled, source_locale, target_locale, options).await?;
+    let mut translated: Vec<String> = translated.iter().map(|t| style.recover(t)).collect();
+    // Directional marks MT added around anchors when either side is
+    // right-to-left are dropped
+    let rtl = direction_of(source_locale) == Direction::Rtl
+        || direction_of(target_locale) == Direction::Rtl;
+    if !rtl {
+        return Ok(translated);
+    }
+    for (text, source) in translated.iter_mut().zip(texts) {
+        if !has_bidi_controls(source) {
+            *text = strip_bidi_around_anchors(text);
+        }
+    }
+    Ok(translated)
+}
+
+/// Send texts to the provider in as few requests as it allows
+///
+/// Providers with a native batch API get one `translate_batch_with_options`
+/// request. For the others a batch would cost one request per text anyway,
+/// so the texts are sent as concurrent `translate_with_options` requests.
+async fn send_batch(
+    provider: &dyn MachineTranslator,
+    texts: &[String],
+    source_locale: &str,
+    target_locale: &str,
+    options: &TranslationOptions,
+) -> MtResult<Vec<String>> {
+    if provider.supports_native_batch() {
+        return provider
+            .translate_batch_with_options(texts, source_locale, target_locale, options)
+            .await;
+    }
+    try_join_all(
+        texts.iter().map(|text| {
+            provider.translate_with_options(text, source_locale, target_locale, options)
+        }),
+    )
+    .await
+}
+
+/// Anchor style for re-translating a variant that lost an anchor
+///
+/// An engine that dropped a bare number often keeps a bracketed one, and one
+/// that mangled a styled anchor may keep a plain number.
+fn retry_anchor_style(style: AnchorStyle) -> AnchorStyle {
+    match style {
+        AnchorStyle::Numeric => AnchorStyle::Bracketed,
+        _ => AnchorStyle::Numeric,
+    }
+}
+
+/// Machine translate only the PLURAL/GENDER options of a human translation
+///
+/// `human_base` is a message whose stable text was translated by a human but
+/// whose magic word options are still in the source language. The stable text
+/// is kept verbatim; every option fragment (including those of nested magic
+/// words) is anchor-protected and sent to the provider as one batch (a single
+/// request with a native batch API, one request per fragment otherwise),
+/// then substituted back in place. Explicit plural prefixes such as `0=` and
+/// surrounding whitespace are preserved. The human translation, rendered as
+/// plain text, is passed as `TranslationOptions::context`, so context-aware
+/// providers translate the fragments to fit it.
+///
+/// # Arguments
+/// * `human_base` - Parsed human translation with untranslated options
+/// * `provider` - Machine translation provider
+/// * `source_locale` - Language of the option fragments
+/// * `target_locale` - Language of the human translation
+///
+/// # Returns
+/// * `Ok(String)` - The human translation with translated options
+/// * `Err(MtError)` - If translation fails or a fragment loses a placeholder
+pub async fn translate_variable_forms(
+    human_base: &AstNodeList,
+    provider: &dyn MachineTranslator,
+    source_locale: &str,
+    target_locale: &str,
+) -> MtResult<String> {
+    let mut fragments = Vec::new();
+    collect_option_fragments(human_base, &mut fragments);
+    if fragments.is_empty() {
+        return Ok(ast_to_wikitext(human_base));
+    }
+
+    let protected = fragments
+        .iter()
+        .map(|f| protect(f))
+        .collect::<MtResult<Vec<_>>>()?;
+    let texts: Vec<String> = protected.iter().map(|(text, _)| text.clone()).collect();
+    let options = TranslationOptions {
+        context: Some(preview_plain(&ast_to_wikitext(human_base), target_locale)),
+        ..Default::default()
+    };
+    let translated = send_batch(provider, &texts, source_locale, target_locale, &options).await?;
+    if translated.len() != texts.len() {
+        return Err(MtError::TranslationError(
+            format!(
+                "Provider returned {} translations for {} option fragments",
+                translated.len(),
+                texts.len()
+            )
+            .into(),
+        ));
+    }
+
+    let mut restored = Vec::with_capacity(translated.len());
+    for (text, (_, anchors)) in translated.iter().zip(&protected) {
+        restored.push(unprotect(text.trim(), anchors)?);
+    }
+
+    let mut replacements = restored.into_iter();
+    Ok(replace_option_fragments(human_base, &mut replacements))
+}
+
+/// Translate a message, adding a GENDER axis the source does not have
+///
+/// Expands `ast` as usual, then translates every variant once per GENDER form
+/// (male, female, unknown) with `TranslationOptions::subject_gender` set, one
+/// `translate_batch_with_options` call per form. The results are reassembled
+/// with `var_id` as an extra GENDER variable, so forms that come out identical
+/// collapse and only the differing text ends up in the GENDER block. Providers
+/// that ignore the hint therefore produce a message without a GENDER block.
+///
+/// # Arguments
+/// * `ast` - Parsed AST of the source message
+/// * `var_id` - Variable the new GENDER block is keyed by (e.g. "$1")
+/// * `provider` - Machine translation provider
+/// * `source_locale` - Source language code
+/// * `target_locale` - Target language code (also used for plural form selection)
+///
+/// # Returns
+/// * `Ok(String)` - Reassembled wikitext with the new GENDER axis
+/// * `Err(MtError)` - If `var_id` already controls a magic word in the source,
+///   or if expansion, translation or reassembly fails
+pub async fn introduce_gender_axis(
+    ast: &AstNodeList,
+    var_id: &str,
+    provider: &dyn MachineTranslator,
+    source_locale: &str,
+    target_locale: &str,
+) -> MtResult<String> {
+    let mut context = prepare_for_translation(ast, target_locale, "message")?;
+    if context.variable_types.contains_key(var_id) {
+        return Err(MtError::ExpansionError(
+            format!(
+                "{} already controls a {} in the source message",
+                var_id, context.variable_types[var_id]
+            )
+            .into(),
+        ));
+    }
+
+    let source_texts = context.source_texts();
+    let mut variants = Vec::with_capacity(source_texts.len() * DEFAULT_GENDER_FORM_COUNT);
+    for index in 0..DEFAULT_GENDER_FORM_COUNT {
+        let options = TranslationOptions {
+            subject_gender: SubjectGender::from_index(index),
+            ..Default::default()
+        };
+        let translated = translate_anchored(
+            provider,
+            &source_texts,
+            source_locale,
+            target_locale,
+            &options,
+        )
+        .await?;
+        if translated.len() != source_texts.len() {
+            return Err(MtError::TranslationError(
+                format!(
+                    "Provider returned {} translations for {} variants",
+                    translated.len(),
+                    source_texts.len()
+                )
+                .into(),
+            ));
+        }
+
+        for (variant, text) in context.variants.iter().zip(translated) {
+            let mut state = variant.state.clone();
+            state.insert(var_id.to_string(), index);
+            variants.push(TranslationVariant::with_translation(
+                state,
+                variant.source_text.clone(),
+                text,
+            ));
+        }
+    }
+
+    context.add_variable(var_id.to_string(), "GENDER".to_string());
+    Reassembler::from_context(&context)
+        .with_target_locale(target_locale)
+        .reassemble(variants)
+}
+
+/// Split an option into (prefix, translatable fragment, suffix)
+///
+/// The prefix holds leading whitespace and an explicit plural form such as
+/// `12=`; the suffix holds trailing whitespace.
+fn split_option(option: &str) -> (&str, &str, &str) {
+    let trimmed_start = option.trim_start();
+    let mut start = option.len() - trimmed_start.len();
+    let digits = trimmed_start
+        .chars()
+        .take_while(|c| c.is_ascii_digit())
+        .count();
+    if digits > 0 && trimmed_start[digits..].starts_with('=') {
+        start += digits + 1;
+    }
+    let end = start + option[start..].trim_end().len();
+    (&option[..start], &option[start..end], &option[end..])
+}
+
+/// Options that hold magic words are themselves parsed and walked
+fn is_nested(option: &str) -> bool {
+    option.contains("{{")
+}
+
+/// Collect option fragments of all magic words, in document order
+fn collect_option_fragments(ast: &AstNodeList, fragments: &mut Vec<String>) {
+    for node in ast {
+        let AstNode::Transclusion(trans) = node else {
+            continue;
+        };
+        if MagicWordRegistry::default_registry()
+            .resolve(&trans.name)
+            .is_none()
+        {
+            continue;
+        }
+        for option in &trans.options {
+            if is_nested(option) {
+                collect_option_fragments(&Parser::new(option).parse(), fragments);
+            } else {
+                let (_, fragment, _) = split_option(option);
+                if !fragment.is_empty() {
+                    fragments.push(fragment.to_string());
+                }
+            }
+        }
+    }
+}
+
+/// Rebuild the wikitext, taking fragment translations in document order
+fn replace_option_fragments(
+    ast: &AstNodeList,
+    replacements: &mut impl Iterator<Item = String>,
+) -> String {
+    let mut rebuilt = ast.clone();
+    for node in &mut rebuilt {
+        let AstNode::Transclusion(trans) = node else {
+            continue;
+        };
+        if MagicWordRegistry::default_registry()
+            .resolve(&trans.name)
+            .is_none()
+        {
+            continue;
+        }
+        for option in &mut trans.options {
+            if is_nested(option) {
+                *option = replace_option_fragments(&Parser::new(option).parse(), replacements);
+            } else {
+                let (prefix, fragment, suffix) = split_option(option);
+                if !fragment.is_empty() {
+                    let translated = replacements.next().unwrap_or_default();
+                    *option = format!("{}{}{}", prefix, translated, suffix);
+                }
+            }
+        }
+    }
+    ast_to_wikitext(&rebuilt)
+}
+
+/// Placeholder anchors (777001, 777002, ...) present in a text
+fn anchors_in(text: &str) -> BTreeSet<String> {
+    find_anchors(text)
+        .into_iter()
+        .filter(|m| !m.is_link())
+        .map(|m| m.anchor.to_string())
+        .collect()
+}
+
+/// Anchors of `source` that are missing from `target`, sorted
+fn lost_anchors(source: &str, target: &str) -> Vec<String> {
+    anchors_in(source)
+        .difference(&anchors_in(target))
+        .cloned()
+        .collect()
+}
+
+/// Whether the anchors kept by a translation appear in a different order
+fn anchors_reordered(source: &str, target: &str) -> bool {
+    let kept = anchors_in(source)
+        .intersection(&anchors_in(target))
+        .cloned()
+        .collect::<BTreeSet<_>>();
+    let order = |text: &str| -> Vec<String> {
+        find_anchors(text)
+            .into_iter()
+            .map(|m| m.anchor.to_string())
+            .filter(|anchor| kept.contains(anchor))
+            .collect()
+    };
+    order(source) != order(target)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{MockMode, MockTranslator};
+    use crate::reassembly::reassemble_from_context;
+    use banana_i18n::parser::Parser;
+
+    fn parse(text: &str) -> AstNodeList {
+        let mut parser = Parser::new(text);
+        parser.parse()
+    }
+
+    /// Translate a message from scratch with the given provider
+    async fn translate_fresh(text: &str, provider: &MockTranslator) -> MessageContext {
+        let mut context = prepare_for_translation(&parse(text), "fr", "test").unwrap();
+        let translated = provider
+            .translate_batch(&context.source_texts(), "en", "fr")
+            .await
+            .unwrap();
+        context.update_translations(translated);
+        context
+    }
+
+    // ========== Retranslation Tests ==========
+
+    #[tokio::test]
+    async fn test_retranslate_sends_only_changed_variants() {
+        let initial = MockTranslator::new(MockMode::Suffix);
+        let previous = translate_fresh("{{GENDER:$1|He|She|They}} sent a message", &initial).await;
+        assert_eq!(previous.variant_count(), 3);
+
+        // Only the third GENDER form changes
+        let provider = MockTranslator::new(MockMode::Suffix);
+        let new_ast = parse("{{GENDER:$1|He|She|Someone}} sent a message");
+        let context = retranslate(&previous, &new_ast, &provider, "en", "fr")
+            .await
+            .unwrap();
+
+        assert_eq!(provider.call_count(), 1);
+        assert_eq!(provider.text_count(), 1);
+        assert!(context.is_fully_translated());
+        assert_eq!(context.variants[0].translated_text, "He sent a message_fr");
+        assert_eq!(
+            context.variants[2].translated_text,
+            "Someone sent a message_fr"
+        );
+        assert!(reassemble_from_context(&context).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_retranslate_unchanged_message_skips_provider() {
+        let initial = MockTranslator::new(MockMode::Suffix);
+        let previous = translate_fresh("Hello, $1!", &initial).await;
+
+        let provider = MockTranslator::new(MockMode::Suffix);
+        let context = retranslate(&previous, &parse("Hello, $1!"), &provider, "en", "fr")
+            .await
+            .unwrap();
+
+        assert_eq!(provider.call_count(), 0);
+        assert_eq!(context.variants[0].translated_text, "Hello, 777001!_fr");
+    }
+
+    // ========== Instrumented Translation Tests ==========
+
+    #[tokio::test]
+    async fn test_translate_message_reports_outcome() {
+        let provider = MockTranslator::new(MockMode::Suffix);
+        let ast = parse("{{GENDER:$1|He|She|They}} sent {{PLURAL:$2|a message|$2 messages}}");
+
+        let outcome = translate_message(&ast, &provider, "en", "fr")
+            .await
+            .unwrap();
+
+        assert_eq!(outcome.provider, provider.provider_name());
+        assert_eq!(outcome.api_calls, provider.call_count());
+        assert_eq!(outcome.api_calls, 1);
+        assert_eq!(provider.text_count(), 6);
+        assert!(outcome.wikitext.contains("{{GENDER:$1|"));
+        assert!(outcome.wikitext.contains("{{PLURAL:$2|"));
+        assert!(outcome.warnings.is_empty());
+        assert_eq!(outcome.confidence, 1.0);
+    }
+
+    #[tokio::test]
+    async fn test_translate_message_warns_about_borrowed_plural_rules() {
+        let provider = MockTranslator::new(MockMode::Suffix);
+        let ast = parse("{{PLURAL:$1|a message|$1 messages}}");
+
+        let outcome = translate_message(&ast, &provider, "en", "zh-classical")
+            .await
+            .unwrap();
+
+        assert_eq!(outcome.warnings.len(), 1);
+        assert!(outcome.warnings[0].ends_with("using plural rules of 'lzh'"));
+    }
+
+    #[tokio::test]
+    async fn test_translate_message_fans_out_without_native_batch() {
+        let provider = MockTranslator::new(MockMode::Suffix).with_native_batch(false);
+        let ast = parse("{{GENDER:$1|He|She|They}} sent {{PLURAL:$2|a message|$2 messages}}");
+
+        let outcome = translate_message(&ast, &provider, "en", "fr")
+            .await
+            .unwrap();
+
+        // One translate request per variant
+        assert_eq!(provider.call_count(), 6);
+        assert_eq!(provider.text_count(), 6);
+        assert_eq!(outcome.api_calls, 6);
+        assert!(outcome.wikitext.contains("{{GENDER:$1|"));
+        assert!(outcome.warnings.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_translate_message_lowers_confidence_on_lost_anchor() {
+        // Only the second variant drops the $2 anchor
+        let mut map = HashMap::new();
+        map.insert(
+            (
+                "She edited the page about 777002 yesterday".to_string(),
+                "fr".to_string(),
+            ),
+            "She edited the page about yesterday_fr".to_string(),
+        );
+        // The retry in bracketed style drops it as well
+        map.insert(
+            (
+                "She edited the page about ⟦2⟧ yesterday".to_string(),
+                "fr".to_string(),
+            ),
+            "She edited the page yesterday_fr".to_string(),
+        );
+        let provider = MockTranslator::new(MockMode::Mappings(map));
+        let outcome = translate_message(
+            &parse("{{GENDER:$1|He|She|They}} edited the page about $2 yesterday"),
+            &provider,
+            "en",
+            "fr",
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(outcome.warnings.len(), 1);
+        assert!(outcome.warnings[0].contains("777002"));
+        assert!((outcome.confidence - 2.0 / 3.0).abs() < 1e-6);
+        assert_eq!(outcome.api_calls, 2);
+        assert_eq!(outcome.api_calls, provider.call_count());
+        assert_eq!(outcome.retried, 0);
+    }
+
+    #[tokio::test]
+    async fn test_translate_message_retries_variant_that_lost_anchor() {
+        // The second variant drops $2 in numeric style but keeps the
+        // bracketed anchor of the retry
+        let mut map = HashMap::new();
+        map.insert(
+            (
+                "She edited the page about 777002 yesterday".to_string(),
+                "fr".to_string(),
+            ),
+            "Elle a modifié la page hier".to_string(),
+        );
+        map.insert(
+            (
+                "She edited the page about ⟦2⟧ yesterday".to_string(),
+                "fr".to_string(),
+            ),
+            "Elle a modifié la page sur ⟦2⟧ hier".to_string(),
+        );
+        for (en, fr) in [
+            ("He", "Il a modifié la page sur 777002 hier"),
+            ("They", "Iel a modifié la page sur 777002 hier"),
+        ] {
+            map.insert(
+                (
+                    format!("{} edited the page about 777002 yesterday", en),
+                    "fr".to_string(),
+                ),
+                fr.to_string(),
+            );
+        }
+        let provider = MockTranslator::new(MockMode::Mappings(map));
+        let outcome = translate_message(
+            &parse("{{GENDER:$1|He|She|They}} edited the page about $2 yesterday"),
+            &provider,
+            "en",
+            "fr",
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(outcome.api_calls, 2);
+        assert_eq!(provider.call_count(), 2);
+        // Only the lossy variant is sent again
+        assert_eq!(provider.text_count(), 4);
+        assert_eq!(outcome.retried, 1);
+        assert!(outcome.warnings.is_empty(), "{:?}", outcome.warnings);
+        assert_eq!(outcome.confidence, 1.0);
+        assert_eq!(
+            outcome.wikitext,
+            "{{GENDER:$1|Il|Elle|Iel}} a modifié la page sur $2 hier"
+        );
+    }
+
+    // ========== Blank Message Tests ==========
+
+    #[tokio::test]
+    async fn test_blank_messages_round_trip_without_provider_calls() {
+        for message in ["", "   ", "\n"] {
+            for mode in [MockMode::Suffix, MockMode::Reorder] {
+                let provider = MockTranslator::new(mode).with_anchor_style(AnchorStyle::Bracketed);
+                let outcome = translate_message(&parse(message), &provider, "en", "fr")
+                    .await
+                    .unwrap();
+
+                assert_eq!(outcome.wikitext, message);
+                assert_eq!(outcome.api_calls, 0);
+                assert_eq!(provider.call_count(), 0);
+                assert!(outcome.warnings.is_empty());
+                assert_eq!(outcome.confidence, 1.0);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_blank_variants_are_not_sent() {
+        let provider = MockTranslator::new(MockMode::Suffix);
+        let texts = vec![
+            "777001 left".to_string(),
+            " ".to_string(),
+            "777001 stayed".to_string(),
+        ];
+        let translated = translate_anchored(
+            &provider,
+            &texts,
+            "en",
+            "fr",
+            &TranslationOptions::default(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(translated, vec!["777001 left_fr", " ", "777001 stayed_fr"]);
+        assert_eq!(provider.text_count(), 2);
+    }
+
+    // ========== Multi-Target Tests ==========
+
+    #[tokio::test]
+    async fn test_translate_to_many_shares_expansions() {
+        let provider = MockTranslator::new(MockMode::Suffix);
+        let ast = parse("{{GENDER:$1|He|She|They}} sent {{PLURAL:$2|a message|$2 messages}}");
+
+        let result = translate_to_many(&ast, &provider, "en", &["fr", "de", "es"]).await;
+
+        // French, German and Spanish all expand to the one/other plural forms
+        assert_eq!(result.expansions, 1);
+        assert_eq!(provider.call_count(), 3);
+        let targets: Vec<&str> = result
+            .translations
+            .iter()
+            .map(|(t, _)| t.as_str())
+            .collect();
+        assert_eq!(targets, vec!["fr", "de", "es"]);
+        for (target, wikitext) in &result.translations {
+            let wikitext = wikitext.as_ref().unwrap();
+            assert!(
+                wikitext.contains("{{PLURAL:$2|"),
+                "{}: {}",
+                target,
+                wikitext
+            );
+            assert!(wikitext.contains(&format!("_{}", target)));
+        }
+
+        // Russian (one, few, many) needs an expansion of its own
+        let result = translate_to_many(&ast, &provider, "en", &["fr", "ru", "de", "es"]).await;
+        assert_eq!(result.expansions, 2);
+        assert!(
+            result
+                .translations
+                .iter()
+                .all(|(_, wikitext)| wikitext.is_ok())
+        );
+
+        // Without PLURAL every target shares a single expansion
+        let ast = parse("{{GENDER:$1|He|She|They}} left");
+        let result = translate_to_many(&ast, &provider, "en", &["fr", "de", "es"]).await;
+        assert_eq!(result.expansions, 1);
+    }
+
+    // ========== Plural Hint Tests ==========
+
+    #[tokio::test]
+    async fn test_plural_hints_show_numbers_to_provider() {
+        // Only texts with a number in place of the anchor are mapped
+        let mut map = HashMap::new();
+        for (en, fr) in [
+            ("There is 1 item", "Il y a 1 élément"),
+            ("There are 6 items", "Il y a 6 éléments"),
+        ] {
+            map.insert((en.to_string(), "fr".to_string()), fr.to_string());
+        }
+        let provider = MockTranslator::new(MockMode::Mappings(map)).with_plural_hints(true);
+
+        let outcome = translate_message(
+            &parse("There {{PLURAL:$1|is $1 item|are $1 items}}"),
+            &provider,
+            "en",
+            "fr",
+        )
+        .await
+        .unwrap();
+
+        assert!(outcome.warnings.is_empty(), "{:?}", outcome.warnings);
+        assert_eq!(outcome.wikitext, "Il y a $1 {{PLURAL:$1|élément|éléments}}");
+    }
+
+    #[test]
+    fn test_plural_hints_skip_ambiguous_numbers() {
+        let ast =
+            parse("{{PLURAL:$1|$1 of 6 files|$1 of 6 files}} and {{GENDER:$2|his|her|their}}");
+        let context = prepare_for_translation(&ast, "en", "test").unwrap();
+        let hints = plural_hints(&context, "en").unwrap();
+
+        for (variant, swaps) in context.variants.iter().zip(&hints) {
+            if variant.state["$1"] == 0 {
+                assert_eq!(swaps, &vec![("1".to_string(), "777001".to_string())]);
+            } else {
+                // "6" already occurs in the text
+                assert!(swaps.is_empty());
+            }
+        }
+        assert_eq!(
+            restore_plural_hints("1 sur 6 fichiers", &hints[0]),
+            "777001 sur 6 fichiers"
+        );
+    }
+
+    // ========== Review Classification Tests ==========
+
+    #[tokio::test]
+    async fn test_classify_clean_translation_is_auto_approved() {
+        let provider = MockTranslator::new(MockMode::Suffix);
+        let outcome = translate_message(&parse("$1 sent $2 a message"), &provider, "en", "fr")
+            .await
+            .unwrap();
+
+        assert_eq!(outcome.reordered, 0);
+        let status = classify(
+            &outcome,
+            &ReviewPolicy {
+                min_confidence: 0.9,
+                flag_on_reordering: true,
+            },
+        );
+        assert_eq!(status, ReviewStatus::AutoApproved);
+        assert_eq!(status.to_string(), "auto-approved");
+    }
+
+    #[tokio::test]
+    async fn test_classify_reordered_translation_needs_review() {
+        let provider = MockTranslator::new(MockMode::Reorder);
+        let outcome = translate_message(&parse("$1 sent $2 a message"), &provider, "en", "ja")
+            .await
+            .unwrap();
+        assert_eq!(outcome.reordered, 1);
+        assert!(outcome.warnings.is_empty());
+
+        let flagging = ReviewPolicy {
+            min_confidence: 0.9,
+            flag_on_reordering: true,
+        };
+        let status = classify(&outcome, &flagging);
+        assert_eq!(status.label(), "needs-review");
+        let ReviewStatus::NeedsReview(reasons) = status else {
+            unreachable!()
+        };
+        assert!(reasons[0].contains("reordered"));
+
+        // Reordering alone is accepted by the default policy
+        assert_eq!(
+            classify(&outcome, &ReviewPolicy::default()),
+            ReviewStatus::AutoApproved
+        );
+    }
+
+    // ========== Variable Form Tests ==========
+
+    #[tokio::test]
+    async fn test_translate_variable_forms_sends_only_fragments() {
+        let mut map = HashMap::new();
+        for (en, fr) in [
+            ("He", "Il"),
+            ("She", "Elle"),
+            ("They", "Iel"),
+            ("a message", "un message"),
+            ("777002 messages", "777002 messages"),
+        ] {
+            map.insert((en.to_string(), "fr".to_string()), fr.to_string());
+        }
+        let provider = MockTranslator::new(MockMode::Mappings(map));
+
+        let base = parse(
+            "{{GENDER:$1|He|She|They}} a envoyé {{PLURAL:$2|a message|$2 messages}} à l'équipe",
+        );
+        let wikitext = translate_variable_forms(&base, &provider, "en", "fr")
+            .await
+            .unwrap();
+
+        assert_eq!(
+            wikitext,
+            "{{GENDER:$1|Il|Elle|Iel}} a envoyé {{PLURAL:$2|un message|$2 messages}} à l'équipe"
+        );
+        // Only the five option fragments, in one call; the human text is never sent
+        assert_eq!(provider.call_count(), 1);
+        assert_eq!(provider.text_count(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_translate_variable_forms_without_magic_words() {
+        let provider = MockTranslator::new(MockMode::Suffix);
+        let wikitext = translate_variable_forms(&parse("Bonjour, $1 !"), &provider, "en", "fr")
+            .await
+            .unwrap();
+        assert_eq!(wikitext, "Bonjour, $1 !");
+        assert_eq!(provider.call_count(), 0);
+    }
+
+    // ========== Anchor Style Tests ==========
+
+    #[tokio::test]
+    async fn test_translate_message_uses_provider_anchor_style() {
+        // The mapping only matches the tag-styled source text
+        let mut map = HashMap::new();
+        map.insert(
+            (
+                r#"<x id="1"/> sent a message"#.to_string(),
+                "fr".to_string(),
+            ),
+            r#"<x id="1" /> a envoyé un message"#.to_string(),
+        );
+        let provider =
+            MockTranslator::new(MockMode::Mappings(map)).with_anchor_style(AnchorStyle::XmlTag);
+
+        let outcome = translate_message(&parse("$1 sent a message"), &provider, "en", "fr")
+            .await
+            .unwrap();
+        assert_eq!(outcome.wikitext, "$1 a envoyé un message");
+        assert!(outcome.warnings.is_empty());
+        assert_eq!(outcome.confidence, 1.0);
+    }
+
+    #[tokio::test]
+    async fn test_translate_message_keeps_numbers_next_to_placeholders() {
+        let provider = MockTranslator::new(MockMode::NoOp);
+        for message in ["Room 77$1 is free", "Call 7770123 or $1"] {
+            let outcome = translate_message(&parse(message), &provider, "en", "fr")
+                .await
+                .unwrap();
+            assert_eq!(outcome.wikitext, message);
+            assert!(outcome.warnings.is_empty(), "{:?}", outcome.warnings);
+        }
+    }
+
+    // ========== Source Context Tests ==========
+
+    /// Provider that records the context of every request
+    #[derive(Default)]
+    struct ContextRecorder {
+        contexts: std::sync::Mutex<Vec<Option<String>>>,
+    }
+
+    #[async_trait::async_trait]
+    impl MachineTranslator for ContextRecorder {
+        async fn translate(&self, text: &str, _: &str, _: &str) -> MtResult<String> {
+            Ok(text.to_string())
+        }
+
+        async fn translate_batch(
+            &self,
+            texts: &[String],
+            _: &str,
+            _: &str,
+        ) -> MtResult<Vec<String>> {
+            self.contexts.lock().unwrap().push(None);
+            Ok(texts.to_vec())
+        }
+
+        async fn translate_batch_with_options(
+            &self,
+            texts: &[String],
+            _: &str,
+            _: &str,
+            options: &TranslationOptions,
+        ) -> MtResult<Vec<String>> {
+            self.contexts.lock().unwrap().push(options.context.clone());
+            Ok(texts.to_vec())
+        }
+
+        fn provider_name(&self) -> &str {
+            "Context recorder"
+        }
+
+        fn supports_native_batch(&self) -> bool {
+            true
+        }
+    }
+
+    #[tokio::test]
+    async fn test_translate_message_in_context_passes_source() {
+        let source = "Show {{PLURAL:$1|message|messages}}";
+        let provider = ContextRecorder::default();
+
+        let outcome = translate_message_in_context(&parse(source), &provider, "en", "fr")
+            .await
+            .unwrap();
+        assert_eq!(outcome.wikitext, source);
+        let plain = "Show messages".to_string();
+
+        translate_message(&parse(source), &provider, "en", "fr")
+            .await
+            .unwrap();
+
+        let texts = vec!["Show messages".to_string()];
+        provider
+            .translate_with_context(&texts, source, "en", "fr")
+            .await
+            .unwrap();
+
+        assert_eq!(
+            *provider.contexts.lock().unwrap(),
+            vec![Some(plain), None, Some(source.to_string())]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_translate_variable_forms_passes_human_base_as_context() {
+        let provider = ContextRecorder::default();
+        let base = parse("{{GENDER:$1|He|She}} a envoyé {{PLURAL:$2|a message|$2 messages}}");
+
+        translate_variable_forms(&base, &provider, "en", "fr")
+            .await
+            .unwrap();
+
+        assert_eq!(
+            *provider.contexts.lock().unwrap(),
+            vec![Some("He a envoyé ⟦$2⟧ messages".to_string())]
+        );
+    }
+
+    // ========== Introduced Axis Tests ==========
+
+    /// Provider that inflects a French participle by the subject gender hint
+    struct GenderedProvider;
+
+    #[async_trait::async_trait]
+    impl MachineTranslator for GenderedProvider {
+        async fn translate(&self, text: &str, _: &str, _: &str) -> MtResult<String> {
+            Ok(text.replace("joined the project", "est arrivé dans le projet"))
+        }
+
+        async fn translate_batch(
+            &self,
+            texts: &[String],
+            source_locale: &str,
+            target_locale: &str,
+        ) -> MtResult<Vec<String>> {
+            let mut results = Vec::new();
+            for text in texts {
+                results.push(self.translate(text, source_locale, target_locale).await?);
+            }
+            Ok(results)
+        }
+
+        async fn translate_batch_with_options(
+            &self,
+            texts: &[String],
+            source_locale: &str,
+            target_locale: &str,
+            options: &TranslationOptions,
+        ) -> MtResult<Vec<String>> {
+            let translated = self
+                .translate_batch(texts, source_locale, target_locale)
+                .await?;
+            Ok(match options.subject_gender {
+                Some(SubjectGender::Female) => translated
+                    .into_iter()
+                    .map(|t| t.replace("arrivé", "arrivée"))
+                    .collect(),
+                Some(SubjectGender::Unknown) => translated
+                    .into_iter()
+                    .map(|t| t.replace("arrivé", "arrivé·e"))
+                    .collect(),
+                _ => translated,
+            })
+        }
+
+        fn provider_name(&self) -> &str {
+            "Gendered"
+        }
+
+        fn supports_native_batch(&self) -> bool {
+            true
+        }
+    }
+
+    #[tokio::test]
+    async fn test_introduce_gender_axis() {
+        let ast = parse("$1 joined the project");
+        let wikitext = introduce_gender_axis(&ast, "$1", &GenderedProvider, "en", "fr")
+            .await
+            .unwrap();
+        assert_eq!(
+            wikitext,
+            "$1 est {{GENDER:$1|arrivé|arrivée|arrivé·e}} dans le projet"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_introduce_gender_axis_rejects_existing_variable() {
+        let ast = parse("{{GENDER:$1|He|She|They}} joined the project");
+        let result = introduce_gender_axis(&ast, "$1", &GenderedProvider, "en", "fr").await;
+        assert!(matches!(result, Err(MtError::ExpansionError(_))));
+    }
+
+    #[test]
+    fn test_split_option() {
+        assert_eq!(split_option(" 0=no files "), (" 0=", "no files", " "));
+        assert_eq!(split_option("$1 files"), ("", "$1 files", ""));
+        assert_eq!(split_option("   "), ("   ", "", ""));
+    }
+}
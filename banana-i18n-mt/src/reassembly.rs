@@ -21,15 +21,176 @@
 //! - `Reassembler._fold_strings()` - Extracts LCP/LCS and builds wikitext
 //! - Word boundary snapping (lines 278-298 in Python)
 
+use super::anchors::{AnchorMatch, find_anchors, replace_anchors};
 use super::data::{MessageContext, TranslationVariant};
 use super::error::{MtError, MtResult};
-use regex::Regex;
-use std::collections::HashMap;
-
-/// Consistency threshold for MT translation similarity
+use super::expansion::{choice_key, is_positional_selector, placeholder_indices, selector_of};
+use super::translator::normalize_locale;
+use super::typography::{apply_typography, normalize_quotes};
+use banana_i18n::ast::{AstNode, AstNodeList, Placeholder, Transclusion};
+use banana_i18n::parser::Parser;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use unicode_normalization::UnicodeNormalization;
+
+/// Default consistency threshold for MT translation similarity
 /// Below this threshold, we consider the MT output too inconsistent to reassemble
 const CONSISTENCY_THRESHOLD: f32 = 0.7;
 
+/// Characters the stable prefix and suffix are snapped to
+const WORD_BOUNDARIES: [char; 3] = [' ', '\t', '\n'];
+
+/// How reassembled output is checked against the source structure
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StructureCheck {
+    /// No re-parse validation (default)
+    #[default]
+    Off,
+    /// Re-parse the output and report mismatches as warnings
+    Warn,
+    /// Re-parse the output and fail with `ReassemblyError` on mismatch
+    Strict,
+}
+
+/// Where the neutral GENDER form goes in reassembled output
+///
+/// Expansion always translates GENDER forms in MediaWiki order (male,
+/// female, neutral); this only affects how the options are written back.
+///
+/// # Warning
+///
+/// MediaWiki reads GENDER options by position only: the first is always
+/// male, the second female and the third neutral. Output written with
+/// `First` is therefore *not* valid MediaWiki wikitext: `{{GENDER:$1|they|he|she}}`
+/// would show "they" to men and "he" to women. Only use `First` for a
+/// consumer with its own neutral-first convention, and keep such output out
+/// of MediaWiki message catalogs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GenderNeutralPlacement {
+    /// `{{GENDER:$1|male|female|neutral}}`, as MediaWiki expects (default)
+    #[default]
+    Last,
+    /// `{{GENDER:$1|neutral|male|female}}`, which MediaWiki misreads (see above)
+    First,
+}
+
+/// Memoized similarity scores for one reassembly
+///
+/// The consistency guard compares every member of every group, and when an
+/// axis does not change the translation (e.g. GENDER in a language without
+/// gendered verbs) the same pair of strings is compared once per group. The
+/// LCS behind `get_similarity()` is O(n·m), so repeated pairs are looked up
+/// instead. Keys are the hashes of both strings, in sorted order since the
+/// similarity is symmetric.
+#[derive(Debug, Default)]
+struct SimilarityCache {
+    scores: HashMap<(u64, u64), f32>,
+    /// Number of similarities actually computed
+    computed: usize,
+}
+
+impl SimilarityCache {
+    fn similarity(&mut self, a: &str, b: &str) -> f32 {
+        let (ha, hb) = (hash_str(a), hash_str(b));
+        let key = if ha <= hb { (ha, hb) } else { (hb, ha) };
+        if let Some(&score) = self.scores.get(&key) {
+            return score;
+        }
+        self.computed += 1;
+        let score = get_similarity(a, b);
+        self.scores.insert(key, score);
+        score
+    }
+}
+
+fn hash_str(text: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    text.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Reassembled wikitext together with any non-fatal warnings
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReassemblyOutput {
+    /// The reconstructed wikitext
+    pub wikitext: String,
+    /// Non-fatal problems found while reassembling
+    pub warnings: Vec<String>,
+}
+
+/// Locale-specific finishing step applied to reassembled wikitext
+pub type PostProcessor = Box<dyn Fn(String) -> String + Send + Sync>;
+
+/// Post-processors by lowercase locale code
+#[derive(Default)]
+struct PostProcessors(HashMap<String, PostProcessor>);
+
+impl fmt::Debug for PostProcessors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut locales: Vec<&String> = self.0.keys().collect();
+        locales.sort();
+        f.debug_tuple("PostProcessors").field(&locales).finish()
+    }
+}
+
+/// What to do about variants that fail the consistency check
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FixAction {
+    /// Only the outlier disagrees with the others: translate it again
+    RetranslateVariant,
+    /// The variants all disagree, so the forms likely just differ more than
+    /// usual in the target language; this threshold accepts them
+    LowerThreshold(f32),
+}
+
+/// Diagnosis of a consistency failure, from `Reassembler::suggest_fix()`
+#[derive(Debug, Clone, PartialEq)]
+pub struct FixSuggestion {
+    /// Variable whose variants disagree
+    pub var_id: String,
+    /// Index of the outlier in the variants passed in
+    pub outlier_index: usize,
+    /// State of the outlier variant
+    pub outlier_state: HashMap<String, usize>,
+    /// Translation of the outlier
+    pub outlier_text: String,
+    /// Translation of the variant the outlier is compared with
+    pub reference_text: String,
+    /// Similarity of the two translations, from 0.0 to 1.0
+    pub similarity: f32,
+    /// Suggested fix
+    pub action: FixAction,
+}
+
+impl fmt::Display for FixSuggestion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut state: Vec<String> = self
+            .outlier_state
+            .iter()
+            .map(|(var_id, idx)| format!("{}={}", var_id, idx))
+            .collect();
+        state.sort();
+        writeln!(
+            f,
+            "Variant {} ({}) of {} is {:.1}% similar to the others:",
+            self.outlier_index,
+            state.join(", "),
+            self.var_id,
+            self.similarity * 100.0
+        )?;
+        writeln!(f, "  outlier:   {}", self.outlier_text)?;
+        writeln!(f, "  reference: {}", self.reference_text)?;
+        match self.action {
+            FixAction::RetranslateVariant => write!(f, "Re-translate this variant"),
+            FixAction::LowerThreshold(threshold) => {
+                write!(f, "Lower the consistency threshold to {:.2}", threshold)
+            }
+        }
+    }
+}
+
 /// Reassembler handles reconstruction of wikitext from translated variants
 ///
 /// This struct implements the axis-collapsing algorithm from the Python reference,
@@ -39,12 +200,237 @@ const CONSISTENCY_THRESHOLD: f32 = 0.7;
 pub struct Reassembler {
     /// Maps variable IDs to their magic word type (e.g., {"$1": "GENDER", "$2": "PLURAL"})
     variable_types: HashMap<String, String>,
+    /// Link targets and URLs to re-insert (Nth entry replaces anchor 888000 + N)
+    link_targets: Vec<String>,
+    /// Target locale of the translation, used by locale-aware post-processing
+    target_locale: Option<String>,
+    /// Whether to apply locale typography rules to the final output
+    typography: bool,
+    /// Whether to re-parse the output and compare its magic word structure
+    structure_check: StructureCheck,
+    /// Order in which axes are collapsed (variable IDs by AST position)
+    axis_order: Vec<String>,
+    /// Whether to restore the source's leading letter case in each variant
+    preserve_case: bool,
+    /// Whether leftover variants after collapsing are an error
+    strict_collapse: bool,
+    /// Position of the neutral form in three-option GENDER output
+    gender_neutral: GenderNeutralPlacement,
+    /// Whether a neutral GENDER form identical to the male form is dropped
+    drop_identical_neutral: bool,
+    /// Magic word names as written in the source, per variable (e.g. {"$1": "plural"})
+    magic_word_names: HashMap<String, String>,
+    /// Whether magic words keep the source's casing instead of uppercase
+    source_magic_word_case: bool,
+    /// Whether translations are NFC-normalized before collapsing
+    nfc: bool,
+    /// Minimum similarity between variants of one axis
+    consistency_threshold: f32,
+    /// Finishing steps for the output of particular target locales
+    post_processors: PostProcessors,
+    /// Placeholders translated as sample text, expected back as `$N`
+    translated_placeholders: Vec<usize>,
 }
 
 impl Reassembler {
     /// Create a new reassembler with variable type information
     pub fn new(variable_types: HashMap<String, String>) -> Self {
-        Self { variable_types }
+        Self {
+            variable_types,
+            link_targets: Vec::new(),
+            target_locale: None,
+            typography: false,
+            structure_check: StructureCheck::Off,
+            axis_order: Vec::new(),
+            preserve_case: false,
+            strict_collapse: false,
+            gender_neutral: GenderNeutralPlacement::Last,
+            drop_identical_neutral: false,
+            magic_word_names: HashMap::new(),
+            source_magic_word_case: false,
+            nfc: false,
+            consistency_threshold: CONSISTENCY_THRESHOLD,
+            post_processors: PostProcessors::default(),
+            translated_placeholders: Vec::new(),
+        }
+    }
+
+    /// Create a reassembler carrying all metadata recorded in a MessageContext
+    ///
+    /// Prefer this over `new()` when the context came from `prepare_for_translation()`,
+    /// so link targets held constant during translation are re-inserted.
+    pub fn from_context(context: &MessageContext) -> Self {
+        Self::new(context.variable_types.clone())
+            .with_link_targets(context.link_targets.clone())
+            .with_axis_order(context.variable_order.clone())
+            .with_magic_word_names(context.magic_word_names.clone())
+            .with_translated_placeholders(context.translated_placeholders.clone())
+    }
+
+    /// Set the order in which axes are collapsed
+    ///
+    /// Pass variable IDs by position in the source AST. Axes not listed are
+    /// collapsed afterwards in numeric order ($1, $2, ..., $10), so the
+    /// collapse order never depends on hash map iteration.
+    pub fn with_axis_order(mut self, axis_order: Vec<String>) -> Self {
+        self.axis_order = axis_order;
+        self
+    }
+
+    /// Set the placeholders that were sent to MT as sample text
+    ///
+    /// `reassemble_detailed()` warns about each of them missing from the
+    /// output, since its translated sample text now stands where `$N` was.
+    pub fn with_translated_placeholders(mut self, indices: Vec<usize>) -> Self {
+        self.translated_placeholders = indices;
+        self
+    }
+
+    /// Set the link targets to re-insert in place of link anchors
+    pub fn with_link_targets(mut self, link_targets: Vec<String>) -> Self {
+        self.link_targets = link_targets;
+        self
+    }
+
+    /// Set the target locale of the translation
+    pub fn with_target_locale(mut self, locale: &str) -> Self {
+        self.target_locale = Some(locale.to_string());
+        self
+    }
+
+    /// Enable locale typography post-processing (see `apply_typography()`)
+    ///
+    /// Quotation marks are normalized in every variant before collapsing
+    /// (see `normalize_quotes()`), so variants that came back with different
+    /// quote styles still agree; spacing rules are applied after placeholders
+    /// are restored. Has no effect unless a target locale is set.
+    pub fn with_typography(mut self, enabled: bool) -> Self {
+        self.typography = enabled;
+        self
+    }
+
+    /// Re-parse the output and compare it against the source magic word structure
+    ///
+    /// Catches translations that inject pipes or braces, which would change
+    /// the number of options or break a magic word when the output is parsed.
+    pub fn with_structure_check(mut self, check: StructureCheck) -> Self {
+        self.structure_check = check;
+        self
+    }
+
+    /// Restore the source text's leading letter case in each translated variant
+    ///
+    /// MT engines sometimes lowercase the first word of one variant but not
+    /// the others (e.g. "il est parti" next to "Elle est partie"), which makes
+    /// the variants look less alike and leaks the casing difference into the
+    /// magic word options. When enabled, a variant whose source starts with a
+    /// cased letter gets its translation's first letter upper- or lowercased to
+    /// match before collapsing. Variants starting with a placeholder or
+    /// punctuation are left alone.
+    ///
+    /// For a target locale that capitalizes every noun (German, Luxembourgish)
+    /// a leading capital does not mark a sentence start, so translations are
+    /// only ever uppercased: "files deleted" must stay "Dateien gelöscht".
+    pub fn with_preserve_case_from_source(mut self, enabled: bool) -> Self {
+        self.preserve_case = enabled;
+        self
+    }
+
+    /// Fail when collapsing leaves more than one variant (off by default)
+    ///
+    /// Inconsistent state maps (e.g. a variant with a variable the others
+    /// lack) keep collapsing from reducing the variants to one. By default
+    /// `reassemble_detailed()` then uses the leftover variant agreeing most
+    /// with the others and reports a warning; in strict mode this is a
+    /// `ReassemblyError`. `reassemble()` and `reassemble_to_ast()` cannot
+    /// report the warning, so they are always strict.
+    pub fn with_strict_collapse(mut self, enabled: bool) -> Self {
+        self.strict_collapse = enabled;
+        self
+    }
+
+    /// Set where the neutral form goes in three-option GENDER output
+    ///
+    /// Anything but the default `GenderNeutralPlacement::Last` produces
+    /// GENDER blocks that MediaWiki reads with the wrong genders; see
+    /// `GenderNeutralPlacement` before changing it.
+    pub fn with_gender_neutral_placement(mut self, placement: GenderNeutralPlacement) -> Self {
+        self.gender_neutral = placement;
+        self
+    }
+
+    /// Drop the neutral GENDER form when its translation equals the male one
+    ///
+    /// Languages without a neutral gender usually translate the neutral form
+    /// like the male form; MediaWiki falls back to the first option for an
+    /// unknown gender anyway, so the output becomes a two-option
+    /// `{{GENDER:$1|male|female}}`. Off by default.
+    pub fn with_drop_identical_neutral(mut self, enabled: bool) -> Self {
+        self.drop_identical_neutral = enabled;
+        self
+    }
+
+    /// Set the magic word names as written in the source, per variable
+    ///
+    /// Only used with `with_source_magic_word_case()`. `from_context()` sets
+    /// them from `MessageContext::magic_word_names`.
+    pub fn with_magic_word_names(mut self, names: HashMap<String, String>) -> Self {
+        self.magic_word_names = names;
+        self
+    }
+
+    /// Write magic words in the casing the source used (off by default)
+    ///
+    /// By default magic words are written in canonical uppercase
+    /// (`{{PLURAL:...}}`). When enabled, a variable whose source wrote it as
+    /// e.g. `{{plural:$1|...}}` is reassembled as `{{plural:...}}`, keeping
+    /// diffs against the catalog minimal. Source names that are aliases
+    /// rather than a different casing of the canonical name (such as a
+    /// localized magic word) are not carried over to the target.
+    pub fn with_source_magic_word_case(mut self, enabled: bool) -> Self {
+        self.source_magic_word_case = enabled;
+        self
+    }
+
+    /// NFC-normalize every translation before collapsing (off by default)
+    ///
+    /// MT engines mix precomposed and combining forms ("é" as U+00E9 or
+    /// "e" + U+0301). Similarity checks always compare NFC forms, but the
+    /// common prefix and suffix are found byte by byte, so variants in mixed
+    /// forms would move identical words into the options. When enabled the
+    /// variants are normalized first and the output is NFC.
+    pub fn with_unicode_normalization(mut self, enabled: bool) -> Self {
+        self.nfc = enabled;
+        self
+    }
+
+    /// Set the minimum similarity between variants of one axis (default 0.7)
+    ///
+    /// Variants less similar than this fail with a `ConsistencyError`, since
+    /// MT likely translated one of them too freely. `suggest_fix()` tells
+    /// whether a lower threshold is reasonable for a failing message.
+    pub fn with_consistency_threshold(mut self, threshold: f32) -> Self {
+        self.consistency_threshold = threshold;
+        self
+    }
+
+    /// Register a finishing step for output in one target locale
+    ///
+    /// The hook receives the final wikitext, after placeholders and link
+    /// targets are restored and typography is applied, e.g. to insert word
+    /// joiners in Thai. It runs when the target locale set with
+    /// `with_target_locale()` is `locale` or a regional variant of it ("de"
+    /// also covers "de-AT", unless "de-AT" has a hook of its own). Like
+    /// typography, it only applies to the string API.
+    ///
+    /// # Arguments
+    /// * `locale` - Target locale code (e.g., "de")
+    /// * `processor` - Function from the reassembled wikitext to the final text
+    pub fn with_post_processor(mut self, locale: &str, processor: PostProcessor) -> Self {
+        self.post_processors
+            .0
+            .insert(locale.to_lowercase().replace('_', "-"), processor);
+        self
     }
 
     /// Main reassembly entry point - collapses all dimensions
@@ -62,7 +448,7 @@ impl Reassembler {
     ///
     /// # Algorithm (matches Python lines 204-218)
     /// ```text
-    /// 1. Determine axes to collapse (all variable IDs from first variant)
+    /// 1. Determine axes to collapse (all variable IDs, in a deterministic order)
     /// 2. For each axis:
     ///    - Group variants by all other dimensions  
     ///    - Collapse the current axis using LCP/LCS + word boundary snapping
@@ -70,44 +456,525 @@ impl Reassembler {
     /// 3. Restore placeholders (777001 → $1)
     /// ```
     pub fn reassemble(&self, variants: Vec<TranslationVariant>) -> MtResult<String> {
+        self.reassemble_checked(variants, None)
+    }
+
+    /// Reassemble from raw (state, translated text) pairs
+    ///
+    /// A thinner entry point for callers with their own expansion, who would
+    /// otherwise build `TranslationVariant`s by hand. Each state maps variable
+    /// IDs to option indices, as in `TranslationVariant::state`. Source texts
+    /// are not known here, so `with_preserve_case_from_source()` has no effect.
+    ///
+    /// # Arguments
+    /// * `variants` - One (state, translated text) pair per variant
+    ///
+    /// # Returns
+    /// * `Ok(String)` - Reconstructed wikitext
+    /// * `Err(MtError)` - If inconsistency detected or reassembly fails
+    pub fn reassemble_pairs(
+        &self,
+        variants: &[(HashMap<String, usize>, String)],
+    ) -> MtResult<String> {
+        self.reassemble(
+            variants
+                .iter()
+                .map(|(state, text)| {
+                    TranslationVariant::with_translation(state.clone(), String::new(), text.clone())
+                })
+                .collect(),
+        )
+    }
+
+    /// Reassemble variants, also returning non-fatal warnings
+    ///
+    /// Same as `reassemble()`, but structure check mismatches found in
+    /// `StructureCheck::Warn` mode are returned instead of discarded, and
+    /// variants left over after collapsing are resolved with a warning
+    /// unless `with_strict_collapse()` is enabled. Placeholders translated as
+    /// sample text (see `with_translated_placeholders()`) that are missing
+    /// from the output are reported as well.
+    pub fn reassemble_detailed(
+        &self,
+        variants: Vec<TranslationVariant>,
+    ) -> MtResult<ReassemblyOutput> {
+        let mut warnings = Vec::new();
+        let wikitext = self.reassemble_checked(variants, Some(&mut warnings))?;
+        Ok(ReassemblyOutput { wikitext, warnings })
+    }
+
+    /// Reassemble and run the structure check
+    ///
+    /// Warnings are pushed to `warnings`; without it they are discarded and
+    /// leftover variants after collapsing are an error.
+    fn reassemble_checked(
+        &self,
+        variants: Vec<TranslationVariant>,
+        mut warnings: Option<&mut Vec<String>>,
+    ) -> MtResult<String> {
+        // Number of options each variable should end up with
+        let mut axis_sizes: HashMap<String, usize> = HashMap::new();
+        for variant in &variants {
+            for (var_id, &idx) in &variant.state {
+                let size = axis_sizes.entry(var_id.clone()).or_insert(0);
+                *size = (*size).max(idx + 1);
+            }
+        }
+
+        let variants = self.prepare_variants(variants);
+        let wikitext = self.collapse_all(
+            variants,
+            &mut SimilarityCache::default(),
+            warnings.as_deref_mut(),
+        )?;
+
+        if self.structure_check != StructureCheck::Off {
+            let problems = self.check_structure(&wikitext, &axis_sizes);
+            if !problems.is_empty() {
+                if self.structure_check == StructureCheck::Strict {
+                    return Err(MtError::ReassemblyError(
+                        format!(
+                            "Reassembled wikitext does not match source structure: {}",
+                            problems.join("; ")
+                        )
+                        .into(),
+                    ));
+                }
+                if let Some(warnings) = warnings.as_deref_mut() {
+                    warnings.extend(problems);
+                }
+            }
+        }
+
+        if let Some(warnings) = warnings {
+            let present = placeholder_indices(&wikitext);
+            for index in &self.translated_placeholders {
+                if !present.contains(index) {
+                    warnings.push(format!(
+                        "Placeholder ${} was translated as sample text and is missing from the output",
+                        index
+                    ));
+                }
+            }
+        }
+
+        Ok(wikitext)
+    }
+
+    /// Diagnose variants of one axis that fail the consistency check
+    ///
+    /// Translations are compared pairwise, skipping pairs whose sources
+    /// already differ that much, as the check itself does. The outlier is the
+    /// variant least similar to the others on average. If the remaining
+    /// variants agree, re-translating the outlier is suggested; if they
+    /// disagree as well, the threshold is likely too strict for this message
+    /// and the highest threshold that accepts it is suggested.
+    ///
+    /// # Arguments
+    /// * `members` - Variants that differ only in `var_id`
+    /// * `var_id` - Variable ID of the axis (e.g., "$1")
+    ///
+    /// # Returns
+    /// The suggestion, or `None` if the variants pass the consistency check
+    /// (`#if` branches always do)
+    pub fn suggest_fix(
+        &self,
+        members: &[TranslationVariant],
+        var_id: &str,
+    ) -> Option<FixSuggestion> {
+        let is_if = self
+            .variable_types
+            .get(var_id)
+            .is_some_and(|var_type| var_type.eq_ignore_ascii_case("#if"));
+        if is_if {
+            return None;
+        }
+
+        let count = members.len();
+        let mut scores: Vec<Vec<Option<f32>>> = vec![vec![None; count]; count];
+        for i in 0..count {
+            for j in i + 1..count {
+                let (a, b) = (&members[i].source_text, &members[j].source_text);
+                if a != b && get_similarity(a, b) < self.consistency_threshold {
+                    continue;
+                }
+                let sim = get_similarity(&members[i].translated_text, &members[j].translated_text);
+                scores[i][j] = Some(sim);
+                scores[j][i] = Some(sim);
+            }
+        }
+        let failing =
+            |i: usize, j: usize| scores[i][j].is_some_and(|s| s < self.consistency_threshold);
+        if !(0..count).any(|i| (0..count).any(|j| failing(i, j))) {
+            return None;
+        }
+
+        let mean = |i: usize| {
+            let row: Vec<f32> = scores[i].iter().flatten().copied().collect();
+            if row.is_empty() {
+                1.0
+            } else {
+                row.iter().sum::<f32>() / row.len() as f32
+            }
+        };
+        // Ties go to the later variant; the check compares against the first
+        let outlier = (0..count)
+            .rev()
+            .min_by(|&a, &b| mean(a).total_cmp(&mean(b)))?;
+        let reference = (0..count)
+            .rev()
+            .filter(|&i| scores[outlier][i].is_some())
+            .max_by(|&a, &b| mean(a).total_cmp(&mean(b)))?;
+
+        let others_agree = (0..count)
+            .filter(|&i| i != outlier)
+            .all(|i| (0..count).filter(|&j| j != outlier).all(|j| !failing(i, j)));
+        let action = if others_agree {
+            FixAction::RetranslateVariant
+        } else {
+            let lowest = scores
+                .iter()
+                .flatten()
+                .flatten()
+                .copied()
+                .fold(1.0, f32::min);
+            FixAction::LowerThreshold((lowest * 100.0).floor() / 100.0)
+        };
+
+        Some(FixSuggestion {
+            var_id: var_id.to_string(),
+            outlier_index: outlier,
+            outlier_state: members[outlier].state.clone(),
+            outlier_text: members[outlier].translated_text.clone(),
+            reference_text: members[reference].translated_text.clone(),
+            similarity: scores[outlier][reference].unwrap_or(0.0),
+            action,
+        })
+    }
+
+    /// Reassemble variants into AST nodes instead of a string
+    ///
+    /// The result has the structure reassembly built: text as `Text` nodes,
+    /// placeholders as `Placeholder` nodes and each top-level magic word as a
+    /// `Transclusion` (whose options, like any parsed transclusion, are
+    /// wikitext strings and may contain nested magic words). Transclusions
+    /// are taken from the collapse itself, so the output is not re-parsed.
+    /// `ast_to_wikitext()` of the result equals `reassemble()`'s output,
+    /// except that typography spacing rules and the structure check only
+    /// apply to the string API.
+    ///
+    /// # Arguments
+    /// * `variants` - All translated variants with their state information
+    ///
+    /// # Returns
+    /// * `Ok(AstNodeList)` - The reconstructed message
+    /// * `Err(MtError)` - If inconsistency detected or reassembly fails
+    pub fn reassemble_to_ast(&self, variants: Vec<TranslationVariant>) -> MtResult<AstNodeList> {
+        let variants = self.prepare_variants(variants);
+        let mut transclusions = Vec::new();
+        let anchored = self.collapse_anchored(
+            variants,
+            &mut SimilarityCache::default(),
+            None,
+            &mut transclusions,
+        )?;
+        Ok(self.anchored_to_ast(&anchored, &transclusions))
+    }
+
+    /// Apply the per-variant normalizations enabled on this reassembler
+    fn prepare_variants(&self, variants: Vec<TranslationVariant>) -> Vec<TranslationVariant> {
+        let quote_locale = self.target_locale.as_deref().filter(|_| self.typography);
+        if !self.preserve_case && !self.nfc && quote_locale.is_none() {
+            return variants;
+        }
+        let lowercase = !self.target_locale.as_deref().is_some_and(|locale| {
+            NOUN_CAPITALIZING_LANGUAGES
+                .contains(&normalize_locale(&locale.replace('_', "-")).as_str())
+        });
+        variants
+            .into_iter()
+            .map(|mut variant| {
+                if self.nfc {
+                    variant.translated_text = variant.translated_text.nfc().collect();
+                }
+                if self.preserve_case {
+                    variant.translated_text = match_leading_case(
+                        &variant.source_text,
+                        &variant.translated_text,
+                        lowercase,
+                    );
+                }
+                if let Some(locale) = quote_locale {
+                    variant.translated_text = normalize_quotes(&variant.translated_text, locale);
+                }
+                variant
+            })
+            .collect()
+    }
+
+    /// Split collapsed text (anchors still in place) into AST nodes
+    ///
+    /// `transclusions` are all magic words built while collapsing; the
+    /// longest one starting at a position is the outermost there.
+    fn anchored_to_ast(&self, anchored: &str, transclusions: &[Transclusion]) -> AstNodeList {
+        let rendered: Vec<(String, &Transclusion)> = transclusions
+            .iter()
+            .map(|trans| (render_transclusion(trans), trans))
+            .collect();
+
+        let mut nodes = Vec::new();
+        let mut text_start = 0;
+        let mut pos = 0;
+        while pos < anchored.len() {
+            let rest = &anchored[pos..];
+            let outermost = rest
+                .starts_with("{{")
+                .then(|| {
+                    rendered
+                        .iter()
+                        .filter(|(wikitext, _)| rest.starts_with(wikitext.as_str()))
+                        .max_by_key(|(wikitext, _)| wikitext.len())
+                })
+                .flatten();
+            match outermost {
+                Some((wikitext, trans)) => {
+                    self.push_text_nodes(&anchored[text_start..pos], &mut nodes);
+                    nodes.push(AstNode::Transclusion(Transclusion {
+                        name: trans.name.clone(),
+                        param: trans.param.clone(),
+                        options: trans
+                            .options
+                            .iter()
+                            .map(|option| self.restore_placeholders(option))
+                            .collect(),
+                    }));
+                    pos += wikitext.len();
+                    text_start = pos;
+                }
+                None => pos += rest.chars().next().map_or(1, char::len_utf8),
+            }
+        }
+        self.push_text_nodes(&anchored[text_start..], &mut nodes);
+        nodes
+    }
+
+    /// Push text as `Text` and `Placeholder` nodes, restoring link targets
+    fn push_text_nodes(&self, text: &str, nodes: &mut AstNodeList) {
+        let mut last = 0;
+        for anchor in find_anchors(text).iter().filter(|anchor| !anchor.is_link()) {
+            if anchor.start > last {
+                nodes.push(AstNode::Text(
+                    self.restore_link_targets(&text[last..anchor.start]),
+                ));
+            }
+            nodes.push(AstNode::Placeholder(Placeholder {
+                index: anchor.index(),
+            }));
+            last = anchor.end;
+        }
+        if last < text.len() {
+            nodes.push(AstNode::Text(self.restore_link_targets(&text[last..])));
+        }
+    }
+
+    /// Collapse all axes and produce the final wikitext
+    fn collapse_all(
+        &self,
+        variants: Vec<TranslationVariant>,
+        cache: &mut SimilarityCache,
+        warnings: Option<&mut Vec<String>>,
+    ) -> MtResult<String> {
+        self.collapse_anchored(variants, cache, warnings, &mut Vec::new())
+            .map(|text| self.finish(&text))
+    }
+
+    /// Collapse all axes, leaving anchors in place
+    ///
+    /// Every magic word built along the way is appended to `transclusions`.
+    /// Leftover variants after collapsing are resolved with a warning only
+    /// when `warnings` is given and strict collapsing is off.
+    fn collapse_anchored(
+        &self,
+        variants: Vec<TranslationVariant>,
+        cache: &mut SimilarityCache,
+        warnings: Option<&mut Vec<String>>,
+        transclusions: &mut Vec<Transclusion>,
+    ) -> MtResult<String> {
         if variants.is_empty() {
-            return Err(MtError::ReassemblyError(
-                "No variants to reassemble".to_string(),
-            ));
+            return Err(MtError::ReassemblyError("No variants to reassemble".into()));
         }
 
         // Handle single variant case (no magic words)
         if variants.len() == 1 {
-            let final_text = &variants[0].translated_text;
-            return Ok(self.restore_placeholders(final_text));
+            return Ok(variants[0].translated_text.clone());
         }
 
         // 1. Determine the axes to collapse (Python line 209)
         let axes: Vec<String> = if variants[0].state.is_empty() {
             // No state means no magic words
-            let final_text = &variants[0].translated_text;
-            return Ok(self.restore_placeholders(final_text));
+            return Ok(variants[0].translated_text.clone());
         } else {
-            variants[0].state.keys().cloned().collect()
+            self.ordered_axes(&variants[0])
         };
 
         // 2. Collapse each axis one by one (Python lines 212-214)
         let mut current_set = variants;
         for axis in &axes {
-            current_set = self.collapse_axis(current_set, axis)?;
+            current_set = self.collapse_axis(current_set, axis, cache, transclusions)?;
         }
 
         // 3. Should have single variant left after all collapses
-        if current_set.len() != 1 {
-            return Err(MtError::ReassemblyError(format!(
-                "Expected 1 variant after collapse, got {}",
-                current_set.len()
-            )));
+        let best = match warnings {
+            _ if current_set.len() == 1 => 0,
+            Some(warnings) if !self.strict_collapse => {
+                let best = most_agreeing(&current_set, cache);
+                warnings.push(format!(
+                    "Expected 1 variant after collapse, got {}; using \"{}\"",
+                    current_set.len(),
+                    current_set[best].translated_text
+                ));
+                best
+            }
+            _ => {
+                return Err(MtError::ReassemblyError(
+                    format!(
+                        "Expected 1 variant after collapse, got {}",
+                        current_set.len()
+                    )
+                    .into(),
+                ));
+            }
+        };
+
+        // 4. Placeholders (777001 → $1) are restored by the caller - Python line 217
+        Ok(current_set.swap_remove(best).translated_text)
+    }
+
+    /// Axes of a variant in collapse order: `axis_order` first, then numeric order
+    fn ordered_axes(&self, variant: &TranslationVariant) -> Vec<String> {
+        let mut axes: Vec<String> = self
+            .axis_order
+            .iter()
+            .filter(|id| variant.state.contains_key(*id))
+            .cloned()
+            .collect();
+
+        let mut rest: Vec<String> = variant
+            .state
+            .keys()
+            .filter(|id| !axes.contains(id))
+            .cloned()
+            .collect();
+        rest.sort_by_key(|id| {
+            let number = id
+                .trim_start_matches('$')
+                .parse::<usize>()
+                .unwrap_or(usize::MAX);
+            (number, id.clone())
+        });
+
+        axes.extend(rest);
+        axes
+    }
+
+    /// Re-parse reassembled wikitext and compare with the expected structure
+    ///
+    /// Every magic word must control a known variable with its source type and
+    /// must not have more options than that variable has forms. Fewer options
+    /// are tolerated because the parser drops empty options. Braces left over
+    /// in plain text indicate a magic word the translation broke.
+    fn check_structure(&self, wikitext: &str, axis_sizes: &HashMap<String, usize>) -> Vec<String> {
+        let mut problems = Vec::new();
+        let ast = Parser::new(wikitext).parse();
+        self.check_nodes(&ast, axis_sizes, &mut problems);
+        problems
+    }
+
+    fn check_nodes(
+        &self,
+        ast: &AstNodeList,
+        axis_sizes: &HashMap<String, usize>,
+        problems: &mut Vec<String>,
+    ) {
+        for node in ast {
+            match node {
+                AstNode::Transclusion(trans) => {
+                    let name = trans.name.to_uppercase();
+                    // A parameterless word may stand for any positional selector
+                    let var_id = if trans.param.trim().is_empty() {
+                        self.variable_types
+                            .iter()
+                            .filter(|(id, kind)| {
+                                is_positional_selector(selector_of(id))
+                                    && kind.eq_ignore_ascii_case(&name)
+                            })
+                            .map(|(id, _)| id.clone())
+                            .max_by_key(|id| axis_sizes.get(id).copied().unwrap_or(0))
+                            .unwrap_or_default()
+                    } else {
+                        choice_key(&name, trans.param.clone())
+                    };
+                    match self.variable_types.get(&var_id) {
+                        Some(expected) if expected.eq_ignore_ascii_case(&name) => {
+                            let size = axis_sizes.get(&var_id).copied().unwrap_or(0);
+                            if trans.options.len() > size {
+                                problems.push(format!(
+                                    "{{{{{}:{}}}}} has {} options, expected {}",
+                                    name,
+                                    trans.param,
+                                    trans.options.len(),
+                                    size
+                                ));
+                            }
+                        }
+                        Some(expected) => problems.push(format!(
+                            "{} is a {} variable but was reassembled as {}",
+                            trans.param, expected, name
+                        )),
+                        None => problems.push(format!(
+                            "Unexpected magic word {{{{{}:{}}}}}",
+                            name, trans.param
+                        )),
+                    }
+
+                    // Nested magic words live inside the options
+                    for option in &trans.options {
+                        let nested = Parser::new(option).parse();
+                        self.check_nodes(&nested, axis_sizes, problems);
+                    }
+                }
+                AstNode::Text(text) if text.contains("{{") || text.contains("}}") => {
+                    problems.push(format!("Unbalanced braces in output: '{}'", text));
+                }
+                _ => {}
+            }
         }
+    }
+
+    /// Turn the fully collapsed text into final wikitext
+    ///
+    /// Restores placeholders and link targets, then applies optional
+    /// locale post-processing.
+    fn finish(&self, text: &str) -> String {
+        let restored = self.restore_placeholders(text);
+        let Some(locale) = &self.target_locale else {
+            return restored;
+        };
+        let typeset = if self.typography {
+            apply_typography(&restored, locale)
+        } else {
+            restored
+        };
 
-        // 4. Restore placeholders (777001 → $1) - Python line 217
-        let final_text = &current_set[0].translated_text;
-        Ok(self.restore_placeholders(final_text))
+        let code = locale.to_lowercase().replace('_', "-");
+        let processors = &self.post_processors.0;
+        match processors
+            .get(&code)
+            .or_else(|| processors.get(&normalize_locale(&code)))
+        {
+            Some(processor) => processor(typeset),
+            None => typeset,
+        }
     }
 
     /// Collapse one axis by grouping variants and folding strings
@@ -119,6 +986,8 @@ impl Reassembler {
     /// # Arguments
     /// * `variants` - Current set of variants to collapse
     /// * `axis` - The variable ID to collapse (e.g., "$1", "$2")
+    /// * `cache` - Similarity scores shared by all axes of this reassembly
+    /// * `transclusions` - Receives the magic words built for this axis
     ///
     /// # Returns
     /// * `Ok(Vec<TranslationVariant>)` - New set with this axis collapsed
@@ -127,9 +996,12 @@ impl Reassembler {
         &self,
         variants: Vec<TranslationVariant>,
         axis: &str,
+        cache: &mut SimilarityCache,
+        transclusions: &mut Vec<Transclusion>,
     ) -> MtResult<Vec<TranslationVariant>> {
         // Group variants by all dimensions EXCEPT the current axis (Python lines 225-231)
-        let mut groups: HashMap<Vec<(String, usize)>, Vec<TranslationVariant>> = HashMap::new();
+        // BTreeMap keeps group order stable across runs
+        let mut groups: BTreeMap<Vec<(String, usize)>, Vec<TranslationVariant>> = BTreeMap::new();
 
         for variant in variants {
             // Create a key from all state dimensions except the axis we're collapsing
@@ -155,7 +1027,7 @@ impl Reassembler {
             sorted_members.sort_by_key(|v| v.state.get(axis).copied().unwrap_or(0));
 
             // Perform the fold using LCP/LCS (Python line 239)
-            let new_text = self.fold_strings(&sorted_members, axis)?;
+            let new_text = self.fold_strings(&sorted_members, axis, cache, transclusions)?;
 
             // Create a new "virtual" variant for the next iteration (Python lines 242-247)
             let new_state: HashMap<String, usize> = other_dims.into_iter().collect();
@@ -163,6 +1035,7 @@ impl Reassembler {
                 state: new_state,
                 source_text: String::new(), // Not needed for virtual variants
                 translated_text: new_text,
+                pinned: false,
             });
         }
 
@@ -178,11 +1051,19 @@ impl Reassembler {
     /// # Arguments
     /// * `members` - Variants in this group (sorted by axis value)
     /// * `var_id` - Variable ID being collapsed (e.g., "$1")
+    /// * `cache` - Similarity scores for the consistency check
+    /// * `transclusions` - Receives the magic word built, if any
     ///
     /// # Returns
     /// * `Ok(String)` - Collapsed text with wikitext magic word syntax
     /// * `Err(MtError)` - If consistency check fails
-    fn fold_strings(&self, members: &[TranslationVariant], var_id: &str) -> MtResult<String> {
+    fn fold_strings(
+        &self,
+        members: &[TranslationVariant],
+        var_id: &str,
+        cache: &mut SimilarityCache,
+        transclusions: &mut Vec<Transclusion>,
+    ) -> MtResult<String> {
         let texts: Vec<String> = members.iter().map(|m| m.translated_text.clone()).collect();
 
         // If all texts are identical, no magic word needed (Python line 257-259)
@@ -196,17 +1077,30 @@ impl Reassembler {
         }
 
         // === CONSISTENCY GUARD === (Python lines 263-272)
-        // Check similarity between variants - if too different, MT likely hallucinated
-        for i in 1..texts.len() {
-            let sim = get_similarity(&texts[0], &texts[i]);
-            if sim < CONSISTENCY_THRESHOLD {
+        // Check similarity between variants - if too different, MT likely hallucinated.
+        // The branches of an #if are different texts by design, so they are exempt.
+        let is_if = self
+            .variable_types
+            .get(var_id)
+            .is_some_and(|var_type| var_type.eq_ignore_ascii_case("#if"));
+        // So are variants whose sources already differ that much, such as a
+        // message that is nothing but the magic word ("{{GENDER:$1|He|She}}").
+        let sources: Vec<&str> = members.iter().map(|m| m.source_text.as_str()).collect();
+        for i in (1..texts.len()).filter(|_| !is_if) {
+            if sources[0] != sources[i]
+                && cache.similarity(sources[0], sources[i]) < self.consistency_threshold
+            {
+                continue;
+            }
+            let sim = cache.similarity(&texts[0], &texts[i]);
+            if sim < self.consistency_threshold {
                 return Err(MtError::ConsistencyError(format!(
                     "MT Inconsistency detected on {}. Variants are too different (similarity: {:.1}%):\n1: {}\n2: {}",
                     var_id,
                     sim * 100.0,
                     texts[0],
                     texts[i]
-                )));
+                ).into()));
             }
         }
 
@@ -215,26 +1109,34 @@ impl Reassembler {
         let raw_suffix = get_lcs(&texts);
 
         // Snap prefix BACK to last word boundary (Python lines 278-285)
-        let prefix = if raw_prefix.is_empty() || raw_prefix.ends_with(' ') {
+        // Spaces, tabs and newlines are boundaries, so a magic word at the start
+        // of a line leaves the lines before it in the stable prefix. A run of
+        // whitespace ends up whole in the prefix or suffix, byte for byte,
+        // since the prefix snaps to its last character and the suffix to its
+        // first. Soft hyphens
+        // (U+00AD) and <wbr> hints sit inside words, so they stay in the stable
+        // prefix/suffix or move into the options together with their whole
+        // word, and are never dropped
+        let prefix = if raw_prefix.is_empty() || raw_prefix.ends_with(WORD_BOUNDARIES) {
             raw_prefix
         } else {
-            // Find last space and include it
-            if let Some(last_space) = raw_prefix.rfind(' ') {
-                raw_prefix[..=last_space].to_string()
+            // Find last boundary and include it
+            if let Some(last_boundary) = raw_prefix.rfind(WORD_BOUNDARIES) {
+                raw_prefix[..=last_boundary].to_string()
             } else {
-                String::new() // No space found, no prefix
+                String::new() // No boundary found, no prefix
             }
         };
 
         // Snap suffix FORWARD to first word boundary (Python lines 287-297)
-        let suffix = if raw_suffix.is_empty() || raw_suffix.starts_with(' ') {
+        let suffix = if raw_suffix.is_empty() || raw_suffix.starts_with(WORD_BOUNDARIES) {
             raw_suffix
         } else {
-            // Find first space
-            if let Some(first_space) = raw_suffix.find(' ') {
-                raw_suffix[first_space..].to_string()
+            // Find first boundary
+            if let Some(first_boundary) = raw_suffix.find(WORD_BOUNDARIES) {
+                raw_suffix[first_boundary..].to_string()
             } else {
-                String::new() // No space found, no suffix
+                String::new() // No boundary found, no suffix
             }
         };
 
@@ -257,35 +1159,145 @@ impl Reassembler {
         }
 
         // Get tag type and construct wikitext (Python lines 307-311)
-        let tag_type = self
+        let mut tag_type = self
             .variable_types
             .get(var_id)
             .cloned()
             .unwrap_or_else(|| "PLURAL".to_string());
+        if self.source_magic_word_case
+            && let Some(name) = self.magic_word_names.get(var_id)
+            && name.eq_ignore_ascii_case(&tag_type)
+        {
+            tag_type = name.clone();
+        }
+
+        if tag_type.eq_ignore_ascii_case("GENDER") && middles.len() == 3 {
+            if self.drop_identical_neutral && middles[2] == middles[0] {
+                middles.truncate(2);
+            } else if self.gender_neutral == GenderNeutralPlacement::First {
+                middles.rotate_right(1);
+            }
+        }
 
-        let options = middles.join("|");
-        Ok(format!(
-            "{}{{{{{}:{}|{}}}}}{}",
-            prefix, tag_type, var_id, options, suffix
-        ))
+        let transclusion = Transclusion {
+            name: tag_type,
+            param: match selector_of(var_id) {
+                selector if is_positional_selector(selector) => String::new(),
+                selector => selector.to_string(),
+            },
+            options: middles,
+        };
+        let wikitext = format!("{}{}{}", prefix, render_transclusion(&transclusion), suffix);
+        transclusions.push(transclusion);
+        Ok(wikitext)
     }
 
     /// Restore placeholders: 777001 → $1 (Python lines 329-334)
+    ///
+    /// Link anchors (888001 → original target) are restored in the same pass,
+    /// so a link target that happens to contain "777" is never rewritten.
     fn restore_placeholders(&self, text: &str) -> String {
-        let re = Regex::new(r"777(\d+)").unwrap();
-        re.replace_all(text, |caps: &regex::Captures| {
-            let num_str = &caps[1]; // Get digits after 777
-            let num: usize = num_str.parse().unwrap(); // Convert "001" to 1
-            format!("${}", num)
+        // Adjacent anchors ("777001777002", from "$1$2") are restored one by
+        // one; a number that merely starts with 777 is left alone
+        replace_anchors(text, |anchor| {
+            if anchor.is_link() {
+                self.link_target(text, anchor)
+            } else {
+                format!("${}", anchor.index())
+            }
+        })
+    }
+
+    /// Restore link targets: 888001 → first link target
+    fn restore_link_targets(&self, text: &str) -> String {
+        if self.link_targets.is_empty() {
+            return text.to_string();
+        }
+        replace_anchors(text, |anchor| {
+            if anchor.is_link() {
+                self.link_target(text, anchor)
+            } else {
+                text[anchor.start..anchor.end].to_string()
+            }
         })
-        .to_string()
     }
+
+    /// Original target of a link anchor, or the anchor itself if unknown
+    fn link_target(&self, text: &str, anchor: &AnchorMatch) -> String {
+        anchor
+            .index()
+            .checked_sub(1)
+            .and_then(|idx| self.link_targets.get(idx))
+            .cloned()
+            .unwrap_or_else(|| text[anchor.start..anchor.end].to_string())
+    }
+}
+
+/// Wikitext of a magic word built by reassembly: `{{TAG:VAR|opt1|opt2}}`
+fn render_transclusion(trans: &Transclusion) -> String {
+    format!(
+        "{{{{{}:{}|{}}}}}",
+        trans.name,
+        trans.param,
+        trans.options.join("|")
+    )
+}
+
+/// Index of the variant most similar to all others (the first on ties)
+fn most_agreeing(variants: &[TranslationVariant], cache: &mut SimilarityCache) -> usize {
+    let mut best = (0, f32::MIN);
+    for (i, candidate) in variants.iter().enumerate() {
+        let agreement: f32 = variants
+            .iter()
+            .enumerate()
+            .filter(|(j, _)| *j != i)
+            .map(|(_, other)| cache.similarity(&candidate.translated_text, &other.translated_text))
+            .sum();
+        if agreement > best.1 {
+            best = (i, agreement);
+        }
+    }
+    best.0
+}
+
+/// Languages that capitalize every noun, whatever its position in the sentence
+const NOUN_CAPITALIZING_LANGUAGES: &[&str] = &["de", "lb"];
+
+/// Give `translated` the same leading letter case as `source`
+///
+/// Only applies when both texts start (after whitespace) with a cased letter.
+/// Without `lowercase`, a capital is never lowered, only a lowercase letter raised.
+fn match_leading_case(source: &str, translated: &str, lowercase: bool) -> String {
+    let Some(source_first) = source.trim_start().chars().next() else {
+        return translated.to_string();
+    };
+    let offset = translated.len() - translated.trim_start().len();
+    let Some(first) = translated[offset..].chars().next() else {
+        return translated.to_string();
+    };
+
+    let replacement: String = if source_first.is_uppercase() && first.is_lowercase() {
+        first.to_uppercase().collect()
+    } else if lowercase && source_first.is_lowercase() && first.is_uppercase() {
+        first.to_lowercase().collect()
+    } else {
+        return translated.to_string();
+    };
+
+    format!(
+        "{}{}{}",
+        &translated[..offset],
+        replacement,
+        &translated[offset + first.len_utf8()..]
+    )
 }
 
 /// Calculate similarity ratio between two strings using sequence matching
 ///
 /// This implements a simple LCS-based similarity measure similar to Python's
 /// `difflib.SequenceMatcher.ratio()` function (Python line 189-190).
+/// Strings are compared in Unicode normalization form C, so precomposed and
+/// combining spellings of the same text are identical.
 ///
 /// # Arguments
 /// * `a` - First string
@@ -306,9 +1318,12 @@ pub fn get_similarity(a: &str, b: &str) -> f32 {
         return 0.0;
     }
 
-    // Convert to character vectors for LCS computation
-    let a_chars: Vec<char> = a.chars().collect();
-    let b_chars: Vec<char> = b.chars().collect();
+    // Convert to NFC character vectors for LCS computation
+    let a_chars: Vec<char> = a.nfc().collect();
+    let b_chars: Vec<char> = b.nfc().collect();
+    if a_chars == b_chars {
+        return 1.0;
+    }
 
     // Calculate LCS length using dynamic programming
     let lcs_length = longest_common_subsequence(&a_chars, &b_chars);
@@ -321,30 +1336,22 @@ pub fn get_similarity(a: &str, b: &str) -> f32 {
 
 /// Get Longest Common Prefix of all strings (Python line 313-320)
 fn get_lcp(strings: &[String]) -> String {
-    if strings.is_empty() {
+    let Some((first, rest)) = strings.split_first() else {
         return String::new();
-    }
-
-    if strings.len() == 1 {
-        return strings[0].clone();
-    }
-
-    // Find the shortest string length
-    let min_len = strings.iter().map(|s| s.len()).min().unwrap_or(0);
+    };
 
-    // Find common prefix length
+    // Walk all strings in lockstep, one char at a time, so the prefix is
+    // found in linear time and always ends on a char boundary
+    let mut others: Vec<std::str::Chars> = rest.iter().map(|s| s.chars()).collect();
     let mut prefix_len = 0;
-    'outer: for i in 0..min_len {
-        let first_char = strings[0].chars().nth(i);
-        for string in &strings[1..] {
-            if string.chars().nth(i) != first_char {
-                break 'outer;
-            }
+    for (i, c) in first.char_indices() {
+        if !others.iter_mut().all(|chars| chars.next() == Some(c)) {
+            break;
         }
-        prefix_len = i + 1;
+        prefix_len = i + c.len_utf8();
     }
 
-    strings[0].chars().take(prefix_len).collect()
+    first[..prefix_len].to_string()
 }
 
 /// Get Longest Common Suffix by reversing and using LCP (Python lines 322-327)
@@ -391,15 +1398,45 @@ fn longest_common_subsequence(a: &[char], b: &[char]) -> usize {
 /// Convenience function to reassemble variants from a MessageContext
 ///
 /// This function provides a high-level interface matching the expected
-/// workflow from the integration tests.
+/// workflow from the integration tests. Errors are wrapped in
+/// `MtError::InContext` carrying the context's `original_key`.
 pub fn reassemble_from_context(context: &MessageContext) -> MtResult<String> {
-    let reassembler = Reassembler::new(context.variable_types.clone());
-    reassembler.reassemble(context.variants.clone())
+    let reassembler = Reassembler::from_context(context);
+    reassembler
+        .reassemble(context.variants.clone())
+        .map_err(|e| e.in_context(&context.original_key))
+}
+
+/// Reassemble many contexts, collecting per-message results
+///
+/// A failing message does not stop the others: each context is reassembled
+/// with `reassemble_from_context()` and its result is returned next to its
+/// `original_key`, in input order.
+///
+/// # Arguments
+/// * `contexts` - Translated contexts, e.g. of a whole catalog
+///
+/// # Returns
+/// One (key, result) pair per context
+pub fn reassemble_all(contexts: &[MessageContext]) -> Vec<(String, MtResult<String>)> {
+    contexts
+        .iter()
+        .map(|context| {
+            (
+                context.original_key.clone(),
+                reassemble_from_context(context),
+            )
+        })
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::anchors::ANCHOR_JOINER;
+    use crate::expansion::prepare_for_translation;
+    use crate::mock::{MockMode, MockTranslator};
+    use crate::translator::MachineTranslator;
     use std::collections::HashMap;
 
     /// Helper to create a variant with state and translation
@@ -499,6 +1536,16 @@ mod tests {
         assert_eq!(get_similarity("", "xyz"), 0.0);
     }
 
+    #[test]
+    fn test_get_similarity_normalizes_to_nfc() {
+        // Precomposed "é" (U+00E9) vs "e" + combining acute (U+0301)
+        assert_eq!(get_similarity("caf\u{e9}", "cafe\u{301}"), 1.0);
+        assert_eq!(
+            get_similarity("d\u{e9}j\u{e0} vu", "de\u{301}ja\u{300} vu"),
+            1.0
+        );
+    }
+
     // ========== LCS Algorithm Tests ==========
 
     #[test]
@@ -539,7 +1586,14 @@ mod tests {
             create_variant(&[("$1", 1)], "She sent a message"),
         ];
 
-        let result = reassembler.fold_strings(&variants, "$1").unwrap();
+        let result = reassembler
+            .fold_strings(
+                &variants,
+                "$1",
+                &mut SimilarityCache::default(),
+                &mut Vec::new(),
+            )
+            .unwrap();
 
         // Should be "{{GENDER:$1|He|She}} sent a message"
         // NOT "{{GENDER:$1|He s|She s}}ent a message" (broken word boundary)
@@ -562,7 +1616,12 @@ mod tests {
             create_variant(&[("$1", 1)], "Completely different sentence"), // Very different
         ];
 
-        let result = reassembler.fold_strings(&variants, "$1");
+        let result = reassembler.fold_strings(
+            &variants,
+            "$1",
+            &mut SimilarityCache::default(),
+            &mut Vec::new(),
+        );
 
         assert!(result.is_err());
         match result {
@@ -575,29 +1634,84 @@ mod tests {
     }
 
     #[test]
-    fn test_consistency_passes_similar_variants() {
+    fn test_suggest_fix_names_outlier_variant() {
         let mut var_types = HashMap::new();
         var_types.insert("$1".to_string(), "GENDER".to_string());
         let reassembler = Reassembler::new(var_types);
 
-        // Create variants that are similar enough
         let variants = vec![
-            create_variant(&[("$1", 0)], "He sent a message"),
-            create_variant(&[("$1", 1)], "She sent a message"), // Very similar
+            create_variant(&[("$1", 0)], "Il a envoyé un message"),
+            create_variant(&[("$1", 1)], "Elle a envoyé un message"),
+            create_variant(&[("$1", 2)], "Quelqu'un a posté une lettre recommandée"),
         ];
-
-        let result = reassembler.fold_strings(&variants, "$1");
-        assert!(result.is_ok());
+        assert!(reassembler.reassemble(variants.clone()).is_err());
+
+        let suggestion = reassembler.suggest_fix(&variants, "$1").unwrap();
+        assert_eq!(suggestion.outlier_index, 2);
+        assert_eq!(suggestion.outlier_state, variants[2].state);
+        assert_eq!(
+            suggestion.outlier_text,
+            "Quelqu'un a posté une lettre recommandée"
+        );
+        assert!(suggestion.similarity < CONSISTENCY_THRESHOLD);
+        assert_eq!(suggestion.action, FixAction::RetranslateVariant);
+        assert!(suggestion.to_string().starts_with("Variant 2 ($1=2) of $1"));
+
+        // Consistent variants need no fix
+        assert_eq!(reassembler.suggest_fix(&variants[..2], "$1"), None);
     }
 
-    // ========== Placeholder Restoration Tests ==========
-
     #[test]
-    fn test_restore_placeholders() {
-        let reassembler = Reassembler::new(HashMap::new());
+    fn test_suggest_fix_lowers_threshold_when_all_disagree() {
+        let mut var_types = HashMap::new();
+        var_types.insert("$1".to_string(), "GENDER".to_string());
+        let reassembler = Reassembler::new(var_types.clone());
 
-        let text = "777001 sent 777002 messages to 777003";
-        let result = reassembler.restore_placeholders(text);
+        let variants = vec![
+            create_variant(&[("$1", 0)], "Er schickte es"),
+            create_variant(&[("$1", 1)], "Sie sandte es"),
+            create_variant(&[("$1", 2)], "Man übermittelte es"),
+        ];
+        let suggestion = reassembler.suggest_fix(&variants, "$1").unwrap();
+        let FixAction::LowerThreshold(threshold) = suggestion.action else {
+            panic!("Expected LowerThreshold, got {:?}", suggestion.action);
+        };
+        assert!(threshold < CONSISTENCY_THRESHOLD);
+
+        let relaxed = Reassembler::new(var_types).with_consistency_threshold(threshold);
+        assert_eq!(relaxed.suggest_fix(&variants, "$1"), None);
+        assert!(relaxed.reassemble(variants).is_ok());
+    }
+
+    #[test]
+    fn test_consistency_passes_similar_variants() {
+        let mut var_types = HashMap::new();
+        var_types.insert("$1".to_string(), "GENDER".to_string());
+        let reassembler = Reassembler::new(var_types);
+
+        // Create variants that are similar enough
+        let variants = vec![
+            create_variant(&[("$1", 0)], "He sent a message"),
+            create_variant(&[("$1", 1)], "She sent a message"), // Very similar
+        ];
+
+        let result = reassembler.fold_strings(
+            &variants,
+            "$1",
+            &mut SimilarityCache::default(),
+            &mut Vec::new(),
+        );
+        assert!(result.is_ok());
+    }
+
+    // ========== Placeholder Restoration Tests ==========
+
+    #[test]
+    fn test_restore_placeholders() {
+        let reassembler = Reassembler::new(HashMap::new());
+
+        let text = "777001 sent 777002 messages to 777003";
+        let result = reassembler.restore_placeholders(text);
         assert_eq!(result, "$1 sent $2 messages to $3");
     }
 
@@ -619,6 +1733,277 @@ mod tests {
         assert_eq!(result, "$1 and normal 777 text and $2");
     }
 
+    #[test]
+    fn test_restore_placeholders_leaves_literal_numbers() {
+        let reassembler =
+            Reassembler::new(HashMap::new()).with_link_targets(vec!["Main Page".to_string()]);
+
+        let text = "Call 7770123 or 777001, order 888001234 at 888001";
+        assert_eq!(
+            reassembler.restore_placeholders(text),
+            "Call 7770123 or $1, order 888001234 at Main Page"
+        );
+    }
+
+    // ========== Adjacent Anchor Tests ==========
+
+    /// Run a message through expand → mock translate → reassemble
+    async fn round_trip(message: &str, mode: MockMode) -> String {
+        let ast = Parser::new(message).parse();
+        let mut context = prepare_for_translation(&ast, "en", "test").unwrap();
+        let translated = MockTranslator::new(mode)
+            .translate_batch(&context.source_texts(), "en", "fr")
+            .await
+            .unwrap();
+        context.update_translations(translated);
+        reassemble_from_context(&context).unwrap()
+    }
+
+    #[test]
+    fn test_restore_adjacent_anchors() {
+        let reassembler = Reassembler::new(HashMap::new());
+        assert_eq!(reassembler.restore_placeholders("777001777002"), "$1$2");
+        assert_eq!(
+            reassembler.restore_placeholders("777001777002777003."),
+            "$1$2$3."
+        );
+    }
+
+    #[tokio::test]
+    async fn test_adjacent_placeholders_round_trip() {
+        for mode in [MockMode::NoOp, MockMode::Reorder] {
+            assert_eq!(round_trip("$1$2", mode.clone()).await, "$1$2");
+            assert_eq!(round_trip("$1$2$3", mode.clone()).await, "$1$2$3");
+        }
+        assert_eq!(
+            round_trip("Total: $1$2", MockMode::Reorder).await,
+            "$1$2 Total:"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_numbers_next_to_placeholders_round_trip() {
+        for (message, expected) in [
+            ("Call 7770123 or $1", "Call 7770123 or $1"),
+            ("Room 77$1 is free", "Room 77$1 is free"),
+            (
+                "Room 77{{PLURAL:$1|$1 is|$1 are}} free",
+                "Room 77$1 {{PLURAL:$1|is|are}} free",
+            ),
+        ] {
+            assert_eq!(round_trip(message, MockMode::NoOp).await, expected);
+            let suffixed = round_trip(message, MockMode::Suffix).await;
+            assert!(!suffixed.contains(ANCHOR_JOINER), "{}", suffixed);
+        }
+
+        // A digit after a placeholder keeps its joiner rather than turning
+        // "$1" into "$17"
+        let wikitext = round_trip("{{PLURAL:$1|$1 room|Rooms $1}}7 left", MockMode::NoOp).await;
+        assert!(!wikitext.contains("$17"), "{}", wikitext);
+        assert!(
+            wikitext.contains(&format!("$1{}7", ANCHOR_JOINER)),
+            "{}",
+            wikitext
+        );
+    }
+
+    #[tokio::test]
+    async fn test_adjacent_placeholders_next_to_magic_word() {
+        assert_eq!(
+            round_trip("$1$2 {{PLURAL:$3|file|files}}", MockMode::NoOp).await,
+            "$1$2 {{PLURAL:$3|file|files}}"
+        );
+        assert_eq!(
+            round_trip("{{GENDER:$3|He|She|They}} got $1$2", MockMode::NoOp).await,
+            "{{GENDER:$3|He|She|They}} got $1$2"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_multiline_message_round_trip() {
+        let message = "Your changes were saved.\n{{PLURAL:$1|One page was|$1 pages were}} updated.";
+        for mode in [MockMode::NoOp, MockMode::Suffix] {
+            let wikitext = round_trip(message, mode.clone()).await;
+            assert!(
+                wikitext.starts_with(
+                    "Your changes were saved.\n{{PLURAL:$1|One page was|$1 pages were}} updated."
+                ),
+                "{:?}: {}",
+                mode,
+                wikitext
+            );
+        }
+
+        // The line after the magic word stays out of its options
+        assert_eq!(
+            round_trip("{{PLURAL:$1|One page|$1 pages}}\nSaved.", MockMode::NoOp).await,
+            "{{PLURAL:$1|One page|$1 pages}}\nSaved."
+        );
+    }
+
+    #[tokio::test]
+    async fn test_whitespace_runs_in_stable_text_round_trip() {
+        for message in [
+            "Hello  world, {{GENDER:$1|he|she|they}} left",
+            "Name:\t{{GENDER:$1|his|her|their}} profile",
+            "Total:\t\t{{PLURAL:$1|one item|$1 items}} in  cart",
+            // Double spaces right at the magic word boundary
+            "Deleted  {{PLURAL:$1|one file|$1 files}}  today",
+            "{{GENDER:$1|He|She|They}}\t \tsaid hi",
+        ] {
+            assert_eq!(round_trip(message, MockMode::NoOp).await, message);
+        }
+    }
+
+    #[test]
+    fn test_leftover_variants_fall_back_with_warning() {
+        let mut var_types = HashMap::new();
+        var_types.insert("$1".to_string(), "GENDER".to_string());
+
+        // The third variant has a variable the others lack, so it ends up in
+        // a group of its own
+        let variants = vec![
+            create_variant(&[("$1", 0)], "Il est parti"),
+            create_variant(&[("$1", 1)], "Elle est partie"),
+            create_variant(&[("$1", 0), ("$2", 0)], "Il est parti hier"),
+            create_variant(&[("$1", 0), ("$2", 1)], "Il est parti hier soir"),
+        ];
+
+        let reassembler = Reassembler::new(var_types.clone());
+        let output = reassembler.reassemble_detailed(variants.clone()).unwrap();
+        assert_eq!(output.wikitext, "Il est parti hier");
+        assert_eq!(output.warnings.len(), 1);
+        assert!(output.warnings[0].contains("got 3"));
+
+        // Entry points without warnings never drop the magic word silently
+        assert!(matches!(
+            reassembler.reassemble(variants.clone()),
+            Err(MtError::ReassemblyError(msg)) if msg.contains("Expected 1 variant")
+        ));
+        assert!(reassembler.reassemble_to_ast(variants.clone()).is_err());
+
+        let strict = Reassembler::new(var_types).with_strict_collapse(true);
+        assert!(matches!(
+            strict.reassemble_detailed(variants),
+            Err(MtError::ReassemblyError(msg)) if msg.contains("Expected 1 variant")
+        ));
+    }
+
+    // ========== Bare Magic Word Tests ==========
+
+    /// Expand, translate with a fixed table and reassemble
+    async fn round_trip_mapped(message: &str, target: &str, table: &[(&str, &str)]) -> String {
+        let ast = Parser::new(message).parse();
+        let mut context = prepare_for_translation(&ast, "en", "test").unwrap();
+        let map = table
+            .iter()
+            .map(|(en, tr)| ((en.to_string(), target.to_string()), tr.to_string()))
+            .collect();
+        let translated = MockTranslator::new(MockMode::Mappings(map))
+            .translate_batch(&context.source_texts(), "en", target)
+            .await
+            .unwrap();
+        context.update_translations(translated);
+        reassemble_from_context(&context).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_bare_gender_round_trip() {
+        let table = [("He", "Il"), ("She", "Elle"), ("They", "Iel")];
+        assert_eq!(
+            round_trip_mapped("{{GENDER:$1|He|She|They}}", "fr", &table).await,
+            "{{GENDER:$1|Il|Elle|Iel}}"
+        );
+
+        // Two forms coincide after translation
+        let table = [("He", "Il"), ("She", "Elle"), ("They", "Il")];
+        assert_eq!(
+            round_trip_mapped("{{GENDER:$1|He|She|They}}", "fr", &table).await,
+            "{{GENDER:$1|Il|Elle|Il}}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_bare_plural_round_trip() {
+        let table = [("cat", "chat"), ("cats", "chats")];
+        assert_eq!(
+            round_trip_mapped("{{PLURAL:$1|cat|cats}}", "fr", &table).await,
+            "{{PLURAL:$1|chat|chats}}"
+        );
+
+        let table = [("cat", "Katze"), ("cats", "Katzen")];
+        assert_eq!(
+            round_trip_mapped("{{PLURAL:$1|cat|cats}}", "de", &table).await,
+            "{{PLURAL:$1|Katze|Katzen}}"
+        );
+
+        // Identical forms collapse to plain text
+        let table = [("cat", "猫"), ("cats", "猫")];
+        assert_eq!(
+            round_trip_mapped("{{PLURAL:$1|cat|cats}}", "ja", &table).await,
+            "猫"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_bare_magic_words_side_by_side() {
+        let table = [
+            ("He1 file", "Il1 fichier"),
+            ("He777002 files", "Il777002 fichiers"),
+            ("She1 file", "Elle1 fichier"),
+            ("She777002 files", "Elle777002 fichiers"),
+            ("They1 file", "Iel1 fichier"),
+            ("They777002 files", "Iel777002 fichiers"),
+        ];
+        let wikitext = round_trip_mapped(
+            "{{GENDER:$1|He|She|They}}{{PLURAL:$2|1 file|$2 files}}",
+            "fr",
+            &table,
+        )
+        .await;
+        // Without a space between them the words cannot be split apart, so
+        // GENDER is nested in each PLURAL option; still valid wikitext
+        assert_eq!(
+            wikitext,
+            "{{PLURAL:$2|{{GENDER:$1|Il1|Elle1|Iel1}} fichier|{{GENDER:$1|Il$2|Elle$2|Iel$2}} fichiers}}"
+        );
+    }
+
+    // ========== Parser Function Tests ==========
+
+    #[tokio::test]
+    async fn test_if_round_trip() {
+        let ast = Parser::new("Blocked{{#if:$1|: $1|. No reason was given}}").parse();
+        let mut context = prepare_for_translation(&ast, "en", "test").unwrap();
+        assert_eq!(context.variant_count(), 2);
+
+        let mut map = HashMap::new();
+        map.insert(
+            ("Blocked: 777001".to_string(), "fr".to_string()),
+            "Bloqué : 777001".to_string(),
+        );
+        map.insert(
+            ("Blocked. No reason was given".to_string(), "fr".to_string()),
+            "Bloqué. Aucune raison donnée".to_string(),
+        );
+        let translated = MockTranslator::new(MockMode::Mappings(map))
+            .translate_batch(&context.source_texts(), "en", "fr")
+            .await
+            .unwrap();
+        context.update_translations(translated);
+
+        let output = Reassembler::from_context(&context)
+            .with_structure_check(StructureCheck::Warn)
+            .reassemble_detailed(context.variants.clone())
+            .unwrap();
+        assert!(output.warnings.is_empty(), "{:?}", output.warnings);
+        // The shared word is not split off, since it is not followed by a space
+        assert_eq!(
+            output.wikitext,
+            "{{#if:$1|Bloqué : $1|Bloqué. Aucune raison donnée}}"
+        );
+    }
+
     // ========== Simple Reassembly Tests ==========
 
     #[test]
@@ -683,6 +2068,293 @@ mod tests {
         assert!(result.contains("}} sent a message"));
     }
 
+    #[test]
+    fn test_identical_neutral_gender_is_dropped() {
+        let mut var_types = HashMap::new();
+        var_types.insert("$1".to_string(), "GENDER".to_string());
+        let reassembler = Reassembler::new(var_types).with_drop_identical_neutral(true);
+
+        let variants = vec![
+            create_variant(&[("$1", 0)], "Il a envoyé un message"),
+            create_variant(&[("$1", 1)], "Elle a envoyé un message"),
+            create_variant(&[("$1", 2)], "Il a envoyé un message"),
+        ];
+
+        let result = reassembler.reassemble(variants).unwrap();
+        assert_eq!(result, "{{GENDER:$1|Il|Elle}} a envoyé un message");
+    }
+
+    #[test]
+    fn test_distinct_neutral_gender_is_kept() {
+        let mut var_types = HashMap::new();
+        var_types.insert("$1".to_string(), "GENDER".to_string());
+        let variants = vec![
+            create_variant(&[("$1", 0)], "He sent a message"),
+            create_variant(&[("$1", 1)], "She sent a message"),
+            create_variant(&[("$1", 2)], "They sent a message"),
+        ];
+
+        let reassembler = Reassembler::new(var_types.clone()).with_drop_identical_neutral(true);
+        let result = reassembler.reassemble(variants.clone()).unwrap();
+        assert_eq!(result, "{{GENDER:$1|He|She|They}} sent a message");
+
+        // Neutral first is for non-MediaWiki consumers: MediaWiki would read "They" as male
+        let reassembler = Reassembler::new(var_types)
+            .with_drop_identical_neutral(true)
+            .with_gender_neutral_placement(GenderNeutralPlacement::First);
+        let result = reassembler.reassemble(variants).unwrap();
+        assert_eq!(result, "{{GENDER:$1|They|He|She}} sent a message");
+    }
+
+    // ========== Formatting Character Tests ==========
+
+    #[test]
+    fn test_soft_hyphens_and_break_hints_survive_round_trip() {
+        let source = "{{GENDER:$1|Er|Sie|Er}} hat die Konto\u{AD}einstellungen<wbr>seite in {{PLURAL:$2|einem Schritt|$2 Schritten}} geändert";
+        let ast = banana_i18n::parser::Parser::new(source).parse();
+        let mut context = prepare_for_translation(&ast, "de", "test").unwrap();
+
+        // Identity "translation": everything stable must come back unchanged
+        let texts = context.source_texts();
+        assert!(
+            texts
+                .iter()
+                .all(|t| t.contains("Konto\u{AD}einstellungen<wbr>seite"))
+        );
+        context.update_translations(texts);
+
+        assert_eq!(reassemble_from_context(&context).unwrap(), source);
+    }
+
+    #[test]
+    fn test_soft_hyphen_next_to_differing_word_is_kept() {
+        let mut var_types = HashMap::new();
+        var_types.insert("$1".to_string(), "PLURAL".to_string());
+        let variants = vec![
+            create_variant(&[("$1", 0)], "Ein Benutzer\u{AD}konto wurde gesperrt"),
+            create_variant(&[("$1", 1)], "777001 Benutzer\u{AD}konten wurden gesperrt"),
+        ];
+        let result = Reassembler::new(var_types).reassemble(variants).unwrap();
+        assert_eq!(
+            result,
+            "{{PLURAL:$1|Ein Benutzer\u{AD}konto wurde|$1 Benutzer\u{AD}konten wurden}} gesperrt"
+        );
+    }
+
+    #[test]
+    fn test_arabic_six_form_plural_round_trip() {
+        let ast = Parser::new("{{PLURAL:$1|One file|$1 files}} in the selected folder").parse();
+        let mut context = prepare_for_translation(&ast, "ar", "files").unwrap();
+        assert_eq!(context.variant_count(), 6);
+
+        // zero, one, two, few, many, other
+        let options = [
+            "لا توجد ملفات",
+            "ملف واحد",
+            "ملفان",
+            "777001 ملفات",
+            "777001 ملفًا",
+            "777001 ملف",
+        ];
+        let translations = context
+            .variants
+            .iter()
+            .map(|v| format!("{} في المجلد المحدد", options[v.state["$1"]]))
+            .collect();
+        context.update_translations(translations);
+
+        assert_eq!(
+            reassemble_from_context(&context).unwrap(),
+            "{{PLURAL:$1|لا توجد ملفات|ملف واحد|ملفان|$1 ملفات|$1 ملفًا|$1 ملف}} في المجلد المحدد"
+        );
+    }
+
+    // ========== Determinism Tests ==========
+
+    #[test]
+    fn test_reassembly_is_deterministic() {
+        let mut context = MessageContext::new("test".to_string());
+        context.add_variable("$1".to_string(), "GENDER".to_string());
+        context.add_variable("$2".to_string(), "PLURAL".to_string());
+        for (gender, pronoun) in ["Il", "Elle"].iter().enumerate() {
+            context.add_variant(create_variant(
+                &[("$1", gender), ("$2", 0)],
+                &format!("{} a envoyé un message", pronoun),
+            ));
+            context.add_variant(create_variant(
+                &[("$1", gender), ("$2", 1)],
+                &format!("{} a envoyé 777002 messages", pronoun),
+            ));
+        }
+
+        let expected = reassemble_from_context(&context).unwrap();
+        for _ in 0..50 {
+            assert_eq!(reassemble_from_context(&context).unwrap(), expected);
+            let reassembler = Reassembler::new(context.variable_types.clone());
+            assert_eq!(
+                reassembler.reassemble(context.variants.clone()).unwrap(),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_axis_order_falls_back_to_numeric() {
+        let variant = create_variant(&[("$10", 0), ("$2", 0), ("$1", 0)], "x");
+
+        let reassembler = Reassembler::new(HashMap::new());
+        assert_eq!(reassembler.ordered_axes(&variant), vec!["$1", "$2", "$10"]);
+
+        let reassembler = reassembler.with_axis_order(vec!["$2".to_string()]);
+        assert_eq!(reassembler.ordered_axes(&variant), vec!["$2", "$1", "$10"]);
+    }
+
+    // ========== Similarity Cache Tests ==========
+
+    #[test]
+    fn test_similarity_cache_reuses_repeated_pairs() {
+        // Six plural forms × two GENDER axes the translation ignores: the same
+        // six strings appear in each of the 9 groups of the PLURAL axis
+        let forms = ["zero", "one", "two", "few", "many", "other"];
+        let mut variants = Vec::new();
+        for (p, form) in forms.iter().enumerate() {
+            for g1 in 0..3 {
+                for g2 in 0..3 {
+                    variants.push(create_variant(
+                        &[("$1", p), ("$2", g1), ("$3", g2)],
+                        &format!("Uploaded 777001 {} documents to the shared folder", form),
+                    ));
+                }
+            }
+        }
+        let mut var_types = HashMap::new();
+        var_types.insert("$1".to_string(), "PLURAL".to_string());
+        var_types.insert("$2".to_string(), "GENDER".to_string());
+        var_types.insert("$3".to_string(), "GENDER".to_string());
+        let reassembler = Reassembler::new(var_types);
+
+        let mut cache = SimilarityCache::default();
+        let cached = reassembler
+            .collapse_all(variants.clone(), &mut cache, None)
+            .unwrap();
+        assert_eq!(
+            cached,
+            "Uploaded $1 {{PLURAL:$1|zero|one|two|few|many|other}} documents to the shared folder"
+        );
+        assert_eq!(reassembler.reassemble(variants).unwrap(), cached);
+
+        // 5 comparisons per group; only the first group computes them
+        assert_eq!(cache.computed, 5);
+        assert_eq!(cache.scores.len(), 5);
+    }
+
+    // ========== Raw Pair Tests ==========
+
+    #[test]
+    fn test_reassemble_pairs_matches_variant_path() {
+        let translations = [
+            "Il a envoyé un message",
+            "Elle a envoyé un message",
+            "Iel a envoyé un message",
+        ];
+        let pairs: Vec<(HashMap<String, usize>, String)> = translations
+            .iter()
+            .enumerate()
+            .map(|(i, text)| {
+                let mut state = HashMap::new();
+                state.insert("$1".to_string(), i);
+                (state, text.to_string())
+            })
+            .collect();
+        let variants: Vec<TranslationVariant> = pairs
+            .iter()
+            .map(|(state, text)| create_variant(&[("$1", state["$1"])], text))
+            .collect();
+
+        let mut var_types = HashMap::new();
+        var_types.insert("$1".to_string(), "GENDER".to_string());
+        let reassembler = Reassembler::new(var_types);
+
+        let from_pairs = reassembler.reassemble_pairs(&pairs).unwrap();
+        assert_eq!(from_pairs, "{{GENDER:$1|Il|Elle|Iel}} a envoyé un message");
+        assert_eq!(reassembler.reassemble(variants).unwrap(), from_pairs);
+    }
+
+    // ========== AST Output Tests ==========
+
+    #[test]
+    fn test_reassemble_to_ast() {
+        let ast = Parser::new(
+            "{{GENDER:$1|He|She}} sent {{PLURAL:$2|a message|$2 messages}} to [[Special:Inbox|the inbox]]",
+        )
+        .parse();
+        let mut context = prepare_for_translation(&ast, "en", "sent").unwrap();
+        let texts = context.source_texts();
+        context.update_translations(texts);
+
+        let reassembler = Reassembler::from_context(&context);
+        let nodes = reassembler
+            .reassemble_to_ast(context.variants.clone())
+            .unwrap();
+
+        assert_eq!(
+            nodes[0],
+            AstNode::Transclusion(Transclusion {
+                name: "GENDER".to_string(),
+                param: "$1".to_string(),
+                options: vec!["He".to_string(), "She".to_string(), "She".to_string()],
+            })
+        );
+        assert_eq!(nodes[1], AstNode::Text(" sent ".to_string()));
+        let AstNode::Transclusion(plural) = &nodes[2] else {
+            panic!("expected PLURAL, got {:?}", nodes[2]);
+        };
+        assert_eq!(plural.options, vec!["a message", "$2 messages"]);
+        assert_eq!(
+            nodes[3],
+            AstNode::Text(" to [[Special:Inbox|the inbox]]".to_string())
+        );
+
+        assert_eq!(
+            banana_i18n::ast::ast_to_wikitext(&nodes),
+            reassembler.reassemble(context.variants).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_reassemble_to_ast_placeholders_and_nesting() {
+        let mut var_types = HashMap::new();
+        var_types.insert("$1".to_string(), "GENDER".to_string());
+        var_types.insert("$2".to_string(), "PLURAL".to_string());
+        let variants = vec![
+            create_variant(&[("$1", 0), ("$2", 0)], "777003: il a un fichier"),
+            create_variant(&[("$1", 0), ("$2", 1)], "777003: il a 777002 fichiers"),
+            create_variant(&[("$1", 1), ("$2", 0)], "777003: elle a une fiche"),
+            create_variant(&[("$1", 1), ("$2", 1)], "777003: elle a 777002 fiches"),
+        ];
+
+        let nodes = Reassembler::new(var_types)
+            .with_axis_order(vec!["$2".to_string(), "$1".to_string()])
+            .reassemble_to_ast(variants)
+            .unwrap();
+
+        assert_eq!(nodes[0], AstNode::Placeholder(Placeholder { index: 3 }));
+        assert_eq!(nodes[1], AstNode::Text(": ".to_string()));
+        // PLURAL was collapsed first, so it is nested in the GENDER options
+        let AstNode::Transclusion(gender) = &nodes[2] else {
+            panic!("expected GENDER, got {:?}", nodes[2]);
+        };
+        assert_eq!(gender.name, "GENDER");
+        assert_eq!(
+            gender.options,
+            vec![
+                "il a {{PLURAL:$2|un fichier|$2 fichiers}}",
+                "elle a {{PLURAL:$2|une fiche|$2 fiches}}",
+            ]
+        );
+        assert_eq!(nodes.len(), 3);
+    }
+
     // ========== MessageContext Convenience Test ==========
 
     #[test]
@@ -705,4 +2377,348 @@ mod tests {
         assert!(result.contains("|He|She}"));
         assert!(result.contains("}} is here"));
     }
+
+    #[test]
+    fn test_reassemble_from_context_error_carries_key() {
+        let mut context = MessageContext::new("notification-sent".to_string());
+        context.add_variable("$1".to_string(), "GENDER".to_string());
+        context.add_variant(create_variant(&[("$1", 0)], "He sent a message"));
+        context.add_variant(create_variant(
+            &[("$1", 1)],
+            "Completely different sentence",
+        ));
+
+        let err = reassemble_from_context(&context).unwrap_err();
+
+        assert_eq!(err.message_key(), Some("notification-sent"));
+        assert!(matches!(err.root(), MtError::ConsistencyError(_)));
+        assert!(
+            err.to_string()
+                .starts_with("[notification-sent] MT consistency error")
+        );
+    }
+
+    #[test]
+    fn test_reassemble_all_collects_partial_success() {
+        let context = |key: &str, texts: [&str; 2]| {
+            let mut context = MessageContext::new(key.to_string());
+            context.add_variable("$1".to_string(), "GENDER".to_string());
+            for (i, text) in texts.iter().enumerate() {
+                context.add_variant(create_variant(&[("$1", i)], text));
+            }
+            context
+        };
+        let contexts = vec![
+            context("arrived", ["He arrived", "She arrived"]),
+            context(
+                "sent",
+                ["He sent a message", "Completely different sentence"],
+            ),
+            context("left", ["He left", "She left"]),
+        ];
+
+        let results = reassemble_all(&contexts);
+
+        let keys: Vec<&str> = results.iter().map(|(key, _)| key.as_str()).collect();
+        assert_eq!(keys, ["arrived", "sent", "left"]);
+        assert_eq!(results[0].1.as_deref(), Ok("{{GENDER:$1|He|She}} arrived"));
+        let err = results[1].1.as_ref().unwrap_err();
+        assert_eq!(err.message_key(), Some("sent"));
+        assert!(matches!(err.root(), MtError::ConsistencyError(_)));
+        assert_eq!(results[2].1.as_deref(), Ok("{{GENDER:$1|He|She}} left"));
+    }
+
+    #[test]
+    fn test_in_context_keeps_innermost_key() {
+        let err = MtError::ReassemblyError("boom".into())
+            .in_context("inner")
+            .in_context("outer");
+        assert_eq!(err.message_key(), Some("inner"));
+        assert_eq!(err.to_string(), "[inner] Reassembly error: boom");
+    }
+
+    // ========== Structure Check Tests ==========
+
+    #[test]
+    fn test_structure_check_detects_stray_pipe() {
+        let mut var_types = HashMap::new();
+        var_types.insert("$1".to_string(), "GENDER".to_string());
+        let variants = vec![
+            create_variant(&[("$1", 0)], "Il|elle est là"),
+            create_variant(&[("$1", 1)], "Elle est là"),
+        ];
+
+        // Off by default: the broken output is returned silently
+        let output = Reassembler::new(var_types.clone())
+            .reassemble_detailed(variants.clone())
+            .unwrap();
+        assert_eq!(output.wikitext, "{{GENDER:$1|Il|elle|Elle}} est là");
+        assert!(output.warnings.is_empty());
+
+        let output = Reassembler::new(var_types.clone())
+            .with_structure_check(StructureCheck::Warn)
+            .reassemble_detailed(variants.clone())
+            .unwrap();
+        assert_eq!(output.warnings.len(), 1);
+        assert!(output.warnings[0].contains("has 3 options, expected 2"));
+
+        let result = Reassembler::new(var_types)
+            .with_structure_check(StructureCheck::Strict)
+            .reassemble(variants);
+        match result {
+            Err(MtError::ReassemblyError(msg)) => assert!(msg.contains("source structure")),
+            other => panic!("Expected ReassemblyError, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_structure_check_passes_clean_output() {
+        let mut var_types = HashMap::new();
+        var_types.insert("$1".to_string(), "GENDER".to_string());
+        let variants = vec![
+            create_variant(&[("$1", 0)], "Il est là"),
+            create_variant(&[("$1", 1)], "Elle est là"),
+            create_variant(&[("$1", 2)], "Iel est là"),
+        ];
+
+        let output = Reassembler::new(var_types)
+            .with_structure_check(StructureCheck::Strict)
+            .reassemble_detailed(variants)
+            .unwrap();
+        assert_eq!(output.wikitext, "{{GENDER:$1|Il|Elle|Iel}} est là");
+        assert!(output.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_structure_check_detects_injected_braces() {
+        let mut var_types = HashMap::new();
+        var_types.insert("$1".to_string(), "PLURAL".to_string());
+        let variants = vec![
+            create_variant(&[("$1", 0)], "Supprimer le}} fichier"),
+            create_variant(&[("$1", 1)], "Supprimer les fichiers"),
+        ];
+
+        let output = Reassembler::new(var_types)
+            .with_structure_check(StructureCheck::Warn)
+            .reassemble_detailed(variants)
+            .unwrap();
+        assert!(!output.warnings.is_empty());
+    }
+
+    // ========== Case Preservation Tests ==========
+
+    #[test]
+    fn test_preserve_case_from_source() {
+        let mut var_types = HashMap::new();
+        var_types.insert("$1".to_string(), "GENDER".to_string());
+
+        // MT lowercased the first word of the first variant only
+        let variants = vec![
+            TranslationVariant::with_translation(
+                [("$1".to_string(), 0)].into(),
+                "He left the meeting".to_string(),
+                "il a quitté la réunion".to_string(),
+            ),
+            TranslationVariant::with_translation(
+                [("$1".to_string(), 1)].into(),
+                "She left the meeting".to_string(),
+                "Elle a quitté la réunion".to_string(),
+            ),
+        ];
+
+        let result = Reassembler::new(var_types)
+            .with_preserve_case_from_source(true)
+            .reassemble(variants)
+            .unwrap();
+        assert_eq!(result, "{{GENDER:$1|Il|Elle}} a quitté la réunion");
+    }
+
+    #[test]
+    fn test_preserve_case_keeps_german_nouns_capitalized() {
+        let mut var_types = HashMap::new();
+        var_types.insert("$1".to_string(), "PLURAL".to_string());
+
+        // The source starts lowercase, but German capitalizes the noun
+        let variants = vec![
+            TranslationVariant::with_translation(
+                [("$1".to_string(), 0)].into(),
+                "one file deleted".to_string(),
+                "eine Datei gelöscht".to_string(),
+            ),
+            TranslationVariant::with_translation(
+                [("$1".to_string(), 1)].into(),
+                "files deleted".to_string(),
+                "Dateien gelöscht".to_string(),
+            ),
+        ];
+
+        let result = Reassembler::new(var_types)
+            .with_target_locale("de-AT")
+            .with_preserve_case_from_source(true)
+            .reassemble(variants)
+            .unwrap();
+        assert_eq!(result, "{{PLURAL:$1|eine Datei|Dateien}} gelöscht");
+    }
+
+    // ========== Unicode Normalization Tests ==========
+
+    #[test]
+    fn test_unicode_normalization_of_mixed_forms() {
+        let mut var_types = HashMap::new();
+        var_types.insert("$1".to_string(), "GENDER".to_string());
+
+        // The MT engine used a combining accent in the second variant only
+        let variants = vec![
+            create_variant(&[("$1", 0)], "Il a choisi le caf\u{e9}"),
+            create_variant(&[("$1", 1)], "Elle a choisi le cafe\u{301}"),
+        ];
+
+        let result = Reassembler::new(var_types.clone())
+            .reassemble(variants.clone())
+            .unwrap();
+        assert!(result.contains("cafe\u{301}"));
+
+        let result = Reassembler::new(var_types)
+            .with_unicode_normalization(true)
+            .reassemble(variants)
+            .unwrap();
+        assert_eq!(result, "{{GENDER:$1|Il|Elle}} a choisi le caf\u{e9}");
+    }
+
+    #[test]
+    fn test_match_leading_case() {
+        assert_eq!(match_leading_case("Hello", "bonjour", true), "Bonjour");
+        assert_eq!(match_leading_case("hello", "Bonjour", true), "bonjour");
+        assert_eq!(match_leading_case("hello", "Bonjour", false), "Bonjour");
+        assert_eq!(match_leading_case("Hello", "hallo", false), "Hallo");
+        assert_eq!(match_leading_case("777001 sent", "envoyé", true), "envoyé");
+        assert_eq!(match_leading_case("Émile", " émile", true), " Émile");
+    }
+
+    #[test]
+    fn test_source_magic_word_case() {
+        let ast = Parser::new("{{plural:$1|One file|$1 files}} deleted").parse();
+        let mut context = prepare_for_translation(&ast, "en", "deleted").unwrap();
+        context.update_translations(vec![
+            "Un fichier supprimé".to_string(),
+            "777001 fichiers supprimé".to_string(),
+        ]);
+
+        let result = Reassembler::from_context(&context)
+            .with_source_magic_word_case(true)
+            .reassemble(context.variants.clone())
+            .unwrap();
+        assert_eq!(result, "{{plural:$1|Un fichier|$1 fichiers}} supprimé");
+
+        // Canonical uppercase by default
+        assert_eq!(
+            reassemble_from_context(&context).unwrap(),
+            "{{PLURAL:$1|Un fichier|$1 fichiers}} supprimé"
+        );
+    }
+
+    // ========== Typography Tests ==========
+
+    #[test]
+    fn test_reassemble_applies_french_typography() {
+        let mut var_types = HashMap::new();
+        var_types.insert("$1".to_string(), "GENDER".to_string());
+        let variants = vec![
+            create_variant(&[("$1", 0)], "Il a envoyé 777002 messages!"),
+            create_variant(&[("$1", 1)], "Elle a envoyé 777002 messages!"),
+        ];
+
+        // Off by default
+        let plain = Reassembler::new(var_types.clone())
+            .reassemble(variants.clone())
+            .unwrap();
+        assert!(plain.ends_with("messages!"));
+
+        let result = Reassembler::new(var_types)
+            .with_target_locale("fr")
+            .with_typography(true)
+            .reassemble(variants)
+            .unwrap();
+        assert_eq!(
+            result,
+            "{{GENDER:$1|Il|Elle}} a envoyé $2 messages\u{202F}!"
+        );
+    }
+
+    #[test]
+    fn test_reassemble_normalizes_quotes_across_variants() {
+        let mut var_types = HashMap::new();
+        var_types.insert("$1".to_string(), "GENDER".to_string());
+        // MT used straight quotes in one variant and German quotes in the other
+        let variants = vec![
+            create_variant(&[("$1", 0)], "Er hat \"Speichern\" gewählt"),
+            create_variant(&[("$1", 1)], "Sie hat „Speichern“ gewählt"),
+        ];
+
+        let result = Reassembler::new(var_types)
+            .with_target_locale("de")
+            .with_typography(true)
+            .reassemble(variants)
+            .unwrap();
+        assert_eq!(result, "{{GENDER:$1|Er|Sie}} hat „Speichern“ gewählt");
+    }
+
+    #[test]
+    fn test_post_processor_runs_only_for_its_locale() {
+        let mut var_types = HashMap::new();
+        var_types.insert("$1".to_string(), "GENDER".to_string());
+        let variants = vec![
+            create_variant(&[("$1", 0)], "Er hat 777002 gespeichert"),
+            create_variant(&[("$1", 1)], "Sie hat 777002 gespeichert"),
+        ];
+        let reassemble = |locale: &str| {
+            Reassembler::new(var_types.clone())
+                .with_target_locale(locale)
+                .with_post_processor("de", Box::new(|text: String| text.to_uppercase()))
+                .reassemble(variants.clone())
+                .unwrap()
+        };
+
+        assert_eq!(reassemble("de"), "{{GENDER:$1|ER|SIE}} HAT $2 GESPEICHERT");
+        assert_eq!(
+            reassemble("de-AT"),
+            "{{GENDER:$1|ER|SIE}} HAT $2 GESPEICHERT"
+        );
+        assert_eq!(reassemble("fr"), "{{GENDER:$1|Er|Sie}} hat $2 gespeichert");
+    }
+
+    // ========== Link Tests ==========
+
+    #[test]
+    fn test_reassemble_restores_link_targets() {
+        let mut context = MessageContext::new("test".to_string());
+        context.link_targets = vec!["Cat".to_string(), "https://example.org".to_string()];
+        context.add_variant(create_variant(
+            &[],
+            "Voir [[888001|le chat]] et [888002 les docs de 777001]",
+        ));
+
+        let result = reassemble_from_context(&context).unwrap();
+        assert_eq!(
+            result,
+            "Voir [[Cat|le chat]] et [https://example.org les docs de $1]"
+        );
+    }
+
+    #[test]
+    fn test_link_target_translation_round_trip() {
+        use banana_i18n::parser::Parser;
+
+        let ast = Parser::new("[[Cat|the cat]]").parse();
+        let mut context = crate::expansion::prepare_for_translation(&ast, "en", "test").unwrap();
+
+        // Simulate MT: only the display text changes, the anchor survives
+        let translated = context.variants[0]
+            .source_text
+            .replace("the cat", "le chat");
+        context.update_translations(vec![translated]);
+
+        let result = reassemble_from_context(&context).unwrap();
+        assert_eq!(result, "[[Cat|le chat]]");
+    }
 }
@@ -0,0 +1,205 @@
+//! Flat phrase tables for segment-level alignment and MT
+//!
+//! Full expansion produces one string per PLURAL/GENDER combination. Tools
+//! that align source and translation, score MT quality, or run their own
+//! segment-level MT often want something simpler: the plain-text runs of a
+//! message and what sits between them. `extract_segments()` returns exactly
+//! that, without expanding anything.
+//!
+//! # Example
+//!
+//! ```ignore
+//! let ast = Parser::new("$1 sent {{PLURAL:$2|a message|$2 messages}} to you").parse();
+//! let segments = extract_segments(&ast);
+//! // " sent "  between 777001 and {{PLURAL:$2}}
+//! // " to you" after {{PLURAL:$2}}, at the end of the message
+//! ```
+
+use crate::anchors::AnchorToken;
+use crate::expansion::placeholder_anchor;
+use banana_i18n::ast::{AstNode, AstNodeList, ast_to_wikitext};
+use std::ops::Range;
+
+/// A non-translatable node next to a segment
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SegmentBoundary {
+    /// A placeholder, with the anchor token MT sees in its place
+    Placeholder(AnchorToken),
+    /// A magic word such as PLURAL or GENDER, by name and parameter
+    MagicWord { name: String, param: String },
+    /// An internal or external link, as wikitext
+    Link(String),
+    /// A `<!-- ... -->` comment, whose text is not translated
+    Comment,
+}
+
+/// A contiguous run of translatable text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Segment {
+    /// The text, including its surrounding whitespace
+    pub text: String,
+    /// Byte range of the text in `ast_to_wikitext(ast)`
+    pub span: Range<usize>,
+    /// The node before the segment (`None` at the start of the message)
+    pub before: Option<SegmentBoundary>,
+    /// The node after the segment (`None` at the end of the message)
+    pub after: Option<SegmentBoundary>,
+}
+
+/// Extract the translatable text runs of a message
+///
+/// Adjacent text nodes are merged into one segment. Placeholders, magic words,
+/// links and comments end a segment and are reported as its boundaries. Runs
+/// that are only whitespace carry nothing to translate and are skipped. Magic word
+/// options are not descended into; they are part of the magic word boundary.
+///
+/// # Arguments
+/// * `ast` - Parsed message
+///
+/// # Returns
+/// Segments in message order
+pub fn extract_segments(ast: &AstNodeList) -> Vec<Segment> {
+    let mut segments = Vec::new();
+    let mut offset = 0;
+    let mut before = None;
+    let mut run = String::new();
+    let mut run_start = 0;
+
+    for node in ast {
+        let node_text = ast_to_wikitext(&vec![node.clone()]);
+        let boundary = match node {
+            AstNode::Text(text) => {
+                if run.is_empty() {
+                    run_start = offset;
+                }
+                run.push_str(text);
+                offset += node_text.len();
+                continue;
+            }
+            AstNode::Placeholder(placeholder) => {
+                let original = format!("${}", placeholder.index);
+                SegmentBoundary::Placeholder(AnchorToken {
+                    // Out-of-range placeholders are sent to MT as is
+                    anchor: placeholder_anchor(placeholder.index)
+                        .unwrap_or_else(|_| original.clone()),
+                    original,
+                })
+            }
+            AstNode::Transclusion(trans) => SegmentBoundary::MagicWord {
+                name: trans.name.to_uppercase(),
+                param: trans.param.clone(),
+            },
+            AstNode::InternalLink(_) | AstNode::ExternalLink(_) => {
+                SegmentBoundary::Link(node_text.clone())
+            }
+            AstNode::Comment(_) => SegmentBoundary::Comment,
+        };
+
+        push_segment(
+            &mut segments,
+            &mut run,
+            run_start,
+            before.take(),
+            Some(boundary.clone()),
+        );
+        offset += node_text.len();
+        before = Some(boundary);
+    }
+    push_segment(&mut segments, &mut run, run_start, before, None);
+
+    segments
+}
+
+/// Emit the pending text run as a segment, unless it is only whitespace
+fn push_segment(
+    segments: &mut Vec<Segment>,
+    run: &mut String,
+    start: usize,
+    before: Option<SegmentBoundary>,
+    after: Option<SegmentBoundary>,
+) {
+    let text = std::mem::take(run);
+    if text.trim().is_empty() {
+        return;
+    }
+    segments.push(Segment {
+        span: start..start + text.len(),
+        text,
+        before,
+        after,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use banana_i18n::parser::Parser;
+
+    fn segments_of(text: &str) -> Vec<Segment> {
+        extract_segments(&Parser::new(text).parse())
+    }
+
+    fn placeholder(index: usize) -> SegmentBoundary {
+        SegmentBoundary::Placeholder(AnchorToken {
+            anchor: placeholder_anchor(index).unwrap(),
+            original: format!("${}", index),
+        })
+    }
+
+    #[test]
+    fn test_segments_around_placeholders() {
+        let segments = segments_of("Hello $1, you have $2 new messages");
+        assert_eq!(segments.len(), 3);
+
+        assert_eq!(segments[0].text, "Hello ");
+        assert_eq!(segments[0].span, 0..6);
+        assert_eq!(segments[0].before, None);
+        assert_eq!(segments[0].after, Some(placeholder(1)));
+
+        assert_eq!(segments[1].text, ", you have ");
+        assert_eq!(segments[1].before, Some(placeholder(1)));
+        assert_eq!(segments[1].after, Some(placeholder(2)));
+
+        assert_eq!(segments[2].text, " new messages");
+        assert_eq!(segments[2].after, None);
+    }
+
+    #[test]
+    fn test_segments_around_magic_words() {
+        let wikitext = "$1 sent {{PLURAL:$2|a message|$2 messages}} to [[Help:Inbox|your inbox]]";
+        let segments = segments_of(wikitext);
+        assert_eq!(segments.len(), 2);
+
+        let plural = SegmentBoundary::MagicWord {
+            name: "PLURAL".to_string(),
+            param: "$2".to_string(),
+        };
+        assert_eq!(segments[0].text, " sent ");
+        assert_eq!(segments[0].before, Some(placeholder(1)));
+        assert_eq!(segments[0].after, Some(plural.clone()));
+
+        assert_eq!(segments[1].text, " to ");
+        assert_eq!(segments[1].before, Some(plural));
+        assert_eq!(
+            segments[1].after,
+            Some(SegmentBoundary::Link(
+                "[[Help:Inbox|your inbox]]".to_string()
+            ))
+        );
+
+        // Spans index into the serialized message
+        for segment in &segments {
+            assert_eq!(&wikitext[segment.span.clone()], segment.text);
+        }
+    }
+
+    #[test]
+    fn test_whitespace_between_boundaries_is_skipped() {
+        let segments = segments_of("$1 $2");
+        assert!(segments.is_empty());
+
+        let segments = segments_of("Plain text");
+        assert_eq!(segments.len(), 1);
+        assert_eq!(segments[0].span, 0..10);
+    }
+}
@@ -0,0 +1,309 @@
+//! Translation memory interchange in TMX format
+//!
+//! TMX (Translation Memory eXchange) is the format CAT tools use to share
+//! translation memories. `export_tmx()` writes machine-translated source/target
+//! pairs so they can be reviewed in existing tools, and `import_tmx()` reads
+//! them back, e.g. to pre-populate a translation cache.
+//!
+//! Only the subset of TMX 1.4 needed for plain-text segment pairs is supported:
+//! one `<tu>` per pair, with one `<tuv>`/`<seg>` per language. Inline markup
+//! elements inside `<seg>` are not interpreted.
+//!
+//! # Example
+//!
+//! ```ignore
+//! let pairs = vec![("Hello".to_string(), "Bonjour".to_string())];
+//! let xml = export_tmx(&pairs, "en", "fr");
+//! assert_eq!(import_tmx(&xml), pairs);
+//! ```
+
+/// Export source/target pairs as a TMX 1.4 document
+///
+/// Segment text is XML-escaped; newlines inside segments are preserved.
+///
+/// # Arguments
+/// * `pairs` - (source, target) segment pairs
+/// * `src_lang` - Source language code
+/// * `tgt_lang` - Target language code
+///
+/// # Returns
+/// The TMX document as a string
+pub fn export_tmx(pairs: &[(String, String)], src_lang: &str, tgt_lang: &str) -> String {
+    let mut xml = String::new();
+    xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str("<tmx version=\"1.4\">\n");
+    xml.push_str(&format!(
+        "  <header creationtool=\"banana-i18n-mt\" creationtoolversion=\"{}\" \
+         segtype=\"sentence\" o-tmf=\"banana-i18n-mt\" adminlang=\"en\" \
+         srclang=\"{}\" datatype=\"plaintext\"/>\n",
+        env!("CARGO_PKG_VERSION"),
+        escape_xml(src_lang)
+    ));
+    xml.push_str("  <body>\n");
+    for (source, target) in pairs {
+        xml.push_str("    <tu>\n");
+        push_tuv(&mut xml, src_lang, source);
+        push_tuv(&mut xml, tgt_lang, target);
+        xml.push_str("    </tu>\n");
+    }
+    xml.push_str("  </body>\n");
+    xml.push_str("</tmx>\n");
+    xml
+}
+
+/// Import source/target pairs from a TMX document
+///
+/// The source segment of each `<tu>` is the one whose `xml:lang` matches the
+/// header's `srclang` (or the first `<tuv>` if there is no match); the target
+/// is the first other `<tuv>`. Translation units with fewer than two segments
+/// are skipped.
+///
+/// # Arguments
+/// * `xml` - TMX document
+///
+/// # Returns
+/// (source, target) pairs in document order
+pub fn import_tmx(xml: &str) -> Vec<(String, String)> {
+    let src_lang = find_element(xml, "header", 0)
+        .and_then(|(start, _)| attribute(&xml[start..], "srclang"))
+        .map(|lang| lang.to_lowercase());
+
+    let mut pairs = Vec::new();
+    let mut pos = 0;
+    while let Some((tu_start, tu_end)) = find_block(xml, "tu", pos) {
+        pos = tu_end;
+        let unit = &xml[tu_start..tu_end];
+
+        // (language, segment) for each <tuv>
+        let mut variants: Vec<(Option<String>, String)> = Vec::new();
+        let mut tuv_pos = 0;
+        while let Some((tuv_start, tuv_end)) = find_block(unit, "tuv", tuv_pos) {
+            tuv_pos = tuv_end;
+            let tuv = &unit[tuv_start..tuv_end];
+            let lang = attribute(tuv, "xml:lang")
+                .or_else(|| attribute(tuv, "lang"))
+                .map(|lang| lang.to_lowercase());
+            if let Some(seg) = element_text(tuv, "seg") {
+                variants.push((lang, unescape_xml(seg)));
+            }
+        }
+
+        if variants.len() < 2 {
+            continue;
+        }
+
+        let source_idx = src_lang
+            .as_ref()
+            .and_then(|src| {
+                variants
+                    .iter()
+                    .position(|(lang, _)| lang.as_ref() == Some(src))
+            })
+            .unwrap_or(0);
+        let target_idx = if source_idx == 0 { 1 } else { 0 };
+        pairs.push((
+            variants[source_idx].1.clone(),
+            variants[target_idx].1.clone(),
+        ));
+    }
+
+    pairs
+}
+
+/// Append a `<tuv>` element for one language
+fn push_tuv(xml: &mut String, lang: &str, text: &str) {
+    xml.push_str(&format!(
+        "      <tuv xml:lang=\"{}\"><seg>{}</seg></tuv>\n",
+        escape_xml(lang),
+        escape_xml(text)
+    ));
+}
+
+/// Escape text for use in XML content and attribute values
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Resolve XML entities and character references
+fn unescape_xml(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(amp) = rest.find('&') {
+        result.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+
+        let Some(semi) = rest.find(';') else {
+            break;
+        };
+        let entity = &rest[1..semi];
+        let resolved = match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => entity
+                .strip_prefix("#x")
+                .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                .or_else(|| entity.strip_prefix('#').and_then(|dec| dec.parse().ok()))
+                .and_then(char::from_u32),
+        };
+
+        match resolved {
+            Some(c) => {
+                result.push(c);
+                rest = &rest[semi + 1..];
+            }
+            None => {
+                // Not an entity we understand: keep the '&' literally
+                result.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    result.push_str(rest);
+    result
+}
+
+/// Find the start tag `<name ...>` at or after `from`, returning (start, end of tag)
+fn find_element(xml: &str, name: &str, from: usize) -> Option<(usize, usize)> {
+    let open = format!("<{}", name);
+    let mut pos = from;
+    while let Some(offset) = xml[pos..].find(&open) {
+        let start = pos + offset;
+        let after = start + open.len();
+        // Make sure "<tu" does not match "<tuv"
+        match xml[after..].chars().next() {
+            Some(c) if c == '>' || c == '/' || c.is_whitespace() => {
+                let end = after + xml[after..].find('>')? + 1;
+                return Some((start, end));
+            }
+            _ => pos = after,
+        }
+    }
+    None
+}
+
+/// Find a complete `<name>...</name>` block at or after `from`
+fn find_block(xml: &str, name: &str, from: usize) -> Option<(usize, usize)> {
+    let (start, tag_end) = find_element(xml, name, from)?;
+    if xml[..tag_end].ends_with("/>") {
+        return Some((start, tag_end));
+    }
+    let close = format!("</{}>", name);
+    let end = tag_end + xml[tag_end..].find(&close)? + close.len();
+    Some((start, end))
+}
+
+/// Raw content of the first `<name>...</name>` element in `xml`
+fn element_text<'a>(xml: &'a str, name: &str) -> Option<&'a str> {
+    let (_, tag_end) = find_element(xml, name, 0)?;
+    if xml[..tag_end].ends_with("/>") {
+        return Some("");
+    }
+    let close = format!("</{}>", name);
+    let end = tag_end + xml[tag_end..].find(&close)?;
+    Some(&xml[tag_end..end])
+}
+
+/// Value of an attribute in the first start tag of `xml`
+fn attribute(xml: &str, name: &str) -> Option<String> {
+    let tag = &xml[..xml.find('>')?];
+    let mut pos = 0;
+    while let Some(offset) = tag[pos..].find(name) {
+        let start = pos + offset;
+        pos = start + name.len();
+        // Attribute names are preceded by whitespace and followed by '='
+        let preceded = tag[..start].ends_with(char::is_whitespace);
+        let rest = tag[pos..].trim_start();
+        if !preceded || !rest.starts_with('=') {
+            continue;
+        }
+        let value = rest[1..].trim_start();
+        let quote = value.chars().next()?;
+        if quote != '"' && quote != '\'' {
+            return None;
+        }
+        let end = value[1..].find(quote)?;
+        return Some(unescape_xml(&value[1..1 + end]));
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pairs(items: &[(&str, &str)]) -> Vec<(String, String)> {
+        items
+            .iter()
+            .map(|(s, t)| (s.to_string(), t.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_tmx_round_trip() {
+        let original = pairs(&[
+            ("Hello, $1!", "Bonjour, $1 !"),
+            (
+                "Use <b>bold</b> & \"quotes\"",
+                "Utilisez <b>gras</b> & « guillemets »",
+            ),
+            ("Line one\nLine two", "Ligne un\nLigne deux"),
+            (
+                "{{PLURAL:$1|a file|$1 files}}",
+                "{{PLURAL:$1|un fichier|$1 fichiers}}",
+            ),
+        ]);
+
+        let xml = export_tmx(&original, "en", "fr");
+        assert!(xml.contains("&lt;b&gt;bold&lt;/b&gt; &amp; &quot;quotes&quot;"));
+        assert!(xml.contains("srclang=\"en\""));
+        assert!(xml.contains("<tuv xml:lang=\"fr\">"));
+
+        assert_eq!(import_tmx(&xml), original);
+    }
+
+    #[test]
+    fn test_import_uses_header_source_language() {
+        let xml = r#"<?xml version="1.0"?>
+<tmx version="1.4">
+  <header srclang="en-US" datatype="plaintext"/>
+  <body>
+    <tu tuid="1">
+      <tuv xml:lang="de-DE"><seg>Hallo</seg></tuv>
+      <tuv xml:lang="en-us"><seg>Hello</seg></tuv>
+    </tu>
+    <tu><tuv xml:lang="en-US"><seg>Only one side</seg></tuv></tu>
+  </body>
+</tmx>"#;
+
+        assert_eq!(import_tmx(xml), pairs(&[("Hello", "Hallo")]));
+    }
+
+    #[test]
+    fn test_unescape_character_references() {
+        assert_eq!(unescape_xml("a &#60; b &#x3E; c"), "a < b > c");
+        assert_eq!(unescape_xml("R&D &unknown; x"), "R&D &unknown; x");
+    }
+
+    #[test]
+    fn test_export_empty() {
+        let xml = export_tmx(&[], "en", "fr");
+        assert!(xml.contains("<body>"));
+        assert!(import_tmx(&xml).is_empty());
+    }
+}
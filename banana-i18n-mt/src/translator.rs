@@ -27,8 +27,124 @@
 //! }
 //! ```
 
-use crate::error::MtResult;
+use crate::anchors::AnchorStyle;
+use crate::error::{MtError, MtResult};
 use async_trait::async_trait;
+use std::collections::HashSet;
+
+/// Register of address for languages that distinguish formal and informal "you"
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Formality {
+    /// Formal address (German Sie, French vous)
+    Formal,
+    /// Informal address (German du, French tu)
+    Informal,
+}
+
+/// Gender of the person a text refers to, in GENDER option order
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubjectGender {
+    Male,
+    Female,
+    Unknown,
+}
+
+impl SubjectGender {
+    /// The gender for a GENDER option index (0 = male, 1 = female, 2 = unknown)
+    pub fn from_index(index: usize) -> Option<Self> {
+        match index {
+            0 => Some(SubjectGender::Male),
+            1 => Some(SubjectGender::Female),
+            2 => Some(SubjectGender::Unknown),
+            _ => None,
+        }
+    }
+
+    /// A short English sentence describing the subject, usable as context
+    pub fn description(&self) -> &'static str {
+        match self {
+            SubjectGender::Male => "The person referred to is male.",
+            SubjectGender::Female => "The person referred to is female.",
+            SubjectGender::Unknown => "The gender of the person referred to is unknown.",
+        }
+    }
+}
+
+/// Per-request hints for providers that support them
+///
+/// Providers that cannot use a hint ignore it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TranslationOptions {
+    /// Preferred register of address
+    pub formality: Option<Formality>,
+    /// Subject area of the text (e.g. "software user interface")
+    pub domain: Option<String>,
+    /// Gender of the person the text refers to or addresses
+    pub subject_gender: Option<SubjectGender>,
+    /// Text the translated texts belong to (e.g. the whole source message),
+    /// used to disambiguate but not translated
+    pub context: Option<String>,
+}
+
+/// Source and target languages a provider can translate between
+///
+/// Codes are compared case-insensitively with `_` read as `-`. A locale with
+/// a region or script ("pt-BR", "zh-Hant") is supported when either the full
+/// code or its base language is listed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LanguageMatrix {
+    sources: HashSet<String>,
+    targets: HashSet<String>,
+}
+
+impl LanguageMatrix {
+    /// Create a matrix from the supported source and target languages
+    ///
+    /// Every listed source can be translated into every listed target.
+    ///
+    /// # Arguments
+    ///
+    /// * `sources` - Language codes accepted as source
+    /// * `targets` - Language codes accepted as target
+    pub fn new<S: AsRef<str>, T: AsRef<str>>(
+        sources: impl IntoIterator<Item = S>,
+        targets: impl IntoIterator<Item = T>,
+    ) -> Self {
+        LanguageMatrix {
+            sources: sources
+                .into_iter()
+                .map(|code| canonical_code(code.as_ref()))
+                .collect(),
+            targets: targets
+                .into_iter()
+                .map(|code| canonical_code(code.as_ref()))
+                .collect(),
+        }
+    }
+
+    /// Check whether text can be translated from `source_locale` into `target_locale`
+    ///
+    /// # Arguments
+    ///
+    /// * `source_locale` - Source language code (e.g., "en", "en-US")
+    /// * `target_locale` - Target language code (e.g., "pt-BR")
+    ///
+    /// # Returns
+    ///
+    /// `true` if both languages are in the matrix
+    pub fn can_translate(&self, source_locale: &str, target_locale: &str) -> bool {
+        Self::contains(&self.sources, source_locale) && Self::contains(&self.targets, target_locale)
+    }
+
+    fn contains(codes: &HashSet<String>, locale: &str) -> bool {
+        let code = canonical_code(locale);
+        codes.contains(&code) || codes.contains(&normalize_locale(&code))
+    }
+}
+
+fn canonical_code(locale: &str) -> String {
+    locale.trim().to_lowercase().replace('_', "-")
+}
 
 /// Generic trait for machine translation providers
 ///
@@ -101,6 +217,78 @@ pub trait MachineTranslator: Send + Sync {
         target_locale: &str,
     ) -> MtResult<Vec<String>>;
 
+    /// Translate a single text with per-request options
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The text to translate
+    /// * `source_locale` - Source language code
+    /// * `target_locale` - Target language code
+    /// * `options` - Formality and domain hints
+    ///
+    /// # Returns
+    ///
+    /// The default ignores `options` and calls `translate()`
+    async fn translate_with_options(
+        &self,
+        text: &str,
+        source_locale: &str,
+        target_locale: &str,
+        options: &TranslationOptions,
+    ) -> MtResult<String> {
+        let _ = options;
+        self.translate(text, source_locale, target_locale).await
+    }
+
+    /// Translate a batch with per-request options
+    ///
+    /// # Returns
+    ///
+    /// The default ignores `options` and calls `translate_batch()`
+    async fn translate_batch_with_options(
+        &self,
+        texts: &[String],
+        source_locale: &str,
+        target_locale: &str,
+        options: &TranslationOptions,
+    ) -> MtResult<Vec<String>> {
+        let _ = options;
+        self.translate_batch(texts, source_locale, target_locale)
+            .await
+    }
+
+    /// Translate a batch, giving the provider surrounding text as context
+    ///
+    /// Only `texts` are translated (and billed); `context` helps
+    /// context-aware providers pick the right sense of ambiguous words.
+    ///
+    /// # Arguments
+    ///
+    /// * `texts` - Strings to translate
+    /// * `context` - Surrounding text, e.g. the full source message
+    /// * `source_locale` - Source language code
+    /// * `target_locale` - Target language code
+    ///
+    /// # Returns
+    ///
+    /// The default passes `context` as `TranslationOptions::context` to
+    /// `translate_batch_with_options()`, so providers that do not read it
+    /// ignore the context
+    async fn translate_with_context(
+        &self,
+        texts: &[String],
+        context: &str,
+        source_locale: &str,
+        target_locale: &str,
+    ) -> MtResult<Vec<String>> {
+        let options = TranslationOptions {
+            context: Some(context.to_string()),
+            ..Default::default()
+        };
+        self.translate_batch_with_options(texts, source_locale, target_locale, &options)
+            .await
+    }
+
     /// Get the name of this translation provider
     ///
     /// Used for logging and debugging to identify which provider handled a translation.
@@ -109,6 +297,321 @@ pub trait MachineTranslator: Send + Sync {
     ///
     /// A string identifying the provider (e.g., "Google Translate", "Mock Translator")
     fn provider_name(&self) -> &str;
+
+    /// How anchor tokens should be written in text sent to this provider
+    ///
+    /// The pipeline renders anchors in this style before translation and
+    /// recovers them afterwards (see `AnchorStyle`). Override it for engines
+    /// that mangle numeric anchors but keep another form intact.
+    ///
+    /// # Returns
+    ///
+    /// `AnchorStyle::Numeric` unless the provider overrides it
+    fn preferred_anchor_style(&self) -> AnchorStyle {
+        AnchorStyle::Numeric
+    }
+
+    /// Whether PLURAL variants should show the provider a number
+    ///
+    /// An anchor such as `777001` tells the engine nothing about quantity, so
+    /// it may pick the wrong agreement for the surrounding words. When this
+    /// returns `true`, `translate_message()` replaces the anchor of a PLURAL
+    /// parameter with a representative number of the variant's plural
+    /// category (e.g. `5` for "many") and swaps the anchor back afterwards.
+    ///
+    /// # Returns
+    ///
+    /// `false` unless the provider overrides it
+    fn wants_plural_hints(&self) -> bool {
+        false
+    }
+
+    /// Whether `translate_batch` sends all texts in a single request
+    ///
+    /// Providers with a native batch API (Google, Yandex, Azure, DeepL) return
+    /// `true`; callers can then send one request. For providers without one, a
+    /// batch costs one request per text, so the pipeline fans out concurrent
+    /// `translate` requests instead.
+    ///
+    /// # Returns
+    ///
+    /// `false` unless the provider overrides it
+    fn supports_native_batch(&self) -> bool {
+        false
+    }
+
+    /// The language pairs this provider can translate, if known
+    ///
+    /// Lets callers hide unsupported languages or skip requests that would
+    /// fail anyway, by checking `LanguageMatrix::can_translate()` first.
+    ///
+    /// # Returns
+    ///
+    /// `None` (assume every pair is supported) unless the provider overrides it
+    fn supported_pairs(&self) -> Option<LanguageMatrix> {
+        None
+    }
+
+    /// Translate multiple variants as a single block
+    ///
+    /// This method implements the Python `translate_as_block()` approach,
+    /// joining all variants into one text to force MT consistency. All
+    /// variants are translated in one `translate()` call, ensuring the MT
+    /// system sees the context and maintains consistency across related
+    /// variants. Being a default method, it is available on any provider,
+    /// including boxed ones returned by `from_config()`.
+    ///
+    /// Variants are joined with `DEFAULT_BLOCK_SEPARATOR`; see
+    /// `translate_as_block_with_separator()` for how the block is split back.
+    ///
+    /// # Arguments
+    /// * `variants` - Vector of strings to translate as a block
+    /// * `source_locale` - Source language code
+    /// * `target_locale` - Target language code
+    ///
+    /// # Returns
+    /// * `Ok(Vec<String>)` - Translated variants in same order as input
+    /// * `Err(MtError)` - If translation fails
+    ///
+    /// # Example
+    /// ```ignore
+    /// let variants = vec![
+    ///     "777001 sent a message".to_string(),
+    ///     "777001 sent 777002 messages".to_string(),
+    /// ];
+    /// let results = provider.translate_as_block(&variants, "en", "fr").await?;
+    /// // Results maintain consistency: ["777001 a envoyé un message", "777001 a envoyé 777002 messages"]
+    /// ```
+    async fn translate_as_block(
+        &self,
+        variants: &[String],
+        source_locale: &str,
+        target_locale: &str,
+    ) -> MtResult<Vec<String>> {
+        self.translate_as_block_with_separator(
+            variants,
+            DEFAULT_BLOCK_SEPARATOR,
+            source_locale,
+            target_locale,
+        )
+        .await
+    }
+
+    /// Translate multiple variants as a single block joined by `separator`
+    ///
+    /// The translated block is split at the separator with surrounding
+    /// whitespace ignored (its trimmed form is what has to survive MT), and
+    /// each translation is trimmed. If MT added, dropped or altered a
+    /// separator, splitting would pair translations with the wrong variants,
+    /// so the variants are translated one by one with `translate_batch()`
+    /// instead, and the fallback is passed to `report_warning()`. The same
+    /// happens when a variant itself contains the separator.
+    ///
+    /// # Arguments
+    /// * `variants` - Vector of strings to translate as a block
+    /// * `separator` - Text placed between variants, e.g. `"\n§§§\n"`
+    /// * `source_locale` - Source language code
+    /// * `target_locale` - Target language code
+    ///
+    /// # Returns
+    /// * `Ok(Vec<String>)` - Translated variants in same order as input
+    /// * `Err(MtError)` - If translation fails
+    ///
+    /// # Algorithm (after Python lines 145-186)
+    /// ```text
+    /// 1. Join variants with the separator
+    /// 2. Translate the entire block as single text
+    /// 3. Split back at the separator; on a count mismatch, translate per item
+    /// 4. Clean up any anchor token spacing issues
+    /// ```
+    async fn translate_as_block_with_separator(
+        &self,
+        variants: &[String],
+        separator: &str,
+        source_locale: &str,
+        target_locale: &str,
+    ) -> MtResult<Vec<String>> {
+        translate_block(
+            self,
+            variants,
+            separator,
+            source_locale,
+            target_locale,
+            &|warning| self.report_warning(warning),
+        )
+        .await
+    }
+
+    /// Report a non-fatal problem noticed by a default method
+    ///
+    /// Called, for instance, when `translate_as_block_with_separator()` falls
+    /// back to translating variants one by one. Ignored by default;
+    /// `LoggingTranslator` writes it to its log.
+    ///
+    /// # Arguments
+    /// * `warning` - Description of the problem
+    fn report_warning(&self, warning: &str) {
+        let _ = warning;
+    }
+
+    /// Check that the provider is reachable and its credentials are valid
+    ///
+    /// Meant to be called before a long batch job or at server startup, so
+    /// configuration problems surface immediately rather than on the first
+    /// real translation. The default translates a tiny fixed string;
+    /// providers with a cheaper authenticated endpoint should override it.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - The provider is ready
+    /// * `Err(MtError)` - Connectivity, authentication, or response error
+    async fn health_check(&self) -> MtResult<()> {
+        let result = self.translate("ok", "en", "fr").await?;
+        if result.trim().is_empty() {
+            return Err(MtError::TranslationError(
+                format!(
+                    "{} returned an empty translation during health check",
+                    self.provider_name()
+                )
+                .into(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Block translation behind `MachineTranslator::translate_as_block_with_separator()`
+///
+/// Fallbacks to per-item translation are passed to `report`.
+pub(crate) async fn translate_block<T: MachineTranslator + ?Sized>(
+    provider: &T,
+    variants: &[String],
+    separator: &str,
+    source_locale: &str,
+    target_locale: &str,
+    report: &(dyn Fn(&str) + Send + Sync),
+) -> MtResult<Vec<String>> {
+    // Handle empty case
+    if variants.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    // Handle single variant case
+    if variants.len() == 1 {
+        let result = provider
+            .translate(&variants[0], source_locale, target_locale)
+            .await?;
+        return Ok(vec![result]);
+    }
+
+    let marker = match separator.trim() {
+        "" => separator,
+        trimmed => trimmed,
+    };
+    if marker.is_empty() || variants.iter().any(|v| v.contains(marker)) {
+        report(&format!(
+            "block separator {:?} occurs in the variants; translating them one by one",
+            separator
+        ));
+        return provider
+            .translate_batch(variants, source_locale, target_locale)
+            .await;
+    }
+
+    // 1. Join with the separator
+    let input_block = variants.join(separator);
+
+    // 2. Translate the entire block
+    let translated_block = provider
+        .translate(&input_block, source_locale, target_locale)
+        .await?;
+
+    // 3. Split back, ignoring whitespace MT added around the separator
+    let lines: Vec<String> = translated_block
+        .split(marker)
+        .map(|s| s.trim().to_string())
+        .collect();
+
+    // Safety check: same count (Python lines 173-175)
+    if lines.len() != variants.len() {
+        report(&format!(
+            "block translation returned {} parts for {} variants; translating them one by one",
+            lines.len(),
+            variants.len()
+        ));
+        return provider
+            .translate_batch(variants, source_locale, target_locale)
+            .await;
+    }
+
+    // 4. Clean up anchor token mangling (Python lines 177-180)
+    // Sometimes MT systems add spaces: "777 001" instead of "777001"
+    let cleaned: Vec<String> = lines
+        .iter()
+        .map(|line| {
+            // Fix common anchor mangling patterns
+            line.replace("777 ", "777") // "777 001" → "777001"
+                .replace(" 777", "777") // " 777001" → "777001"
+        })
+        .collect();
+
+    Ok(cleaned)
+}
+
+/// Separator `translate_as_block()` joins variants with
+///
+/// A symbol MT engines leave alone, on a line of its own so it does not
+/// attach to the words around it.
+pub const DEFAULT_BLOCK_SEPARATOR: &str = "\n§§§\n";
+
+/// Provider names accepted by `from_config()`
+pub const SUPPORTED_PROVIDERS: &[&str] = &["google", "deepl", "yandex", "azure", "mock"];
+
+/// Construct a provider by name, for configuration-driven setups
+///
+/// Dispatches to the provider's `from_env()`, so credentials come from the
+/// usual environment variables. Names are matched case-insensitively. The
+/// `mock` provider is a `MockTranslator` in `MockMode::Suffix` and needs no
+/// credentials.
+///
+/// # Arguments
+///
+/// * `name` - Provider name, one of `SUPPORTED_PROVIDERS`
+///
+/// # Returns
+///
+/// * `Ok(Box<dyn MachineTranslator>)` - The configured provider
+/// * `Err(MtError::ConfigError)` - Unknown name (the message lists the
+///   supported names) or missing credentials
+///
+/// # Example
+///
+/// ```ignore
+/// let provider = from_config(&settings.provider)?;
+/// let translated = provider.translate("Hello", "en", "fr").await?;
+/// ```
+pub fn from_config(name: &str) -> MtResult<Box<dyn MachineTranslator>> {
+    use crate::azure_translate::AzureTranslateProvider;
+    use crate::deepl_translate::DeepLProvider;
+    use crate::google_translate::GoogleTranslateProvider;
+    use crate::mock::{MockMode, MockTranslator};
+    use crate::yandex_translate::YandexProvider;
+
+    match name.trim().to_lowercase().as_str() {
+        "google" => Ok(Box::new(GoogleTranslateProvider::from_env()?)),
+        "deepl" => Ok(Box::new(DeepLProvider::from_env()?)),
+        "yandex" => Ok(Box::new(YandexProvider::from_env()?)),
+        "azure" => Ok(Box::new(AzureTranslateProvider::from_env()?)),
+        "mock" => Ok(Box::new(MockTranslator::new(MockMode::Suffix))),
+        _ => Err(MtError::ConfigError(
+            format!(
+                "Unknown translation provider '{}'; supported providers: {}",
+                name,
+                SUPPORTED_PROVIDERS.join(", ")
+            )
+            .into(),
+        )),
+    }
 }
 
 /// Normalize a locale code by stripping region information
@@ -162,7 +665,7 @@ pub fn normalize_locale(locale: &str) -> String {
 pub fn validate_locale(locale: &str) -> MtResult<()> {
     if locale.is_empty() {
         return Err(crate::error::MtError::InvalidLocale(
-            "Locale code is empty".to_string(),
+            "Locale code is empty".into(),
         ));
     }
 
@@ -171,10 +674,9 @@ pub fn validate_locale(locale: &str) -> MtResult<()> {
         .chars()
         .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
     {
-        return Err(crate::error::MtError::InvalidLocale(format!(
-            "Invalid characters in locale code: {}",
-            locale
-        )));
+        return Err(crate::error::MtError::InvalidLocale(
+            format!("Invalid characters in locale code: {}", locale).into(),
+        ));
     }
 
     Ok(())
@@ -234,6 +736,50 @@ mod tests {
         assert!(validate_locale("es!error").is_err());
     }
 
+    #[test]
+    fn test_supports_native_batch_per_provider() {
+        use crate::google_translate::GoogleTranslateProvider;
+        use crate::mock::{MockMode, MockTranslator};
+        use crate::yandex_translate::YandexProvider;
+
+        let google = GoogleTranslateProvider::new("test-key".to_string()).unwrap();
+        let yandex = YandexProvider::new("token".to_string(), "folder".to_string()).unwrap();
+        let mock = MockTranslator::new(MockMode::Suffix);
+
+        let providers: Vec<&dyn MachineTranslator> = vec![&google, &yandex, &mock];
+        let reported: Vec<(&str, bool)> = providers
+            .iter()
+            .map(|p| (p.provider_name(), p.supports_native_batch()))
+            .collect();
+
+        assert_eq!(
+            reported,
+            vec![
+                ("Google Translate", true),
+                ("Yandex Translate", true),
+                ("Mock Translator", true),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_default_health_check() {
+        use crate::mock::{MockMode, MockTranslator};
+
+        assert!(
+            MockTranslator::new(MockMode::Suffix)
+                .health_check()
+                .await
+                .is_ok()
+        );
+
+        let failing = MockTranslator::new(MockMode::Error("invalid key".to_string()));
+        match failing.health_check().await {
+            Err(crate::error::MtError::TranslationError(msg)) => assert_eq!(msg, "invalid key"),
+            other => panic!("Expected TranslationError, got {:?}", other),
+        }
+    }
+
     #[test]
     fn test_validate_locale_error_messages() {
         use crate::error::MtError;
@@ -244,4 +790,139 @@ mod tests {
             _ => panic!("Expected InvalidLocale error"),
         }
     }
+
+    #[tokio::test]
+    async fn test_translate_with_options_defaults_to_translate() {
+        use crate::mock::{MockMode, MockTranslator};
+
+        let provider = MockTranslator::new(MockMode::Suffix);
+        let options = TranslationOptions {
+            formality: Some(Formality::Informal),
+            domain: None,
+            subject_gender: None,
+            context: None,
+        };
+        let result = provider
+            .translate_with_options("Hello", "en", "de", &options)
+            .await
+            .unwrap();
+        assert_eq!(result, "Hello_de");
+    }
+
+    // ========== Block Translation Tests ==========
+
+    fn block_mock(table: &[(&str, &str)]) -> crate::mock::MockTranslator {
+        use crate::mock::{MockMode, MockTranslator};
+
+        let map = table
+            .iter()
+            .map(|(en, fr)| ((en.to_string(), "fr".to_string()), fr.to_string()))
+            .collect();
+        MockTranslator::new(MockMode::Mappings(map))
+    }
+
+    #[tokio::test]
+    async fn test_translate_as_block_trims_around_separator() {
+        let provider = block_mock(&[("Hello\n§§§\nGoodbye", "Bonjour \n §§§\n\nAu revoir ")]);
+        let variants = vec!["Hello".to_string(), "Goodbye".to_string()];
+
+        let result = provider
+            .translate_as_block(&variants, "en", "fr")
+            .await
+            .unwrap();
+        assert_eq!(result, vec!["Bonjour", "Au revoir"]);
+        assert_eq!(provider.call_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_translate_as_block_falls_back_on_mangled_separator() {
+        let provider = block_mock(&[
+            ("Hello\n§§§\nGoodbye", "Bonjour\n§ §\nAu revoir"),
+            ("Hello", "Salut"),
+            ("Goodbye", "Au revoir"),
+        ]);
+        let variants = vec!["Hello".to_string(), "Goodbye".to_string()];
+
+        let result = provider
+            .translate_as_block(&variants, "en", "fr")
+            .await
+            .unwrap();
+        assert_eq!(result, vec!["Salut", "Au revoir"]);
+        // The block, then one batch with the variants
+        assert_eq!(provider.call_count(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_translate_as_block_with_custom_separator() {
+        let provider = block_mock(&[]);
+        let variants = vec!["777001 left".to_string(), "777001 stayed".to_string()];
+
+        let result = provider
+            .translate_as_block_with_separator(&variants, " ||| ", "en", "fr")
+            .await
+            .unwrap();
+        assert_eq!(result, vec!["777001 left", "777001 stayed_fr"]);
+
+        // A separator occurring in a variant cannot split the block back
+        let variants = vec!["a ||| b".to_string(), "c".to_string()];
+        let result = provider
+            .translate_as_block_with_separator(&variants, " ||| ", "en", "fr")
+            .await
+            .unwrap();
+        assert_eq!(result, vec!["a ||| b_fr", "c_fr"]);
+    }
+
+    // ========== Language Matrix Tests ==========
+
+    #[test]
+    fn test_supported_pairs_can_translate() {
+        use crate::deepl_translate::DeepLProvider;
+        use crate::mock::{MockMode, MockTranslator};
+
+        let deepl = DeepLProvider::new("key:fx".to_string()).unwrap();
+        let matrix = deepl.supported_pairs().unwrap();
+        assert!(matrix.can_translate("en", "de"));
+        assert!(matrix.can_translate("EN-US", "pt_BR"));
+        assert!(matrix.can_translate("de", "zh-Hant"));
+        assert!(!matrix.can_translate("en", "ml"));
+        assert!(!matrix.can_translate("ml", "en"));
+
+        // Providers without a known matrix support every pair
+        assert!(
+            MockTranslator::new(MockMode::Suffix)
+                .supported_pairs()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_language_matrix_sources_and_targets_differ() {
+        let matrix = LanguageMatrix::new(["en"], ["fr", "pt-BR"]);
+        assert!(matrix.can_translate("en-GB", "fr"));
+        assert!(matrix.can_translate("en", "pt-br"));
+        // Only the regional code is listed, not the base language
+        assert!(!matrix.can_translate("en", "pt"));
+        assert!(!matrix.can_translate("fr", "en"));
+    }
+
+    // ========== Provider Factory Tests ==========
+
+    #[tokio::test]
+    async fn test_from_config_mock() {
+        let provider = from_config("Mock").unwrap();
+        assert_eq!(provider.provider_name(), "Mock Translator");
+        let result = provider.translate("Hello", "en", "fr").await.unwrap();
+        assert_eq!(result, "Hello_fr");
+    }
+
+    #[test]
+    fn test_from_config_unknown_lists_supported_names() {
+        match from_config("nope") {
+            Err(MtError::ConfigError(msg)) => {
+                assert!(msg.contains("'nope'"));
+                assert!(msg.contains("google, deepl, yandex, azure, mock"));
+            }
+            _ => panic!("Expected ConfigError"),
+        }
+    }
 }
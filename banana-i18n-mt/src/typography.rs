@@ -0,0 +1,497 @@
+//! Locale-specific typography post-processing for translated wikitext
+//!
+//! MT systems are inconsistent about locale typography conventions. French,
+//! for example, requires a no-break space before high punctuation (`: ; ! ?`),
+//! which MT output may include as a plain space, a no-break space, or not at
+//! all. `apply_typography()` normalizes these spaces after reassembly.
+//!
+//! Wikitext syntax is left alone: magic word headers (`{{PLURAL:`), internal
+//! link targets (`[[Special:Page`), HTML tags and URLs are never modified.
+//!
+//! Quotation marks are a second source of inconsistency: one variant may come
+//! back with straight quotes and another with the target language's marks.
+//! `normalize_quotes()` rewrites all quotation marks to the locale's primary
+//! and secondary pairs, so variants agree before they are reassembled.
+//!
+//! # Example
+//!
+//! ```ignore
+//! assert_eq!(apply_typography("Bonjour!", "fr"), "Bonjour\u{202F}!");
+//! assert_eq!(normalize_quotes("Er sagte \"Hallo\"", "de"), "Er sagte „Hallo“");
+//! ```
+
+use banana_i18n::is_cjk;
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// No-break space (U+00A0)
+const NBSP: char = '\u{00A0}';
+
+/// Narrow no-break space (U+202F)
+const NNBSP: char = '\u{202F}';
+
+/// A punctuation mark and the space that must precede it
+#[derive(Debug, Clone, Copy)]
+struct SpaceBefore {
+    punctuation: char,
+    space: char,
+}
+
+/// Typography rules by base language code
+const TYPOGRAPHY_RULES: &[(&str, &[SpaceBefore])] = &[(
+    "fr",
+    &[
+        SpaceBefore {
+            punctuation: ':',
+            space: NBSP,
+        },
+        SpaceBefore {
+            punctuation: ';',
+            space: NNBSP,
+        },
+        SpaceBefore {
+            punctuation: '!',
+            space: NNBSP,
+        },
+        SpaceBefore {
+            punctuation: '?',
+            space: NNBSP,
+        },
+    ],
+)];
+
+/// Regions that must never be touched: URLs (including those without `//`,
+/// such as `mailto:`), magic word headers, link targets, HTML tags and HTML
+/// entities (`&amp;`, `&#160;`)
+static PROTECTED: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(&format!(
+        r"(?:[A-Za-z][A-Za-z0-9+.-]*:)?//[^\s\]]+|{}|\{{\{{[^{{}}|:]*:|\[\[[^\]|]*|<[^<>]*>|&#?\w+;",
+        SCHEME_URI
+    ))
+    .unwrap()
+});
+
+/// URIs of the schemes MediaWiki links without `//` (`mailto:`, `tel:`, ...)
+const SCHEME_URI: &str =
+    r"\b(?i:mailto|news|tel|sip|sips|sms|urn|xmpp|geo|magnet|bitcoin):[^\s\]<>|]+";
+
+/// Quotation marks of a language: (primary open, primary close, secondary open, secondary close)
+type QuoteMarks = (char, char, char, char);
+
+/// Quotation marks by base language code
+const QUOTE_MARKS: &[(&str, QuoteMarks)] = &[
+    ("de", ('„', '“', '‚', '‘')),
+    ("en", ('“', '”', '‘', '’')),
+    ("es", ('«', '»', '“', '”')),
+    ("fr", ('«', '»', '“', '”')),
+    ("it", ('«', '»', '“', '”')),
+    ("ja", ('「', '」', '『', '』')),
+    ("nl", ('“', '”', '‘', '’')),
+    ("pl", ('„', '”', '«', '»')),
+    ("pt", ('“', '”', '‘', '’')),
+    ("ru", ('«', '»', '„', '“')),
+    ("uk", ('«', '»', '„', '“')),
+];
+
+/// Characters read as double quotation marks, in any direction
+const DOUBLE_QUOTES: &[char] = &['"', '“', '”', '„', '«', '»', '「', '」'];
+
+/// Characters read as single quotation marks (or apostrophes), in any direction
+const SINGLE_QUOTES: &[char] = &['\'', '‘', '’', '‚', '『', '』'];
+
+/// Regions quotes are never replaced in: URLs, wikitext headers, HTML tags
+/// and runs of apostrophes (`''italic''`, `'''bold'''`)
+static QUOTE_PROTECTED: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(&format!(
+        r"(?:[A-Za-z][A-Za-z0-9+.-]*:)?//[^\s\]]+|{}|\{{\{{[^{{}}|:]*:|\[\[[^\]|]*|<[^<>]*>|'{{2,}}",
+        SCHEME_URI
+    ))
+    .unwrap()
+});
+
+/// Base language code of a locale ("fr-CA", "fr_BE" → "fr")
+fn base_language(locale: &str) -> String {
+    locale
+        .split(['-', '_'])
+        .next()
+        .unwrap_or(locale)
+        .to_lowercase()
+}
+
+/// Look up the typography rules for a locale ("fr", "fr-CA", "fr_BE" → French)
+fn rules_for(locale: &str) -> Option<&'static [SpaceBefore]> {
+    let language = base_language(locale);
+    TYPOGRAPHY_RULES
+        .iter()
+        .find(|(code, _)| *code == language)
+        .map(|(_, rules)| *rules)
+}
+
+/// Apply locale typography conventions to translated text
+///
+/// For French, inserts (or normalizes to) a narrow no-break space before
+/// `;`, `!` and `?`, and a no-break space before `:`. Existing plain,
+/// no-break, or narrow no-break spaces before these marks are replaced.
+/// Locales without rules are returned unchanged.
+///
+/// Punctuation inside URLs (`mailto:` ones too), magic word headers
+/// (`{{GENDER:`), internal link targets, HTML tags and HTML entities
+/// (`&amp;`) is never modified, nor are colons
+/// between digits (`10:30`) or punctuation at the start of a line.
+///
+/// # Arguments
+/// * `text` - Reassembled wikitext with placeholders restored
+/// * `locale` - Target locale code
+///
+/// # Returns
+/// The text with typography normalized for the locale
+pub fn apply_typography(text: &str, locale: &str) -> String {
+    let Some(rules) = rules_for(locale) else {
+        return text.to_string();
+    };
+
+    let mut result = String::with_capacity(text.len());
+    let mut last_end = 0;
+    for protected in PROTECTED.find_iter(text) {
+        apply_rules(&text[last_end..protected.start()], rules, &mut result);
+        result.push_str(protected.as_str());
+        last_end = protected.end();
+    }
+    apply_rules(&text[last_end..], rules, &mut result);
+
+    result
+}
+
+/// Apply spacing rules to an unprotected segment, appending to `result`
+fn apply_rules(segment: &str, rules: &[SpaceBefore], result: &mut String) {
+    let chars: Vec<char> = segment.chars().collect();
+
+    for (i, &c) in chars.iter().enumerate() {
+        let Some(rule) = rules.iter().find(|r| r.punctuation == c) else {
+            result.push(c);
+            continue;
+        };
+
+        // Drop whatever space MT put before the mark
+        let trimmed_len = result.trim_end_matches([' ', NBSP, NNBSP]).len();
+        let removed = result.split_off(trimmed_len);
+
+        let prev = result.chars().last();
+        let next = chars.get(i + 1).copied();
+        let keep_as_is = match prev {
+            // Start of text or line: nothing to attach to
+            None | Some('\n') => true,
+            // Runs like "?!" only get a space before the first mark
+            Some(p) if rules.iter().any(|r| r.punctuation == p) => true,
+            // Times and ratios like 10:30
+            Some(p)
+                if c == ':' && p.is_ascii_digit() && next.is_some_and(|n| n.is_ascii_digit()) =>
+            {
+                true
+            }
+            _ => false,
+        };
+
+        if keep_as_is {
+            result.push_str(&removed);
+        } else {
+            result.push(rule.space);
+        }
+        result.push(c);
+    }
+}
+
+/// Replace quotation marks with the locale's conventional marks
+///
+/// Straight quotes and typographic quotes of any language are recognized.
+/// Whether a mark opens or closes is decided from its neighbours: after the
+/// start of text, whitespace, an opening bracket, a wikitext `|`, the end of
+/// an HTML tag or another opening mark it
+/// opens, otherwise it closes. CJK scripts do not put spaces around
+/// quotations, so there a double quote also opens when no double quotation
+/// is open (`彼は"はい"と言った`). The outermost quotation gets the primary
+/// marks and a quotation nested inside it the secondary marks (German
+/// `„…‚…‘…“`), alternating with depth. A single quote between letters, or a
+/// closing single quote with no quotation open, is an apostrophe and is left
+/// alone.
+///
+/// Quotes inside URLs, magic word headers, internal link targets and HTML
+/// tags are never modified, nor are the apostrophes of italic and bold
+/// markup. Anchor tokens contain no quotes and are unaffected. Locales
+/// without known quotation marks are returned unchanged.
+///
+/// # Arguments
+/// * `text` - Translated text (anchors recovered or placeholders restored)
+/// * `locale` - Target locale code
+///
+/// # Returns
+/// The text with quotation marks normalized for the locale
+pub fn normalize_quotes(text: &str, locale: &str) -> String {
+    let language = base_language(locale);
+    let Some(&(_, marks)) = QUOTE_MARKS.iter().find(|(code, _)| *code == language) else {
+        return text.to_string();
+    };
+    let cjk = is_cjk(locale);
+
+    let mut result = String::with_capacity(text.len());
+    // Open quotations, innermost last: true for double quotes
+    let mut open: Vec<bool> = Vec::new();
+    let mut last_end = 0;
+    for protected in QUOTE_PROTECTED.find_iter(text) {
+        replace_quotes(
+            &text[last_end..protected.start()],
+            marks,
+            cjk,
+            &mut open,
+            &mut result,
+        );
+        result.push_str(protected.as_str());
+        last_end = protected.end();
+    }
+    replace_quotes(&text[last_end..], marks, cjk, &mut open, &mut result);
+
+    result
+}
+
+/// Replace quotes in an unprotected segment, appending to `result`
+fn replace_quotes(
+    segment: &str,
+    marks: QuoteMarks,
+    cjk: bool,
+    open: &mut Vec<bool>,
+    result: &mut String,
+) {
+    let chars: Vec<char> = segment.chars().collect();
+
+    for (i, &c) in chars.iter().enumerate() {
+        let double = DOUBLE_QUOTES.contains(&c);
+        if !double && !SINGLE_QUOTES.contains(&c) {
+            result.push(c);
+            continue;
+        }
+
+        let prev = result.chars().last();
+        let next = chars.get(i + 1).copied();
+        let opens = match prev {
+            None => true,
+            Some(p) => {
+                p.is_whitespace()
+                    || "([{|>-–—/".contains(p)
+                    || [marks.0, marks.2].contains(&p) && !next.is_none_or(char::is_whitespace)
+                    || cjk && double && !open.contains(&true)
+            }
+        };
+
+        if !double {
+            let between_letters =
+                prev.is_some_and(char::is_alphanumeric) && next.is_some_and(char::is_alphanumeric);
+            if between_letters || (!opens && open.last() != Some(&false)) {
+                // Apostrophe: "don't", "l'homme", "the users' files"
+                result.push(c);
+                continue;
+            }
+        }
+
+        if opens {
+            let level = open.len();
+            open.push(double);
+            result.push(if level.is_multiple_of(2) {
+                marks.0
+            } else {
+                marks.2
+            });
+        } else {
+            // Close the innermost quotation of this kind
+            match open.iter().rposition(|&d| d == double) {
+                Some(pos) => {
+                    open.truncate(pos);
+                    result.push(if pos.is_multiple_of(2) {
+                        marks.1
+                    } else {
+                        marks.3
+                    });
+                }
+                None => result.push(c),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_french_high_punctuation() {
+        assert_eq!(apply_typography("Bonjour!", "fr"), "Bonjour\u{202F}!");
+        assert_eq!(apply_typography("Vraiment ?", "fr"), "Vraiment\u{202F}?");
+        assert_eq!(apply_typography("Oui; non", "fr"), "Oui\u{202F}; non");
+        assert_eq!(apply_typography("Note : lire", "fr"), "Note\u{00A0}: lire");
+    }
+
+    #[test]
+    fn test_normalizes_existing_spaces() {
+        assert_eq!(
+            apply_typography("Bonjour\u{00A0}!", "fr"),
+            "Bonjour\u{202F}!"
+        );
+        assert_eq!(
+            apply_typography("Bonjour\u{202F}!", "fr"),
+            "Bonjour\u{202F}!"
+        );
+        assert_eq!(apply_typography("Bonjour  !", "fr"), "Bonjour\u{202F}!");
+    }
+
+    #[test]
+    fn test_regional_variants_and_other_locales() {
+        assert_eq!(apply_typography("Salut!", "fr-CA"), "Salut\u{202F}!");
+        assert_eq!(apply_typography("Hello!", "en"), "Hello!");
+        assert_eq!(apply_typography("Hallo: Welt?", "de"), "Hallo: Welt?");
+    }
+
+    #[test]
+    fn test_punctuation_runs_and_digits() {
+        assert_eq!(apply_typography("Quoi?!", "fr"), "Quoi\u{202F}?!");
+        assert_eq!(apply_typography("À 10:30", "fr"), "À 10:30");
+        assert_eq!(apply_typography("!important", "fr"), "!important");
+    }
+
+    #[test]
+    fn test_wikitext_and_urls_untouched() {
+        assert_eq!(
+            apply_typography("{{GENDER:$1|Il|Elle}} est là!", "fr"),
+            "{{GENDER:$1|Il|Elle}} est là\u{202F}!"
+        );
+        assert_eq!(
+            apply_typography("Voir [[Special:Preferences|vos préférences]]!", "fr"),
+            "Voir [[Special:Preferences|vos préférences]]\u{202F}!"
+        );
+        assert_eq!(
+            apply_typography("Voir [https://example.org/a?b=c;d la page]?", "fr"),
+            "Voir [https://example.org/a?b=c;d la page]\u{202F}?"
+        );
+    }
+
+    #[test]
+    fn test_html_tags_untouched() {
+        assert_eq!(
+            apply_typography("<span title=\"Note: a; b!\">Voir</span>!", "fr"),
+            "<span title=\"Note: a; b!\">Voir</span>\u{202F}!"
+        );
+        assert_eq!(
+            apply_typography("<abbr title=\"Q?\">FAQ</abbr>: $1", "fr"),
+            "<abbr title=\"Q?\">FAQ</abbr>\u{00A0}: $1"
+        );
+    }
+
+    #[test]
+    fn test_scheme_uris_untouched() {
+        assert_eq!(
+            apply_typography(
+                "Écrivez à [mailto:info@example.org?subject=Aide;x le support]!",
+                "fr"
+            ),
+            "Écrivez à [mailto:info@example.org?subject=Aide;x le support]\u{202F}!"
+        );
+        assert_eq!(
+            apply_typography("Appelez tel:+33-1-23 ou écrivez!", "fr"),
+            "Appelez tel:+33-1-23 ou écrivez\u{202F}!"
+        );
+        assert_eq!(
+            apply_typography("Note: voir mailto:a@b.org?", "fr"),
+            "Note\u{00A0}: voir mailto:a@b.org?"
+        );
+    }
+
+    #[test]
+    fn test_html_entities_untouched() {
+        assert_eq!(
+            apply_typography("Tom &amp; Jerry; 10&#160;km!", "fr"),
+            "Tom &amp; Jerry\u{202F}; 10&#160;km\u{202F}!"
+        );
+        assert_eq!(
+            apply_typography("A&nbsp;; B&#x2014;C ?", "fr"),
+            "A&nbsp;; B&#x2014;C\u{202F}?"
+        );
+    }
+
+    #[test]
+    fn test_placeholders_keep_their_space() {
+        assert_eq!(
+            apply_typography("$1 a envoyé $2 messages!", "fr"),
+            "$1 a envoyé $2 messages\u{202F}!"
+        );
+        assert_eq!(apply_typography("Nom: $1", "fr"), "Nom\u{00A0}: $1");
+    }
+
+    // ========== Quotation Mark Tests ==========
+
+    #[test]
+    fn test_quotes_german_and_french() {
+        assert_eq!(
+            normalize_quotes("Er sagte \"Hallo\" zu $1.", "de"),
+            "Er sagte „Hallo“ zu $1."
+        );
+        assert_eq!(
+            normalize_quotes("Il a dit \"bonjour\" à $1.", "fr-CA"),
+            "Il a dit «bonjour» à $1."
+        );
+        // Already typographic quotes of another language are converted too
+        assert_eq!(
+            normalize_quotes("Il a dit “bonjour”.", "fr"),
+            "Il a dit «bonjour»."
+        );
+        assert_eq!(normalize_quotes("Say \"hi\"", "xx"), "Say \"hi\"");
+    }
+
+    #[test]
+    fn test_nested_quotes_and_apostrophes() {
+        assert_eq!(
+            normalize_quotes("\"Click 'Save' now\", they said", "de"),
+            "„Click ‚Save‘ now“, they said"
+        );
+        assert_eq!(
+            normalize_quotes("Il dit \"l'homme a écrit \"oui\"\"", "fr"),
+            "Il dit «l'homme a écrit “oui”»"
+        );
+        assert_eq!(
+            normalize_quotes("don't touch the users' files", "en"),
+            "don't touch the users' files"
+        );
+    }
+
+    #[test]
+    fn test_quotes_without_spaces_in_cjk() {
+        assert_eq!(
+            normalize_quotes("彼は\"はい\"と言った", "ja"),
+            "彼は「はい」と言った"
+        );
+        assert_eq!(
+            normalize_quotes("$1さんが\"保存\"をクリックしました", "ja-JP"),
+            "$1さんが「保存」をクリックしました"
+        );
+    }
+
+    #[test]
+    fn test_quotes_in_urls_and_tags_untouched() {
+        assert_eq!(
+            normalize_quotes(
+                "See [https://example.org/?q=\"x\" \"the page\"] or <span class=\"a\">\"b\"</span>",
+                "de"
+            ),
+            "See [https://example.org/?q=\"x\" „the page“] or <span class=\"a\">„b“</span>"
+        );
+    }
+
+    #[test]
+    fn test_italic_and_bold_markup_untouched() {
+        assert_eq!(
+            normalize_quotes("Das ist ''kursiv'' und '''fett'''.", "de"),
+            "Das ist ''kursiv'' und '''fett'''."
+        );
+        assert_eq!(
+            normalize_quotes("Il a dit \"''oui''\" à '''$1'''", "fr"),
+            "Il a dit «''oui''» à '''$1'''"
+        );
+    }
+}
@@ -0,0 +1,515 @@
+//! Yandex Cloud Translate API provider for machine translation
+//!
+//! This module integrates with Yandex Cloud Translate API v2. For Russian and
+//! Turkic language pairs Yandex often produces better results than Google.
+//!
+//! # Authentication
+//!
+//! The provider loads an IAM token from the `YANDEX_IAM_TOKEN` environment
+//! variable and the cloud folder id from `YANDEX_FOLDER_ID`. See:
+//! https://yandex.cloud/en/docs/translate/
+//!
+//! # Example
+//!
+//! ```ignore
+//! use banana_i18n_mt::{MachineTranslator, YandexProvider};
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!     let provider = YandexProvider::from_env()?;
+//!
+//!     let texts = vec!["Hello".to_string(), "Goodbye".to_string()];
+//!     let results = provider.translate_batch(&texts, "en", "ru").await?;
+//!     println!("{:?}", results);
+//!
+//!     Ok(())
+//! }
+//! ```
+
+use crate::error::{MtError, MtResult};
+use crate::translator::{MachineTranslator, validate_locale};
+use async_trait::async_trait;
+use serde_json::json;
+
+/// Yandex Cloud Translate API v2 provider
+///
+/// Sends all texts of a batch in a single `texts[]` request, chunking
+/// transparently when the per-request character limit would be exceeded.
+#[derive(Clone)]
+pub struct YandexProvider {
+    /// IAM token for authentication
+    iam_token: String,
+    /// Cloud folder id the translation is billed to
+    folder_id: String,
+    /// HTTP client for async requests
+    client: reqwest::Client,
+    /// Base URL for Yandex Translate API
+    base_url: String,
+}
+
+impl YandexProvider {
+    /// Maximum total characters per API request
+    /// Yandex Translate v2 accepts up to 10,000 characters across all `texts`
+    const MAX_CHARS_PER_REQUEST: usize = 10_000;
+
+    /// Create a new YandexProvider with an explicit IAM token and folder id
+    ///
+    /// # Arguments
+    ///
+    /// * `iam_token` - Yandex Cloud IAM token
+    /// * `folder_id` - Yandex Cloud folder id
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Self)` - New provider instance
+    /// * `Err(MtError)` - If a credential is empty or HTTP client creation fails
+    pub fn new(iam_token: String, folder_id: String) -> MtResult<Self> {
+        if iam_token.trim().is_empty() {
+            return Err(MtError::ConfigError("IAM token cannot be empty".into()));
+        }
+        if folder_id.trim().is_empty() {
+            return Err(MtError::ConfigError("Folder id cannot be empty".into()));
+        }
+
+        let client = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(30))
+            .build()
+            .map_err(|e| {
+                MtError::NetworkError(format!("Failed to create HTTP client: {}", e).into())
+                    .caused_by(e)
+            })?;
+
+        Ok(Self {
+            iam_token,
+            folder_id,
+            client,
+            base_url: "https://translate.api.cloud.yandex.net/translate/v2/translate".to_string(),
+        })
+    }
+
+    /// Create a YandexProvider from the `YANDEX_IAM_TOKEN` and `YANDEX_FOLDER_ID`
+    /// environment variables
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Self)` - New provider instance
+    /// * `Err(MtError)` - If an environment variable is not set or creation fails
+    pub fn from_env() -> MtResult<Self> {
+        let iam_token = std::env::var("YANDEX_IAM_TOKEN").map_err(|e| {
+            MtError::ConfigError("YANDEX_IAM_TOKEN environment variable not set".into())
+                .caused_by(e)
+        })?;
+        let folder_id = std::env::var("YANDEX_FOLDER_ID").map_err(|e| {
+            MtError::ConfigError("YANDEX_FOLDER_ID environment variable not set".into())
+                .caused_by(e)
+        })?;
+
+        Self::new(iam_token, folder_id)
+    }
+
+    /// Map a BCP 47 locale code to the language code Yandex expects
+    ///
+    /// Yandex uses ISO 639-1 codes, with a few exceptions that keep a
+    /// region or script subtag:
+    /// - `pt-BR` → `pt-BR`
+    /// - `sr-Latn` → `sr-Latn`
+    /// - `en-US` → `en`
+    /// - `zh-Hans` → `zh`
+    pub fn yandex_locale(locale: &str) -> String {
+        let lower = locale.to_lowercase().replace('_', "-");
+        match lower.as_str() {
+            "pt-br" => "pt-BR".to_string(),
+            "sr-latn" | "sr-el" => "sr-Latn".to_string(),
+            _ => lower.split('-').next().unwrap_or(&lower).to_string(),
+        }
+    }
+
+    /// Chunk a batch of texts so each request stays under the character limit
+    ///
+    /// Mirrors `GoogleTranslateProvider::chunk_batch`, but Yandex limits the
+    /// total characters per request rather than the number of texts.
+    ///
+    /// # Arguments
+    ///
+    /// * `texts` - All texts to chunk
+    ///
+    /// # Returns
+    ///
+    /// Vector of text slices, each totalling ≤ MAX_CHARS_PER_REQUEST characters
+    /// (a single oversized text forms its own chunk)
+    fn chunk_batch(texts: &[String]) -> Vec<&[String]> {
+        let mut chunks = Vec::new();
+        let mut start = 0;
+        let mut chars = 0;
+
+        for (i, text) in texts.iter().enumerate() {
+            let len = text.chars().count();
+            if i > start && chars + len > Self::MAX_CHARS_PER_REQUEST {
+                chunks.push(&texts[start..i]);
+                start = i;
+                chars = 0;
+            }
+            chars += len;
+        }
+
+        if start < texts.len() {
+            chunks.push(&texts[start..]);
+        }
+
+        chunks
+    }
+
+    /// Build the JSON request body for a chunk of texts
+    fn build_request_body(
+        &self,
+        texts: &[String],
+        source_locale: &str,
+        target_locale: &str,
+    ) -> serde_json::Value {
+        json!({
+            "folderId": self.folder_id,
+            "texts": texts,
+            "sourceLanguageCode": Self::yandex_locale(source_locale),
+            "targetLanguageCode": Self::yandex_locale(target_locale),
+            "format": "PLAIN_TEXT"
+        })
+    }
+
+    /// Extract the translated texts from a Yandex API response
+    ///
+    /// # Arguments
+    ///
+    /// * `json` - Parsed response body
+    /// * `expected` - Number of texts sent in the request
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<String>)` - Translated texts in request order
+    /// * `Err(MtError)` - If the response is malformed or the count differs
+    fn parse_response(json: &serde_json::Value, expected: usize) -> MtResult<Vec<String>> {
+        let translations = json["translations"].as_array().ok_or_else(|| {
+            MtError::TranslationError("Invalid API response: missing 'translations' array".into())
+        })?;
+
+        let results: Vec<String> = translations
+            .iter()
+            .map(|t| {
+                t["text"].as_str().map(|s| s.to_string()).ok_or_else(|| {
+                    MtError::TranslationError("Invalid API response: missing 'text' field".into())
+                })
+            })
+            .collect::<MtResult<_>>()?;
+
+        if results.len() != expected {
+            return Err(MtError::TranslationError(
+                format!(
+                    "API returned {} translations for {} texts",
+                    results.len(),
+                    expected
+                )
+                .into(),
+            ));
+        }
+
+        Ok(results)
+    }
+
+    /// Translate a single chunk of texts via the API
+    async fn translate_chunk(
+        &self,
+        texts: &[String],
+        source_locale: &str,
+        target_locale: &str,
+    ) -> MtResult<Vec<String>> {
+        let body = self.build_request_body(texts, source_locale, target_locale);
+
+        let response = self
+            .client
+            .post(&self.base_url)
+            .bearer_auth(&self.iam_token)
+            .json(&body)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Unknown error".to_string());
+
+            return Err(if status.is_client_error() {
+                MtError::ConfigError(
+                    format!("API client error ({}): {}", status, error_text).into(),
+                )
+            } else {
+                MtError::TranslationError(
+                    format!("API server error ({}): {}", status, error_text).into(),
+                )
+            });
+        }
+
+        let json: serde_json::Value = response.json().await?;
+
+        Self::parse_response(&json, texts.len())
+    }
+}
+
+impl std::fmt::Debug for YandexProvider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("YandexProvider")
+            .field("iam_token", &"***")
+            .field("folder_id", &self.folder_id)
+            .field("base_url", &self.base_url)
+            .finish()
+    }
+}
+
+#[async_trait]
+impl MachineTranslator for YandexProvider {
+    async fn translate(
+        &self,
+        text: &str,
+        source_locale: &str,
+        target_locale: &str,
+    ) -> MtResult<String> {
+        validate_locale(source_locale)?;
+        validate_locale(target_locale)?;
+
+        if text.is_empty() {
+            return Ok(String::new());
+        }
+
+        let results = self
+            .translate_batch(&[text.to_string()], source_locale, target_locale)
+            .await?;
+
+        Ok(results.into_iter().next().unwrap_or_default())
+    }
+
+    async fn translate_batch(
+        &self,
+        texts: &[String],
+        source_locale: &str,
+        target_locale: &str,
+    ) -> MtResult<Vec<String>> {
+        validate_locale(source_locale)?;
+        validate_locale(target_locale)?;
+
+        if texts.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        for (i, text) in texts.iter().enumerate() {
+            if text.chars().count() > Self::MAX_CHARS_PER_REQUEST {
+                return Err(MtError::TranslationError(
+                    format!(
+                        "Text at index {} exceeds maximum length of {} characters",
+                        i,
+                        Self::MAX_CHARS_PER_REQUEST
+                    )
+                    .into(),
+                ));
+            }
+        }
+
+        let mut all_results = Vec::new();
+        for chunk in Self::chunk_batch(texts) {
+            let chunk_results = self
+                .translate_chunk(chunk, source_locale, target_locale)
+                .await?;
+            all_results.extend(chunk_results);
+        }
+
+        Ok(all_results)
+    }
+
+    fn provider_name(&self) -> &str {
+        "Yandex Translate"
+    }
+
+    fn supports_native_batch(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn provider() -> YandexProvider {
+        YandexProvider::new("test-token".to_string(), "test-folder".to_string()).unwrap()
+    }
+
+    // ========== Initialization Tests ==========
+
+    #[test]
+    fn test_new_with_empty_credentials() {
+        let result = YandexProvider::new("".to_string(), "folder".to_string());
+        match result {
+            Err(MtError::ConfigError(msg)) => assert!(msg.contains("IAM token")),
+            _ => panic!("Expected ConfigError"),
+        }
+
+        let result = YandexProvider::new("token".to_string(), "  ".to_string());
+        match result {
+            Err(MtError::ConfigError(msg)) => assert!(msg.contains("Folder id")),
+            _ => panic!("Expected ConfigError"),
+        }
+    }
+
+    #[test]
+    fn test_debug_output_masks_token() {
+        let debug_str = format!("{:?}", provider());
+        assert!(debug_str.contains("***"));
+        assert!(!debug_str.contains("test-token"));
+        assert!(debug_str.contains("test-folder"));
+    }
+
+    // ========== Locale Mapping Tests ==========
+
+    #[test]
+    fn test_yandex_locale_mapping() {
+        assert_eq!(YandexProvider::yandex_locale("en-US"), "en");
+        assert_eq!(YandexProvider::yandex_locale("ru"), "ru");
+        assert_eq!(YandexProvider::yandex_locale("tt"), "tt");
+        assert_eq!(YandexProvider::yandex_locale("zh-Hans"), "zh");
+        assert_eq!(YandexProvider::yandex_locale("pt-BR"), "pt-BR");
+        assert_eq!(YandexProvider::yandex_locale("pt_br"), "pt-BR");
+        assert_eq!(YandexProvider::yandex_locale("sr-Latn"), "sr-Latn");
+    }
+
+    // ========== Chunking Tests ==========
+
+    #[test]
+    fn test_chunk_under_limit() {
+        let texts = vec!["hello".to_string(), "world".to_string()];
+        let chunks = YandexProvider::chunk_batch(&texts);
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks[0].len(), 2);
+    }
+
+    #[test]
+    fn test_chunk_over_char_limit() {
+        let texts = vec!["x".repeat(6_000), "y".repeat(6_000), "z".repeat(3_000)];
+        let chunks = YandexProvider::chunk_batch(&texts);
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].len(), 1);
+        assert_eq!(chunks[1].len(), 2);
+    }
+
+    #[test]
+    fn test_chunk_counts_characters_not_bytes() {
+        // 4,000 Cyrillic chars are 8,000 bytes but only 4,000 characters
+        let texts = vec!["я".repeat(4_000), "я".repeat(4_000)];
+        let chunks = YandexProvider::chunk_batch(&texts);
+        assert_eq!(chunks.len(), 1);
+    }
+
+    #[test]
+    fn test_chunk_empty() {
+        let texts: Vec<String> = vec![];
+        assert!(YandexProvider::chunk_batch(&texts).is_empty());
+    }
+
+    // ========== Request Construction Tests ==========
+
+    #[test]
+    fn test_build_request_body() {
+        let texts = vec![
+            "777001 sent a message".to_string(),
+            "777001 sent 777002 messages".to_string(),
+        ];
+        let body = provider().build_request_body(&texts, "en-US", "ru");
+
+        assert_eq!(
+            body,
+            json!({
+                "folderId": "test-folder",
+                "texts": ["777001 sent a message", "777001 sent 777002 messages"],
+                "sourceLanguageCode": "en",
+                "targetLanguageCode": "ru",
+                "format": "PLAIN_TEXT"
+            })
+        );
+    }
+
+    // ========== Response Parsing Tests ==========
+
+    #[test]
+    fn test_parse_recorded_response() {
+        let recorded = r#"{
+            "translations": [
+                {"text": "777001 отправил сообщение", "detectedLanguageCode": "en"},
+                {"text": "777001 отправил 777002 сообщений", "detectedLanguageCode": "en"}
+            ]
+        }"#;
+        let json: serde_json::Value = serde_json::from_str(recorded).unwrap();
+        let results = YandexProvider::parse_response(&json, 2).unwrap();
+
+        assert_eq!(
+            results,
+            vec![
+                "777001 отправил сообщение".to_string(),
+                "777001 отправил 777002 сообщений".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_response_missing_translations() {
+        let json: serde_json::Value =
+            serde_json::from_str(r#"{"code": 16, "message": "Unauthenticated"}"#).unwrap();
+        match YandexProvider::parse_response(&json, 1) {
+            Err(MtError::TranslationError(msg)) => assert!(msg.contains("translations")),
+            _ => panic!("Expected TranslationError"),
+        }
+    }
+
+    #[test]
+    fn test_parse_response_count_mismatch() {
+        let json: serde_json::Value =
+            serde_json::from_str(r#"{"translations": [{"text": "Привет"}]}"#).unwrap();
+        assert!(YandexProvider::parse_response(&json, 2).is_err());
+    }
+
+    // ========== Validation Tests ==========
+
+    #[tokio::test]
+    async fn test_translate_empty_text() {
+        let result = provider().translate("", "en", "ru").await.unwrap();
+        assert_eq!(result, "");
+    }
+
+    #[tokio::test]
+    async fn test_batch_text_too_long() {
+        let texts = vec!["x".repeat(YandexProvider::MAX_CHARS_PER_REQUEST + 1)];
+        let result = provider().translate_batch(&texts, "en", "ru").await;
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_provider_name() {
+        assert_eq!(provider().provider_name(), "Yandex Translate");
+    }
+
+    // ========== Integration Tests (require real credentials) ==========
+
+    #[tokio::test]
+    #[ignore] // Run with: cargo test --ignored
+    async fn test_real_api_preserves_anchor_tokens() {
+        if std::env::var("YANDEX_IAM_TOKEN").is_err() {
+            eprintln!("Skipping: YANDEX_IAM_TOKEN not set");
+            return;
+        }
+
+        let provider = YandexProvider::from_env().unwrap();
+        let result = provider
+            .translate("777001 sent 777002 messages", "en", "ru")
+            .await
+            .unwrap();
+
+        assert!(result.contains("777001"));
+        assert!(result.contains("777002"));
+    }
+}
@@ -1,5 +1,8 @@
+use fixed_decimal::Decimal;
 use icu_locale::Locale;
-use icu_plurals::{PluralCategory, PluralRuleType, PluralRules};
+use icu_plurals::{PluralCategory, PluralOperands, PluralRuleType, PluralRules};
+
+use crate::magic_words::{MagicWordRegistry, MagicWordType};
 
 // Import for verbosity logging (will be used via crate::VerbosityLevel in context-aware methods)
 use crate::VerbosityLevel;
@@ -15,6 +18,7 @@ pub enum AstNode {
     Transclusion(Transclusion),
     InternalLink(WikiInternalLink),
     ExternalLink(WikiExternalLink),
+    Comment(String), // <!-- ... -->, holding the text between the markers
 }
 
 /// Placeholder: $1, $2, $3, etc. (1-indexed)
@@ -32,10 +36,13 @@ pub struct Transclusion {
     pub options: Vec<String>, // e.g., ["is", "are"] or multiple forms for other languages
 }
 
-/// Internal wiki link: [[Page]] or [[Page|Display Text]]
+/// Internal wiki link: [[Page]], [[Page|Display Text]] or [[File:X.png|thumb|Caption]]
 #[derive(Debug, Clone, PartialEq)]
 pub struct WikiInternalLink {
     pub target: String,
+    /// Parameters between the target and the display text, e.g. ["thumb"]
+    pub params: Vec<String>,
+    /// The last parameter: display text, or the caption of a file link
     pub display_text: Option<String>,
 }
 
@@ -149,13 +156,59 @@ impl Localizable for Placeholder {
     }
 }
 
+/// Substitute placeholders inside a selected magic word option
+///
+/// Options are stored as raw text, so `{{PLURAL:$1|$1 file|$1 files}}` selects
+/// "$1 files" and the `$1` still needs its value. Placeholders without a value
+/// are left as-is, matching `Placeholder::localize`.
+fn substitute_placeholders(text: &str, values: &Vec<String>) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((_, c)) = chars.next() {
+        if c != '$' {
+            result.push(c);
+            continue;
+        }
+
+        let mut digits = String::new();
+        while let Some(&(_, d)) = chars.peek() {
+            if !d.is_ascii_digit() {
+                break;
+            }
+            digits.push(d);
+            chars.next();
+        }
+
+        let placeholder = digits
+            .parse::<usize>()
+            .ok()
+            .map(|index| Placeholder { index });
+        match placeholder {
+            Some(placeholder) => result.push_str(&placeholder.localize("", values)),
+            None => result.push('$'),
+        }
+    }
+
+    result
+}
+
 impl Localizable for Transclusion {
     fn localize(&self, locale: &str, values: &Vec<String>) -> String {
-        match self.name.to_uppercase().as_str() {
-            "PLURAL" => self.localize_plural(locale, values),
-            "GENDER" => self.localize_gender(locale, values),
+        match MagicWordRegistry::default_registry().resolve(&self.name) {
+            Some(MagicWordType::Plural) => {
+                substitute_placeholders(&self.localize_plural(locale, values), values)
+            }
+            Some(MagicWordType::PluralOrdinal) => {
+                substitute_placeholders(&self.localize_ordinal(locale, values), values)
+            }
+            Some(MagicWordType::Gender) => {
+                substitute_placeholders(&self.localize_gender(locale, values), values)
+            }
+            Some(MagicWordType::If) => substitute_placeholders(&self.localize_if(values), values),
+            None if self.is_ifeq() => substitute_placeholders(&self.localize_ifeq(values), values),
             // Future: Add GRAMMAR, etc.
-            _ => {
+            None => {
                 // Unknown magic word - log warning and return original syntax
                 eprintln!("Warning: Unknown magic word '{}'", self.name);
                 format!(
@@ -170,6 +223,49 @@ impl Localizable for Transclusion {
 }
 
 impl Transclusion {
+    /// Localize an `{{#if:value|set|empty}}` parser function
+    ///
+    /// The value (usually a placeholder) is tested after substitution: the
+    /// first option is used if it is non-empty, the second otherwise. A missing
+    /// option renders as nothing.
+    fn localize_if(&self, values: &Vec<String>) -> String {
+        let index = if self.parser_function_value(values).is_empty() {
+            1
+        } else {
+            0
+        };
+        self.options.get(index).cloned().unwrap_or_default()
+    }
+
+    /// Localize an `{{#ifeq:value|other|equal|different}}` parser function
+    ///
+    /// Both values are compared after substitution and trimming. A missing
+    /// option renders as nothing.
+    fn localize_ifeq(&self, values: &Vec<String>) -> String {
+        let other = self
+            .options
+            .first()
+            .map(|option| substitute_placeholders(option, values))
+            .unwrap_or_default();
+        let index = if self.parser_function_value(values) == other.trim() {
+            1
+        } else {
+            2
+        };
+        self.options.get(index).cloned().unwrap_or_default()
+    }
+
+    fn is_ifeq(&self) -> bool {
+        self.name.trim().eq_ignore_ascii_case("#ifeq")
+    }
+
+    /// The tested value of a parser function: its parameter, substituted and trimmed
+    fn parser_function_value(&self, values: &Vec<String>) -> String {
+        substitute_placeholders(&self.param, values)
+            .trim()
+            .to_string()
+    }
+
     /// Localize a PLURAL magic word using language-specific plural rules from ICU
     ///
     /// Uses ICU plural rules for proper multi-language support. Falls back to English
@@ -187,20 +283,7 @@ impl Transclusion {
             return String::new();
         }
 
-        // Extract the count from param (e.g., "$1" -> values[0])
-        let count = if self.param.starts_with('$') {
-            // It's a placeholder reference
-            let index_str = &self.param[1..];
-            let index: usize = index_str.parse().unwrap_or(0);
-            if index > 0 && index <= values.len() {
-                values[index - 1].parse::<u32>().unwrap_or(0)
-            } else {
-                0
-            }
-        } else {
-            // Direct number
-            self.param.parse::<u32>().unwrap_or(0)
-        };
+        let count = self.plural_count(values);
 
         // Try to get the plural category using ICU rules
         let form_index = match get_plural_form_index(locale, count, self.options.len()) {
@@ -214,7 +297,7 @@ impl Transclusion {
                 );
 
                 // English rule: 1 = singular (index 0), others = plural (index 1 or last)
-                if count == 1 {
+                if count == PluralOperands::from(1u32) {
                     0
                 } else {
                     1.min(self.options.len() - 1)
@@ -228,6 +311,27 @@ impl Transclusion {
             .unwrap_or_else(|| self.options.last().cloned().unwrap_or_default())
     }
 
+    /// The PLURAL count: the value of the placeholder param, or the param itself
+    ///
+    /// e.g. `$1` -> values[0]. Decimal counts such as "1.5" are kept as
+    /// decimals; anything that is not a number counts as 0.
+    fn plural_count(&self, values: &[String]) -> PluralOperands {
+        let value = match self.param.strip_prefix('$') {
+            // It's a placeholder reference
+            Some(index_str) => {
+                let index: usize = index_str.parse().unwrap_or(0);
+                if index > 0 && index <= values.len() {
+                    values[index - 1].as_str()
+                } else {
+                    ""
+                }
+            }
+            // Direct number
+            None => self.param.as_str(),
+        };
+        plural_operands(value)
+    }
+
     /// Localize with fallback chain support and verbosity context
     /// This is an internal method called from lib.rs with verbosity level
     pub fn localize_with_context(
@@ -236,11 +340,41 @@ impl Transclusion {
         values: &Vec<String>,
         verbosity: VerbosityLevel,
     ) -> String {
-        match self.name.to_uppercase().as_str() {
-            "PLURAL" => self.localize_plural_with_fallback(locale, values, verbosity),
-            "GENDER" => self.localize_gender_with_context(locale, values, verbosity),
+        self.localize_with_registry(
+            locale,
+            values,
+            verbosity,
+            MagicWordRegistry::default_registry(),
+        )
+    }
+
+    /// Localize with context, resolving the magic word name through a registry
+    ///
+    /// Lets configured aliases (e.g. a localized name for PLURAL) render like
+    /// the canonical magic word.
+    pub fn localize_with_registry(
+        &self,
+        locale: &str,
+        values: &Vec<String>,
+        verbosity: VerbosityLevel,
+        registry: &MagicWordRegistry,
+    ) -> String {
+        match registry.resolve(&self.name) {
+            Some(MagicWordType::Plural) => substitute_placeholders(
+                &self.localize_plural_with_fallback(locale, values, verbosity),
+                values,
+            ),
+            Some(MagicWordType::PluralOrdinal) => {
+                substitute_placeholders(&self.localize_ordinal(locale, values), values)
+            }
+            Some(MagicWordType::Gender) => substitute_placeholders(
+                &self.localize_gender_with_context(locale, values, verbosity),
+                values,
+            ),
+            Some(MagicWordType::If) => substitute_placeholders(&self.localize_if(values), values),
+            None if self.is_ifeq() => substitute_placeholders(&self.localize_ifeq(values), values),
             // Future: Add GRAMMAR, etc.
-            _ => {
+            None => {
                 // Unknown magic word - log warning and return original syntax
                 eprintln!("Warning: Unknown magic word '{}'", self.name);
                 format!(
@@ -266,20 +400,7 @@ impl Transclusion {
             return String::new();
         }
 
-        // Extract the count from param (e.g., "$1" -> values[0])
-        let count = if self.param.starts_with('$') {
-            // It's a placeholder reference
-            let index_str = &self.param[1..];
-            let index: usize = index_str.parse().unwrap_or(0);
-            if index > 0 && index <= values.len() {
-                values[index - 1].parse::<u32>().unwrap_or(0)
-            } else {
-                0
-            }
-        } else {
-            // Direct number
-            self.param.parse::<u32>().unwrap_or(0)
-        };
+        let count = self.plural_count(values);
 
         // Try to get the plural category using ICU rules with fallback chain
         let form_index = match get_plural_form_index_with_fallback(
@@ -299,7 +420,7 @@ impl Transclusion {
                 }
 
                 // English rule: 1 = singular (index 0), others = plural (index 1 or last)
-                if count == 1 {
+                if count == PluralOperands::from(1u32) {
                     0
                 } else {
                     1.min(self.options.len() - 1)
@@ -313,6 +434,26 @@ impl Transclusion {
             .unwrap_or_else(|| self.options.last().cloned().unwrap_or_default())
     }
 
+    /// Localize a PLURAL-ORDINAL magic word
+    ///
+    /// Options follow the CLDR ordinal categories the locale uses, in the
+    /// order zero, one, two, few, many, other; e.g. `st|nd|rd|th` in English.
+    /// Missing options fall back to the last one. Ordinal rules are looked up
+    /// along the locale fallback chain.
+    fn localize_ordinal(&self, locale: &str, values: &[String]) -> String {
+        if self.options.is_empty() {
+            return String::new();
+        }
+
+        let count = self.plural_count(values);
+        let form_index = ordinal_form_index(locale, count).unwrap_or(self.options.len() - 1);
+
+        self.options
+            .get(form_index)
+            .cloned()
+            .unwrap_or_else(|| self.options.last().cloned().unwrap_or_default())
+    }
+
     /// Localize a GENDER magic word
     ///
     /// Selects a form based on a gender parameter (male, female, or other).
@@ -383,8 +524,9 @@ impl Transclusion {
         // Just use the basic gender localization
         let result = self.localize_gender(_locale, values);
 
-        // Log invalid gender values at appropriate verbosity levels
-        if !self.param.starts_with('$') {
+        // Log invalid gender values at appropriate verbosity levels; an empty
+        // parameter ({{GENDER:|...}}) means no gender context, not an error
+        if !self.param.starts_with('$') && !self.param.trim().is_empty() {
             let gender = self.param.to_lowercase();
             if !matches!(gender.as_str(), "male" | "female") && verbosity >= VerbosityLevel::Verbose
             {
@@ -399,11 +541,26 @@ impl Transclusion {
     }
 }
 
+/// Plural operands of a count such as "5", "-2" or "1.5"
+///
+/// Decimals keep their visible fraction digits, since plural rules can tell
+/// them apart from integers: in English "1" is "one" but "1.0" and "1.5" are
+/// "other". Values that are not numbers count as 0.
+fn plural_operands(value: &str) -> PluralOperands {
+    Decimal::try_from_str(value.trim())
+        .map(|decimal| PluralOperands::from(&decimal))
+        .unwrap_or_else(|_| PluralOperands::from(0u32))
+}
+
 /// Get the appropriate plural form index for a given locale and count
 ///
 /// Returns the form index to use for the plural forms array, or an error if
 /// plural rules cannot be determined for the locale.
-fn get_plural_form_index(locale_str: &str, count: u32, form_count: usize) -> Result<usize, String> {
+fn get_plural_form_index(
+    locale_str: &str,
+    count: PluralOperands,
+    form_count: usize,
+) -> Result<usize, String> {
     if form_count == 0 {
         return Ok(0);
     }
@@ -416,7 +573,7 @@ fn get_plural_form_index(locale_str: &str, count: u32, form_count: usize) -> Res
         .map_err(|e| format!("Failed to create PluralRules: {}", e))?;
 
     // Get the plural category for this count
-    let category = pr.category_for(count as usize);
+    let category = pr.category_for(count);
 
     // Map the category to a form index
     let form_index = plural_category_to_index(category, form_count);
@@ -424,13 +581,44 @@ fn get_plural_form_index(locale_str: &str, count: u32, form_count: usize) -> Res
     Ok(form_index)
 }
 
+/// CLDR plural categories in the order their forms are written
+const CLDR_CATEGORY_ORDER: [PluralCategory; 6] = [
+    PluralCategory::Zero,
+    PluralCategory::One,
+    PluralCategory::Two,
+    PluralCategory::Few,
+    PluralCategory::Many,
+    PluralCategory::Other,
+];
+
+/// Form index of a count under the ordinal rules of a locale or its fallbacks
+///
+/// The index is the position of the count's category among the ordinal
+/// categories the locale uses, in CLDR order. `None` if no ordinal rules are
+/// found in the fallback chain.
+fn ordinal_form_index(locale_str: &str, count: PluralOperands) -> Option<usize> {
+    let rules = crate::fallbacks::resolve_locale_chain(locale_str)
+        .iter()
+        .find_map(|fallback| {
+            let locale = parse_locale(fallback).ok()?;
+            PluralRules::try_new(locale.into(), PluralRuleType::Ordinal.into()).ok()
+        })?;
+
+    let category = rules.category_for(count);
+    let used: Vec<PluralCategory> = rules.categories().collect();
+    CLDR_CATEGORY_ORDER
+        .iter()
+        .filter(|c| used.contains(c))
+        .position(|c| *c == category)
+}
+
 /// Get the appropriate plural form index for a given locale and count, with fallback chain support
 ///
 /// Tries the requested locale first, then follows the fallback chain to find the first
 /// locale with working plural rules. Returns the form index or an error if none found.
 fn get_plural_form_index_with_fallback(
     locale_str: &str,
-    count: u32,
+    count: PluralOperands,
     form_count: usize,
     verbosity: VerbosityLevel,
 ) -> Result<usize, String> {
@@ -473,7 +661,223 @@ fn get_plural_form_index_with_fallback(
     ))
 }
 
+/// Index of the most general PLURAL form for a locale
+///
+/// That is the form selected for the smallest count from 2 that falls in the
+/// "other" category, or in "many" for languages where no whole number is
+/// "other" (e.g. Russian). Falls back to the last form if no plural rules
+/// are found in the locale's fallback chain.
+pub(crate) fn general_plural_index(locale_str: &str, form_count: usize) -> usize {
+    let last = form_count.saturating_sub(1);
+    let rules = crate::fallbacks::resolve_locale_chain(locale_str)
+        .iter()
+        .find_map(|fallback| {
+            let locale = parse_locale(fallback).ok()?;
+            PluralRules::try_new(locale.into(), PluralRuleType::Cardinal.into()).ok()
+        });
+    let Some(rules) = rules else {
+        return last;
+    };
+
+    let counts = 2..=1000usize;
+    let count = counts
+        .clone()
+        .find(|&n| rules.category_for(n) == PluralCategory::Other)
+        .or_else(|| {
+            counts
+                .clone()
+                .find(|&n| rules.category_for(n) == PluralCategory::Many)
+        })
+        .unwrap_or(2);
+
+    get_plural_form_index_with_fallback(
+        locale_str,
+        PluralOperands::from(count),
+        form_count,
+        VerbosityLevel::Silent,
+    )
+    .map_or(last, |index| index.min(last))
+}
+
+/// Serialize an AST back to wikitext
+///
+/// Inverse of `Parser::parse()` for the node types the parser produces:
+/// parsing the output yields the same AST. Option and display text is
+/// written as stored, without escaping.
+///
+/// # Arguments
+/// * `ast` - The AST to serialize
+///
+/// # Returns
+/// The wikitext source for the AST
+pub fn ast_to_wikitext(ast: &AstNodeList) -> String {
+    let mut result = String::new();
+    for node in ast {
+        match node {
+            AstNode::Text(text) => result.push_str(text),
+            AstNode::Placeholder(placeholder) => {
+                result.push_str(&format!("${}", placeholder.index));
+            }
+            AstNode::Transclusion(trans) => {
+                result.push_str(&format!("{{{{{}:{}", trans.name, trans.param));
+                for option in &trans.options {
+                    result.push('|');
+                    result.push_str(option);
+                }
+                result.push_str("}}");
+            }
+            AstNode::InternalLink(link) => {
+                result.push_str("[[");
+                result.push_str(&link.target);
+                for param in &link.params {
+                    result.push('|');
+                    result.push_str(param);
+                }
+                if let Some(ref display_text) = link.display_text {
+                    result.push('|');
+                    result.push_str(display_text);
+                }
+                result.push_str("]]");
+            }
+            AstNode::ExternalLink(link) => {
+                result.push('[');
+                result.push_str(&link.url);
+                if let Some(ref text) = link.text {
+                    result.push(' ');
+                    result.push_str(text);
+                }
+                result.push(']');
+            }
+            AstNode::Comment(comment) => {
+                result.push_str("<!--");
+                result.push_str(comment);
+                result.push_str("-->");
+            }
+        }
+    }
+    result
+}
+
+/// Lowercased namespace of a link target (`File:X.png` → "file")
+///
+/// A target with a leading colon (`[[:Category:Foo]]`) links to the page
+/// itself and has no namespace behaviour, so it gets `None`.
+fn link_namespace(target: &str) -> Option<String> {
+    if target.starts_with(':') {
+        return None;
+    }
+    let (namespace, _) = target.split_once(':')?;
+    Some(namespace.trim().to_lowercase())
+}
+
+/// Whether a part of a file link is an image option rather than a caption
+fn is_image_option(part: &str) -> bool {
+    const KEYWORDS: &[&str] = &[
+        "thumb",
+        "thumbnail",
+        "frame",
+        "framed",
+        "frameless",
+        "border",
+        "left",
+        "right",
+        "center",
+        "centre",
+        "none",
+        "upright",
+        "baseline",
+        "middle",
+        "sub",
+        "super",
+        "top",
+        "text-top",
+        "bottom",
+        "text-bottom",
+    ];
+    const PREFIXES: &[&str] = &[
+        "upright=",
+        "upright ",
+        "alt=",
+        "link=",
+        "page=",
+        "class=",
+        "lang=",
+        "thumb=",
+        "thumbnail=",
+    ];
+
+    let part = part.trim().to_lowercase();
+    // Sizes: 200px, 200x100px, x100px
+    let is_size = part.strip_suffix("px").is_some_and(|size| {
+        size.split('x').count() <= 2
+            && size.chars().any(|c| c.is_ascii_digit())
+            && size.chars().all(|c| c.is_ascii_digit() || c == 'x')
+    });
+    is_size
+        || KEYWORDS.contains(&part.as_str())
+        || PREFIXES.iter().any(|prefix| part.starts_with(prefix))
+}
+
 impl WikiInternalLink {
+    /// Build a link from the text between `[[` and `]]`
+    ///
+    /// The text is split at pipes outside nested links and transclusions:
+    /// the first part is the target, the last the display text, and any
+    /// parts in between (such as `thumb` in `[[File:X.png|thumb|Caption]]`)
+    /// are parameters. All parts are trimmed, and a display text left empty
+    /// (`[[Foo| ]]`) counts as none, since `[[Foo|]]` is not a link.
+    ///
+    /// File, image and media links only have a display text (their caption)
+    /// if the last part is not an image option such as `thumb` or `200px`,
+    /// and the part after a category is a sort key, so both are parameters
+    /// there: `[[File:X.png|thumb]]` and `[[Category:Foo|Sortkey]]` have no
+    /// display text.
+    pub fn from_link_text(inner: &str) -> Self {
+        let mut parts = Vec::new();
+        let mut current = String::new();
+        let mut depth = 0usize;
+        let mut rest = inner;
+        while let Some(c) = rest.chars().next() {
+            if rest.starts_with("[[") || rest.starts_with("{{") {
+                depth += 1;
+                current.push_str(&rest[..2]);
+                rest = &rest[2..];
+                continue;
+            }
+            if depth > 0 && (rest.starts_with("]]") || rest.starts_with("}}")) {
+                depth -= 1;
+                current.push_str(&rest[..2]);
+                rest = &rest[2..];
+                continue;
+            }
+            if c == '|' && depth == 0 {
+                parts.push(current.trim().to_string());
+                current.clear();
+            } else {
+                current.push(c);
+            }
+            rest = &rest[c.len_utf8()..];
+        }
+        parts.push(current.trim().to_string());
+
+        let target = parts.remove(0);
+        let display_text = match link_namespace(&target).as_deref() {
+            Some("category") => None,
+            Some("file" | "image" | "media") => parts
+                .last()
+                .is_some_and(|part| !is_image_option(part))
+                .then(|| parts.pop())
+                .flatten(),
+            _ => parts.pop(),
+        }
+        .filter(|text| !text.is_empty());
+        WikiInternalLink {
+            target,
+            params: parts,
+            display_text,
+        }
+    }
+
     pub fn to_html(&self) -> String {
         let display = self.display_text.as_ref().unwrap_or(&self.target);
         format!("<a href=\"{}\">{}</a>", self.target, display)
@@ -566,10 +970,35 @@ mod tests {
         assert_eq!(transclusion.localize("en", &values), "two");
     }
 
+    #[test]
+    fn test_plural_ordinal_english() {
+        let transclusion = Transclusion {
+            name: "PLURAL-ORDINAL".to_string(),
+            param: "$1".to_string(),
+            options: vec![
+                "$1st".to_string(),
+                "$1nd".to_string(),
+                "$1rd".to_string(),
+                "$1th".to_string(),
+            ],
+        };
+        let ordinals: Vec<String> = ["1", "2", "3", "4", "11", "12", "13", "21", "22", "103"]
+            .iter()
+            .map(|n| transclusion.localize("en", &vec![n.to_string()]))
+            .collect();
+        assert_eq!(
+            ordinals,
+            vec![
+                "1st", "2nd", "3rd", "4th", "11th", "12th", "13th", "21st", "22nd", "103rd"
+            ]
+        );
+    }
+
     #[test]
     fn test_internal_link_html() {
         let link = WikiInternalLink {
             target: "box".to_string(),
+            params: Vec::new(),
             display_text: None,
         };
         assert_eq!(link.to_html(), "<a href=\"box\">box</a>");
@@ -579,11 +1008,64 @@ mod tests {
     fn test_internal_link_with_display() {
         let link = WikiInternalLink {
             target: "Main Page".to_string(),
+            params: Vec::new(),
             display_text: Some("home".to_string()),
         };
         assert_eq!(link.to_html(), "<a href=\"Main Page\">home</a>");
     }
 
+    #[test]
+    fn test_internal_link_from_link_text() {
+        let link = WikiInternalLink::from_link_text("File:Bar.png| thumb |A [[Foo|bar]] caption");
+        assert_eq!(link.target, "File:Bar.png");
+        assert_eq!(link.params, vec!["thumb".to_string()]);
+        assert_eq!(link.display_text.as_deref(), Some("A [[Foo|bar]] caption"));
+
+        let link = WikiInternalLink::from_link_text("Category:Foo");
+        assert_eq!(link.target, "Category:Foo");
+        assert!(link.params.is_empty());
+        assert_eq!(link.display_text, None);
+
+        let link = WikiInternalLink::from_link_text("Foo| ");
+        assert_eq!(link.display_text, None);
+    }
+
+    #[test]
+    fn test_file_link_options_are_not_display_text() {
+        let link = WikiInternalLink::from_link_text("File:Bar.png|thumb");
+        assert_eq!(link.params, vec!["thumb".to_string()]);
+        assert_eq!(link.display_text, None);
+
+        let link = WikiInternalLink::from_link_text("Image:X.jpg|200px");
+        assert_eq!(link.params, vec!["200px".to_string()]);
+        assert_eq!(link.display_text, None);
+
+        let link = WikiInternalLink::from_link_text("File:X.png|thumb|200x100px|alt=A map|left");
+        assert_eq!(link.params.len(), 4);
+        assert_eq!(link.display_text, None);
+
+        let link = WikiInternalLink::from_link_text("file:X.png|frameless|upright=1.2|A map");
+        assert_eq!(link.params.len(), 2);
+        assert_eq!(link.display_text.as_deref(), Some("A map"));
+
+        // Outside the file namespaces, a keyword is ordinary display text
+        let link = WikiInternalLink::from_link_text("Help:Layout|left");
+        assert_eq!(link.display_text.as_deref(), Some("left"));
+    }
+
+    #[test]
+    fn test_category_sort_key_is_not_display_text() {
+        let link = WikiInternalLink::from_link_text("Category:Foo|Sortkey");
+        assert_eq!(link.target, "Category:Foo");
+        assert_eq!(link.params, vec!["Sortkey".to_string()]);
+        assert_eq!(link.display_text, None);
+
+        // A link to the category page itself has display text
+        let link = WikiInternalLink::from_link_text(":Category:Foo|the Foo category");
+        assert!(link.params.is_empty());
+        assert_eq!(link.display_text.as_deref(), Some("the Foo category"));
+    }
+
     #[test]
     fn test_external_link_html() {
         let link = WikiExternalLink {
@@ -825,6 +1307,49 @@ mod tests {
         assert_eq!(transclusion.localize("en", &vec![]), "items");
     }
 
+    /// Test decimal counts: English "one" needs an integer 1 without visible fraction digits
+    #[test]
+    fn test_plural_decimal_english() {
+        let transclusion = Transclusion {
+            name: "PLURAL".to_string(),
+            param: "$1".to_string(),
+            options: vec!["$1 star".to_string(), "$1 stars".to_string()],
+        };
+        let localize = |count: &str| transclusion.localize("en", &vec![count.to_string()]);
+        assert_eq!(localize("1"), "1 star");
+        assert_eq!(localize("1.0"), "1.0 stars");
+        assert_eq!(localize("1.5"), "1.5 stars");
+        assert_eq!(localize("-1"), "-1 star");
+    }
+
+    /// Test decimal counts where rules differ from English: French "one" covers 0 ≤ n < 2
+    #[test]
+    fn test_plural_decimal_french_and_russian() {
+        let transclusion = Transclusion {
+            name: "PLURAL".to_string(),
+            param: "$1".to_string(),
+            options: vec!["étoile".to_string(), "étoiles".to_string()],
+        };
+        let localize = |count: &str| transclusion.localize("fr", &vec![count.to_string()]);
+        assert_eq!(localize("1.5"), "étoile");
+        assert_eq!(localize("0.5"), "étoile");
+        assert_eq!(localize("2.5"), "étoiles");
+
+        // Russian fractions are "other", which uses the second form
+        let transclusion = Transclusion {
+            name: "PLURAL".to_string(),
+            param: "$1".to_string(),
+            options: vec![
+                "звезда".to_string(),
+                "звезды".to_string(),
+                "звёзд".to_string(),
+            ],
+        };
+        let localize = |count: &str| transclusion.localize("ru", &vec![count.to_string()]);
+        assert_eq!(localize("5"), "звёзд");
+        assert_eq!(localize("1.5"), "звезды");
+    }
+
     /// Test plural with fallback chain (via localize_with_context)
     /// de-at should fall back to de's plural rules
     #[test]
@@ -1363,6 +1888,30 @@ mod tests {
         assert_eq!(result, "m");
     }
 
+    /// Test empty parameter: {{GENDER:|...}} has no gender context and uses the neutral form
+    #[test]
+    fn test_gender_empty_param_uses_neutral_form() {
+        let ast = crate::parser::Parser::new("{{GENDER:|he|she|they}}{{GENDER:|him|her}}").parse();
+        let genders: Vec<&Transclusion> = ast
+            .iter()
+            .filter_map(|node| match node {
+                AstNode::Transclusion(trans) => Some(trans),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(genders.len(), 2);
+        assert_eq!(genders[0].param, "");
+
+        // Parameter values do not supply a gender to a parameterless GENDER
+        let values = vec!["female".to_string()];
+        assert_eq!(genders[0].localize("en", &values), "they");
+        assert_eq!(genders[1].localize("en", &values), "him");
+        assert_eq!(
+            genders[0].localize_with_context("en", &values, VerbosityLevel::Verbose),
+            "they"
+        );
+    }
+
     /// Test GENDER with different locales (behavior should be same)
     #[test]
     fn test_gender_multilingual() {
@@ -1425,4 +1974,32 @@ mod tests {
         // Empty gender string is not "male" or "female" -> neutral (forms[2])
         assert_eq!(transclusion.localize("en", &vec!["".to_string()]), "they");
     }
+
+    #[test]
+    fn test_ast_to_wikitext() {
+        let ast = vec![
+            AstNode::Transclusion(Transclusion {
+                name: "GENDER".to_string(),
+                param: "$1".to_string(),
+                options: vec!["He".to_string(), "She".to_string()],
+            }),
+            AstNode::Text(" sent ".to_string()),
+            AstNode::Placeholder(Placeholder { index: 2 }),
+            AstNode::Text(" to ".to_string()),
+            AstNode::InternalLink(WikiInternalLink {
+                target: "Special:Inbox".to_string(),
+                params: Vec::new(),
+                display_text: Some("you".to_string()),
+            }),
+            AstNode::Text(" via ".to_string()),
+            AstNode::ExternalLink(WikiExternalLink {
+                url: "https://example.org".to_string(),
+                text: None,
+            }),
+        ];
+        assert_eq!(
+            ast_to_wikitext(&ast),
+            "{{GENDER:$1|He|She}} sent $2 to [[Special:Inbox|you]] via [https://example.org]"
+        );
+    }
 }
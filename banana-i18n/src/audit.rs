@@ -0,0 +1,279 @@
+use crate::LocalizedMessages;
+use crate::ast::{AstNode, AstNodeList};
+use crate::parser::Parser;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+/// What differs between a source message and its translation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuditIssueKind {
+    /// Placeholders used in the source but not in the translation
+    MissingPlaceholders(Vec<usize>),
+    /// Placeholders used in the translation but not in the source
+    ExtraPlaceholders(Vec<usize>),
+    /// A magic word appears a different number of times
+    MagicWordCountMismatch {
+        magic_word: String,
+        source: usize,
+        target: usize,
+    },
+}
+
+/// A problem found in one translated message of a catalog
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CatalogAuditIssue {
+    /// Message key
+    pub key: String,
+    /// What is wrong with the translation
+    pub kind: AuditIssueKind,
+}
+
+impl fmt::Display for CatalogAuditIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let list = |indices: &[usize]| {
+            indices
+                .iter()
+                .map(|i| format!("${}", i))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        match &self.kind {
+            AuditIssueKind::MissingPlaceholders(indices) => {
+                write!(f, "{}: missing placeholders {}", self.key, list(indices))
+            }
+            AuditIssueKind::ExtraPlaceholders(indices) => {
+                write!(f, "{}: unexpected placeholders {}", self.key, list(indices))
+            }
+            AuditIssueKind::MagicWordCountMismatch {
+                magic_word,
+                source,
+                target,
+            } => write!(
+                f,
+                "{}: {} used {} time(s) in source but {} in translation",
+                self.key, magic_word, source, target
+            ),
+        }
+    }
+}
+
+/// Compare every translated message against its source message
+///
+/// Each key present in both catalogs is parsed in both, and the sets of
+/// placeholders (including those inside magic word options and link text)
+/// and the number of each magic word are compared. Keys missing from the
+/// target are untranslated rather than drifted, and are not reported.
+///
+/// # Arguments
+/// * `source` - Source locale messages
+/// * `target` - Translated messages
+///
+/// # Returns
+/// All issues found, sorted by key
+pub fn audit_catalog(
+    source: &LocalizedMessages,
+    target: &LocalizedMessages,
+) -> Vec<CatalogAuditIssue> {
+    let mut keys: Vec<&String> = source.get_messages().keys().collect();
+    keys.sort();
+
+    let mut issues = Vec::new();
+    for key in keys {
+        let Some(translated) = target.get_message(key) else {
+            continue;
+        };
+        let original = &source.get_messages()[key];
+
+        let source_ast = Parser::new(original).parse();
+        let target_ast = Parser::new(translated).parse();
+
+        let source_placeholders = collect_placeholders(&source_ast);
+        let target_placeholders = collect_placeholders(&target_ast);
+
+        let missing: Vec<usize> = source_placeholders
+            .difference(&target_placeholders)
+            .copied()
+            .collect();
+        if !missing.is_empty() {
+            issues.push(CatalogAuditIssue {
+                key: key.clone(),
+                kind: AuditIssueKind::MissingPlaceholders(missing),
+            });
+        }
+
+        let extra: Vec<usize> = target_placeholders
+            .difference(&source_placeholders)
+            .copied()
+            .collect();
+        if !extra.is_empty() {
+            issues.push(CatalogAuditIssue {
+                key: key.clone(),
+                kind: AuditIssueKind::ExtraPlaceholders(extra),
+            });
+        }
+
+        let source_words = count_magic_words(&source_ast);
+        let target_words = count_magic_words(&target_ast);
+        let names: BTreeSet<&String> = source_words.keys().chain(target_words.keys()).collect();
+        for name in names {
+            let source_count = source_words.get(name).copied().unwrap_or(0);
+            let target_count = target_words.get(name).copied().unwrap_or(0);
+            if source_count != target_count {
+                issues.push(CatalogAuditIssue {
+                    key: key.clone(),
+                    kind: AuditIssueKind::MagicWordCountMismatch {
+                        magic_word: name.clone(),
+                        source: source_count,
+                        target: target_count,
+                    },
+                });
+            }
+        }
+    }
+
+    issues
+}
+
+/// Placeholder indices used anywhere in a message
+fn collect_placeholders(ast: &AstNodeList) -> BTreeSet<usize> {
+    let mut placeholders = BTreeSet::new();
+    for node in ast {
+        match node {
+            AstNode::Placeholder(placeholder) => {
+                placeholders.insert(placeholder.index);
+            }
+            AstNode::Transclusion(trans) => {
+                placeholders.extend(placeholders_in_text(&trans.param));
+                for option in &trans.options {
+                    placeholders.extend(collect_placeholders(&Parser::new(option).parse()));
+                }
+            }
+            AstNode::InternalLink(link) => {
+                placeholders.extend(placeholders_in_text(&link.target));
+                for param in &link.params {
+                    placeholders.extend(placeholders_in_text(param));
+                }
+                if let Some(ref display_text) = link.display_text {
+                    placeholders.extend(placeholders_in_text(display_text));
+                }
+            }
+            AstNode::ExternalLink(link) => {
+                placeholders.extend(placeholders_in_text(&link.url));
+                if let Some(ref text) = link.text {
+                    placeholders.extend(placeholders_in_text(text));
+                }
+            }
+            AstNode::Text(_) | AstNode::Comment(_) => {}
+        }
+    }
+    placeholders
+}
+
+/// Placeholder indices in plain text (`$1`, `$2`, ...)
+fn placeholders_in_text(text: &str) -> Vec<usize> {
+    text.split('$')
+        .skip(1)
+        .filter_map(|rest| {
+            let digits: String = rest.chars().take_while(|c| c.is_ascii_digit()).collect();
+            digits.parse().ok()
+        })
+        .collect()
+}
+
+/// Number of occurrences of each magic word, including nested ones
+fn count_magic_words(ast: &AstNodeList) -> BTreeMap<String, usize> {
+    let mut counts = BTreeMap::new();
+    for node in ast {
+        if let AstNode::Transclusion(trans) = node {
+            *counts.entry(trans.name.to_uppercase()).or_insert(0) += 1;
+            for option in &trans.options {
+                for (name, count) in count_magic_words(&Parser::new(option).parse()) {
+                    *counts.entry(name).or_insert(0) += count;
+                }
+            }
+        }
+    }
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn catalog(messages: &[(&str, &str)]) -> LocalizedMessages {
+        let mut catalog = LocalizedMessages::new();
+        for (key, message) in messages {
+            catalog.with_message(key, message);
+        }
+        catalog
+    }
+
+    #[test]
+    fn test_audit_detects_dropped_placeholder() {
+        let source = catalog(&[
+            ("greeting", "Hello, $1!"),
+            ("sent", "$1 sent {{PLURAL:$2|a message|$2 messages}} to $3"),
+            ("untranslated", "Only in source $1"),
+        ]);
+        let target = catalog(&[
+            ("greeting", "Bonjour, $1 !"),
+            ("sent", "$1 a envoyé des messages à $3"),
+        ]);
+
+        let issues = audit_catalog(&source, &target);
+        assert_eq!(
+            issues,
+            vec![
+                CatalogAuditIssue {
+                    key: "sent".to_string(),
+                    kind: AuditIssueKind::MissingPlaceholders(vec![2]),
+                },
+                CatalogAuditIssue {
+                    key: "sent".to_string(),
+                    kind: AuditIssueKind::MagicWordCountMismatch {
+                        magic_word: "PLURAL".to_string(),
+                        source: 1,
+                        target: 0,
+                    },
+                },
+            ]
+        );
+        assert_eq!(issues[0].to_string(), "sent: missing placeholders $2");
+    }
+
+    #[test]
+    fn test_audit_detects_extra_placeholder() {
+        let source = catalog(&[("files", "{{PLURAL:$1|one file|$1 files}}")]);
+        let target = catalog(&[("files", "{{PLURAL:$1|un fichier|$1 fichiers}} $2")]);
+
+        let issues = audit_catalog(&source, &target);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].kind, AuditIssueKind::ExtraPlaceholders(vec![2]));
+    }
+
+    #[test]
+    fn test_audit_checks_link_targets_and_urls() {
+        let source = catalog(&[(
+            "links",
+            "See [[$2|the page]] or [https://example.org/$1 help]",
+        )]);
+        let target = catalog(&[(
+            "links",
+            "Voir [[Accueil|la page]] ou [https://example.org aide]",
+        )]);
+
+        let issues = audit_catalog(&source, &target);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(
+            issues[0].kind,
+            AuditIssueKind::MissingPlaceholders(vec![1, 2])
+        );
+    }
+
+    #[test]
+    fn test_audit_clean_catalog() {
+        let source = catalog(&[("gender", "{{GENDER:$1|He|She}} edited [[$2|the page]]")]);
+        let target = catalog(&[("gender", "{{GENDER:$1|Il|Elle}} a modifié [[$2|la page]]")]);
+        assert!(audit_catalog(&source, &target).is_empty());
+    }
+}
@@ -0,0 +1,172 @@
+use crate::LocalizedMessages;
+use crate::loader::DOCUMENTATION_LOCALE;
+use std::collections::HashMap;
+use std::fmt;
+
+/// How many reference keys one locale translates
+#[derive(Debug, Clone, PartialEq)]
+pub struct LocaleCoverage {
+    /// Locale code
+    pub locale: String,
+    /// Number of reference keys the locale has a message for
+    pub translated: usize,
+    /// Share of reference keys translated, from 0.0 to 100.0
+    pub percentage: f64,
+}
+
+/// Translation coverage of every loaded locale against a reference locale
+#[derive(Debug, Clone, PartialEq)]
+pub struct CoverageReport {
+    /// Locale whose keys are expected in every other locale
+    pub reference_locale: String,
+    /// Number of keys in the reference locale
+    pub total_keys: usize,
+    /// Coverage per locale, sorted by locale code
+    pub locales: Vec<LocaleCoverage>,
+    /// Locales that translate none of the reference keys, sorted
+    pub missing_locales: Vec<String>,
+}
+
+impl CoverageReport {
+    /// Coverage of one locale, if it is loaded
+    pub fn locale(&self, locale: &str) -> Option<&LocaleCoverage> {
+        let locale = locale.to_lowercase();
+        self.locales
+            .iter()
+            .find(|coverage| coverage.locale == locale)
+    }
+
+    /// Share of reference keys translated across all locales, from 0.0 to 100.0
+    ///
+    /// Every locale counts equally, so this is the percentage of all
+    /// (locale, key) pairs that have a message.
+    pub fn overall_percentage(&self) -> f64 {
+        let translated: usize = self.locales.iter().map(|c| c.translated).sum();
+        percentage(translated, self.total_keys * self.locales.len())
+    }
+}
+
+impl fmt::Display for CoverageReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:.0}% of {} keys translated across {} locales (reference: {})",
+            self.overall_percentage(),
+            self.total_keys,
+            self.locales.len(),
+            self.reference_locale
+        )?;
+        for coverage in &self.locales {
+            writeln!(
+                f,
+                "  {}: {}/{} ({:.0}%)",
+                coverage.locale, coverage.translated, self.total_keys, coverage.percentage
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Count the reference keys present in each locale
+///
+/// The reference locale itself and message documentation (`qqq`) are not
+/// reported. Messages for keys the reference does not have are ignored.
+pub(crate) fn coverage_report(
+    messages: &HashMap<String, LocalizedMessages>,
+    reference_locale: &str,
+) -> CoverageReport {
+    let reference_locale = reference_locale.to_lowercase();
+    let reference_keys: Vec<&String> = messages
+        .get(&reference_locale)
+        .map(|reference| reference.get_messages().keys().collect())
+        .unwrap_or_default();
+
+    let mut locales: Vec<LocaleCoverage> = messages
+        .iter()
+        .filter(|(locale, _)| {
+            **locale != reference_locale && locale.as_str() != DOCUMENTATION_LOCALE
+        })
+        .map(|(locale, catalog)| {
+            let translated = reference_keys
+                .iter()
+                .filter(|key| catalog.get_message(key).is_some())
+                .count();
+            LocaleCoverage {
+                locale: locale.clone(),
+                translated,
+                percentage: percentage(translated, reference_keys.len()),
+            }
+        })
+        .collect();
+    locales.sort_by(|a, b| a.locale.cmp(&b.locale));
+
+    let missing_locales = locales
+        .iter()
+        .filter(|coverage| coverage.translated == 0 && !reference_keys.is_empty())
+        .map(|coverage| coverage.locale.clone())
+        .collect();
+
+    CoverageReport {
+        reference_locale,
+        total_keys: reference_keys.len(),
+        locales,
+        missing_locales,
+    }
+}
+
+/// `part` as a percentage of `whole`; nothing to translate counts as complete
+fn percentage(part: usize, whole: usize) -> f64 {
+    if whole == 0 {
+        100.0
+    } else {
+        part as f64 * 100.0 / whole as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{I18n, LocalizedMessages};
+
+    fn catalog(keys: std::ops::Range<usize>) -> LocalizedMessages {
+        let mut catalog = LocalizedMessages::new();
+        for i in keys {
+            catalog.with_message(&format!("key-{}", i), "text");
+        }
+        catalog
+    }
+
+    #[test]
+    fn test_coverage_report_percentages() {
+        let mut extra = catalog(0..3);
+        extra.with_message("not-in-reference", "text");
+
+        let mut i18n = I18n::new();
+        i18n.with_messages_for_locale("en", catalog(0..10))
+            .with_messages_for_locale("fr", catalog(0..10))
+            .with_messages_for_locale("de", catalog(0..7))
+            .with_messages_for_locale("es", extra)
+            .with_messages_for_locale("ml", LocalizedMessages::new())
+            .with_documentation(catalog(0..10));
+
+        let report = i18n.coverage_report("en");
+        assert_eq!(report.total_keys, 10);
+        let percentages: Vec<(&str, usize, f64)> = report
+            .locales
+            .iter()
+            .map(|c| (c.locale.as_str(), c.translated, c.percentage))
+            .collect();
+        assert_eq!(
+            percentages,
+            vec![
+                ("de", 7, 70.0),
+                ("es", 3, 30.0),
+                ("fr", 10, 100.0),
+                ("ml", 0, 0.0),
+            ]
+        );
+        assert_eq!(report.missing_locales, vec!["ml"]);
+        assert_eq!(report.overall_percentage(), 50.0);
+        assert_eq!(report.locale("DE").unwrap().translated, 7);
+        assert!(report.to_string().starts_with("50% of 10 keys"));
+    }
+}
@@ -0,0 +1,158 @@
+use crate::parser::ParseError;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Errors returned by the fallible APIs of the core crate
+#[derive(Debug)]
+pub enum I18nError {
+    /// The message could not be parsed
+    ParseError(ParseError),
+    /// A message file or directory could not be loaded
+    LoaderError {
+        /// File or directory being loaded (empty if unknown)
+        path: PathBuf,
+        /// The underlying IO, JSON or format error
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+    /// A message catalog could not be written
+    WriterError {
+        /// File being written
+        path: PathBuf,
+        /// The underlying IO error
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+    /// No message exists for the key in the locale or its fallbacks
+    MissingKey { key: String, locale: String },
+    /// The locale code is not a valid BCP 47 tag
+    InvalidLocale(String),
+}
+
+/// Result type for the fallible core APIs
+pub type I18nResult<T> = Result<T, I18nError>;
+
+impl I18nError {
+    /// Create a `LoaderError` for the given path
+    pub fn loader(
+        path: &Path,
+        source: impl Into<Box<dyn std::error::Error + Send + Sync>>,
+    ) -> Self {
+        I18nError::LoaderError {
+            path: path.to_path_buf(),
+            source: source.into(),
+        }
+    }
+
+    /// Create a `WriterError` for the given path
+    pub fn writer(
+        path: &Path,
+        source: impl Into<Box<dyn std::error::Error + Send + Sync>>,
+    ) -> Self {
+        I18nError::WriterError {
+            path: path.to_path_buf(),
+            source: source.into(),
+        }
+    }
+
+    /// Attach a path to a `LoaderError` that was created without one
+    ///
+    /// Other errors, and loader errors that already carry a path, are
+    /// returned unchanged.
+    pub fn at_path(self, path: &Path) -> Self {
+        match self {
+            I18nError::LoaderError { path: old, source } if old.as_os_str().is_empty() => {
+                I18nError::loader(path, source)
+            }
+            other => other,
+        }
+    }
+}
+
+impl fmt::Display for I18nError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            I18nError::ParseError(e) => write!(f, "Parse error: {}", e),
+            I18nError::LoaderError { path, source } if path.as_os_str().is_empty() => {
+                write!(f, "Loader error: {}", source)
+            }
+            I18nError::LoaderError { path, source } => {
+                write!(f, "Loader error in '{}': {}", path.display(), source)
+            }
+            I18nError::WriterError { path, source } => {
+                write!(f, "Writer error in '{}': {}", path.display(), source)
+            }
+            I18nError::MissingKey { key, locale } => write!(
+                f,
+                "Missing message '{}' in locale '{}' and its fallbacks",
+                key, locale
+            ),
+            I18nError::InvalidLocale(locale) => write!(f, "Invalid locale: {}", locale),
+        }
+    }
+}
+
+impl std::error::Error for I18nError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            I18nError::ParseError(e) => Some(e),
+            I18nError::LoaderError { source, .. } | I18nError::WriterError { source, .. } => {
+                Some(source.as_ref())
+            }
+            _ => None,
+        }
+    }
+}
+
+impl From<ParseError> for I18nError {
+    fn from(e: ParseError) -> Self {
+        I18nError::ParseError(e)
+    }
+}
+
+/// IO errors become loader errors without a path (see `at_path()`)
+impl From<std::io::Error> for I18nError {
+    fn from(e: std::io::Error) -> Self {
+        I18nError::loader(Path::new(""), e)
+    }
+}
+
+/// JSON errors become loader errors without a path (see `at_path()`)
+impl From<serde_json::Error> for I18nError {
+    fn from(e: serde_json::Error) -> Self {
+        I18nError::loader(Path::new(""), e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error;
+
+    #[test]
+    fn test_display_and_source() {
+        let io = std::io::Error::new(std::io::ErrorKind::NotFound, "no such file");
+        let err = I18nError::from(io).at_path(Path::new("i18n/en.json"));
+        assert_eq!(
+            err.to_string(),
+            "Loader error in 'i18n/en.json': no such file"
+        );
+        assert!(err.source().is_some());
+
+        let err = I18nError::MissingKey {
+            key: "greeting".to_string(),
+            locale: "fr".to_string(),
+        };
+        assert_eq!(
+            err.to_string(),
+            "Missing message 'greeting' in locale 'fr' and its fallbacks"
+        );
+        assert!(err.source().is_none());
+    }
+
+    #[test]
+    fn test_from_serde_json_error() {
+        let json_err = serde_json::from_str::<serde_json::Value>("{").unwrap_err();
+        let err: I18nError = json_err.into();
+        assert!(matches!(err, I18nError::LoaderError { .. }));
+        assert!(err.to_string().starts_with("Loader error: "));
+    }
+}
@@ -0,0 +1,63 @@
+/// A parameter value that is already HTML and must not be escaped
+///
+/// Only has an effect when HTML escaping is enabled with
+/// `I18n::with_html_escaping()`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawValue(pub String);
+
+/// A parameter value passed to `I18n::localize_params()`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParamValue {
+    /// Plain text, HTML-escaped when escaping is enabled
+    Text(String),
+    /// Trusted HTML, inserted as is
+    Raw(RawValue),
+}
+
+impl From<String> for ParamValue {
+    fn from(value: String) -> Self {
+        ParamValue::Text(value)
+    }
+}
+
+impl From<&str> for ParamValue {
+    fn from(value: &str) -> Self {
+        ParamValue::Text(value.to_string())
+    }
+}
+
+impl From<RawValue> for ParamValue {
+    fn from(value: RawValue) -> Self {
+        ParamValue::Raw(value)
+    }
+}
+
+/// Escape `<`, `>`, `&`, `"` and `'` for safe inclusion in HTML
+pub fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_html() {
+        assert_eq!(
+            escape_html(r#"<a href="x">Tom & Jerry's</a>"#),
+            "&lt;a href=&quot;x&quot;&gt;Tom &amp; Jerry&#39;s&lt;/a&gt;"
+        );
+        assert_eq!(escape_html("plain text"), "plain text");
+    }
+}
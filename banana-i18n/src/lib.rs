@@ -1,18 +1,37 @@
 use std::collections::HashMap;
 
 pub mod ast;
+pub mod audit;
+pub mod coverage;
+pub mod error;
 pub mod fallbacks;
+pub mod html;
 pub mod loader;
+pub mod locale_meta;
+pub mod magic_words;
 pub mod parser;
+pub mod preview;
+pub mod writer;
 
 // Re-export AST types for convenient access
 pub use ast::{
     AstNode, AstNodeList, Localizable, Placeholder, Transclusion, WikiExternalLink,
-    WikiInternalLink,
+    WikiInternalLink, ast_to_wikitext,
 };
+pub use audit::{AuditIssueKind, CatalogAuditIssue, audit_catalog};
+pub use coverage::{CoverageReport, LocaleCoverage};
+pub use error::{I18nError, I18nResult};
 pub use fallbacks::get_fallbacks;
-pub use loader::{load_all_messages_from_dir, load_messages_from_file};
-pub use parser::Parser;
+pub use html::{ParamValue, RawValue, escape_html};
+pub use loader::{
+    DOCUMENTATION_LOCALE, OrderedCatalog, load_all_messages_from_dir, load_documentation_from_dir,
+    load_messages_from_file, load_messages_ndjson, load_ordered_messages_from_file,
+};
+pub use locale_meta::{Direction, Script, direction_of, is_cjk, script_of};
+pub use magic_words::{MagicWordRegistry, MagicWordType, magic_words_in};
+pub use parser::{ParseError, Parser, TextEdit};
+pub use preview::preview_plain;
+pub use writer::{messages_to_json, ordered_messages_to_json, write_messages_to_file};
 
 /// Verbosity level for debug logging during fallback resolution
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
@@ -61,6 +80,12 @@ pub struct I18n {
     messages: HashMap<String, LocalizedMessages>,
     default_locale: String,
     verbosity: VerbosityLevel,
+    // Parse parameter values as wikitext (e.g. a value carrying {{PLURAL:...}})
+    recursive_parameters: bool,
+    // Magic word names and aliases recognized when rendering
+    magic_words: MagicWordRegistry,
+    // HTML-escape substituted parameter values
+    html_escaping: bool,
 }
 
 impl I18n {
@@ -69,6 +94,9 @@ impl I18n {
             messages: HashMap::new(),
             default_locale: "en".to_string(),
             verbosity: VerbosityLevel::Normal,
+            recursive_parameters: false,
+            magic_words: MagicWordRegistry::new(),
+            html_escaping: false,
         }
     }
 
@@ -89,6 +117,47 @@ impl I18n {
     pub fn get_verbosity(&self) -> VerbosityLevel {
         self.verbosity
     }
+
+    /// Parse and localize substituted parameter values (off by default)
+    ///
+    /// When enabled, a value such as `"{{PLURAL:5|item|items}}"` is itself parsed
+    /// and rendered with the same locale instead of being inserted literally.
+    /// Values are rendered without arguments, so a `$1` inside a value stays
+    /// literal and never re-references the argument list; this also means a
+    /// value can never expand into itself.
+    pub fn with_recursive_parameters(&mut self, enabled: bool) -> &mut Self {
+        self.recursive_parameters = enabled;
+        self
+    }
+
+    pub fn get_recursive_parameters(&self) -> bool {
+        self.recursive_parameters
+    }
+
+    /// HTML-escape substituted parameter values (off by default)
+    ///
+    /// Protects HTML output from markup in user-supplied values such as user
+    /// names. Only the values are escaped; the message's own markup is left
+    /// untouched. Pass trusted HTML as a `RawValue` to `localize_params()` to
+    /// opt a value out.
+    pub fn with_html_escaping(&mut self, enabled: bool) -> &mut Self {
+        self.html_escaping = enabled;
+        self
+    }
+
+    pub fn get_html_escaping(&self) -> bool {
+        self.html_escaping
+    }
+
+    /// Render `alias` like the given magic word (e.g. a localized PLURAL name)
+    pub fn with_magic_word_alias(&mut self, alias: &str, magic_type: MagicWordType) -> &mut Self {
+        self.magic_words.with_alias(alias, magic_type);
+        self
+    }
+
+    pub fn get_magic_words(&self) -> &MagicWordRegistry {
+        &self.magic_words
+    }
     pub fn with_messages_for_locale(
         &mut self,
         locale: &str,
@@ -98,6 +167,24 @@ impl I18n {
         self
     }
 
+    /// Set message documentation (the `qqq` pseudo-locale)
+    ///
+    /// Documentation loaded through `with_messages_for_locale("qqq", ...)`,
+    /// e.g. from `load_all_messages_from_dir()`, is picked up the same way.
+    pub fn with_documentation(&mut self, documentation: LocalizedMessages) -> &mut Self {
+        self.with_messages_for_locale(loader::DOCUMENTATION_LOCALE, documentation)
+    }
+
+    /// Documentation for a message key, if any was loaded
+    ///
+    /// Documentation never falls back to other locales.
+    pub fn get_documentation(&self, key: &str) -> Option<&str> {
+        self.messages
+            .get(loader::DOCUMENTATION_LOCALE)
+            .and_then(|docs| docs.get_message(key))
+            .map(String::as_str)
+    }
+
     pub fn add_message(&mut self, locale: &str, key: String, message: Vec<String>) {
         let messages: &mut LocalizedMessages = self
             .messages
@@ -109,10 +196,30 @@ impl I18n {
     }
 
     pub fn get_message(&self, locale: &str, key: &str) -> String {
+        // No message found in any fallback locale, return the key
+        self.find_message(locale, key)
+            .unwrap_or_else(|| key.to_string())
+    }
+
+    /// Find which locale supplies a message, and its raw (unrendered) value
+    ///
+    /// Follows the same path as `get_message()`: the locale itself, then its
+    /// fallback chain. Returns the locale that had the message, so tools and
+    /// tests can check why a message came out in another language; `None`
+    /// if neither the locale nor its fallbacks have it.
+    ///
+    /// ```ignore
+    /// // "de-at" has no "greeting", "de" does
+    /// assert_eq!(
+    ///     i18n.resolve_source("de-at", "greeting"),
+    ///     Some(("de".to_string(), "Hallo $1".to_string()))
+    /// );
+    /// ```
+    pub fn resolve_source(&self, locale: &str, key: &str) -> Option<(String, String)> {
         // Try to get message from requested locale first
         if let Some(messages) = self.messages.get(locale) {
             if let Some(message) = messages.get_message(key) {
-                return message.clone();
+                return Some((locale.to_string(), message.clone()));
             }
         }
 
@@ -132,12 +239,11 @@ impl I18n {
                     if self.verbosity >= VerbosityLevel::Verbose {
                         eprintln!("[i18n] Fallback chain: {}", fallback_chain.join(" -> "));
                     }
-                    return message.clone();
+                    return Some((fallback_locale.clone(), message.clone()));
                 }
             }
         }
 
-        // No message found in any fallback locale, return the key
         if self.verbosity >= VerbosityLevel::Verbose {
             eprintln!(
                 "[i18n] No message found for '{}' in locale '{}' or its fallbacks: {}",
@@ -146,13 +252,94 @@ impl I18n {
                 fallback_chain.join(" -> ")
             );
         }
-        key.to_string()
+        None
+    }
+
+    /// Look up a message in the locale and then its fallback chain
+    fn find_message(&self, locale: &str, key: &str) -> Option<String> {
+        self.resolve_source(locale, key).map(|(_, message)| message)
+    }
+
+    /// Report how many keys of `reference_locale` each loaded locale translates
+    ///
+    /// Only messages loaded for the locale itself count; fallbacks are not
+    /// followed. Locales that translate none of the reference keys are also
+    /// listed in `CoverageReport::missing_locales`.
+    ///
+    /// ```ignore
+    /// let report = i18n.coverage_report("en");
+    /// println!("{:.0}% translated", report.overall_percentage());
+    /// ```
+    pub fn coverage_report(&self, reference_locale: &str) -> CoverageReport {
+        coverage::coverage_report(&self.messages, reference_locale)
     }
 
     pub fn localize(&self, locale: &str, key: &str, values: &Vec<String>) -> String {
         self.localize_internal(locale, key, values, true)
     }
 
+    /// View of this catalog whose keys are relative to `prefix`
+    ///
+    /// Useful when modules own a key prefix: `i18n.scoped("editor.")` resolves
+    /// `"save"` as `"editor.save"`. The prefix is prepended verbatim, so
+    /// include any separator. Locale fallback works on the full key.
+    pub fn scoped(&self, prefix: &str) -> ScopedI18n<'_> {
+        ScopedI18n {
+            i18n: self,
+            prefix: prefix.to_string(),
+        }
+    }
+
+    /// Localize a message, reporting problems instead of falling back
+    ///
+    /// Unlike `localize()`, which returns the key for a missing message and
+    /// renders unparseable messages as plain text, this returns an error.
+    ///
+    /// # Errors
+    /// - `InvalidLocale` if `locale` is not a valid locale code
+    /// - `MissingKey` if neither the locale nor its fallbacks have the message
+    /// - `ParseError` if the message nests deeper than the parser allows
+    pub fn try_localize(
+        &self,
+        locale: &str,
+        key: &str,
+        values: &Vec<String>,
+    ) -> I18nResult<String> {
+        if locale.parse::<icu_locale::Locale>().is_err() {
+            return Err(I18nError::InvalidLocale(locale.to_string()));
+        }
+        let message = self
+            .find_message(locale, key)
+            .ok_or_else(|| I18nError::MissingKey {
+                key: key.to_string(),
+                locale: locale.to_string(),
+            })?;
+        parser::Parser::new(&message).try_parse()?;
+        if self.html_escaping {
+            let escaped: Vec<String> = values.iter().map(|value| escape_html(value)).collect();
+            return Ok(self.render(locale, &message, &escaped, self.recursive_parameters));
+        }
+        Ok(self.render(locale, &message, values, self.recursive_parameters))
+    }
+
+    /// Localize a message with values that may opt out of HTML escaping
+    ///
+    /// Same as `localize()`, but each value is a `ParamValue`: plain text is
+    /// escaped when HTML escaping is enabled, while a `RawValue` is inserted
+    /// as is.
+    pub fn localize_params(&self, locale: &str, key: &str, values: &[ParamValue]) -> String {
+        let values: Vec<String> = values
+            .iter()
+            .map(|value| match value {
+                ParamValue::Text(text) if self.html_escaping => escape_html(text),
+                ParamValue::Text(text) => text.clone(),
+                ParamValue::Raw(RawValue(html)) => html.clone(),
+            })
+            .collect();
+        let message = self.get_message(locale, key);
+        self.render(locale, &message, &values, self.recursive_parameters)
+    }
+
     fn localize_internal(
         &self,
         locale: &str,
@@ -161,7 +348,26 @@ impl I18n {
         _log_fallback: bool,
     ) -> String {
         let message = self.get_message(locale, key);
-        let mut parser = parser::Parser::new(&message);
+        if self.html_escaping {
+            let escaped: Vec<String> = values.iter().map(|value| escape_html(value)).collect();
+            return self.render(locale, &message, &escaped, self.recursive_parameters);
+        }
+        self.render(locale, &message, values, self.recursive_parameters)
+    }
+
+    /// Parse and render a message with the given values
+    ///
+    /// `expand_values` controls whether substituted values are themselves
+    /// rendered; nested renders always pass `false`, which bounds recursion
+    /// to a single level.
+    fn render(
+        &self,
+        locale: &str,
+        message: &str,
+        values: &Vec<String>,
+        expand_values: bool,
+    ) -> String {
+        let mut parser = parser::Parser::new(message);
         let ast: AstNodeList = parser.parse();
         let mut result = String::new();
 
@@ -169,13 +375,23 @@ impl I18n {
             match node {
                 AstNode::Text(text) => result.push_str(&text),
                 AstNode::Placeholder(placeholder) => {
-                    result.push_str(&placeholder.localize(locale, values).as_str());
+                    let value = placeholder.localize(locale, values);
+                    if expand_values && placeholder.index > 0 && placeholder.index <= values.len() {
+                        result.push_str(&self.render(locale, &value, &Vec::new(), false));
+                    } else {
+                        result.push_str(&value);
+                    }
                 }
                 AstNode::Transclusion(transclusion) => {
                     // For transclusions, pass verbosity via context
                     result.push_str(
                         transclusion
-                            .localize_with_context(locale, values, self.verbosity)
+                            .localize_with_registry(
+                                locale,
+                                values,
+                                self.verbosity,
+                                &self.magic_words,
+                            )
                             .as_str(),
                     );
                 }
@@ -185,12 +401,51 @@ impl I18n {
                 AstNode::ExternalLink(link) => {
                     result.push_str(&link.to_string());
                 }
+                // Comments are for translators, never part of the output
+                AstNode::Comment(_) => {}
             }
         }
         result
     }
 }
 
+/// A view of an `I18n` catalog that prepends a key prefix
+///
+/// Created with `I18n::scoped()`.
+pub struct ScopedI18n<'a> {
+    i18n: &'a I18n,
+    prefix: String,
+}
+
+impl<'a> ScopedI18n<'a> {
+    pub fn get_prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// Full key for a key relative to this scope
+    pub fn full_key(&self, key: &str) -> String {
+        format!("{}{}", self.prefix, key)
+    }
+
+    /// Narrow the scope further: `scoped("editor.").scoped("toolbar.")`
+    pub fn scoped(&self, prefix: &str) -> ScopedI18n<'a> {
+        ScopedI18n {
+            i18n: self.i18n,
+            prefix: self.full_key(prefix),
+        }
+    }
+
+    /// Same as `I18n::get_message()`; a missing message returns the full key
+    pub fn get_message(&self, locale: &str, key: &str) -> String {
+        self.i18n.get_message(locale, &self.full_key(key))
+    }
+
+    /// Same as `I18n::localize()`
+    pub fn localize(&self, locale: &str, key: &str, values: &Vec<String>) -> String {
+        self.i18n.localize(locale, &self.full_key(key), values)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -283,6 +538,39 @@ mod tests {
         assert_eq!(i18n.localize("en", "nonexistent", &vec![]), "nonexistent");
     }
 
+    #[test]
+    fn test_resolve_source() {
+        let mut de_messages: LocalizedMessages = LocalizedMessages::new();
+        de_messages.with_message("greeting", "Guten Tag, $1!");
+
+        let mut en_messages: LocalizedMessages = LocalizedMessages::new();
+        en_messages.with_message("greeting", "Hello, $1!");
+        en_messages.with_message("farewell", "Goodbye, $1!");
+
+        let mut i18n = I18n::new();
+        i18n.with_locale("en")
+            .with_messages_for_locale("en", en_messages)
+            .with_messages_for_locale("de", de_messages)
+            .with_verbosity(VerbosityLevel::Silent);
+
+        // Direct hit
+        assert_eq!(
+            i18n.resolve_source("de", "greeting"),
+            Some(("de".to_string(), "Guten Tag, $1!".to_string()))
+        );
+        // Fallback hits
+        assert_eq!(
+            i18n.resolve_source("de-at", "greeting"),
+            Some(("de".to_string(), "Guten Tag, $1!".to_string()))
+        );
+        assert_eq!(
+            i18n.resolve_source("de-at", "farewell"),
+            Some(("en".to_string(), "Goodbye, $1!".to_string()))
+        );
+        // Miss
+        assert_eq!(i18n.resolve_source("de-at", "nonexistent"), None);
+    }
+
     #[test]
     fn test_message_fallback_complex_chain() {
         // Test with a complex fallback chain like zh-cn
@@ -326,4 +614,289 @@ mod tests {
         i18n.with_verbosity(VerbosityLevel::Verbose);
         assert_eq!(i18n.get_verbosity(), VerbosityLevel::Verbose);
     }
+
+    #[test]
+    fn test_recursive_parameters() {
+        let mut en_messages = LocalizedMessages::new();
+        en_messages.with_message("found", "Found $1 in $2");
+
+        let mut i18n = I18n::new();
+        i18n.with_messages_for_locale("en", en_messages);
+
+        let values = vec![
+            "{{PLURAL:5|one file|5 files}}".to_string(),
+            "{{PLURAL:1|one folder|$1 folders}}".to_string(),
+        ];
+
+        // Off by default: values are inserted literally
+        assert!(!i18n.get_recursive_parameters());
+        assert_eq!(
+            i18n.localize("en", "found", &values),
+            "Found {{PLURAL:5|one file|5 files}} in {{PLURAL:1|one folder|$1 folders}}"
+        );
+
+        i18n.with_recursive_parameters(true);
+        assert_eq!(
+            i18n.localize("en", "found", &values),
+            "Found 5 files in one folder"
+        );
+    }
+
+    #[test]
+    fn test_recursive_parameters_do_not_re_expand() {
+        let mut en_messages = LocalizedMessages::new();
+        en_messages.with_message("echo", "Value: $1");
+
+        let mut i18n = I18n::new();
+        i18n.with_messages_for_locale("en", en_messages)
+            .with_recursive_parameters(true);
+
+        // A value referencing $1 must not re-reference the argument list (no cycle)
+        assert_eq!(
+            i18n.localize("en", "echo", &vec!["$1 and $2".to_string()]),
+            "Value: $1 and $2"
+        );
+    }
+
+    #[test]
+    fn test_placeholders_inside_magic_word_options() {
+        let mut en_messages = LocalizedMessages::new();
+        en_messages.with_message("files", "{{PLURAL:$1|$1 file|$1 files}}");
+        en_messages.with_message(
+            "edited",
+            "{{GENDER:$1|He|She}} edited $2 {{PLURAL:$2|page|pages}}",
+        );
+        en_messages.with_message("price", "{{PLURAL:$1|$1 item costs $|items}}");
+
+        let mut i18n = I18n::new();
+        i18n.with_messages_for_locale("en", en_messages);
+
+        assert_eq!(
+            i18n.localize("en", "files", &vec!["3".to_string()]),
+            "3 files"
+        );
+        assert_eq!(
+            i18n.localize("en", "files", &vec!["1".to_string()]),
+            "1 file"
+        );
+        assert_eq!(
+            i18n.localize("en", "edited", &vec!["female".to_string(), "2".to_string()]),
+            "She edited 2 pages"
+        );
+        // A bare "$" is not a placeholder
+        assert_eq!(
+            i18n.localize("en", "price", &vec!["1".to_string()]),
+            "1 item costs $"
+        );
+    }
+
+    #[test]
+    fn test_comments_are_stripped() {
+        let mut en_messages = LocalizedMessages::new();
+        en_messages.with_message(
+            "saved",
+            "Saved<!-- shown after $1 saves --> $1 {{PLURAL:$1|page|pages}}",
+        );
+        en_messages.with_message("broken", "Saved <!-- oops");
+        en_messages.with_message("option", "Hi <!-- note -->{{PLURAL:$1|one<!-- x -->|many}}");
+
+        let mut i18n = I18n::new();
+        i18n.with_messages_for_locale("en", en_messages);
+
+        assert_eq!(
+            i18n.localize("en", "saved", &vec!["2".to_string()]),
+            "Saved 2 pages"
+        );
+        assert_eq!(
+            i18n.localize("en", "option", &vec!["1".to_string()]),
+            "Hi one"
+        );
+        // An unterminated comment stays literal text
+        assert_eq!(
+            i18n.localize("en", "broken", &Vec::new()),
+            "Saved <!-- oops"
+        );
+    }
+
+    #[test]
+    fn test_if_parser_functions() {
+        let mut en_messages = LocalizedMessages::new();
+        en_messages.with_message("blocked", "Blocked{{#if:$1|: $1|. No reason was given}}");
+        en_messages.with_message("role", "{{#ifeq:$1|admin|Administrator|User}} $2");
+
+        let mut i18n = I18n::new();
+        i18n.with_messages_for_locale("en", en_messages);
+
+        assert_eq!(
+            i18n.localize("en", "blocked", &vec!["spam".to_string()]),
+            "Blocked: spam"
+        );
+        assert_eq!(
+            i18n.localize("en", "blocked", &vec!["".to_string()]),
+            "Blocked. No reason was given"
+        );
+        assert_eq!(
+            i18n.localize("en", "blocked", &vec![" ".to_string()]),
+            "Blocked. No reason was given"
+        );
+        assert_eq!(
+            i18n.localize("en", "role", &vec!["admin".to_string(), "Ann".to_string()]),
+            "Administrator Ann"
+        );
+        assert_eq!(
+            i18n.localize("en", "role", &vec!["editor".to_string(), "Bo".to_string()]),
+            "User Bo"
+        );
+    }
+
+    #[test]
+    fn test_documentation_per_key() {
+        let mut en_messages = LocalizedMessages::new();
+        en_messages.with_message("greeting", "Hello, $1!");
+        let mut docs = LocalizedMessages::new();
+        docs.with_message("greeting", "Greeting. $1 is the user name.");
+
+        let mut i18n = I18n::new();
+        i18n.with_messages_for_locale("en", en_messages)
+            .with_documentation(docs);
+
+        assert_eq!(
+            i18n.get_documentation("greeting"),
+            Some("Greeting. $1 is the user name.")
+        );
+        assert_eq!(i18n.get_documentation("farewell"), None);
+        assert_eq!(
+            i18n.localize("en", "greeting", &vec!["World".to_string()]),
+            "Hello, World!"
+        );
+    }
+
+    #[test]
+    fn test_magic_word_alias() {
+        let mut en_messages = LocalizedMessages::new();
+        en_messages.with_message("files", "{{MEHRZAHL:$1|$1 file|$1 files}}");
+
+        let mut i18n = I18n::new();
+        i18n.with_messages_for_locale("en", en_messages)
+            .with_verbosity(VerbosityLevel::Silent);
+
+        // Unknown until registered
+        assert_eq!(
+            i18n.localize("en", "files", &vec!["3".to_string()]),
+            "{{MEHRZAHL:$1|$1 file|$1 files}}"
+        );
+
+        i18n.with_magic_word_alias("Mehrzahl", MagicWordType::Plural);
+        assert_eq!(
+            i18n.localize("en", "files", &vec!["3".to_string()]),
+            "3 files"
+        );
+        assert_eq!(
+            i18n.localize("en", "files", &vec!["1".to_string()]),
+            "1 file"
+        );
+    }
+
+    #[test]
+    fn test_try_localize_errors() {
+        let mut en_messages = LocalizedMessages::new();
+        en_messages.with_message("greeting", "Hello, $1!");
+
+        let mut i18n = I18n::new();
+        i18n.with_messages_for_locale("en", en_messages)
+            .with_verbosity(VerbosityLevel::Silent);
+
+        assert_eq!(
+            i18n.try_localize("en", "greeting", &vec!["World".to_string()])
+                .unwrap(),
+            "Hello, World!"
+        );
+        assert!(matches!(
+            i18n.try_localize("en", "missing", &vec![]),
+            Err(I18nError::MissingKey { .. })
+        ));
+        assert!(matches!(
+            i18n.try_localize("not a locale!", "greeting", &vec![]),
+            Err(I18nError::InvalidLocale(_))
+        ));
+    }
+
+    #[test]
+    fn test_html_escaping_of_values() {
+        let mut en_messages = LocalizedMessages::new();
+        en_messages.with_message(
+            "welcome",
+            "<b>Welcome</b>, $1! You have {{PLURAL:$2|one message|$2 messages}}.",
+        );
+
+        let mut i18n = I18n::new();
+        i18n.with_messages_for_locale("en", en_messages)
+            .with_verbosity(VerbosityLevel::Silent)
+            .with_html_escaping(true);
+
+        let values = vec!["<script>alert('x')</script>".to_string(), "3".to_string()];
+        assert_eq!(
+            i18n.localize("en", "welcome", &values),
+            "<b>Welcome</b>, &lt;script&gt;alert(&#39;x&#39;)&lt;/script&gt;! You have 3 messages."
+        );
+
+        // Trusted HTML opts out
+        let params = vec![
+            ParamValue::from(RawValue("<i>Alice</i>".to_string())),
+            ParamValue::from("1"),
+        ];
+        assert_eq!(
+            i18n.localize_params("en", "welcome", &params),
+            "<b>Welcome</b>, <i>Alice</i>! You have one message."
+        );
+
+        // Escaping is off by default
+        i18n.with_html_escaping(false);
+        assert_eq!(
+            i18n.localize(
+                "en",
+                "welcome",
+                &vec!["<i>Bob</i>".to_string(), "1".to_string()]
+            ),
+            "<b>Welcome</b>, <i>Bob</i>! You have one message."
+        );
+    }
+
+    #[test]
+    fn test_scoped_lookup() {
+        let mut en_messages = LocalizedMessages::new();
+        en_messages
+            .with_message("editor.save", "Save")
+            .with_message("editor.toolbar.bold", "Bold")
+            .with_message("editor.greeting", "Hello, $1!");
+        let mut de_messages = LocalizedMessages::new();
+        de_messages.with_message("editor.save", "Speichern");
+
+        let mut i18n = I18n::new();
+        i18n.with_messages_for_locale("en", en_messages)
+            .with_messages_for_locale("de", de_messages)
+            .with_verbosity(VerbosityLevel::Silent);
+
+        let editor = i18n.scoped("editor.");
+        assert_eq!(editor.get_message("de", "save"), "Speichern");
+        assert_eq!(
+            editor.localize("en", "greeting", &vec!["Ada".to_string()]),
+            "Hello, Ada!"
+        );
+        assert_eq!(editor.scoped("toolbar.").get_message("en", "bold"), "Bold");
+
+        // Fallback resolves the full key: de-at -> de, de -> en
+        assert_eq!(editor.get_message("de-at", "save"), "Speichern");
+        assert_eq!(
+            editor.localize("de", "greeting", &vec!["Ada".to_string()]),
+            "Hello, Ada!"
+        );
+
+        // Missing keys come back as the full key
+        assert_eq!(editor.get_message("en", "cancel"), "editor.cancel");
+        assert_eq!(
+            i18n.scoped("toolbar.").get_message("en", "save"),
+            "toolbar.save"
+        );
+    }
 }
@@ -1,7 +1,9 @@
 use crate::LocalizedMessages;
+use crate::error::{I18nError, I18nResult};
 use serde_json::Value;
 use std::collections::HashMap;
 use std::fs;
+use std::io::{BufRead, BufReader};
 use std::path::Path;
 
 /// Load messages from a single JSON file
@@ -25,40 +27,167 @@ use std::path::Path;
 /// - File not found
 /// - Invalid JSON
 /// - File read errors
-pub fn load_messages_from_file(path: &Path) -> Result<LocalizedMessages, String> {
+pub fn load_messages_from_file(path: &Path) -> I18nResult<LocalizedMessages> {
+    let mut messages = LocalizedMessages::new();
+    for (key, message) in load_ordered_messages_from_file(path)?.messages {
+        messages.with_message(&key, &message);
+    }
+    Ok(messages)
+}
+
+/// A JSON catalog as laid out in its file
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct OrderedCatalog {
+    /// The `@metadata` object (authors and the like), if the file has one
+    pub metadata: Option<Value>,
+    /// (key, message) pairs in file order
+    pub messages: Vec<(String, String)>,
+}
+
+/// Load messages from a single JSON file, keeping the file's key order
+///
+/// Same as `load_messages_from_file()`, but returns (key, message) pairs in
+/// the order they appear in the file along with the `@metadata` object, so a
+/// catalog can be written back with `ordered_messages_to_json()` unchanged.
+///
+/// # Arguments
+/// * `path` - Path to the JSON file
+///
+/// # Returns
+/// The `@metadata` object and the other (key, message) pairs in file order
+///
+/// # Errors
+/// - File not found
+/// - Invalid JSON
+/// - File read errors
+pub fn load_ordered_messages_from_file(path: &Path) -> I18nResult<OrderedCatalog> {
     // Read the file
-    let content = fs::read_to_string(path)
-        .map_err(|e| format!("Failed to read file '{}': {}", path.display(), e))?;
+    let content = fs::read_to_string(path).map_err(|e| I18nError::loader(path, e))?;
 
-    // Parse JSON
-    let json: Value = serde_json::from_str(&content)
-        .map_err(|e| format!("Failed to parse JSON from '{}': {}", path.display(), e))?;
+    // Parse JSON (object keys keep their file order)
+    let json: Value = serde_json::from_str(&content).map_err(|e| I18nError::loader(path, e))?;
 
     // Ensure it's an object
-    let obj = json.as_object().ok_or_else(|| {
-        format!(
-            "Invalid JSON in '{}': root must be an object",
-            path.display()
-        )
-    })?;
+    let obj = json
+        .as_object()
+        .ok_or_else(|| I18nError::loader(path, "Invalid JSON: root must be an object"))?;
 
-    // Extract messages, skipping @metadata
-    let mut messages = LocalizedMessages::new();
+    // Extract messages, keeping @metadata aside
+    let mut catalog = OrderedCatalog::default();
     for (key, value) in obj {
-        // Skip metadata
+        if key == "@metadata" {
+            catalog.metadata = Some(value.clone());
+            continue;
+        }
+        // Skip other metadata
         if key.starts_with('@') {
             continue;
         }
 
         // Extract string value
         if let Some(message) = value.as_str() {
-            messages.with_message(key, message);
+            catalog.messages.push((key.clone(), message.to_string()));
         } else {
             eprintln!("Warning: Message '{}' is not a string, skipping", key);
         }
     }
 
-    Ok(messages)
+    Ok(catalog)
+}
+
+/// Load messages from a JSON lines (NDJSON) file, one message per line
+///
+/// Each line is an object of the form `{"key": "...", "value": "..."}`. The
+/// file is read line by line, so memory use is bounded by the messages
+/// themselves rather than the size of the parsed JSON document, which matters
+/// for merged catalogs of hundreds of megabytes.
+///
+/// Malformed lines do not abort the load: they are skipped and reported as
+/// warnings with their 1-based line number. Blank lines are ignored.
+///
+/// # Arguments
+/// * `path` - Path to the NDJSON file
+///
+/// # Returns
+/// The messages that loaded, and one warning per skipped line
+///
+/// # Errors
+/// - File not found or read errors
+pub fn load_messages_ndjson(path: &Path) -> I18nResult<(LocalizedMessages, Vec<String>)> {
+    let file = fs::File::open(path).map_err(|e| I18nError::loader(path, e))?;
+    let mut reader = BufReader::new(file);
+
+    let mut messages = LocalizedMessages::new();
+    let mut warnings = Vec::new();
+    let mut buffer = Vec::new();
+    let mut line_number = 0;
+
+    loop {
+        buffer.clear();
+        let read = reader
+            .read_until(b'\n', &mut buffer)
+            .map_err(|e| I18nError::loader(path, e))?;
+        if read == 0 {
+            break;
+        }
+        line_number += 1;
+
+        let line = match std::str::from_utf8(&buffer) {
+            Ok(line) => line.trim(),
+            Err(_) => {
+                warnings.push(format!("line {}: not valid UTF-8", line_number));
+                continue;
+            }
+        };
+        if line.is_empty() {
+            continue;
+        }
+
+        let entry: Value = match serde_json::from_str(line) {
+            Ok(entry) => entry,
+            Err(e) => {
+                warnings.push(format!("line {}: invalid JSON: {}", line_number, e));
+                continue;
+            }
+        };
+        match (entry["key"].as_str(), entry["value"].as_str()) {
+            (Some(key), Some(value)) => {
+                messages.with_message(key, value);
+            }
+            _ => warnings.push(format!(
+                "line {}: expected string \"key\" and \"value\" fields",
+                line_number
+            )),
+        }
+    }
+
+    Ok((messages, warnings))
+}
+
+/// Pseudo-locale holding message documentation in MediaWiki (`qqq.json`)
+pub const DOCUMENTATION_LOCALE: &str = "qqq";
+
+/// Load message documentation (`qqq.json`) from a directory, if present
+///
+/// MediaWiki keeps a description of each message in the `qqq` pseudo-locale.
+/// The documentation is useful context for translators and LLM-based
+/// translation, but is optional: a directory without `qqq.json` is not an error.
+///
+/// # Arguments
+/// * `dir` - Directory path containing JSON files
+///
+/// # Returns
+/// `Some(LocalizedMessages)` mapping message keys to documentation, or `None`
+/// if the directory has no `qqq.json`
+///
+/// # Errors
+/// - File read/parse errors
+pub fn load_documentation_from_dir(dir: &Path) -> I18nResult<Option<LocalizedMessages>> {
+    let path = dir.join(format!("{}.json", DOCUMENTATION_LOCALE));
+    if !path.is_file() {
+        return Ok(None);
+    }
+    load_messages_from_file(&path).map(Some)
 }
 
 /// Load all messages from a directory of JSON files
@@ -76,27 +205,24 @@ pub fn load_messages_from_file(path: &Path) -> Result<LocalizedMessages, String>
 /// # Errors
 /// - Directory not found
 /// - File read/parse errors
-pub fn load_all_messages_from_dir(
-    dir: &Path,
-) -> Result<HashMap<String, LocalizedMessages>, String> {
+pub fn load_all_messages_from_dir(dir: &Path) -> I18nResult<HashMap<String, LocalizedMessages>> {
     // Check if directory exists
     if !dir.exists() {
-        return Err(format!("Directory not found: {}", dir.display()));
+        return Err(I18nError::loader(dir, "Directory not found"));
     }
 
     if !dir.is_dir() {
-        return Err(format!("Path is not a directory: {}", dir.display()));
+        return Err(I18nError::loader(dir, "Path is not a directory"));
     }
 
     let mut all_messages = HashMap::new();
 
     // Read directory entries
-    let entries = fs::read_dir(dir)
-        .map_err(|e| format!("Failed to read directory '{}': {}", dir.display(), e))?;
+    let entries = fs::read_dir(dir).map_err(|e| I18nError::loader(dir, e))?;
 
     // Process each file
     for entry in entries {
-        let entry = entry.map_err(|e| format!("Error reading directory entry: {}", e))?;
+        let entry = entry.map_err(|e| I18nError::loader(dir, e))?;
 
         let path = entry.path();
 
@@ -109,7 +235,7 @@ pub fn load_all_messages_from_dir(
         let locale = path
             .file_stem()
             .and_then(|stem| stem.to_str())
-            .ok_or_else(|| format!("Invalid filename: {}", path.display()))?
+            .ok_or_else(|| I18nError::loader(&path, "Invalid filename"))?
             .to_string();
 
         // Load messages from file
@@ -130,8 +256,79 @@ pub fn load_all_messages_from_dir(
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+
     #[test]
     fn test_loader_module_exists() {
         // Loader module exists and can be compiled
     }
+
+    #[test]
+    fn test_load_documentation_from_dir() {
+        let dir = std::env::temp_dir().join(format!("banana-i18n-qqq-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        // No qqq.json yet: documentation is optional
+        assert!(load_documentation_from_dir(&dir).unwrap().is_none());
+
+        fs::write(
+            dir.join("qqq.json"),
+            r#"{"@metadata": {"authors": []}, "greeting": "Greeting shown on the main page. $1 is the user name."}"#,
+        )
+        .unwrap();
+        let docs = load_documentation_from_dir(&dir).unwrap().unwrap();
+        assert_eq!(
+            docs.get_message("greeting").map(String::as_str),
+            Some("Greeting shown on the main page. $1 is the user name.")
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_load_errors_carry_path() {
+        let missing = std::env::temp_dir().join("banana-i18n-missing-dir");
+        match load_all_messages_from_dir(&missing) {
+            Err(I18nError::LoaderError { path, .. }) => assert_eq!(path, missing),
+            Err(other) => panic!("Expected LoaderError, got {:?}", other),
+            Ok(_) => panic!("Expected LoaderError"),
+        }
+
+        let file =
+            std::env::temp_dir().join(format!("banana-i18n-bad-{}.json", std::process::id()));
+        fs::write(&file, "[1, 2]").unwrap();
+        let err = load_messages_from_file(&file).err().unwrap();
+        assert!(err.to_string().contains("root must be an object"));
+        fs::remove_file(&file).unwrap();
+    }
+
+    #[test]
+    fn test_load_messages_ndjson_with_bad_lines() {
+        let path =
+            std::env::temp_dir().join(format!("banana-i18n-ndjson-{}.jsonl", std::process::id()));
+        fs::write(
+            &path,
+            concat!(
+                "{\"key\": \"greeting\", \"value\": \"Hello, $1!\"}\n",
+                "{\"key\": \"broken\", \"value\": \n",
+                "\n",
+                "{\"key\": \"files\", \"value\": \"{{PLURAL:$1|one file|$1 files}}\"}\n",
+                "{\"key\": \"count\", \"value\": 3}\n",
+                "{\"key\": \"last\", \"value\": \"No trailing newline\"}",
+            ),
+        )
+        .unwrap();
+
+        let (messages, warnings) = load_messages_ndjson(&path).unwrap();
+        assert_eq!(messages.get_messages().len(), 3);
+        assert_eq!(messages.get("greeting"), "Hello, $1!");
+        assert_eq!(messages.get("files"), "{{PLURAL:$1|one file|$1 files}}");
+        assert_eq!(messages.get("last"), "No trailing newline");
+
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[0].starts_with("line 2: invalid JSON"));
+        assert!(warnings[1].starts_with("line 5: expected string"));
+
+        fs::remove_file(&path).unwrap();
+    }
 }
@@ -0,0 +1,353 @@
+/// Writing system of a locale, named after its ISO 15924 code
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Script {
+    Latin,
+    Cyrillic,
+    Greek,
+    Armenian,
+    Georgian,
+    Arabic,
+    Hebrew,
+    Thaana,
+    Syriac,
+    Nko,
+    Devanagari,
+    Bengali,
+    Gurmukhi,
+    Gujarati,
+    Oriya,
+    Tamil,
+    Telugu,
+    Kannada,
+    Malayalam,
+    Sinhala,
+    Thai,
+    Lao,
+    Khmer,
+    Myanmar,
+    Tibetan,
+    Ethiopic,
+    /// Chinese characters, simplified or traditional
+    Han,
+    /// Kanji with hiragana and katakana
+    Japanese,
+    Hangul,
+}
+
+/// Direction in which a script is written
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Ltr,
+    Rtl,
+}
+
+/// ISO 15924 codes, as used in locale script subtags ("sr-latn", "zh-hant")
+const SCRIPT_CODES: &[(&str, Script)] = &[
+    ("latn", Script::Latin),
+    ("cyrl", Script::Cyrillic),
+    ("grek", Script::Greek),
+    ("armn", Script::Armenian),
+    ("geor", Script::Georgian),
+    ("arab", Script::Arabic),
+    ("hebr", Script::Hebrew),
+    ("thaa", Script::Thaana),
+    ("syrc", Script::Syriac),
+    ("nkoo", Script::Nko),
+    ("deva", Script::Devanagari),
+    ("beng", Script::Bengali),
+    ("guru", Script::Gurmukhi),
+    ("gujr", Script::Gujarati),
+    ("orya", Script::Oriya),
+    ("taml", Script::Tamil),
+    ("telu", Script::Telugu),
+    ("knda", Script::Kannada),
+    ("mlym", Script::Malayalam),
+    ("sinh", Script::Sinhala),
+    ("thai", Script::Thai),
+    ("laoo", Script::Lao),
+    ("khmr", Script::Khmer),
+    ("mymr", Script::Myanmar),
+    ("tibt", Script::Tibetan),
+    ("ethi", Script::Ethiopic),
+    ("hani", Script::Han),
+    ("hans", Script::Han),
+    ("hant", Script::Han),
+    ("jpan", Script::Japanese),
+    ("kore", Script::Hangul),
+    ("hang", Script::Hangul),
+];
+
+/// Default script of languages not written in Latin script
+///
+/// Languages missing here are assumed to use Latin script.
+const LANGUAGE_SCRIPTS: &[(&str, Script)] = &[
+    ("ab", Script::Cyrillic),
+    ("acm", Script::Arabic),
+    ("ady", Script::Cyrillic),
+    ("ajp", Script::Arabic),
+    ("am", Script::Ethiopic),
+    ("anp", Script::Devanagari),
+    ("apc", Script::Arabic),
+    ("ar", Script::Arabic),
+    ("arc", Script::Syriac),
+    ("ary", Script::Arabic),
+    ("arz", Script::Arabic),
+    ("as", Script::Bengali),
+    ("av", Script::Cyrillic),
+    ("awa", Script::Devanagari),
+    ("azb", Script::Arabic),
+    ("ba", Script::Cyrillic),
+    ("be", Script::Cyrillic),
+    ("be-tarask", Script::Cyrillic),
+    ("bg", Script::Cyrillic),
+    ("bho", Script::Devanagari),
+    ("bn", Script::Bengali),
+    ("bo", Script::Tibetan),
+    ("bpy", Script::Bengali),
+    ("bqi", Script::Arabic),
+    ("ce", Script::Cyrillic),
+    ("ckb", Script::Arabic),
+    ("cv", Script::Cyrillic),
+    ("dty", Script::Devanagari),
+    ("dv", Script::Thaana),
+    ("dz", Script::Tibetan),
+    ("el", Script::Greek),
+    ("fa", Script::Arabic),
+    ("gan", Script::Han),
+    ("glk", Script::Arabic),
+    ("gu", Script::Gujarati),
+    ("he", Script::Hebrew),
+    ("hi", Script::Devanagari),
+    ("hy", Script::Armenian),
+    ("hyw", Script::Armenian),
+    ("ja", Script::Japanese),
+    ("ka", Script::Georgian),
+    ("kbd", Script::Cyrillic),
+    ("kk", Script::Cyrillic),
+    ("km", Script::Khmer),
+    ("kn", Script::Kannada),
+    ("ko", Script::Hangul),
+    ("krc", Script::Cyrillic),
+    ("ks", Script::Arabic),
+    ("kv", Script::Cyrillic),
+    ("ky", Script::Cyrillic),
+    ("lez", Script::Cyrillic),
+    ("lo", Script::Lao),
+    ("lrc", Script::Arabic),
+    ("lzh", Script::Han),
+    ("mai", Script::Devanagari),
+    ("mhr", Script::Cyrillic),
+    ("mk", Script::Cyrillic),
+    ("ml", Script::Malayalam),
+    ("mn", Script::Cyrillic),
+    ("mnw", Script::Myanmar),
+    ("mr", Script::Devanagari),
+    ("my", Script::Myanmar),
+    ("myv", Script::Cyrillic),
+    ("mzn", Script::Arabic),
+    ("ne", Script::Devanagari),
+    ("new", Script::Devanagari),
+    ("nqo", Script::Nko),
+    ("or", Script::Oriya),
+    ("os", Script::Cyrillic),
+    ("pa", Script::Gurmukhi),
+    ("pnb", Script::Arabic),
+    ("ps", Script::Arabic),
+    ("ru", Script::Cyrillic),
+    ("rue", Script::Cyrillic),
+    ("sa", Script::Devanagari),
+    ("sah", Script::Cyrillic),
+    ("sd", Script::Arabic),
+    ("shn", Script::Myanmar),
+    ("si", Script::Sinhala),
+    ("sr", Script::Cyrillic),
+    ("syc", Script::Syriac),
+    ("ta", Script::Tamil),
+    ("tcy", Script::Kannada),
+    ("te", Script::Telugu),
+    ("tg", Script::Cyrillic),
+    ("th", Script::Thai),
+    ("ti", Script::Ethiopic),
+    ("tt", Script::Cyrillic),
+    ("tyv", Script::Cyrillic),
+    ("udm", Script::Cyrillic),
+    ("ug", Script::Arabic),
+    ("uk", Script::Cyrillic),
+    ("ur", Script::Arabic),
+    ("wuu", Script::Han),
+    ("xal", Script::Cyrillic),
+    ("xmf", Script::Georgian),
+    ("yi", Script::Hebrew),
+    ("yue", Script::Han),
+    ("zh", Script::Han),
+    ("zh-classical", Script::Han),
+];
+
+impl Script {
+    /// The ISO 15924 code of the script (e.g. "Latn", "Arab")
+    pub fn code(&self) -> &'static str {
+        match self {
+            Script::Latin => "Latn",
+            Script::Cyrillic => "Cyrl",
+            Script::Greek => "Grek",
+            Script::Armenian => "Armn",
+            Script::Georgian => "Geor",
+            Script::Arabic => "Arab",
+            Script::Hebrew => "Hebr",
+            Script::Thaana => "Thaa",
+            Script::Syriac => "Syrc",
+            Script::Nko => "Nkoo",
+            Script::Devanagari => "Deva",
+            Script::Bengali => "Beng",
+            Script::Gurmukhi => "Guru",
+            Script::Gujarati => "Gujr",
+            Script::Oriya => "Orya",
+            Script::Tamil => "Taml",
+            Script::Telugu => "Telu",
+            Script::Kannada => "Knda",
+            Script::Malayalam => "Mlym",
+            Script::Sinhala => "Sinh",
+            Script::Thai => "Thai",
+            Script::Lao => "Laoo",
+            Script::Khmer => "Khmr",
+            Script::Myanmar => "Mymr",
+            Script::Tibetan => "Tibt",
+            Script::Ethiopic => "Ethi",
+            Script::Han => "Hani",
+            Script::Japanese => "Jpan",
+            Script::Hangul => "Kore",
+        }
+    }
+
+    /// The direction the script is written in
+    pub fn direction(&self) -> Direction {
+        match self {
+            Script::Arabic | Script::Hebrew | Script::Thaana | Script::Syriac | Script::Nko => {
+                Direction::Rtl
+            }
+            _ => Direction::Ltr,
+        }
+    }
+
+    /// Whether the script is Chinese, Japanese or Korean
+    pub fn is_cjk(&self) -> bool {
+        matches!(self, Script::Han | Script::Japanese | Script::Hangul)
+    }
+}
+
+impl Direction {
+    /// The value of an HTML `dir` attribute ("ltr" or "rtl")
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Direction::Ltr => "ltr",
+            Direction::Rtl => "rtl",
+        }
+    }
+}
+
+/// Get the script a locale is written in
+///
+/// An explicit script subtag wins ("sr-Latn" → Latin, "ku-Arab" → Arabic).
+/// Otherwise the full code and then the base language are looked up in a
+/// built-in table of common languages; languages not in the table are
+/// assumed to use Latin script. Case and `_` separators are ignored.
+///
+/// # Arguments
+/// * `locale` - Language code (e.g., "ar", "zh-Hant", "pt_BR")
+///
+/// # Returns
+/// The script of the locale
+pub fn script_of(locale: &str) -> Script {
+    let locale = locale.trim().to_lowercase().replace('_', "-");
+
+    let subtags = locale.split('-').skip(1);
+    if let Some(script) = subtags
+        .filter(|subtag| subtag.len() == 4)
+        .find_map(|subtag| lookup(SCRIPT_CODES, subtag))
+    {
+        return script;
+    }
+
+    let language = locale.split('-').next().unwrap_or_default();
+    lookup(LANGUAGE_SCRIPTS, &locale)
+        .or_else(|| lookup(LANGUAGE_SCRIPTS, language))
+        .unwrap_or(Script::Latin)
+}
+
+/// Get the writing direction of a locale, from its script (see `script_of()`)
+///
+/// # Arguments
+/// * `locale` - Language code (e.g., "he", "en-GB")
+///
+/// # Returns
+/// `Direction::Rtl` for Arabic, Hebrew, Thaana, Syriac and N'Ko script,
+/// `Direction::Ltr` otherwise
+pub fn direction_of(locale: &str) -> Direction {
+    script_of(locale).direction()
+}
+
+/// Check whether a locale is written in Chinese, Japanese or Korean script
+///
+/// These scripts do not separate words with spaces, which matters to
+/// features that work at word boundaries.
+///
+/// # Arguments
+/// * `locale` - Language code (e.g., "ja", "zh-Hans")
+///
+/// # Returns
+/// `true` for Han, Japanese and Hangul script locales
+pub fn is_cjk(locale: &str) -> bool {
+    script_of(locale).is_cjk()
+}
+
+fn lookup(table: &[(&str, Script)], key: &str) -> Option<Script> {
+    table
+        .iter()
+        .find(|(code, _)| *code == key)
+        .map(|(_, script)| *script)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arabic_is_rtl() {
+        assert_eq!(script_of("ar"), Script::Arabic);
+        assert_eq!(direction_of("ar"), Direction::Rtl);
+        assert!(!is_cjk("ar"));
+    }
+
+    #[test]
+    fn test_hebrew_is_rtl() {
+        assert_eq!(script_of("he"), Script::Hebrew);
+        assert_eq!(direction_of("he"), Direction::Rtl);
+        assert_eq!(direction_of("he").as_str(), "rtl");
+    }
+
+    #[test]
+    fn test_japanese_is_cjk_and_ltr() {
+        assert_eq!(script_of("ja"), Script::Japanese);
+        assert_eq!(direction_of("ja"), Direction::Ltr);
+        assert!(is_cjk("ja"));
+        assert!(is_cjk("zh-Hant"));
+        assert!(is_cjk("ko_KR"));
+    }
+
+    #[test]
+    fn test_english_is_latin_and_ltr() {
+        assert_eq!(script_of("en"), Script::Latin);
+        assert_eq!(script_of("en-GB").code(), "Latn");
+        assert_eq!(direction_of("en"), Direction::Ltr);
+        assert!(!is_cjk("en"));
+    }
+
+    #[test]
+    fn test_script_subtag_and_regional_codes() {
+        assert_eq!(script_of("sr"), Script::Cyrillic);
+        assert_eq!(script_of("sr-Latn"), Script::Latin);
+        assert_eq!(script_of("ku-arab"), Script::Arabic);
+        assert_eq!(direction_of("fa-IR"), Direction::Rtl);
+        assert_eq!(script_of("be-tarask"), Script::Cyrillic);
+    }
+}
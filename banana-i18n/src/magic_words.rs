@@ -0,0 +1,184 @@
+use crate::ast::AstNode;
+use crate::parser::Parser;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+/// MediaWiki magic words that are recognized by name but not rendered
+const OTHER_KNOWN_MAGIC_WORDS: &[&str] = &["GRAMMAR"];
+
+/// Canonical magic word types understood by the renderer and MT expansion
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MagicWordType {
+    Plural,
+    /// `{{PLURAL-ORDINAL:$1|st|nd|rd|th}}`: forms follow CLDR ordinal rules
+    PluralOrdinal,
+    Gender,
+    /// The `{{#if:value|set|empty}}` parser function
+    If,
+}
+
+impl MagicWordType {
+    /// The canonical (English) magic word name, e.g. "PLURAL"
+    pub fn canonical_name(&self) -> &'static str {
+        match self {
+            MagicWordType::Plural => "PLURAL",
+            MagicWordType::PluralOrdinal => "PLURAL-ORDINAL",
+            MagicWordType::Gender => "GENDER",
+            MagicWordType::If => "#if",
+        }
+    }
+}
+
+/// Maps magic word names and their aliases to canonical types
+///
+/// Some MediaWiki languages define localized aliases for magic words. The
+/// default registry only knows the English names `PLURAL`, `PLURAL-ORDINAL`
+/// and `GENDER` and the `#if` parser function; aliases can be added with
+/// `with_alias()`. Names are matched case-insensitively.
+///
+/// # Example
+/// ```ignore
+/// let mut registry = MagicWordRegistry::new();
+/// registry.with_alias("MEHRZAHL", MagicWordType::Plural);
+/// assert_eq!(registry.resolve("mehrzahl"), Some(MagicWordType::Plural));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct MagicWordRegistry {
+    // Keyed by uppercase name
+    names: HashMap<String, MagicWordType>,
+}
+
+static DEFAULT_REGISTRY: LazyLock<MagicWordRegistry> = LazyLock::new(MagicWordRegistry::new);
+
+impl MagicWordRegistry {
+    /// Create a registry with the English magic word names
+    pub fn new() -> Self {
+        let mut registry = MagicWordRegistry {
+            names: HashMap::new(),
+        };
+        registry
+            .with_alias("PLURAL", MagicWordType::Plural)
+            .with_alias("PLURAL-ORDINAL", MagicWordType::PluralOrdinal)
+            .with_alias("GENDER", MagicWordType::Gender)
+            .with_alias("#if", MagicWordType::If);
+        registry
+    }
+
+    /// Shared default registry (English names only)
+    pub fn default_registry() -> &'static MagicWordRegistry {
+        &DEFAULT_REGISTRY
+    }
+
+    /// Treat `alias` as the given magic word type
+    pub fn with_alias(&mut self, alias: &str, magic_type: MagicWordType) -> &mut Self {
+        self.names.insert(alias.to_uppercase(), magic_type);
+        self
+    }
+
+    /// Resolve a magic word name (or alias) to its canonical type
+    pub fn resolve(&self, name: &str) -> Option<MagicWordType> {
+        self.names.get(&name.trim().to_uppercase()).copied()
+    }
+}
+
+impl Default for MagicWordRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// List the magic words used in a message, without expanding it
+///
+/// Returns one (type, parameter) pair per transclusion, in message order,
+/// including transclusions nested inside options. PLURAL and GENDER (and
+/// their aliases in the default registry) are reported by canonical name,
+/// `GRAMMAR` as is; any other transclusion is reported as `"other"`. Names
+/// are matched case-insensitively.
+///
+/// # Arguments
+/// * `message` - Message text
+///
+/// # Returns
+/// (type, parameter) pairs, e.g. `("GENDER", "$1")`
+///
+/// # Example
+/// ```ignore
+/// let words = magic_words_in("{{GENDER:$1|He|She}} sent {{plural:$2|a file|$2 files}}");
+/// assert_eq!(words[1], ("PLURAL".to_string(), "$2".to_string()));
+/// ```
+pub fn magic_words_in(message: &str) -> Vec<(String, String)> {
+    let mut words = Vec::new();
+    collect_magic_words(message, &mut words);
+    words
+}
+
+fn collect_magic_words(message: &str, words: &mut Vec<(String, String)>) {
+    let ast = Parser::new(message).parse();
+    for node in &ast {
+        let AstNode::Transclusion(trans) = node else {
+            continue;
+        };
+
+        let name = trans.name.trim().to_uppercase();
+        let magic_type = match MagicWordRegistry::default_registry().resolve(&name) {
+            Some(magic_type) => magic_type.canonical_name().to_string(),
+            None if OTHER_KNOWN_MAGIC_WORDS.contains(&name.as_str()) => name,
+            None => "other".to_string(),
+        };
+        words.push((magic_type, trans.param.clone()));
+
+        for option in &trans.options {
+            collect_magic_words(option, words);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_registry() {
+        let registry = MagicWordRegistry::default();
+        assert_eq!(registry.resolve("PLURAL"), Some(MagicWordType::Plural));
+        assert_eq!(
+            registry.resolve("plural-ordinal"),
+            Some(MagicWordType::PluralOrdinal)
+        );
+        assert_eq!(registry.resolve("gender"), Some(MagicWordType::Gender));
+        assert_eq!(registry.resolve("#if"), Some(MagicWordType::If));
+        assert_eq!(registry.resolve("GRAMMAR"), None);
+    }
+
+    #[test]
+    fn test_alias_resolves_case_insensitively() {
+        let mut registry = MagicWordRegistry::new();
+        registry.with_alias("Множественное", MagicWordType::Plural);
+        assert_eq!(
+            registry.resolve("МНОЖЕСТВЕННОЕ"),
+            Some(MagicWordType::Plural)
+        );
+        assert_eq!(MagicWordType::Plural.canonical_name(), "PLURAL");
+    }
+
+    #[test]
+    fn test_magic_words_in_message() {
+        let words = magic_words_in(
+            "{{gender:$1|He|She}} sent {{PLURAL:$2|a file|$2 files}} to {{GRAMMAR:genitive|$3}} {{FOO:bar}}",
+        );
+        let expected = [
+            ("GENDER", "$1"),
+            ("PLURAL", "$2"),
+            ("GRAMMAR", "genitive"),
+            ("other", "bar"),
+        ];
+        assert_eq!(
+            words,
+            expected
+                .iter()
+                .map(|(t, p)| (t.to_string(), p.to_string()))
+                .collect::<Vec<_>>()
+        );
+        assert!(magic_words_in("Plain text").is_empty());
+    }
+}
@@ -1,21 +1,187 @@
-use tree_sitter::{Node, Parser as TSParser};
+use std::ops::Range;
+use tree_sitter::{InputEdit, Node, Parser as TSParser, Point, Tree};
 
 use crate::ast::{
     AstNode, AstNodeList, Placeholder, Transclusion, WikiExternalLink, WikiInternalLink,
 };
 
+/// Default maximum nesting depth accepted by the parser
+pub const DEFAULT_MAX_DEPTH: usize = 32;
+
+/// Errors reported by `Parser::try_parse()`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// The syntax tree nests deeper than the configured limit
+    MaxDepthExceeded { limit: usize },
+    /// An edit passed to `Parser::reparse()` does not fit the source
+    InvalidEdit { offset: usize, old_len: usize },
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::MaxDepthExceeded { limit } => {
+                write!(f, "Maximum nesting depth of {} exceeded", limit)
+            }
+            ParseError::InvalidEdit { offset, old_len } => write!(
+                f,
+                "Edit of {} bytes at offset {} is outside the source or splits a character",
+                old_len, offset
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// An edit to a message: `old_len` bytes at byte `offset` replaced by `new_text`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextEdit {
+    pub offset: usize,
+    pub old_len: usize,
+    pub new_text: String,
+}
+
+impl TextEdit {
+    pub fn new(offset: usize, old_len: usize, new_text: &str) -> Self {
+        TextEdit {
+            offset,
+            old_len,
+            new_text: new_text.to_string(),
+        }
+    }
+
+    /// Apply the edit to `source`
+    ///
+    /// # Returns
+    /// The edited source, or `None` if the replaced range is outside `source`
+    /// or does not start and end on character boundaries
+    pub fn apply(&self, source: &str) -> Option<String> {
+        let end = self.offset.checked_add(self.old_len)?;
+        if end > source.len()
+            || !source.is_char_boundary(self.offset)
+            || !source.is_char_boundary(end)
+        {
+            return None;
+        }
+        Some(format!(
+            "{}{}{}",
+            &source[..self.offset],
+            self.new_text,
+            &source[end..]
+        ))
+    }
+}
+
+/// Source range of a syntax node and the AST nodes it produced
+#[derive(Debug, Clone)]
+struct Unit {
+    kind: &'static str,
+    bytes: Range<usize>,
+    ast: Range<usize>,
+}
+
+/// What the previous parse left behind, for reusing unchanged AST nodes
+struct Reuse<'a> {
+    previous: &'a AstNodeList,
+    units: &'a [Unit],
+    edit: &'a InputEdit,
+    /// Ranges of the new source whose syntax changed
+    changed: Vec<Range<usize>>,
+}
+
+/// State threaded through one walk of the syntax tree
+#[derive(Default)]
+struct WalkContext<'a> {
+    units: Vec<Unit>,
+    ast_len: usize,
+    reuse: Option<Reuse<'a>>,
+}
+
+impl WalkContext<'_> {
+    /// Previous AST nodes for a syntax node whose text and structure are unchanged
+    fn reusable(&self, kind: &str, bytes: &Range<usize>) -> Option<AstNodeList> {
+        let reuse = self.reuse.as_ref()?;
+        let edit = reuse.edit;
+        // Nodes touching the edit may have absorbed or lost text
+        let old_bytes = if bytes.end < edit.start_byte {
+            bytes.clone()
+        } else if bytes.start > edit.new_end_byte {
+            bytes.start - edit.new_end_byte + edit.old_end_byte
+                ..bytes.end - edit.new_end_byte + edit.old_end_byte
+        } else {
+            return None;
+        };
+        if reuse
+            .changed
+            .iter()
+            .any(|changed| changed.start < bytes.end && bytes.start < changed.end)
+        {
+            return None;
+        }
+        let unit = reuse
+            .units
+            .iter()
+            .find(|unit| unit.bytes == old_bytes && unit.kind == kind)?;
+        Some(reuse.previous[unit.ast.clone()].to_vec())
+    }
+
+    fn record(&mut self, kind: &'static str, bytes: Range<usize>, count: usize) {
+        self.units.push(Unit {
+            kind,
+            bytes,
+            ast: self.ast_len..self.ast_len + count,
+        });
+        self.ast_len += count;
+    }
+}
+
 pub struct Parser {
     source: String,
+    max_depth: usize,
+    /// Syntax tree of the last parse, reused by `reparse()`
+    tree: Option<Tree>,
+    /// Source ranges behind the AST of the last parse
+    units: Vec<Unit>,
 }
 
 impl Parser {
     pub fn new(source: &str) -> Self {
         Parser {
             source: source.to_string(),
+            max_depth: DEFAULT_MAX_DEPTH,
+            tree: None,
+            units: Vec::new(),
         }
     }
 
+    /// Set the maximum nesting depth (default: `DEFAULT_MAX_DEPTH`)
+    ///
+    /// Pathological input such as thousands of stacked `{{` produces a very
+    /// deep syntax tree; walking it recursively could exhaust the stack.
+    pub fn with_max_depth(&mut self, max_depth: usize) -> &mut Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Parse the source, falling back to plain text if it nests too deeply
+    ///
+    /// Use `try_parse()` to detect the depth limit being hit.
     pub fn parse(&mut self) -> AstNodeList {
+        match self.try_parse() {
+            Ok(ast) => ast,
+            Err(e) => {
+                eprintln!("Warning: {}, returning as plain text", e);
+                vec![AstNode::Text(self.source.clone())]
+            }
+        }
+    }
+
+    /// Parse the source, returning an error if it nests deeper than the limit
+    pub fn try_parse(&mut self) -> Result<AstNodeList, ParseError> {
+        self.tree = None;
+        self.units.clear();
+
         // Initialize tree-sitter parser
         let mut ts_parser = TSParser::new();
         match ts_parser.set_language(&tree_sitter_wikitext::LANGUAGE.into()) {
@@ -23,7 +189,7 @@ impl Parser {
             Err(e) => {
                 eprintln!("Error loading wikitext grammar: {}", e);
                 // Fallback: return source as plain text
-                return vec![AstNode::Text(self.source.clone())];
+                return Ok(vec![AstNode::Text(self.source.clone())]);
             }
         }
 
@@ -32,7 +198,7 @@ impl Parser {
             Some(t) => t,
             None => {
                 eprintln!("Warning: Failed to parse wikitext, returning as plain text");
-                return vec![AstNode::Text(self.source.clone())];
+                return Ok(vec![AstNode::Text(self.source.clone())]);
             }
         };
 
@@ -43,49 +209,184 @@ impl Parser {
         eprintln!("Parse tree s-expression: {}", root.to_sexp());
 
         // Walk the tree and build AST
-        self.walk_node(root)
+        let mut ctx = WalkContext::default();
+        let ast = self.walk_node(root, 0, &mut ctx)?;
+        self.units = ctx.units;
+        self.tree = Some(tree);
+        Ok(ast)
     }
 
-    fn walk_node(&self, node: Node) -> AstNodeList {
+    /// Re-parse the source after an edit, reusing the previous parse
+    ///
+    /// `previous` must be the AST returned by the last `parse()`, `try_parse()`
+    /// or `reparse()` of this parser. The syntax tree is re-parsed
+    /// incrementally, so only the region around the edit is re-tokenized, and
+    /// AST nodes outside the changed region are copied from `previous`. The
+    /// result is always the same as a full parse of the edited source; when
+    /// the previous parse cannot be reused, a full parse is done.
+    ///
+    /// # Arguments
+    /// * `previous` - AST of the source before the edit
+    /// * `edit` - The edit, in bytes of the source before the edit
+    ///
+    /// # Returns
+    /// * `Ok(AstNodeList)` - AST of the edited source
+    /// * `Err(ParseError::InvalidEdit)` - If the edit does not fit the source;
+    ///   the parser is left unchanged
+    ///
+    /// # Example
+    /// ```ignore
+    /// let mut parser = Parser::new("Hello $1");
+    /// let ast = parser.parse();
+    /// let ast = parser.reparse(&ast, &TextEdit::new(0, 5, "Goodbye"))?;
+    /// ```
+    pub fn reparse(
+        &mut self,
+        previous: &AstNodeList,
+        edit: &TextEdit,
+    ) -> Result<AstNodeList, ParseError> {
+        let source = edit.apply(&self.source).ok_or(ParseError::InvalidEdit {
+            offset: edit.offset,
+            old_len: edit.old_len,
+        })?;
+
+        let reusable = self.tree.is_some()
+            && self.units.last().map_or(0, |unit| unit.ast.end) == previous.len();
+        let old_tree = self.tree.take().filter(|_| reusable);
+        let Some(mut old_tree) = old_tree else {
+            self.source = source;
+            return Ok(self.parse());
+        };
+
+        let input_edit = InputEdit {
+            start_byte: edit.offset,
+            old_end_byte: edit.offset + edit.old_len,
+            new_end_byte: edit.offset + edit.new_text.len(),
+            start_position: point_at(&self.source, edit.offset),
+            old_end_position: point_at(&self.source, edit.offset + edit.old_len),
+            new_end_position: point_at(&source, edit.offset + edit.new_text.len()),
+        };
+        old_tree.edit(&input_edit);
+        self.source = source;
+
+        let mut ts_parser = TSParser::new();
+        let new_tree = ts_parser
+            .set_language(&tree_sitter_wikitext::LANGUAGE.into())
+            .ok()
+            .and_then(|_| ts_parser.parse(&self.source, Some(&old_tree)));
+        let Some(new_tree) = new_tree else {
+            return Ok(self.parse());
+        };
+
+        let units = std::mem::take(&mut self.units);
+        let mut ctx = WalkContext {
+            reuse: Some(Reuse {
+                previous,
+                units: &units,
+                edit: &input_edit,
+                changed: old_tree
+                    .changed_ranges(&new_tree)
+                    .map(|range| range.start_byte..range.end_byte)
+                    .collect(),
+            }),
+            ..Default::default()
+        };
+        match self.walk_node(new_tree.root_node(), 0, &mut ctx) {
+            Ok(ast) => {
+                self.units = ctx.units;
+                self.tree = Some(new_tree);
+                Ok(ast)
+            }
+            // Same plain text fallback as parse()
+            Err(_) => Ok(self.parse()),
+        }
+    }
+
+    fn walk_node(
+        &self,
+        node: Node,
+        depth: usize,
+        ctx: &mut WalkContext,
+    ) -> Result<AstNodeList, ParseError> {
+        // Guard against pathologically deep trees before recursing further
+        if depth >= self.max_depth {
+            return Err(ParseError::MaxDepthExceeded {
+                limit: self.max_depth,
+            });
+        }
+
         let mut ast_nodes = Vec::new();
 
         // Process children
         let mut cursor = node.walk();
         for child in node.children(&mut cursor) {
-            ast_nodes.extend(self.process_node(child));
+            ast_nodes.extend(self.process_node(child, depth + 1, ctx)?);
         }
 
         // If no children, process as leaf node
         if ast_nodes.is_empty() && node.child_count() == 0 {
-            ast_nodes.extend(self.process_node(node));
+            ast_nodes.extend(self.process_node(node, depth + 1, ctx)?);
         }
 
-        ast_nodes
+        Ok(ast_nodes)
     }
 
-    fn process_node(&self, node: Node) -> AstNodeList {
+    fn process_node(
+        &self,
+        node: Node,
+        depth: usize,
+        ctx: &mut WalkContext,
+    ) -> Result<AstNodeList, ParseError> {
         let node_type = node.kind();
 
-        match node_type {
+        if node.is_error() {
+            let text = self.node_text(node);
+            if let Some(start) = unterminated_comment_start(&text) {
+                return self.parse_unterminated_comment(
+                    node,
+                    node.start_byte() + start,
+                    depth,
+                    ctx,
+                );
+            }
+        }
+
+        // Containers are walked; everything else becomes AST nodes on its own
+        let is_container = matches!(node_type, "document" | "paragraph")
+            || !matches!(
+                node_type,
+                "parser_function" | "wikilink" | "medialink" | "external_link" | "text" | "comment"
+            ) && node.child_count() > 0;
+        if is_container {
+            return self.walk_node(node, depth, ctx);
+        }
+
+        let bytes = node.start_byte()..node.end_byte();
+        if let Some(nodes) = ctx.reusable(node_type, &bytes) {
+            ctx.record(node_type, bytes, nodes.len());
+            return Ok(nodes);
+        }
+
+        let nodes = match node_type {
             "parser_function" => self.parse_parser_function(node),
-            "wikilink" => self.parse_wikilink(node),
+            // [[File:...|thumb|caption]] has its own node type but the same syntax
+            "wikilink" | "medialink" => self.parse_wikilink(node),
             "external_link" => self.parse_external_link(node),
             "text" => self.parse_text(node),
-            "document" | "paragraph" => self.walk_node(node),
+            "comment" => self.parse_comment(node),
             _ => {
-                // Unknown node type - walk children or return text
-                if node.child_count() > 0 {
-                    self.walk_node(node)
+                // Unknown leaf node - return its text
+                let text = self.node_text(node);
+                if !text.is_empty() {
+                    vec![AstNode::Text(text)]
                 } else {
-                    let text = self.node_text(node);
-                    if !text.is_empty() {
-                        vec![AstNode::Text(text)]
-                    } else {
-                        vec![]
-                    }
+                    vec![]
                 }
             }
-        }
+        };
+
+        ctx.record(node_type, bytes, nodes.len());
+        Ok(nodes)
     }
 
     fn parse_parser_function(&self, node: Node) -> AstNodeList {
@@ -99,11 +400,18 @@ impl Parser {
         //      -> template_argument (contains "is")
         //      -> template_argument (contains "are")
 
-        // Try to find the parser_function_colon child node
+        //
+        // {{#if:$1|yes|no}} has a parser_function_hash child instead, with a
+        // function_prefix ("#") before the name.
+
+        // Try to find the parser_function_colon or parser_function_hash child node
         let mut cursor = node.walk();
-        let pf_colon = node
-            .children(&mut cursor)
-            .find(|child| child.kind() == "parser_function_colon");
+        let pf_colon = node.children(&mut cursor).find(|child| {
+            matches!(
+                child.kind(),
+                "parser_function_colon" | "parser_function_hash"
+            )
+        });
 
         if let Some(pf_colon_node) = pf_colon {
             if let (Some(name), Some(param)) = (
@@ -131,18 +439,34 @@ impl Parser {
 
     fn extract_parser_function_name(&self, pf_colon_node: Node) -> Option<String> {
         let mut cursor = pf_colon_node.walk();
-        pf_colon_node
+        let name = pf_colon_node
             .children(&mut cursor)
             .find(|child| child.kind() == "parser_function_name")
-            .map(|name_node| self.node_text(name_node).trim().to_string())
+            .map(|name_node| self.node_text(name_node).trim().to_string())?;
+
+        if pf_colon_node.kind() != "parser_function_hash" {
+            return Some(name);
+        }
+        // Only the conditionals are supported among the #-prefixed functions
+        let name = format!("#{}", name);
+        ["#if", "#ifeq"]
+            .iter()
+            .any(|supported| name.eq_ignore_ascii_case(supported))
+            .then_some(name)
     }
 
     fn extract_parser_function_param(&self, pf_colon_node: Node) -> Option<String> {
         let mut cursor = pf_colon_node.walk();
-        pf_colon_node
-            .children(&mut cursor)
-            .find(|child| child.kind() == "param_text")
-            .map(|param_node| self.node_text(param_node).trim().to_string())
+        let mut has_delimiter = false;
+        for child in pf_colon_node.children(&mut cursor) {
+            match child.kind() {
+                "param_text" => return Some(self.node_text(child).trim().to_string()),
+                "function_delimiter" => has_delimiter = true,
+                _ => {}
+            }
+        }
+        // {{GENDER:|he|she}} has no param_text; the param is empty
+        has_delimiter.then(String::new)
     }
 
     fn extract_parser_function_arguments(&self, pf_colon_node: Node) -> Vec<String> {
@@ -156,7 +480,7 @@ impl Parser {
                 let arg_text = arg_node
                     .children(&mut arg_cursor)
                     .find(|child| child.kind() == "template_param_value")
-                    .map(|value_node| self.node_text(value_node).trim().to_string())
+                    .map(|value_node| self.text_without_comments(value_node).trim().to_string())
                     .unwrap_or_else(|| self.node_text(arg_node).trim().to_string());
 
                 if !arg_text.is_empty() {
@@ -171,20 +495,11 @@ impl Parser {
     fn parse_wikilink(&self, node: Node) -> AstNodeList {
         let text = self.node_text(node);
 
-        // Parse [[target]] or [[target|display]]
+        // Parse [[target]], [[target|display]] or [[target|param|...|display]]
         if let Some(inner) = text.strip_prefix("[[").and_then(|s| s.strip_suffix("]]")) {
-            let parts: Vec<&str> = inner.splitn(2, '|').collect();
-            let target = parts[0].trim().to_string();
-            let display_text = if parts.len() > 1 {
-                Some(parts[1].trim().to_string())
-            } else {
-                None
-            };
-
-            return vec![AstNode::InternalLink(WikiInternalLink {
-                target,
-                display_text,
-            })];
+            return vec![AstNode::InternalLink(WikiInternalLink::from_link_text(
+                inner,
+            ))];
         }
 
         eprintln!("Warning: Failed to parse wikilink: {}", text);
@@ -210,11 +525,72 @@ impl Parser {
         vec![AstNode::Text(text)]
     }
 
+    fn parse_comment(&self, node: Node) -> AstNodeList {
+        let text = self.node_text(node);
+        self.extract_comments(&text)
+    }
+
+    /// Parse an error node holding an unterminated `<!--` starting at byte `comment_start`
+    ///
+    /// tree-sitter drops some of the tokens after the `<!--`, so from there on
+    /// the source is kept unchanged as text.
+    fn parse_unterminated_comment(
+        &self,
+        node: Node,
+        comment_start: usize,
+        depth: usize,
+        ctx: &mut WalkContext,
+    ) -> Result<AstNodeList, ParseError> {
+        let mut nodes = Vec::new();
+        let mut text_start = comment_start;
+
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            if child.end_byte() > comment_start {
+                text_start = text_start.min(child.start_byte());
+                break;
+            }
+            nodes.extend(self.process_node(child, depth + 1, ctx)?);
+        }
+
+        let bytes = text_start..node.end_byte();
+        let text = self.extract_comments(&self.source[bytes.clone()]);
+        ctx.record("unterminated_comment", bytes, text.len());
+        nodes.extend(text);
+        Ok(nodes)
+    }
+
     fn parse_text(&self, node: Node) -> AstNodeList {
         let text = self.node_text(node);
 
-        // Check for placeholders like $1, $2, etc.
-        self.extract_placeholders(&text)
+        // Comments may reach us as plain text; split them out, then check the
+        // remaining text for placeholders like $1, $2, etc.
+        self.extract_comments(&text)
+    }
+
+    /// Split `<!-- ... -->` comments out of text
+    ///
+    /// An unterminated `<!--` is kept as literal text.
+    fn extract_comments(&self, text: &str) -> AstNodeList {
+        let mut nodes = Vec::new();
+        let mut rest = text;
+
+        while let Some(start) = rest.find("<!--") {
+            let Some(len) = rest[start + 4..].find("-->") else {
+                break;
+            };
+            if start > 0 {
+                nodes.extend(self.extract_placeholders(&rest[..start]));
+            }
+            let comment = &rest[start + 4..start + 4 + len];
+            nodes.push(AstNode::Comment(comment.to_string()));
+            rest = &rest[start + 4 + len + 3..];
+        }
+
+        if !rest.is_empty() {
+            nodes.extend(self.extract_placeholders(rest));
+        }
+        nodes
     }
 
     fn extract_placeholders(&self, text: &str) -> AstNodeList {
@@ -264,6 +640,25 @@ impl Parser {
         nodes
     }
 
+    /// Text of a node with its `<!-- ... -->` comment children left out
+    ///
+    /// Options of a magic word are plain strings, so a comment inside one
+    /// would otherwise be rendered and sent to MT as part of the option.
+    fn text_without_comments(&self, node: Node) -> String {
+        let mut text = String::new();
+        let mut pos = node.start_byte();
+        let mut cursor = node.walk();
+        for comment in node
+            .children(&mut cursor)
+            .filter(|child| child.kind() == "comment")
+        {
+            text.push_str(&self.source[pos..comment.start_byte()]);
+            pos = comment.end_byte();
+        }
+        text.push_str(&self.source[pos..node.end_byte()]);
+        text
+    }
+
     fn node_text(&self, node: Node) -> String {
         node.utf8_text(self.source.as_bytes())
             .unwrap_or("")
@@ -271,6 +666,30 @@ impl Parser {
     }
 }
 
+/// Byte offset of a `<!--` in `text` that has no closing `-->`
+fn unterminated_comment_start(text: &str) -> Option<usize> {
+    let mut offset = 0;
+    while let Some(start) = text[offset..].find("<!--") {
+        let start = offset + start;
+        match text[start + 4..].find("-->") {
+            Some(len) => offset = start + 4 + len + 3,
+            None => return Some(start),
+        }
+    }
+    None
+}
+
+/// Row and column (in bytes) of a byte offset, as tree-sitter counts them
+fn point_at(source: &str, byte: usize) -> Point {
+    let before = &source.as_bytes()[..byte];
+    let row = before.iter().filter(|&&b| b == b'\n').count();
+    let column = before
+        .iter()
+        .rposition(|&b| b == b'\n')
+        .map_or(byte, |newline| byte - newline - 1);
+    Point { row, column }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -308,6 +727,30 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_if_parser_functions() {
+        let ast = Parser::new("{{#if:$1|yes|no}}{{#ifeq:$1|a|b|c}}").parse();
+        assert_eq!(
+            ast,
+            vec![
+                AstNode::Transclusion(Transclusion {
+                    name: "#if".to_string(),
+                    param: "$1".to_string(),
+                    options: vec!["yes".to_string(), "no".to_string()],
+                }),
+                AstNode::Transclusion(Transclusion {
+                    name: "#ifeq".to_string(),
+                    param: "$1".to_string(),
+                    options: vec!["a".to_string(), "b".to_string(), "c".to_string()],
+                }),
+            ]
+        );
+
+        // Other # functions are not supported and stay text
+        let ast = Parser::new("{{#time:Y}}").parse();
+        assert_eq!(ast, vec![AstNode::Text("{{#time:Y}}".to_string())]);
+    }
+
     #[test]
     fn test_internal_link() {
         let mut parser = Parser::new("[[box]]");
@@ -325,6 +768,35 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_file_link_with_parameters_round_trips() {
+        let source = "See [[File:Bar.png|thumb|The $1 caption]] in [[Category:Foo]]";
+        let ast = Parser::new(source).parse();
+        let links: Vec<&WikiInternalLink> = ast
+            .iter()
+            .filter_map(|node| match node {
+                AstNode::InternalLink(l) => Some(l),
+                _ => None,
+            })
+            .collect();
+
+        assert_eq!(links.len(), 2);
+        assert_eq!(links[0].target, "File:Bar.png");
+        assert_eq!(links[0].params, vec!["thumb".to_string()]);
+        assert_eq!(links[0].display_text.as_deref(), Some("The $1 caption"));
+        assert_eq!(links[1].target, "Category:Foo");
+        assert_eq!(links[1].display_text, None);
+        assert_eq!(crate::ast::ast_to_wikitext(&ast), source);
+    }
+
+    #[test]
+    fn test_blank_link_display_text_reparses() {
+        let ast = Parser::new("[[a| ]]").parse();
+        let serialized = crate::ast::ast_to_wikitext(&ast);
+        assert_eq!(serialized, "[[a]]");
+        assert_eq!(Parser::new(&serialized).parse(), ast);
+    }
+
     #[test]
     fn test_internal_link_with_display() {
         let mut parser = Parser::new("[[Main Page|home]]");
@@ -368,4 +840,282 @@ mod tests {
             _ => panic!("Expected text node, got {:?}", ast[0]),
         }
     }
+
+    #[test]
+    fn test_comment() {
+        let mut parser = Parser::new("Hello <!-- greeting for $1 -->$1!");
+        let ast = parser.parse();
+        assert_eq!(
+            ast,
+            vec![
+                AstNode::Text("Hello ".to_string()),
+                AstNode::Comment(" greeting for $1 ".to_string()),
+                AstNode::Placeholder(Placeholder { index: 1 }),
+                AstNode::Text("!".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_comments_in_options_are_dropped() {
+        let ast =
+            Parser::new("Hi <!-- note -->{{PLURAL:$1|one<!-- x -->|many <!-- y -->}}").parse();
+        assert_eq!(
+            ast,
+            vec![
+                AstNode::Text("Hi ".to_string()),
+                AstNode::Comment(" note ".to_string()),
+                AstNode::Transclusion(Transclusion {
+                    name: "PLURAL".to_string(),
+                    param: "$1".to_string(),
+                    options: vec!["one".to_string(), "many".to_string()],
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unterminated_comment_is_text() {
+        let mut parser = Parser::new("Hello <!-- no end");
+        let ast = parser.parse();
+        assert_eq!(crate::ast::ast_to_wikitext(&ast), "Hello <!-- no end");
+        assert!(!ast.iter().any(|node| matches!(node, AstNode::Comment(_))));
+
+        // Nodes before the unterminated comment are still parsed
+        let source = "{{PLURAL:$1|a|b}} <!-- c --> $2 <!-- no end $3";
+        let ast = Parser::new(source).parse();
+        assert_eq!(crate::ast::ast_to_wikitext(&ast), source);
+        assert!(matches!(ast[0], AstNode::Transclusion(_)));
+        assert_eq!(
+            ast.iter()
+                .filter(|node| matches!(node, AstNode::Comment(_)))
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_max_depth_exceeded() {
+        let mut parser = Parser::new("Hello, World!");
+        let result = parser.with_max_depth(0).try_parse();
+        assert_eq!(result, Err(ParseError::MaxDepthExceeded { limit: 0 }));
+
+        // parse() bails out to plain text instead of failing
+        let ast = parser.parse();
+        assert_eq!(ast, vec![AstNode::Text("Hello, World!".to_string())]);
+    }
+
+    #[test]
+    fn test_nesting_beyond_limit_is_rejected() {
+        let nested = |levels: usize| format!("{}x{}", "{{a|".repeat(levels), "}}".repeat(levels));
+
+        // Within the limit the nested templates parse normally
+        let mut parser = Parser::new(&nested(1));
+        assert!(parser.with_max_depth(8).try_parse().is_ok());
+
+        // Past it the walk stops with an error instead of recursing further
+        let mut parser = Parser::new(&nested(10));
+        let result = parser.with_max_depth(8).try_parse();
+        assert_eq!(result, Err(ParseError::MaxDepthExceeded { limit: 8 }));
+
+        // parse() falls back to the source as plain text
+        assert_eq!(parser.parse(), vec![AstNode::Text(nested(10))]);
+    }
+
+    #[test]
+    fn test_deeply_nested_input_bails_cleanly() {
+        let depth = 5000;
+        let source = format!("{}x{}", "{{".repeat(depth), "}}".repeat(depth));
+
+        let mut parser = Parser::new(&source);
+        // The grammar recovers from the unbalanced braces by falling back to
+        // plain text, so the depth limit is never reached and nothing is lost
+        let ast = parser.try_parse().unwrap();
+        assert!(ast.iter().all(|node| matches!(node, AstNode::Text(_))));
+        let text: String = ast
+            .iter()
+            .map(|node| match node {
+                AstNode::Text(text) => text.as_str(),
+                _ => unreachable!(),
+            })
+            .collect();
+        assert_eq!(text, source);
+
+        // The infallible entry point always produces something
+        assert!(!parser.parse().is_empty());
+    }
+
+    // ========== Incremental Parsing Tests ==========
+
+    #[test]
+    fn test_text_edit_apply() {
+        let edit = TextEdit::new(6, 2, "$2");
+        assert_eq!(edit.apply("Hello $1!"), Some("Hello $2!".to_string()));
+        assert_eq!(
+            TextEdit::new(9, 0, "?").apply("Hello $1!"),
+            Some("Hello $1!?".to_string())
+        );
+        assert_eq!(TextEdit::new(8, 2, "").apply("Hello $1!"), None);
+        // "é" is two bytes; an edit may not split it
+        assert_eq!(TextEdit::new(1, 1, "e").apply("é"), None);
+    }
+
+    #[test]
+    fn test_reparse_matches_full_parse() {
+        let edits = [
+            // Plain text
+            TextEdit::new(0, 5, "Hi"),
+            // Change the count placeholder
+            TextEdit::new(3, 2, "$2"),
+            // Open a magic word, then complete it
+            TextEdit::new(5, 0, " {{PLURAL:$2|file"),
+            TextEdit::new(22, 0, "|files}}"),
+            // Edit inside an option
+            TextEdit::new(18, 4, "item"),
+            // Add a link and a comment after the magic word
+            TextEdit::new(30, 0, " in [[Special:Files|the list]] <!-- list page -->"),
+            // Break the link, then delete everything
+            TextEdit::new(35, 1, ""),
+        ];
+
+        let mut source = "Hello $1".to_string();
+        let mut parser = Parser::new(&source);
+        let mut ast = parser.parse();
+        for edit in &edits {
+            source = edit.apply(&source).unwrap();
+            ast = parser.reparse(&ast, edit).unwrap();
+            assert_eq!(
+                ast,
+                Parser::new(&source).parse(),
+                "after editing to {:?}",
+                source
+            );
+        }
+
+        let edit = TextEdit::new(0, source.len(), "Bye");
+        assert_eq!(
+            parser.reparse(&ast, &edit).unwrap(),
+            vec![AstNode::Text("Bye".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_reparse_rejects_invalid_edit() {
+        let mut parser = Parser::new("Hello $1");
+        let ast = parser.parse();
+        let edit = TextEdit::new(4, 10, "");
+        assert_eq!(
+            parser.reparse(&ast, &edit),
+            Err(ParseError::InvalidEdit {
+                offset: 4,
+                old_len: 10
+            })
+        );
+
+        // The parser still holds the unedited source
+        let edit = TextEdit::new(0, 5, "Bye");
+        assert_eq!(
+            parser.reparse(&ast, &edit).unwrap(),
+            Parser::new("Bye $1").parse()
+        );
+    }
+
+    // ========== Property Tests ==========
+
+    mod properties {
+        use super::*;
+        use crate::ast::ast_to_wikitext;
+        use proptest::prelude::*;
+
+        /// Plain text, including multibyte characters, without wikitext syntax
+        fn plain_text() -> impl Strategy<Value = String> {
+            "[a-zA-Z0-9 ,.!?éñüß日本語മലയാളം😀-]{1,12}"
+        }
+
+        fn placeholder() -> impl Strategy<Value = String> {
+            (1usize..=12).prop_map(|index| format!("${}", index))
+        }
+
+        fn transclusion() -> impl Strategy<Value = String> {
+            (
+                prop::sample::select(vec!["PLURAL", "GENDER", "GRAMMAR"]),
+                1usize..=9,
+                prop::collection::vec(prop_oneof![plain_text(), placeholder()], 1..4),
+            )
+                .prop_map(|(name, index, options)| {
+                    format!("{{{{{}:${}|{}}}}}", name, index, options.join("|"))
+                })
+        }
+
+        fn internal_link() -> impl Strategy<Value = String> {
+            (
+                "[A-Za-z][A-Za-z0-9 :]{0,10}",
+                prop::option::of(plain_text()),
+            )
+                .prop_map(|(target, display)| match display {
+                    Some(display) => format!("[[{}|{}]]", target, display),
+                    None => format!("[[{}]]", target),
+                })
+        }
+
+        fn external_link() -> impl Strategy<Value = String> {
+            ("[a-z]{1,8}", prop::option::of(plain_text())).prop_map(|(host, text)| match text {
+                Some(text) => format!("[https://{}.org {}]", host, text),
+                None => format!("[https://{}.org]", host),
+            })
+        }
+
+        fn wikitext() -> impl Strategy<Value = String> {
+            prop::collection::vec(
+                prop_oneof![
+                    3 => plain_text(),
+                    2 => placeholder(),
+                    2 => transclusion(),
+                    1 => internal_link(),
+                    1 => external_link(),
+                ],
+                0..8,
+            )
+            .prop_map(|parts| parts.concat())
+        }
+
+        proptest! {
+            #[test]
+            fn parse_never_panics(source in "\\PC{0,40}") {
+                let _ = Parser::new(&source).parse();
+            }
+
+            #[test]
+            fn incremental_reparse_matches_full_parse(
+                source in wikitext(),
+                insert in wikitext(),
+                start in 0usize..64,
+                len in 0usize..16,
+            ) {
+                // Snap the edited range to character boundaries
+                let snap = |mut i: usize| {
+                    i = i.min(source.len());
+                    while !source.is_char_boundary(i) {
+                        i -= 1;
+                    }
+                    i
+                };
+                let offset = snap(start);
+                let end = snap(offset + len);
+                let edit = TextEdit::new(offset, end - offset, &insert);
+
+                let mut parser = Parser::new(&source);
+                let ast = parser.parse();
+                let edited = edit.apply(&source).unwrap();
+                prop_assert_eq!(parser.reparse(&ast, &edit).unwrap(), Parser::new(&edited).parse());
+            }
+
+            #[test]
+            fn serialize_reparses_to_same_ast(source in wikitext()) {
+                let ast = Parser::new(&source).parse();
+                let serialized = ast_to_wikitext(&ast);
+                prop_assert_eq!(Parser::new(&serialized).parse(), ast);
+            }
+        }
+    }
 }
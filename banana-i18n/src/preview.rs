@@ -0,0 +1,114 @@
+use crate::ast::{AstNode, general_plural_index};
+use crate::magic_words::{MagicWordRegistry, MagicWordType};
+use crate::parser::Parser;
+
+/// Render a message as plain text without argument values, e.g. for search indexing
+///
+/// Unlike `I18n::localize()`, no values are needed: magic words take their
+/// most general form and placeholders become labels.
+/// - PLURAL uses the "other" form of the locale ("many" where whole numbers
+///   are never "other", as in Russian)
+/// - GENDER uses the neutral form (the third option, else the first)
+/// - `#if` uses the branch for a non-empty value, `#ifeq` the one for
+///   different values
+/// - Placeholders become `⟦$1⟧`, other transclusions `⟦NAME⟧`
+/// - Links become their display text (or target), comments are dropped
+///
+/// Runs of whitespace, including newlines, are collapsed to a single space.
+///
+/// # Arguments
+/// * `message` - The wikitext message
+/// * `locale` - Locale whose plural rules select the PLURAL form
+///
+/// # Returns
+/// A single line of plain text
+///
+/// # Example
+/// ```ignore
+/// let preview = preview_plain("{{GENDER:$1|He|She|They}} sent {{PLURAL:$2|a message|$2 messages}}", "en");
+/// assert_eq!(preview, "They sent ⟦$2⟧ messages");
+/// ```
+pub fn preview_plain(message: &str, locale: &str) -> String {
+    render_preview(message, locale)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn render_preview(message: &str, locale: &str) -> String {
+    let registry = MagicWordRegistry::default_registry();
+    let mut result = String::new();
+
+    for node in Parser::new(message).parse() {
+        match node {
+            AstNode::Text(text) => result.push_str(&text),
+            AstNode::Placeholder(placeholder) => {
+                result.push_str(&format!("⟦${}⟧", placeholder.index));
+            }
+            AstNode::Transclusion(trans) => {
+                let index = match registry.resolve(&trans.name) {
+                    Some(MagicWordType::Plural) => {
+                        Some(general_plural_index(locale, trans.options.len()))
+                    }
+                    // "other" is always the last ordinal category
+                    Some(MagicWordType::PluralOrdinal) => {
+                        Some(trans.options.len().saturating_sub(1))
+                    }
+                    Some(MagicWordType::Gender) => {
+                        Some(if trans.options.len() >= 3 { 2 } else { 0 })
+                    }
+                    Some(MagicWordType::If) => Some(0),
+                    None if trans.name.trim().eq_ignore_ascii_case("#ifeq") => Some(2),
+                    None => None,
+                };
+                match index {
+                    Some(index) => {
+                        if let Some(option) = trans.options.get(index) {
+                            result.push_str(&render_preview(option, locale));
+                        }
+                    }
+                    None => result.push_str(&format!("⟦{}⟧", trans.name.trim())),
+                }
+            }
+            AstNode::InternalLink(link) => {
+                let text = link.display_text.as_deref().unwrap_or(&link.target);
+                result.push_str(&render_preview(text, locale));
+            }
+            AstNode::ExternalLink(link) => match link.text {
+                Some(text) => result.push_str(&render_preview(&text, locale)),
+                None => result.push_str(&link.url),
+            },
+            AstNode::Comment(_) => {}
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plural_gender_preview() {
+        let message = "{{GENDER:$1|He|She|They}} sent {{PLURAL:$2|a message|$2 messages}}";
+        assert_eq!(preview_plain(message, "en"), "They sent ⟦$2⟧ messages");
+    }
+
+    #[test]
+    fn test_preview_uses_locale_plural_rules() {
+        // Russian whole numbers are never "other"; "many" is the general form
+        let message = "{{PLURAL:$1|$1 файл|$1 файла|$1 файлов}}";
+        assert_eq!(preview_plain(message, "ru"), "⟦$1⟧ файлов");
+
+        // French 0 and 1 are both "one", so the preview must not pick by a zero count
+        let message = "{{PLURAL:$1|$1 fichier|$1 fichiers}}";
+        assert_eq!(preview_plain(message, "fr"), "⟦$1⟧ fichiers");
+    }
+
+    #[test]
+    fn test_two_option_gender_and_links() {
+        let message = "{{GENDER:$1|His|Her}} [[Special:Preferences|preferences]]\n<!-- shown in the sidebar -->";
+        assert_eq!(preview_plain(message, "en"), "His preferences");
+    }
+}
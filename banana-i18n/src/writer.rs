@@ -0,0 +1,159 @@
+use crate::LocalizedMessages;
+use crate::error::{I18nError, I18nResult};
+use crate::loader::OrderedCatalog;
+use std::fs;
+use std::path::Path;
+
+/// Serialize messages as a JSON catalog with keys sorted
+///
+/// `LocalizedMessages` does not remember insertion order, so keys are sorted
+/// to make the output deterministic: writing the same catalog twice produces
+/// byte-identical files, and machine-translated catalogs diff cleanly in
+/// version control. Use `ordered_messages_to_json()` to keep a given order.
+///
+/// # Arguments
+/// * `messages` - Messages to serialize
+///
+/// # Returns
+/// The JSON document, indented with 2 spaces and ending in a newline
+pub fn messages_to_json(messages: &LocalizedMessages) -> String {
+    let mut entries: Vec<(&str, &str)> = messages
+        .get_messages()
+        .iter()
+        .map(|(key, message)| (key.as_str(), message.as_str()))
+        .collect();
+    entries.sort();
+    entries_to_json(None, entries)
+}
+
+/// Serialize a catalog in its own key order, `@metadata` first
+///
+/// The catalog usually comes from `load_ordered_messages_from_file()`, so it
+/// round-trips without reordering or losing its metadata.
+///
+/// # Arguments
+/// * `catalog` - Metadata and (key, message) pairs in output order
+///
+/// # Returns
+/// The JSON document, indented with 2 spaces and ending in a newline
+pub fn ordered_messages_to_json(catalog: &OrderedCatalog) -> String {
+    entries_to_json(
+        catalog.metadata.as_ref(),
+        catalog
+            .messages
+            .iter()
+            .map(|(key, message)| (key.as_str(), message.as_str())),
+    )
+}
+
+/// Write messages to a JSON catalog file with keys sorted
+///
+/// See `messages_to_json()` for the output format.
+///
+/// # Errors
+/// - File write errors (`I18nError::WriterError`)
+pub fn write_messages_to_file(path: &Path, messages: &LocalizedMessages) -> I18nResult<()> {
+    fs::write(path, messages_to_json(messages)).map_err(|e| I18nError::writer(path, e))
+}
+
+/// Format entries the way MediaWiki JSON catalogs are laid out
+fn entries_to_json<'a>(
+    metadata: Option<&serde_json::Value>,
+    entries: impl IntoIterator<Item = (&'a str, &'a str)>,
+) -> String {
+    let metadata = metadata.map(|metadata| {
+        let pretty = serde_json::to_string_pretty(metadata).expect("JSON values serialize");
+        format!("  \"@metadata\": {}", pretty.replace('\n', "\n  "))
+    });
+    let lines: Vec<String> = metadata
+        .into_iter()
+        .chain(
+            entries
+                .into_iter()
+                .map(|(key, message)| format!("  {}: {}", json_string(key), json_string(message))),
+        )
+        .collect();
+    if lines.is_empty() {
+        return "{}\n".to_string();
+    }
+    format!("{{\n{}\n}}\n", lines.join(",\n"))
+}
+
+/// JSON string literal for `text` (non-ASCII characters are kept as is)
+fn json_string(text: &str) -> String {
+    serde_json::Value::String(text.to_string()).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::loader::load_ordered_messages_from_file;
+
+    #[test]
+    fn test_messages_to_json_is_deterministic() {
+        let mut messages = LocalizedMessages::new();
+        messages
+            .with_message("zebra", "Zèbre")
+            .with_message("apple", "Say \"hi\"\n")
+            .with_message("mango", "{{PLURAL:$1|one|$1 mangoes}}");
+
+        let first = messages_to_json(&messages);
+        assert_eq!(first, messages_to_json(&messages));
+        assert_eq!(
+            first,
+            "{\n  \"apple\": \"Say \\\"hi\\\"\\n\",\n  \"mango\": \"{{PLURAL:$1|one|$1 mangoes}}\",\n  \"zebra\": \"Zèbre\"\n}\n"
+        );
+        assert_eq!(messages_to_json(&LocalizedMessages::new()), "{}\n");
+    }
+
+    #[test]
+    fn test_ordered_round_trip() {
+        let path =
+            std::env::temp_dir().join(format!("banana-i18n-order-{}.json", std::process::id()));
+        let source = "{\n  \"@metadata\": {\"authors\": []},\n  \"zebra\": \"Zebra\",\n  \"apple\": \"Apple\",\n  \"mango\": \"Mango $1\"\n}\n";
+        fs::write(&path, source).unwrap();
+
+        let catalog = load_ordered_messages_from_file(&path).unwrap();
+        let keys: Vec<&str> = catalog
+            .messages
+            .iter()
+            .map(|(key, _)| key.as_str())
+            .collect();
+        assert_eq!(keys, vec!["zebra", "apple", "mango"]);
+
+        let written = ordered_messages_to_json(&catalog);
+        fs::write(&path, &written).unwrap();
+        assert_eq!(load_ordered_messages_from_file(&path).unwrap(), catalog);
+        assert_eq!(
+            written,
+            "{\n  \"@metadata\": {\n    \"authors\": []\n  },\n  \"zebra\": \"Zebra\",\n  \"apple\": \"Apple\",\n  \"mango\": \"Mango $1\"\n}\n"
+        );
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_metadata_round_trips() {
+        let path =
+            std::env::temp_dir().join(format!("banana-i18n-meta-{}.json", std::process::id()));
+        let source = "{\n  \"@metadata\": {\n    \"authors\": [\n      \"Ålice\",\n      \"Bob\"\n    ],\n    \"last-updated\": \"2024-01-01\"\n  },\n  \"greeting\": \"Hello\"\n}\n";
+        fs::write(&path, source).unwrap();
+
+        let catalog = load_ordered_messages_from_file(&path).unwrap();
+        assert_eq!(catalog.metadata.as_ref().unwrap()["authors"][0], "Ålice");
+        assert_eq!(ordered_messages_to_json(&catalog), source);
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_write_failure_is_a_writer_error() {
+        let dir = std::env::temp_dir().join(format!("banana-i18n-missing-{}", std::process::id()));
+        let path = dir.join("en.json");
+        let result = write_messages_to_file(&path, &LocalizedMessages::new());
+        match result {
+            Err(I18nError::WriterError { path: failed, .. }) => assert_eq!(failed, path),
+            other => panic!("expected a writer error, got {:?}", other.err()),
+        }
+    }
+}